@@ -0,0 +1,111 @@
+//! Benchmarks for catalog loading and template rendering on large catalogs.
+//!
+//! Each case builds a catalog of generated skills in a temporary home directory, runs the
+//! `skills` binary against it with `--profile-perf=json`, and reports the median time of the
+//! catalog and render phases over several runs. The render cache is cleared before every run, so
+//! each one renders every template afresh. Run with `cargo bench`.
+
+use std::{fs, path::Path, process::Command};
+
+use serde_json::Value;
+use tempfile::TempDir;
+
+/// Catalog sizes to measure.
+const SIZES: [usize; 3] = [10, 100, 1000];
+
+/// Runs per measurement; the median is reported.
+const RUNS: usize = 5;
+
+/// Commands run against each catalog.
+const COMMANDS: [&[&str]; 2] = [&["list"], &["push", "--dry-run"]];
+
+fn main() {
+    println!(
+        "{:<16} {:>6} {:>12} {:>12} {:>12}",
+        "command", "skills", "catalog ms", "render ms", "total ms"
+    );
+    for size in SIZES {
+        let home = catalog(size);
+        for command in COMMANDS {
+            let mut runs: Vec<[f64; 3]> =
+                (0..RUNS).map(|_| profile(home.path(), command)).collect();
+            let medians: Vec<f64> = (0..3)
+                .map(|column| {
+                    runs.sort_by(|a, b| a[column].total_cmp(&b[column]));
+                    runs[RUNS / 2][column]
+                })
+                .collect();
+            println!(
+                "{:<16} {:>6} {:>12.2} {:>12.2} {:>12.2}",
+                command.join(" "),
+                size,
+                medians[0],
+                medians[1],
+                medians[2]
+            );
+        }
+    }
+}
+
+/// Build a home directory holding a source of `size` templated skills.
+fn catalog(size: usize) -> TempDir {
+    let home = TempDir::new().expect("temp dir");
+    for index in 0..size {
+        let name = format!("skill-{index:04}");
+        let dir = home.path().join("skills").join(&name);
+        fs::create_dir_all(&dir).expect("create skill dir");
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {name}\ndescription: Generated skill {index} for benchmarks.\n---\n\n\
+                 # {name}\n\n{{% if tool == \"claude\" %}}Use the Claude tools.{{% else %}}\
+                 Use the shell.{{% endif %}}\n\n{}",
+                "Follow the steps in order and report what changed.\n".repeat(20)
+            ),
+        )
+        .expect("write skill");
+    }
+    home
+}
+
+/// Run a command against a catalog and return its catalog, render, and total times in
+/// milliseconds.
+fn profile(home: &Path, args: &[&str]) -> [f64; 3] {
+    for cache in [home.join("cache"), home.join("Library").join("Caches")] {
+        if cache.exists() {
+            fs::remove_dir_all(&cache).expect("clear render cache");
+        }
+    }
+    let output = Command::new(env!("CARGO_BIN_EXE_skills"))
+        .args(["--color", "never", "--profile-perf=json"])
+        .args(args)
+        .current_dir(home)
+        .env("HOME", home)
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        .env("SKILLS_SOURCES", home.join("skills"))
+        .output()
+        .expect("run skills");
+    assert!(
+        output.status.success(),
+        "skills {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line).ok())
+        .expect("timing report");
+    let phase = |name: &str| {
+        report["phases"]
+            .as_array()
+            .and_then(|phases| phases.iter().find(|phase| phase["phase"] == name))
+            .and_then(|phase| phase["ms"].as_f64())
+            .unwrap_or_default()
+    };
+    [
+        phase("catalog"),
+        phase("render"),
+        report["total_ms"].as_f64().unwrap_or_default(),
+    ]
+}
@@ -0,0 +1,744 @@
+//! Skill archives: packing skill directories, reading archives, and installing them safely.
+//!
+//! An archive holds one skill below a single root directory named after it; catalog bundles hold
+//! many, each below `skills/<name>`. Each [`ArchiveFormat`] only converts between its bytes and a
+//! list of [`Entry`] values. Walking a skill directory, re-rooting a subtree, and extraction with
+//! its path traversal and symlink checks work on entries, so every format gets the same
+//! protections.
+
+use std::{
+    fs::{self, File},
+    io::{self, Cursor, ErrorKind, Read, Write},
+    path::{Component, Path},
+};
+
+use clap::ValueEnum;
+use path_clean::PathClean;
+use tar::{Archive, Builder, EntryType, Header};
+use walkdir::WalkDir;
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+use zstd::{decode_all, encode_all};
+
+use crate::{
+    error::{Error, Result},
+    permissions::{EXECUTABLE_MODE, FILE_MODE, is_executable, mode_is_executable, set_executable},
+};
+
+/// How symlinks inside a skill directory are packed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SymlinkPolicy {
+    /// Fail with an error naming the symlink.
+    #[default]
+    Reject,
+    /// Pack the file or directory the symlink points to.
+    Follow,
+    /// Store the symlink itself; its target must stay inside the skill directory.
+    Preserve,
+}
+
+/// What an archive entry holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A directory.
+    Dir,
+    /// A regular file.
+    File {
+        /// File contents.
+        contents: Vec<u8>,
+        /// Whether the file is an executable script.
+        executable: bool,
+    },
+    /// A symlink.
+    Symlink {
+        /// Link target, relative to the directory holding the link.
+        target: String,
+    },
+}
+
+/// One entry in an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Path within the archive, with `/` separators and no trailing slash.
+    pub(crate) path: String,
+    /// What the entry holds.
+    pub(crate) kind: EntryKind,
+}
+
+impl Entry {
+    /// Return the entry's path below `root`, or `None` for the root itself and entries outside
+    /// it.
+    pub(crate) fn path_below(&self, root: &str) -> Option<&str> {
+        self.path
+            .strip_prefix(root)
+            .and_then(|path| path.strip_prefix('/'))
+            .filter(|path| !path.is_empty())
+    }
+
+    /// Return the size of the entry's contents in bytes.
+    pub(crate) fn size(&self) -> u64 {
+        match &self.kind {
+            EntryKind::Dir => 0,
+            EntryKind::File { contents, .. } => contents.len() as u64,
+            EntryKind::Symlink { target } => target.len() as u64,
+        }
+    }
+}
+
+/// A format skill archives are stored in.
+pub trait ArchiveFormat {
+    /// Read the entries of an archive, in archive order.
+    fn read(&self, data: &[u8]) -> Result<Vec<Entry>>;
+
+    /// Write entries as an archive.
+    fn write(&self, entries: &[Entry]) -> io::Result<Vec<u8>>;
+}
+
+/// ZIP archives, with Unix permissions recording executables and symlinks.
+pub struct Zip;
+
+impl ArchiveFormat for Zip {
+    fn read(&self, data: &[u8]) -> Result<Vec<Entry>> {
+        let invalid = |message: String| Error::InvalidZip { message };
+        let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|e| invalid(e.to_string()))?;
+        let mut entries = Vec::with_capacity(archive.len());
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .map_err(|e| invalid(e.to_string()))?;
+            let path = file.name().trim_end_matches('/').to_string();
+            let kind = if file.is_dir() {
+                EntryKind::Dir
+            } else if file.is_symlink() {
+                let mut target = String::new();
+                file.read_to_string(&mut target)
+                    .map_err(|e| invalid(e.to_string()))?;
+                EntryKind::Symlink { target }
+            } else {
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents)
+                    .map_err(|e| invalid(e.to_string()))?;
+                EntryKind::File {
+                    contents,
+                    executable: file.unix_mode().is_some_and(mode_is_executable),
+                }
+            };
+            entries.push(Entry { path, kind });
+        }
+        Ok(entries)
+    }
+
+    fn write(&self, entries: &[Entry]) -> io::Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(FILE_MODE);
+        for entry in entries {
+            match &entry.kind {
+                EntryKind::Dir => zip.add_directory(&entry.path, options)?,
+                EntryKind::File {
+                    contents,
+                    executable,
+                } => {
+                    let mode = if *executable {
+                        EXECUTABLE_MODE
+                    } else {
+                        FILE_MODE
+                    };
+                    zip.start_file(&entry.path, options.unix_permissions(mode))?;
+                    zip.write_all(contents)?;
+                }
+                EntryKind::Symlink { target } => zip.add_symlink(&entry.path, target, options)?,
+            }
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+/// Tar archives compressed with zstd, for bundles holding many skills.
+pub struct TarZst;
+
+impl ArchiveFormat for TarZst {
+    fn read(&self, data: &[u8]) -> Result<Vec<Entry>> {
+        let invalid = |message: String| Error::InvalidTar { message };
+        let tar = decode_all(data).map_err(|e| invalid(e.to_string()))?;
+        let mut archive = Archive::new(tar.as_slice());
+        let mut entries = Vec::new();
+        for file in archive.entries().map_err(|e| invalid(e.to_string()))? {
+            let mut file = file.map_err(|e| invalid(e.to_string()))?;
+            let path = file
+                .path()
+                .map_err(|e| invalid(e.to_string()))?
+                .to_string_lossy()
+                .trim_end_matches('/')
+                .to_string();
+            let kind = match file.header().entry_type() {
+                EntryType::Directory => EntryKind::Dir,
+                EntryType::Symlink => {
+                    let target = file
+                        .link_name()
+                        .map_err(|e| invalid(e.to_string()))?
+                        .ok_or_else(|| invalid(format!("symlink without a target: {path}")))?;
+                    EntryKind::Symlink {
+                        target: target.to_string_lossy().into_owned(),
+                    }
+                }
+                EntryType::Regular => {
+                    let executable = file.header().mode().is_ok_and(mode_is_executable);
+                    let mut contents = Vec::new();
+                    file.read_to_end(&mut contents)
+                        .map_err(|e| invalid(e.to_string()))?;
+                    EntryKind::File {
+                        contents,
+                        executable,
+                    }
+                }
+                _ => continue,
+            };
+            entries.push(Entry { path, kind });
+        }
+        Ok(entries)
+    }
+
+    fn write(&self, entries: &[Entry]) -> io::Result<Vec<u8>> {
+        let mut tar = Builder::new(Vec::new());
+        for entry in entries {
+            let mut header = Header::new_gnu();
+            match &entry.kind {
+                EntryKind::Dir => {
+                    header.set_entry_type(EntryType::Directory);
+                    header.set_mode(EXECUTABLE_MODE);
+                    header.set_size(0);
+                    tar.append_data(&mut header, &entry.path, io::empty())?;
+                }
+                EntryKind::File {
+                    contents,
+                    executable,
+                } => {
+                    header.set_mode(if *executable {
+                        EXECUTABLE_MODE
+                    } else {
+                        FILE_MODE
+                    });
+                    header.set_size(contents.len() as u64);
+                    tar.append_data(&mut header, &entry.path, contents.as_slice())?;
+                }
+                EntryKind::Symlink { target } => {
+                    header.set_entry_type(EntryType::Symlink);
+                    header.set_mode(FILE_MODE);
+                    header.set_size(0);
+                    tar.append_link(&mut header, &entry.path, target)?;
+                }
+            }
+        }
+        encode_all(tar.into_inner()?.as_slice(), 0)
+    }
+}
+
+/// Pack a skill directory into an archive rooted at the skill name.
+///
+/// Returns the archive bytes and the sorted relative paths of the files and links it contains.
+pub fn pack_dir(
+    format: &dyn ArchiveFormat,
+    name: &str,
+    skill_dir: &Path,
+    symlinks: SymlinkPolicy,
+) -> Result<(Vec<u8>, Vec<String>)> {
+    let entries = read_dir(name, skill_dir, symlinks)?;
+    let archive = format.write(&entries).map_err(|e| Error::ZipCreate {
+        path: skill_dir.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let mut files: Vec<String> = entries
+        .iter()
+        .filter(|entry| entry.kind != EntryKind::Dir)
+        .filter_map(|entry| entry.path_below(name))
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    Ok((archive, files))
+}
+
+/// Read a skill directory as archive entries below a root directory named `name`.
+pub fn read_dir(name: &str, skill_dir: &Path, symlinks: SymlinkPolicy) -> Result<Vec<Entry>> {
+    let read_error = |path: &Path, source: io::Error| Error::SkillRead {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut entries = Vec::new();
+    let walker = WalkDir::new(skill_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .follow_links(symlinks == SymlinkPolicy::Follow);
+    for entry in walker {
+        let entry = entry.map_err(|e| Error::ZipCreate {
+            path: skill_dir.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        let path = entry.path();
+        let rel_path = path.strip_prefix(skill_dir).unwrap_or(path);
+        let archive_path = format!("{name}/{}", rel_path.display()).replace('\\', "/");
+
+        let kind = if entry.path_is_symlink() && symlinks != SymlinkPolicy::Follow {
+            EntryKind::Symlink {
+                target: preserved_link_target(skill_dir, path, symlinks)?,
+            }
+        } else if entry.file_type().is_dir() {
+            EntryKind::Dir
+        } else if entry.file_type().is_file() {
+            let metadata = entry
+                .metadata()
+                .map_err(|e| read_error(path, io::Error::other(e)))?;
+            let mut contents = Vec::new();
+            File::open(path)
+                .and_then(|mut file| file.read_to_end(&mut contents))
+                .map_err(|e| read_error(path, e))?;
+            EntryKind::File {
+                contents,
+                executable: is_executable(&metadata),
+            }
+        } else {
+            continue;
+        };
+        entries.push(Entry {
+            path: archive_path,
+            kind,
+        });
+    }
+    Ok(entries)
+}
+
+/// Return the target of a symlink to store in an archive or recreate in a copied skill.
+///
+/// Fails unless the policy preserves symlinks and the target is a relative path that stays inside
+/// the skill directory.
+pub fn preserved_link_target(
+    skill_dir: &Path,
+    link: &Path,
+    symlinks: SymlinkPolicy,
+) -> Result<String> {
+    let refuse = |message: String| Error::UnsupportedSymlink {
+        path: link.to_path_buf(),
+        message,
+    };
+    if symlinks == SymlinkPolicy::Reject {
+        return Err(refuse(
+            "symlinks are rejected by default (use --symlinks follow or --symlinks preserve)"
+                .to_string(),
+        ));
+    }
+
+    let target = fs::read_link(link).map_err(|e| Error::SkillRead {
+        path: link.to_path_buf(),
+        source: e,
+    })?;
+    let parent = link.parent().unwrap_or(skill_dir);
+    if target.is_absolute() || !parent.join(&target).clean().starts_with(skill_dir.clean()) {
+        return Err(refuse(format!(
+            "target {} is outside the skill directory",
+            target.display()
+        )));
+    }
+    Ok(target.to_string_lossy().replace('\\', "/"))
+}
+
+/// Return the root directory of an archive: the first component of its first entry.
+pub fn root_dir(entries: &[Entry]) -> Option<&str> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.path.split('/').next())
+        .find(|root| !root.is_empty())
+}
+
+/// Return the entries below `prefix` re-rooted under `root`, or `None` when there are none.
+///
+/// `prefix` is a `/`-separated path matched by whole components.
+pub fn subtree(entries: Vec<Entry>, prefix: &str, root: &str) -> Option<Vec<Entry>> {
+    let subtree: Vec<Entry> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let path = format!("{root}/{}", entry.path_below(prefix)?);
+            Some(Entry { path, ..entry })
+        })
+        .collect();
+    (!subtree.is_empty()).then_some(subtree)
+}
+
+/// Extract the entries below `root_dir` into a staging directory beside the target, then move
+/// it into place.
+///
+/// A failed extraction never leaves a partially written skill at the target.
+pub fn install(entries: &[Entry], root_dir: &str, target: &Path) -> Result<()> {
+    let file_name = target.file_name().ok_or_else(|| Error::InvalidPath {
+        path: target.to_path_buf(),
+    })?;
+    let staging = target.with_file_name(format!(".{}.partial", file_name.to_string_lossy()));
+    remove_dir_if_exists(&staging)?;
+
+    if let Err(error) = extract(entries, root_dir, &staging) {
+        fs::remove_dir_all(&staging).ok();
+        return Err(error);
+    }
+
+    remove_dir_if_exists(target)?;
+    fs::rename(&staging, target).map_err(|e| Error::SkillMove {
+        from: staging.clone(),
+        to: target.to_path_buf(),
+        source: e,
+    })
+}
+
+/// Remove a directory tree if it exists.
+fn remove_dir_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_dir_all(path) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(Error::SkillWrite {
+            path: path.to_path_buf(),
+            source: error,
+        }),
+    }
+}
+
+/// Extract the entries below `root_dir` into a target directory.
+///
+/// Refuses entries whose paths are rooted or climb out of the target, are written through an extracted symlink, or
+/// are symlinks resolving outside the target.
+pub fn extract(entries: &[Entry], root_dir: &str, target: &Path) -> Result<()> {
+    let write_error = |path: &Path, source: io::Error| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut links = Vec::new();
+
+    fs::create_dir_all(target).map_err(|e| write_error(target, e))?;
+
+    for entry in entries {
+        let Some(rel_path) = entry.path_below(root_dir) else {
+            continue;
+        };
+
+        if !Path::new(rel_path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(Error::InvalidZip {
+                message: format!("path traversal detected: {}", rel_path),
+            });
+        }
+
+        let out_path = target.join(rel_path);
+        if writes_through_symlink(target, &out_path) {
+            return Err(Error::InvalidZip {
+                message: format!("entry is written through a symlink: {rel_path}"),
+            });
+        }
+
+        match &entry.kind {
+            EntryKind::Symlink {
+                target: link_target,
+            } => {
+                extract_symlink(target, &out_path, rel_path, link_target)?;
+                links.push(out_path);
+            }
+            EntryKind::Dir => {
+                fs::create_dir_all(&out_path).map_err(|e| write_error(&out_path, e))?;
+            }
+            EntryKind::File {
+                contents,
+                executable,
+            } => {
+                if let Some(parent) = out_path.parent() {
+                    fs::create_dir_all(parent).map_err(|e| write_error(parent, e))?;
+                }
+                fs::write(&out_path, contents).map_err(|e| write_error(&out_path, e))?;
+                if *executable {
+                    set_executable(&out_path).map_err(|e| write_error(&out_path, e))?;
+                }
+            }
+        }
+    }
+
+    // Links can chain through each other, so check where each one finally resolves.
+    let root = target.canonicalize().map_err(|e| Error::SkillRead {
+        path: target.to_path_buf(),
+        source: e,
+    })?;
+    for link in &links {
+        if !link
+            .canonicalize()
+            .is_ok_and(|resolved| resolved.starts_with(&root))
+        {
+            return Err(Error::InvalidZip {
+                message: format!(
+                    "symlink does not resolve inside the skill directory: {}",
+                    link.strip_prefix(target).unwrap_or(link).display()
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Return whether writing `path` would follow a symlink already extracted below `root`.
+fn writes_through_symlink(root: &Path, path: &Path) -> bool {
+    path.ancestors()
+        .take_while(|ancestor| *ancestor != root)
+        .any(|ancestor| ancestor.is_symlink())
+}
+
+/// Create a symlink from an archive entry, refusing absolute or escaping targets.
+fn extract_symlink(root: &Path, link: &Path, rel_path: &str, link_target: &str) -> Result<()> {
+    let escapes = || Error::InvalidZip {
+        message: format!("symlink escapes the skill directory: {rel_path} -> {link_target}"),
+    };
+    let destination = Path::new(link_target);
+    if destination.is_absolute() || destination.has_root() {
+        return Err(escapes());
+    }
+    let parent = link.parent().unwrap_or(root);
+    if !parent.join(destination).clean().starts_with(root.clean()) {
+        return Err(escapes());
+    }
+
+    fs::create_dir_all(parent).map_err(|e| Error::SkillWrite {
+        path: parent.to_path_buf(),
+        source: e,
+    })?;
+    create_symlink(destination, link).map_err(|e| Error::SkillWrite {
+        path: link.to_path_buf(),
+        source: e,
+    })
+}
+
+/// Create a symlink at `link` pointing to `destination`.
+#[cfg(unix)]
+pub fn create_symlink(destination: &Path, link: &Path) -> io::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    symlink(destination, link)
+}
+
+/// Create a symlink at `link` pointing to `destination`.
+#[cfg(not(unix))]
+pub fn create_symlink(_destination: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        ErrorKind::Unsupported,
+        "symlinks in skill archives are only supported on Unix",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{
+        ArchiveFormat, Entry, EntryKind, SymlinkPolicy, TarZst, Zip, install, pack_dir, root_dir,
+        subtree,
+    };
+    use crate::{error::Error, testutil::simple_skill};
+
+    /// Build a file entry.
+    fn file(path: &str, contents: &str) -> Entry {
+        Entry {
+            path: path.to_string(),
+            kind: EntryKind::File {
+                contents: contents.as_bytes().to_vec(),
+                executable: false,
+            },
+        }
+    }
+
+    /// Build a symlink entry.
+    fn link(path: &str, target: &str) -> Entry {
+        Entry {
+            path: path.to_string(),
+            kind: EntryKind::Symlink {
+                target: target.to_string(),
+            },
+        }
+    }
+
+    /// Build one entry of each kind.
+    fn sample_entries() -> Vec<Entry> {
+        vec![
+            Entry {
+                path: "alpha".to_string(),
+                kind: EntryKind::Dir,
+            },
+            file("alpha/SKILL.md", "skill"),
+            Entry {
+                path: "alpha/run.sh".to_string(),
+                kind: EntryKind::File {
+                    contents: b"#!/bin/sh\n".to_vec(),
+                    executable: true,
+                },
+            },
+            link("alpha/guide.md", "SKILL.md"),
+        ]
+    }
+
+    #[test]
+    fn round_trips_tar_zst_entries() {
+        let entries = sample_entries();
+        let data = TarZst.write(&entries).expect("write");
+        assert_eq!(TarZst.read(&data).expect("read"), entries);
+        assert!(matches!(
+            TarZst.read(b"not a tarball"),
+            Err(Error::InvalidTar { .. })
+        ));
+    }
+
+    #[test]
+    fn round_trips_zip_entries() {
+        let entries = sample_entries();
+        let data = Zip.write(&entries).expect("write");
+        assert_eq!(Zip.read(&data).expect("read"), entries);
+        assert!(matches!(
+            Zip.read(b"not a zip"),
+            Err(Error::InvalidZip { .. })
+        ));
+    }
+
+    #[test]
+    fn re_roots_subtrees_by_whole_components() {
+        let entries = vec![
+            file("repo-abc/README.md", "readme"),
+            file("repo-abc/skills/pdf/SKILL.md", "pdf"),
+            file("repo-abc/skills/pdf-extra/SKILL.md", "extra"),
+        ];
+        assert_eq!(root_dir(&entries), Some("repo-abc"));
+
+        let pdf = subtree(entries.clone(), "repo-abc/skills/pdf", "pdf").expect("subtree");
+        assert_eq!(pdf, [file("pdf/SKILL.md", "pdf")]);
+        assert!(subtree(entries, "repo-abc/skills/missing", "missing").is_none());
+    }
+
+    #[test]
+    fn installs_skill_into_target() {
+        let dir = tempdir().expect("tempdir");
+        let target = dir.path().join("alpha");
+        let entries = [
+            file("alpha/SKILL.md", "skill"),
+            file("alpha/notes/a.txt", "a"),
+        ];
+
+        install(&entries, "alpha", &target).expect("install");
+
+        assert!(target.join("SKILL.md").is_file());
+        assert!(target.join("notes").join("a.txt").is_file());
+        assert!(!dir.path().join(".alpha.partial").exists());
+    }
+
+    #[test]
+    fn failed_extraction_leaves_existing_target_intact() {
+        let dir = tempdir().expect("tempdir");
+        let target = dir.path().join("alpha");
+        install(&[file("alpha/SKILL.md", "original")], "alpha", &target).expect("install");
+
+        let bad = [
+            file("alpha/SKILL.md", "new"),
+            file("alpha/../escape.txt", "x"),
+        ];
+        assert!(install(&bad, "alpha", &target).is_err());
+
+        let contents = fs::read_to_string(target.join("SKILL.md")).expect("read");
+        assert_eq!(contents, "original");
+        assert!(!dir.path().join(".alpha.partial").exists());
+    }
+
+    #[test]
+    fn rejects_rooted_entries() {
+        let dir = tempdir().expect("tempdir");
+        let target = dir.path().join("alpha");
+        let rooted = [
+            file("alpha/SKILL.md", "skill"),
+            file("alpha//etc/cron.d/x", "x"),
+        ];
+
+        let error = install(&rooted, "alpha", &target).expect_err("rooted entry");
+        assert!(matches!(error, Error::InvalidZip { .. }), "{error}");
+        assert!(!target.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn preserves_executable_scripts_through_pack_and_install() {
+        use std::{os::unix::fs::PermissionsExt, path::Path};
+
+        use crate::permissions::set_executable;
+
+        let dir = tempdir().expect("tempdir");
+        let skill = dir.path().join("alpha");
+        fs::create_dir_all(skill.join("scripts")).expect("mkdir");
+        fs::write(skill.join("SKILL.md"), simple_skill("alpha")).expect("write");
+        fs::write(skill.join("scripts").join("run.sh"), "#!/bin/sh\n").expect("write");
+        set_executable(&skill.join("scripts").join("run.sh")).expect("chmod");
+
+        let (archive, files) =
+            pack_dir(&Zip, "alpha", &skill, SymlinkPolicy::Reject).expect("pack");
+        assert_eq!(files, ["SKILL.md", "scripts/run.sh"]);
+        let target = dir.path().join("imported").join("alpha");
+        install(&Zip.read(&archive).expect("read"), "alpha", &target).expect("install");
+
+        let mode = |path: &Path| fs::metadata(path).expect("metadata").permissions().mode() & 0o111;
+        assert_ne!(mode(&target.join("scripts").join("run.sh")), 0);
+        assert_eq!(mode(&target.join("SKILL.md")), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn packs_and_installs_symlinks_by_policy() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().expect("tempdir");
+        let skill = dir.path().join("alpha");
+        fs::create_dir_all(skill.join("docs")).expect("mkdir");
+        fs::write(skill.join("SKILL.md"), simple_skill("alpha")).expect("write");
+        symlink("../SKILL.md", skill.join("docs").join("guide.md")).expect("symlink");
+
+        assert!(matches!(
+            pack_dir(&Zip, "alpha", &skill, SymlinkPolicy::Reject),
+            Err(Error::UnsupportedSymlink { .. })
+        ));
+        let (followed, _) = pack_dir(&Zip, "alpha", &skill, SymlinkPolicy::Follow).expect("pack");
+        let target = dir.path().join("followed").join("alpha");
+        install(&Zip.read(&followed).expect("read"), "alpha", &target).expect("install");
+        assert!(!target.join("docs").join("guide.md").is_symlink());
+
+        let (preserved, _) =
+            pack_dir(&Zip, "alpha", &skill, SymlinkPolicy::Preserve).expect("pack");
+        let target = dir.path().join("preserved").join("alpha");
+        install(&Zip.read(&preserved).expect("read"), "alpha", &target).expect("install");
+        let link = target.join("docs").join("guide.md");
+        assert!(link.is_symlink());
+        assert_eq!(
+            fs::read_to_string(link).expect("read"),
+            simple_skill("alpha")
+        );
+
+        symlink("../../outside", skill.join("escape")).expect("symlink");
+        assert!(pack_dir(&Zip, "alpha", &skill, SymlinkPolicy::Preserve).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinks_that_escape_the_target() {
+        let dir = tempdir().expect("tempdir");
+        let target = dir.path().join("alpha");
+
+        for links in [
+            &[("alpha/up", "../outside")][..],
+            &[("alpha/abs", "/etc/passwd")][..],
+            &[("alpha/here", "."), ("alpha/chain", "here/..")][..],
+        ] {
+            let mut entries = vec![file("alpha/SKILL.md", "skill")];
+            entries.extend(links.iter().map(|(path, target)| link(path, target)));
+            let error = install(&entries, "alpha", &target).expect_err("escape");
+            assert!(matches!(error, Error::InvalidZip { .. }));
+            assert!(!target.exists());
+        }
+    }
+}
@@ -2,17 +2,21 @@
 
 use std::{
     collections::HashMap,
-    env,
-    fs,
     io::ErrorKind,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
 };
 
 use crate::{
-    config::Config,
-    diagnostics::Diagnostics,
-    paths::display_path,
-    skill::{LocalSkill, SkillTemplate, ToolSkill, load_local_skill, load_source_skill, load_tool_skill},
+    diagnostics::{Diagnostics, Verbosity},
+    profile::{Phase, span},
+    render_cache::RenderCache,
+    skill::{
+        ARCHIVE_DIR, BaseTemplates, LocalSkill, RenderedSkill, SkillFileNames, SkillTemplate,
+        TEMPLATES_DIR, ToolSkill, load_local_skill, load_source_skill, load_tool_skill,
+    },
+    store::{FsStore, SkillStore},
     tool::Tool,
 };
 
@@ -25,44 +29,183 @@ pub struct Catalog {
     pub(crate) tools: HashMap<Tool, HashMap<String, ToolSkill>>,
     /// Local skills in the current project directory, keyed by tool.
     pub(crate) local: HashMap<Tool, HashMap<String, LocalSkill>>,
+    /// Names of directories holding a skill of a different name, mapped to the skill's name.
+    pub(crate) aliases: HashMap<String, String>,
+    /// Renderings of source templates, reused across commands and runs.
+    renders: RenderCache,
+    /// Verbosity of the load, which decides whether render timings are printed.
+    verbosity: Verbosity,
+}
+
+/// Directories a catalog is loaded from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogRoots {
+    /// Source directories in priority order.
+    pub(crate) sources: Vec<PathBuf>,
+    /// Global skill directories for each tool.
+    pub(crate) tools: Vec<(Tool, PathBuf)>,
+    /// Project-local skill directories for each tool.
+    pub(crate) local: Vec<(Tool, PathBuf)>,
+    /// Names a skill file may have within its directory.
+    pub(crate) skill_files: SkillFileNames,
+    /// Directories outside the sources that shared templates may link into.
+    pub(crate) include_paths: Vec<PathBuf>,
 }
 
 impl Catalog {
-    /// Create a new catalog with the given data.
-    #[cfg(test)]
-    pub(crate) fn new(
-        sources: HashMap<String, SkillTemplate>,
-        tools: HashMap<Tool, HashMap<String, ToolSkill>>,
-        local: HashMap<Tool, HashMap<String, LocalSkill>>,
+    /// Load sources, tool installs, and local skills from the filesystem into a catalog.
+    pub(crate) fn load(roots: &CatalogRoots, diagnostics: &mut Diagnostics) -> Self {
+        Self::load_from(&FsStore, roots, diagnostics)
+    }
+
+    /// Load a catalog from the given roots in a skill store.
+    pub(crate) fn load_from(
+        store: &dyn SkillStore,
+        roots: &CatalogRoots,
+        diagnostics: &mut Diagnostics,
     ) -> Self {
-        Self { sources, tools, local }
+        let _span = span(Phase::Catalog);
+        let sources = load_sources(store, roots, diagnostics);
+        let tools = load_tools(store, &roots.skill_files, &roots.tools, diagnostics);
+        let local = load_local_skills(store, &roots.skill_files, &roots.local, diagnostics);
+        let aliases = index_aliases(&sources, &tools, &local);
+        Self {
+            sources,
+            tools,
+            local,
+            aliases,
+            renders: RenderCache::default(),
+            verbosity: diagnostics.verbosity(),
+        }
+    }
+
+    /// Resolve a skill name given by the user, which may name the directory holding a skill
+    /// rather than the skill itself.
+    pub(crate) fn resolve_name(&self, name: &str) -> String {
+        if self.sources.contains_key(name) {
+            return name.to_string();
+        }
+        self.aliases
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Use a render cache in place of the default in-memory one.
+    pub(crate) fn with_render_cache(mut self, renders: RenderCache) -> Self {
+        self.renders = renders;
+        self
     }
 
-    /// Load sources, tool installs, and local skills into a catalog.
-    pub(crate) fn load(config: &Config, diagnostics: &mut Diagnostics) -> Self {
-        let sources = load_sources(config, diagnostics);
-        let tools = load_tools(diagnostics);
-        let local = load_local_skills(diagnostics);
-        Self { sources, tools, local }
+    /// Copy the loaded skills into a new catalog with an in-memory render cache.
+    pub(crate) fn clone_skills(&self, verbosity: Verbosity) -> Self {
+        Self {
+            sources: self.sources.clone(),
+            tools: self.tools.clone(),
+            local: self.local.clone(),
+            aliases: self.aliases.clone(),
+            renders: RenderCache::default(),
+            verbosity,
+        }
+    }
+
+    /// Render a source skill's template for a tool, reusing cached renderings.
+    pub(crate) fn render(&self, skill: &SkillTemplate, tool: Tool) -> Result<String, String> {
+        let start = Instant::now();
+        let rendered = self.renders.render(&skill.contents, tool, &skill.bases);
+        self.verbosity.debug(format!(
+            "rendered {} for {} in {:.2?}",
+            skill.name,
+            tool.id(),
+            start.elapsed()
+        ));
+        rendered
+    }
+
+    /// Render a source skill for a tool, its markdown support files included, reusing cached
+    /// renderings.
+    pub(crate) fn render_skill(
+        &self,
+        skill: &SkillTemplate,
+        tool: Tool,
+    ) -> Result<RenderedSkill, String> {
+        let start = Instant::now();
+        let rendered =
+            skill.render_with(|template| self.renders.render(template, tool, &skill.bases));
+        self.verbosity.debug(format!(
+            "rendered {} for {} in {:.2?}",
+            skill.name,
+            tool.id(),
+            start.elapsed()
+        ));
+        rendered
+    }
+}
+
+/// Load a source's shared templates, warning about any refused for linking elsewhere.
+fn load_bases(
+    store: &dyn SkillStore,
+    source_root: &Path,
+    include_paths: &[PathBuf],
+    diagnostics: &mut Diagnostics,
+) -> Arc<BaseTemplates> {
+    let bases = BaseTemplates::load(store, source_root, include_paths);
+    for path in bases.refused() {
+        diagnostics.warn(format!(
+            "{} - template links outside the source; add its directory to [templates] \
+             include_paths to allow it",
+            path.display()
+        ));
     }
+    Arc::new(bases)
 }
 
 /// Load source skills from configured directories.
-fn load_sources(config: &Config, diagnostics: &mut Diagnostics) -> HashMap<String, SkillTemplate> {
+fn load_sources(
+    store: &dyn SkillStore,
+    roots: &CatalogRoots,
+    diagnostics: &mut Diagnostics,
+) -> HashMap<String, SkillTemplate> {
+    let names = &roots.skill_files;
     let mut skills: HashMap<String, SkillTemplate> = HashMap::new();
     let mut conflicts: HashMap<String, Vec<PathBuf>> = HashMap::new();
 
-    for source_root in config.sources() {
-        let entries = match read_source_directory(source_root, diagnostics) {
+    for source_root in &roots.sources {
+        diagnostics.debug(format!(
+            "scanning source directory {}",
+            source_root.display()
+        ));
+        let entries = match read_source_directory(store, source_root, diagnostics) {
             Some(entries) => entries,
             None => continue,
         };
 
-        for entry in entries {
-            let skill_dir = entry.path();
-            let Some(skill) = load_source_skill(source_root, &skill_dir, diagnostics) else {
+        let bases = load_bases(store, source_root, &roots.include_paths, diagnostics);
+        for skill_dir in entries {
+            if skill_dir
+                .file_name()
+                .is_some_and(|name| name == TEMPLATES_DIR || name == ARCHIVE_DIR)
+            {
+                continue;
+            }
+            let Some(skill) =
+                load_source_skill(store, names, source_root, &bases, &skill_dir, diagnostics)
+            else {
                 continue;
             };
+            diagnostics.debug(format!(
+                "loaded source skill {} from {}",
+                skill.name,
+                skill.skill_path.display()
+            ));
+            if skill.dir_name() != skill.name {
+                diagnostics.warn(format!(
+                    "{} - directory name differs from skill name '{}'; run `skills validate \
+                     --fix` to rename it",
+                    skill.skill_dir.display(),
+                    skill.name
+                ));
+            }
 
             if let Some(existing) = skills.get(&skill.name) {
                 let list = conflicts
@@ -81,117 +224,178 @@ fn load_sources(config: &Config, diagnostics: &mut Diagnostics) -> HashMap<Strin
 }
 
 /// Load tool-installed skills for all supported tools.
-fn load_tools(diagnostics: &mut Diagnostics) -> HashMap<Tool, HashMap<String, ToolSkill>> {
+fn load_tools(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
+    tool_dirs: &[(Tool, PathBuf)],
+    diagnostics: &mut Diagnostics,
+) -> HashMap<Tool, HashMap<String, ToolSkill>> {
     let mut tools = HashMap::new();
 
-    for tool in Tool::all() {
-        let dir = match tool.skills_dir() {
-            Ok(dir) => dir,
-            Err(error) => {
-                diagnostics.warn(error.to_string());
-                continue;
-            }
-        };
-        let entries = read_tool_directory(&dir, diagnostics);
+    for (tool, dir) in tool_dirs {
+        diagnostics.debug(format!(
+            "scanning {} skill directory {}",
+            tool.id(),
+            dir.display()
+        ));
+        let entries = read_tool_directory(store, dir, diagnostics);
 
         let mut skills = HashMap::new();
-        for entry in entries {
-            let skill_dir = entry.path();
-            let Some(skill) = load_tool_skill(&skill_dir, diagnostics) else {
+        for skill_dir in entries {
+            let Some(skill) = load_tool_skill(store, names, &skill_dir, diagnostics) else {
                 continue;
             };
             skills.insert(skill.name.clone(), skill);
         }
-        tools.insert(tool, skills);
+        tools.insert(*tool, skills);
     }
 
     tools
 }
 
-/// Load local skills from the current working directory.
-fn load_local_skills(diagnostics: &mut Diagnostics) -> HashMap<Tool, HashMap<String, LocalSkill>> {
-    let mut local = HashMap::new();
+/// Map the names of directories holding a skill of a different name to the skill's name.
+///
+/// Source directories are indexed before tool and project copies, and a directory name never
+/// hides a skill actually called that.
+fn index_aliases(
+    sources: &HashMap<String, SkillTemplate>,
+    tools: &HashMap<Tool, HashMap<String, ToolSkill>>,
+    local: &HashMap<Tool, HashMap<String, LocalSkill>>,
+) -> HashMap<String, String> {
+    let installed = tools.values().flat_map(|skills| {
+        skills
+            .values()
+            .filter_map(|skill| Some((skill.skill_path.parent()?, &skill.name)))
+    });
+    let local_copies = local.values().flat_map(|skills| {
+        skills
+            .values()
+            .map(|skill| (skill.skill_dir.as_path(), &skill.name))
+    });
+    let is_skill = |name: &str| {
+        sources.contains_key(name)
+            || tools.values().any(|skills| skills.contains_key(name))
+            || local.values().any(|skills| skills.contains_key(name))
+    };
 
-    let cwd = match env::current_dir() {
-        Ok(cwd) => cwd,
-        Err(error) => {
-            diagnostics.warn(format!("failed to get current directory: {error}"));
-            return local;
+    let mut aliases = HashMap::new();
+    let dirs = sources
+        .values()
+        .map(|skill| (skill.skill_dir.as_path(), &skill.name))
+        .chain(installed)
+        .chain(local_copies);
+    for (dir, name) in dirs {
+        let Some(dir_name) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if dir_name == name || is_skill(dir_name) {
+            continue;
         }
-    };
+        aliases
+            .entry(dir_name.to_string())
+            .or_insert_with(|| name.clone());
+    }
+    aliases
+}
 
-    for tool in Tool::all() {
-        let local_dir = cwd.join(tool.local_skills_dir());
-        let entries = read_local_directory(&local_dir);
+/// Load local skills from project-local tool directories.
+fn load_local_skills(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
+    local_dirs: &[(Tool, PathBuf)],
+    diagnostics: &mut Diagnostics,
+) -> HashMap<Tool, HashMap<String, LocalSkill>> {
+    let mut local = HashMap::new();
+
+    for (tool, local_dir) in local_dirs {
+        diagnostics.debug(format!(
+            "scanning {} local skill directory {}",
+            tool.id(),
+            local_dir.display()
+        ));
+        let entries = store.list(local_dir).unwrap_or_default();
 
         let mut skills = HashMap::new();
-        for entry in entries {
-            let skill_dir = entry.path();
-            let Some(skill) = load_local_skill(&skill_dir, tool, diagnostics) else {
+        for skill_dir in entries {
+            let Some(skill) = load_local_skill(store, names, &skill_dir, *tool, diagnostics) else {
                 continue;
             };
             skills.insert(skill.name.clone(), skill);
         }
-        local.insert(tool, skills);
+        local.insert(*tool, skills);
     }
 
     local
 }
 
-/// Read a local skills directory, returning empty on missing or error.
-fn read_local_directory(path: &Path) -> Vec<fs::DirEntry> {
-    let mut entries = match fs::read_dir(path) {
-        Ok(entries) => entries,
-        Err(_) => return Vec::new(),
-    }
-    .filter_map(|entry| entry.ok())
-    .collect::<Vec<_>>();
-
-    entries.sort_by_key(|entry| entry.file_name());
-    entries
-}
-
 /// Read a source directory and return sorted entries.
-fn read_source_directory(path: &Path, diagnostics: &mut Diagnostics) -> Option<Vec<fs::DirEntry>> {
-    let mut entries = match fs::read_dir(path) {
-        Ok(entries) => entries,
+fn read_source_directory(
+    store: &dyn SkillStore,
+    path: &Path,
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<PathBuf>> {
+    match store.list(path) {
+        Ok(entries) => Some(entries),
         Err(error) if error.kind() == ErrorKind::NotFound => {
             diagnostics.warn(format!("source directory not found: {}", path.display()));
-            return None;
+            None
         }
         Err(error) => {
             diagnostics.warn(format!(
                 "failed to read directory {}: {error}",
                 path.display()
             ));
-            return None;
+            None
         }
     }
-    .filter_map(|entry| entry.ok())
-    .collect::<Vec<_>>();
+}
 
-    entries.sort_by_key(|entry| entry.file_name());
-    Some(entries)
+/// Load the skills in each source's [`ARCHIVE_DIR`], sorted by name.
+///
+/// Archived skills keep their source's base templates. A source without an archive has no
+/// archived skills.
+pub fn load_archived(
+    store: &dyn SkillStore,
+    roots: &CatalogRoots,
+    diagnostics: &mut Diagnostics,
+) -> Vec<SkillTemplate> {
+    let names = &roots.skill_files;
+    let mut skills = Vec::new();
+    for source_root in &roots.sources {
+        let archive = source_root.join(ARCHIVE_DIR);
+        let entries = read_tool_directory(store, &archive, diagnostics);
+        if entries.is_empty() {
+            continue;
+        }
+        let bases = load_bases(store, source_root, &roots.include_paths, diagnostics);
+        skills.extend(entries.iter().filter_map(|skill_dir| {
+            load_source_skill(store, names, source_root, &bases, skill_dir, diagnostics)
+        }));
+    }
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
 }
 
 /// Read a tool directory and return sorted entries, returning empty on missing.
-fn read_tool_directory(path: &Path, diagnostics: &mut Diagnostics) -> Vec<fs::DirEntry> {
-    let mut entries = match fs::read_dir(path) {
+fn read_tool_directory(
+    store: &dyn SkillStore,
+    path: &Path,
+    diagnostics: &mut Diagnostics,
+) -> Vec<PathBuf> {
+    match store.list(path) {
         Ok(entries) => entries,
-        Err(error) if error.kind() == ErrorKind::NotFound => return Vec::new(),
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            diagnostics.debug(format!("{} does not exist", path.display()));
+            Vec::new()
+        }
         Err(error) => {
             diagnostics.warn(format!(
                 "failed to read directory {}: {error}",
                 path.display()
             ));
-            return Vec::new();
+            Vec::new()
         }
     }
-    .filter_map(|entry| entry.ok())
-    .collect::<Vec<_>>();
-
-    entries.sort_by_key(|entry| entry.file_name());
-    entries
 }
 
 /// Emit warnings for source conflicts.
@@ -204,12 +408,123 @@ fn emit_conflicts(
         let Some(primary) = skills.get(name) else {
             continue;
         };
-        diagnostics.warn(format!(
-            "skill '{name}' exists in multiple sources, using {}",
-            display_path(&primary.source_root)
-        ));
-        for path in paths {
-            diagnostics.note(format!("  - {}", display_path(path)));
-        }
+        diagnostics.warn_conflict(name, &primary.source_root, paths);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Catalog, CatalogRoots, load_archived};
+    use crate::{
+        diagnostics::Diagnostics,
+        testutil::{MemoryStore, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn loads_from_memory_store() {
+        let store = MemoryStore::default()
+            .with_file("/src/alpha/SKILL.md", &simple_skill("alpha"))
+            .with_file("/src/alpha/run.sh", "echo")
+            .with_file("/src/notes.txt", "not a skill")
+            .with_file("/claude/alpha/SKILL.md", &simple_skill("alpha"))
+            .with_file(
+                "/project/.codex/skills/beta/SKILL.md",
+                &simple_skill("beta"),
+            );
+        let roots = CatalogRoots {
+            sources: vec![PathBuf::from("/src")],
+            tools: vec![(Tool::Claude, PathBuf::from("/claude"))],
+            local: vec![(Tool::Codex, PathBuf::from("/project/.codex/skills"))],
+            ..CatalogRoots::default()
+        };
+
+        let catalog = Catalog::load_from(&store, &roots, &mut Diagnostics::default());
+
+        assert_eq!(catalog.sources.len(), 1);
+        assert!(catalog.sources.contains_key("alpha"));
+        let stats = catalog.sources["alpha"].stats;
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.size, simple_skill("alpha").len() as u64 + 4);
+        assert!(catalog.tools[&Tool::Claude].contains_key("alpha"));
+        assert!(catalog.local[&Tool::Codex].contains_key("beta"));
+    }
+
+    #[test]
+    fn resolves_directory_names_to_skill_names() {
+        let store = MemoryStore::default()
+            .with_file("/src/pdf-tools/SKILL.md", &simple_skill("pdf"))
+            .with_file("/src/xlsx/SKILL.md", &simple_skill("xlsx"))
+            .with_file("/claude/old-xlsx/SKILL.md", &simple_skill("xlsx"))
+            .with_file("/claude/pdf/SKILL.md", &simple_skill("pdf"));
+        let roots = CatalogRoots {
+            sources: vec![PathBuf::from("/src")],
+            tools: vec![(Tool::Claude, PathBuf::from("/claude"))],
+            ..CatalogRoots::default()
+        };
+
+        let catalog = Catalog::load_from(&store, &roots, &mut Diagnostics::default());
+
+        assert!(catalog.sources.contains_key("pdf"));
+        assert_eq!(catalog.resolve_name("pdf-tools"), "pdf");
+        assert_eq!(catalog.resolve_name("old-xlsx"), "xlsx");
+        assert_eq!(catalog.resolve_name("xlsx"), "xlsx");
+        assert_eq!(catalog.resolve_name("missing"), "missing");
+    }
+
+    #[test]
+    fn keeps_archived_skills_out_of_the_catalog() {
+        let store = MemoryStore::default()
+            .with_file("/src/alpha/SKILL.md", &simple_skill("alpha"))
+            .with_file("/src/archive/old/SKILL.md", &simple_skill("old"))
+            .with_file("/other/archive/older/SKILL.md", &simple_skill("older"));
+        let roots = CatalogRoots {
+            sources: vec![PathBuf::from("/src"), PathBuf::from("/other")],
+            ..CatalogRoots::default()
+        };
+        let mut diagnostics = Diagnostics::default();
+
+        let catalog = Catalog::load_from(&store, &roots, &mut diagnostics);
+        let names: Vec<&String> = catalog.sources.keys().collect();
+        assert_eq!(names, ["alpha"]);
+
+        let archived = load_archived(&store, &roots, &mut diagnostics);
+        let names: Vec<&str> = archived.iter().map(|skill| skill.name.as_str()).collect();
+        assert_eq!(names, ["old", "older"]);
+        assert_eq!(archived[1].source_root, PathBuf::from("/other"));
+    }
+
+    #[test]
+    fn renders_skills_that_extend_source_templates() {
+        let store = MemoryStore::default()
+            .with_file(
+                "/src/_templates/base-skill.md",
+                "# Usage\n{% block instructions %}{% endblock %}\n# Examples\n{% block examples %}None.{% endblock %}\n",
+            )
+            .with_file("/src/_templates/SKILL.md", &simple_skill("_templates"))
+            .with_file(
+                "/src/alpha/SKILL.md",
+                &skill_content(
+                    "alpha",
+                    "Alpha",
+                    "{% extends \"base-skill.md\" %}{% block instructions %}Run {{ tool }}.{% endblock %}",
+                ),
+            );
+        let roots = CatalogRoots {
+            sources: vec![PathBuf::from("/src")],
+            ..CatalogRoots::default()
+        };
+
+        let catalog = Catalog::load_from(&store, &roots, &mut Diagnostics::default());
+
+        assert_eq!(catalog.sources.len(), 1);
+        assert_eq!(
+            catalog
+                .render(&catalog.sources["alpha"], Tool::Codex)
+                .expect("render"),
+            "---\nname: alpha\ndescription: Alpha\n---\n# Usage\nRun codex.\n# Examples\nNone."
+        );
     }
 }
@@ -0,0 +1,152 @@
+//! Per-skill changelogs kept in a `CHANGELOG.md` beside the skill file.
+//!
+//! Each release is a `## <version> - <date>` heading followed by a bulleted list of changes,
+//! newest release first. `skills bump` adds releases, `skills list --verbose` shows the latest,
+//! and the file travels with the skill through `pack` and `import` like any other.
+
+use std::{fs, path::Path};
+
+/// File name of a skill's changelog.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Heading written at the top of a new changelog.
+const TITLE: &str = "# Changelog";
+
+/// One release recorded in a changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Version the release was made at.
+    pub(crate) version: String,
+    /// Release date as written in the heading, if any.
+    pub(crate) date: Option<String>,
+    /// Changes in the release, one per bullet.
+    pub(crate) changes: Vec<String>,
+}
+
+/// Read the releases from a skill directory's changelog, newest first.
+///
+/// A missing or unreadable changelog has no releases.
+pub fn read_releases(skill_dir: &Path) -> Vec<Release> {
+    fs::read_to_string(skill_dir.join(CHANGELOG_FILE))
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+/// Parse the releases in a changelog, in file order.
+///
+/// Continuation lines of a bullet are joined to it; text that is neither a heading nor part of
+/// a bullet is ignored.
+pub fn parse(contents: &str) -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    for line in contents.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            let (version, date) = match heading.split_once(" - ") {
+                Some((version, date)) => (version, Some(date.trim().to_string())),
+                None => (heading, None),
+            };
+            releases.push(Release {
+                version: version.trim().to_string(),
+                date,
+                changes: Vec::new(),
+            });
+            continue;
+        }
+        let Some(release) = releases.last_mut() else {
+            continue;
+        };
+        if let Some(change) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            release.changes.push(change.trim().to_string());
+        } else if line.starts_with([' ', '\t'])
+            && !line.trim().is_empty()
+            && let Some(change) = release.changes.last_mut()
+        {
+            change.push(' ');
+            change.push_str(line.trim());
+        }
+    }
+    releases
+}
+
+/// Return a changelog with `release` added above its existing releases.
+///
+/// An empty changelog gets a title first. Anything before the first release, such as the title
+/// and an introduction, is kept in place.
+pub fn add_release(contents: &str, release: &Release) -> String {
+    let mut entry = format!("## {}", release.version);
+    if let Some(date) = &release.date {
+        entry.push_str(&format!(" - {date}"));
+    }
+    entry.push_str("\n\n");
+    for change in &release.changes {
+        let mut lines = change.lines();
+        entry.push_str(&format!("- {}\n", lines.next().unwrap_or_default()));
+        for line in lines {
+            entry.push_str(&format!("  {line}\n"));
+        }
+    }
+
+    if contents.trim().is_empty() {
+        return format!("{TITLE}\n\n{entry}");
+    }
+    let first_release = contents
+        .match_indices("## ")
+        .map(|(index, _)| index)
+        .find(|index| *index == 0 || contents[..*index].ends_with('\n'));
+    match first_release {
+        Some(index) => format!("{}{entry}\n{}", &contents[..index], &contents[index..]),
+        None => format!("{}\n\n{entry}", contents.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Release, add_release, parse};
+
+    /// Build a release with one change.
+    fn release(version: &str, change: &str) -> Release {
+        Release {
+            version: version.to_string(),
+            date: Some("2026-10-15".to_string()),
+            changes: vec![change.to_string()],
+        }
+    }
+
+    #[test]
+    fn adds_releases_newest_first() {
+        let first = add_release("", &release("0.1.0", "Initial release"));
+        assert_eq!(
+            first,
+            "# Changelog\n\n## 0.1.0 - 2026-10-15\n\n- Initial release\n"
+        );
+
+        let second = add_release(
+            &first,
+            &release("0.1.1", "Fix the table parser\nfor wide rows"),
+        );
+        assert_eq!(
+            second,
+            "# Changelog\n\n## 0.1.1 - 2026-10-15\n\n- Fix the table parser\n  for wide rows\n\n\
+             ## 0.1.0 - 2026-10-15\n\n- Initial release\n"
+        );
+        assert_eq!(
+            parse(&second),
+            [
+                release("0.1.1", "Fix the table parser for wide rows"),
+                release("0.1.0", "Initial release"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_introductions_and_tolerates_loose_headings() {
+        let contents = "# Changes\n\nNotable changes to this skill.\n\n## 2.0\n\n* Rewrite\n";
+        let updated = add_release(contents, &release("2.0.1", "Fix"));
+        assert!(updated.starts_with("# Changes\n\nNotable changes to this skill.\n\n## 2.0.1"));
+
+        let releases = parse(&updated);
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[1].version, "2.0");
+        assert_eq!(releases[1].date, None);
+        assert_eq!(releases[1].changes, ["Rewrite"]);
+    }
+}
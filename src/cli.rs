@@ -1,26 +1,74 @@
 //! CLI parsing and command dispatch.
 
-use std::path::PathBuf;
+use std::{iter, path::PathBuf, time::Duration};
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 
-use crate::{commands, error::Result, tool::ToolFilter};
+use crate::{
+    archive::SymlinkPolicy,
+    commands::{
+        self, ColorChoice,
+        bump::VersionPart,
+        bundle::OnConflict,
+        export::MetadataFormat,
+        import::ImportOptions,
+        pack::PackFormat,
+        push::PushOptions,
+        render::ContextFormat,
+        search::SearchOptions,
+        shell::{self, Shell},
+    },
+    config::{ProjectDefaults, SyncDirection},
+    context::Context,
+    diagnostics::{Verbosity, WarningFormat},
+    error::Result,
+    palette::{Palette, Theme},
+    profile::{self, ProfileFormat},
+    tool::{Tool, ToolFilter},
+    units::DateFormat,
+};
 
 /// Parsed command line arguments.
 #[derive(Debug, Parser)]
 #[command(name = "skills", version, about = "Manage agent skills")]
 struct Cli {
-    /// Control colored output.
-    #[arg(long, value_enum, default_value = "auto")]
-    color: ColorMode,
-    /// Enable verbose output.
-    #[arg(long)]
-    verbose: bool,
+    /// Control colored output [default: auto].
+    #[arg(long, value_enum)]
+    color: Option<ColorMode>,
+    /// Print each warning as it happens; repeat (-vv) for debug details.
+    #[arg(long, short = 'v', action = ArgAction::Count)]
+    verbose: u8,
+    /// Format of the warnings summary printed on stderr.
+    #[arg(long, value_enum, value_name = "FORMAT", default_value = "text")]
+    warnings: WarningFormat,
+    /// Also write warnings, skipped skills, and conflicts to a file as JSON.
+    #[arg(long, value_name = "PATH")]
+    warnings_file: Option<PathBuf>,
+    /// Show timestamps as relative ages or ISO 8601 UTC times [default: the config's `dates`].
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    dates: Option<DateFormat>,
+    /// Print time spent loading the catalog, rendering, writing files, and on the network.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text"
+    )]
+    profile_perf: Option<ProfileFormat>,
     /// Command to execute (defaults to list).
     #[command(subcommand)]
     command: Option<Command>,
 }
 
+impl Cli {
+    /// Build the parser, listing subcommands alphabetically in help rather than by area.
+    fn parser() -> clap::Command {
+        Self::command().mut_subcommands(|command| command.display_order(0))
+    }
+}
+
 /// Supported color output modes.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum ColorMode {
@@ -32,45 +80,171 @@ enum ColorMode {
     Never,
 }
 
-// Commands are ordered alphabetically - maintain this order.
-/// Top-level subcommands.
+/// Top-level subcommands, grouped by area and listed alphabetically in help.
 #[derive(Debug, Subcommand)]
 enum Command {
-    /// Show diffs between sources and tool copies.
-    Diff {
-        /// Limit diffs to a single skill.
-        skill: Option<String>,
-        /// Send diff output through a pager.
+    /// Commands that set up the machine, its sources, and the session.
+    #[command(flatten)]
+    Setup(SetupCommand),
+    /// Commands that create, edit, and check source skills.
+    #[command(flatten)]
+    Authoring(AuthoringCommand),
+    /// Commands that report on skills.
+    #[command(flatten)]
+    Inspection(InspectionCommand),
+    /// Commands that move skills between sources and tools.
+    #[command(flatten)]
+    Install(InstallCommand),
+    /// Commands that import, export, and package skills.
+    #[command(flatten)]
+    Exchange(ExchangeCommand),
+}
+
+// Commands are ordered alphabetically - maintain this order.
+/// Commands that set up the machine, its sources, and the session.
+#[derive(Debug, Subcommand)]
+enum SetupCommand {
+    /// Set up a machine from a skills repository: clone it, configure it, and push to every tool.
+    Bootstrap {
+        /// URL or path of the repository to clone.
+        url: String,
+        /// Directory name for the clone [default: the repository name].
         #[arg(long)]
-        pager: Option<String>,
+        name: Option<String>,
     },
-    /// Open a skill in your editor.
-    Edit {
-        /// Name of the skill to edit.
-        skill: String,
+    /// Clone a git repository of skills and add it as a source.
+    Clone {
+        /// URL or path of the repository to clone.
+        url: String,
+        /// Directory name for the clone [default: the repository name].
+        #[arg(long)]
+        name: Option<String>,
     },
-    /// Import a skill from a ZIP file, URL, or GitHub.
-    Import {
-        /// Path to ZIP file, URL, or GitHub URL.
-        source: String,
-        /// Import to specific location: claude, codex, source, or path.
+    /// Inspect and maintain the config file.
+    Config {
+        /// Config action.
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Check the config, sources, and state, and show the team policy in force.
+    Doctor,
+    /// Show resolved config, source, tool, and state locations.
+    Env,
+    /// Run a batch of JSON-line requests against one catalog load, answering each as JSON.
+    Exec {
+        /// Read requests from standard input.
+        #[arg(long, required_unless_present = "batch", conflicts_with = "batch")]
+        stdin: bool,
+        /// Read requests from a file instead of standard input.
+        #[arg(long, value_name = "FILE")]
+        batch: Option<PathBuf>,
+    },
+    /// Initialize a skills config file.
+    Init {
+        /// Source directory to configure (repeatable); skips the source prompt.
+        #[arg(long = "source", value_name = "PATH")]
+        sources: Vec<String>,
+        /// Accept all prompts, using `~/skills` when no `--source` is given.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Replace an existing config.
+        #[arg(long, short = 'f')]
+        force: bool,
+        /// Print the config to stdout instead of writing it.
+        #[arg(long, conflicts_with_all = ["git", "remote"])]
+        print: bool,
+        /// Set the source up as a git repository with a README, `.gitignore`, and skill index.
         #[arg(long)]
-        to: Option<String>,
-        /// Import as project-local skill (.claude/skills/, .codex/skills/).
-        #[arg(long, alias = "local")]
-        project: bool,
-        /// Overwrite existing skill without prompting.
+        git: bool,
+        /// Add a git remote as `origin` (implies --git).
+        #[arg(long, value_name = "URL")]
+        remote: Option<String>,
+    },
+    /// Run a language server for skill files over stdio.
+    Lsp,
+    /// Start an interactive prompt that keeps the catalog loaded between commands.
+    Shell,
+    /// Pull git-backed sources from their upstreams and report the skills that changed.
+    UpdateSources {
+        /// Sync the modified skills to the tools afterwards.
+        #[arg(long)]
+        sync: bool,
+    },
+}
+
+// Commands are ordered alphabetically - maintain this order.
+/// Commands that create, edit, and check source skills.
+#[derive(Debug, Subcommand)]
+enum AuthoringCommand {
+    /// Remove a skill from every tool and move its source into the source's archive.
+    Archive {
+        /// Name of the skill to archive.
+        skill: String,
+        /// Preview changes without archiving.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Archive without prompting, replacing an archived skill of the same name.
         #[arg(long, short = 'f')]
         force: bool,
-        /// Preview what would be imported without extracting.
+    },
+    /// Increment a source skill's version and record the change in its CHANGELOG.md.
+    Bump {
+        /// Name of the skill to bump.
+        skill: String,
+        /// Change to record for the new version.
+        #[arg(long, short = 'm')]
+        message: String,
+        /// Part of the version to increment.
+        #[arg(long, value_enum, default_value = "patch")]
+        part: VersionPart,
+        /// Preview the new version without writing.
         #[arg(long, short = 'n')]
         dry_run: bool,
     },
-    /// Initialize a skills config file.
-    Init,
-    /// List skills and their sync status.
-    #[command(alias = "ls", alias = "status")]
-    List,
+    /// Combine several skills into one generated skill, or regenerate compositions.
+    Compose {
+        /// Name of the composed skill [default: regenerate every composition whose members changed].
+        name: Option<String>,
+        /// Comma-separated member skills, in order.
+        #[arg(long, value_delimiter = ',', requires = "name")]
+        from: Vec<String>,
+        /// Description for the composed skill [default: the members' descriptions joined].
+        #[arg(long, requires = "name")]
+        description: Option<String>,
+        /// Show what would be written without writing.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Overwrite an existing skill that is not a composition.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Suggest a trigger-focused description for a skill and write it back.
+    Describe {
+        /// Name of the skill to describe.
+        skill: String,
+        /// Generate the description by piping the skill file to this command instead.
+        #[arg(long, value_name = "COMMAND")]
+        llm_cmd: Option<String>,
+        /// Show the suggestion without writing it.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Write without prompting.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Open a skill in your editor.
+    Edit {
+        /// Name of the skill to edit.
+        skill: String,
+    },
+    /// Normalize the formatting of source skill files.
+    Fmt {
+        /// Names of skills to format (omit for all source skills).
+        skills: Vec<String>,
+        /// Report files that need formatting without changing them, failing if there are any.
+        #[arg(long)]
+        check: bool,
+    },
     /// Rename a skill across source and tools.
     Mv {
         /// Current name of the skill.
@@ -88,39 +262,204 @@ enum Command {
     New {
         /// Destination directory for the new skill.
         path: PathBuf,
+        /// Also scaffold an executable `scripts/<NAME>` helper.
+        #[arg(long, value_name = "NAME")]
+        with_script: Option<String>,
     },
-    /// Package skills into ZIP files for sharing.
-    Pack {
-        /// Names of skills to pack (omit for all skills).
-        skills: Vec<String>,
-        /// Pack all skills.
+    /// Preview rendered skill output for a specific tool.
+    Render {
+        /// Name of the skill to render.
+        #[arg(required_unless_present = "list_filters")]
+        skill: Option<String>,
+        /// Target tool to render for.
+        #[arg(long, value_enum, required_unless_present = "list_filters")]
+        tool: Option<ToolFilter>,
+        /// Prefix each line with the template conditions that produced it.
         #[arg(long)]
-        all: bool,
-        /// Output directory for ZIP files.
-        #[arg(long, short = 'o')]
-        output: Option<PathBuf>,
-        /// Pack from project-local skills instead of sources.
+        annotate: bool,
+        /// Check that the skill renders, listing the machine lookups each rendering makes.
+        #[arg(long, conflicts_with = "annotate")]
+        check: bool,
+        /// Print the variables, functions, and partials the template can use instead of
+        /// rendering it.
+        #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["annotate", "check"])]
+        context: Option<ContextFormat>,
+        /// Render for the current project, exposing its context to the template as `project`.
         #[arg(long, alias = "local")]
         project: bool,
-        /// Preview what would be packed without creating files.
+        /// List the filters templates can use, with what each does, instead of rendering.
+        #[arg(long, conflicts_with_all = ["skill", "tool", "annotate", "check", "context", "project"])]
+        list_filters: bool,
+    },
+    /// Move chosen sections of a skill into new skills of their own.
+    Split {
+        /// Name of the skill to split.
+        skill: String,
+        /// Show what would be written without writing.
         #[arg(long, short = 'n')]
         dry_run: bool,
-        /// Overwrite existing ZIP files.
+    },
+    /// Render every skill for every tool and check the assertions in its tests.toml.
+    Test {
+        /// Names of skills to test (omit for all skills).
+        skills: Vec<String>,
+        /// Rewrite each skill's __snapshots__ with the current renderings.
+        #[arg(long)]
+        update_snapshots: bool,
+    },
+    /// Move an archived skill back into its source.
+    Unarchive {
+        /// Name of the archived skill to restore.
+        skill: String,
+        /// Preview changes without restoring.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Replace a source directory already at the skill's old location.
         #[arg(long, short = 'f')]
         force: bool,
     },
-    /// Package all skills into ZIP files (deprecated: use `pack --all -o <dir>`).
-    #[command(hide = true)]
-    PackAll {
-        /// Output directory for ZIP files.
-        output: PathBuf,
-        /// Pack from project-local skills instead of sources.
-        #[arg(long, alias = "local")]
-        project: bool,
-        /// Preview what would be packed without creating files.
+    /// Validate skill files for correct structure and syntax.
+    Validate {
+        /// Name of skill to validate (omit for all skills).
+        skill: Option<String>,
+        /// Also spellcheck skill bodies and run prose lints.
+        #[arg(long)]
+        prose: bool,
+        /// Rename source directories to match their skills' frontmatter names.
+        #[arg(long)]
+        fix: bool,
+        /// Print each skill's problems and the similar descriptions as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+// Commands are ordered alphabetically - maintain this order.
+/// Commands that report on skills.
+#[derive(Debug, Subcommand)]
+enum InspectionCommand {
+    /// Show diffs between sources and tool copies.
+    Diff {
+        /// Limit diffs to a single skill.
+        skill: Option<String>,
+        /// Compare the source files of two skills with each other instead.
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["SKILL_A", "SKILL_B"],
+            conflicts_with = "skill"
+        )]
+        between: Option<Vec<String>>,
+        /// Compare the tool copy now with how it was before an operation, given by id or date.
+        #[arg(
+            long,
+            value_name = "OP_OR_TIME",
+            requires = "skill",
+            conflicts_with = "between"
+        )]
+        at: Option<String>,
+        /// Send diff output through a pager.
+        #[arg(long)]
+        pager: Option<String>,
+        /// Exit with status 8 when there are differences and 0 when there are none.
+        #[arg(long)]
+        exit_code: bool,
+        /// Print each skill's per-tool status and diffs as JSON.
+        #[arg(long, conflicts_with_all = ["between", "at", "pager"])]
+        json: bool,
+    },
+    /// List skills and their sync status.
+    #[command(alias = "ls")]
+    List {
+        /// Also list archived skills.
+        #[arg(long)]
+        archived: bool,
+        /// End with a count of skills in each status.
+        #[arg(long)]
+        summary: bool,
+        /// End with an explanation of each status and its color.
+        #[arg(long)]
+        legend: bool,
+        /// Print the listing as JSON.
+        #[arg(long, conflicts_with_all = ["summary", "legend"])]
+        json: bool,
+    },
+    /// Scan skills for prompt injection and other risky content.
+    Scan {
+        /// Names of source skills or paths to skill directories (omit for all source skills).
+        skills: Vec<String>,
+    },
+    /// Search skill files in sources, tool directories, and project-local skills.
+    Search {
+        /// Regular expression to search for.
+        pattern: String,
+        /// Matching and output options.
+        #[command(flatten)]
+        options: SearchOptions,
+    },
+    /// Display a skill file with syntax highlighting.
+    Show {
+        /// Name of the skill to display.
+        skill: String,
+        /// Show the tool copy as it was before an operation, given by id or date.
+        #[arg(long, value_name = "OP_OR_TIME")]
+        at: Option<String>,
+        /// List the snapshots taken of the skill's tool copies.
+        #[arg(long, conflicts_with_all = ["at", "pager"])]
+        history: bool,
+        /// Send output through a pager.
+        #[arg(long)]
+        pager: Option<String>,
+    },
+    /// Summarize the catalog and report skills with near-identical descriptions.
+    Stats,
+    /// Show skills that are out of sync, grouped by the action they need.
+    Status {
+        /// Also list skills that are in sync.
+        #[arg(long)]
+        all: bool,
+        /// Offer to fix each skill that needs a push or pull.
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Check installed skill copies against the hashes recorded when they were written.
+    Verify {
+        /// Names of skills to verify (omit for all recorded installs).
+        skills: Vec<String>,
+    },
+}
+
+// Commands are ordered alphabetically - maintain this order.
+/// Commands that move skills between sources and tools.
+#[derive(Debug, Subcommand)]
+enum InstallCommand {
+    /// Drop old snapshots of overwritten copies and remove stored contents nothing refers to.
+    Gc {
+        /// Drop snapshots taken more than this many days ago.
+        #[arg(long, value_name = "DAYS", default_value_t = 90)]
+        older_than: u64,
+        /// Report what would be removed without removing it.
         #[arg(long, short = 'n')]
         dry_run: bool,
-        /// Overwrite existing ZIP files.
+    },
+    /// Hold a skill at its current tool copies, so push and sync skip it (omit to list pins).
+    Pin {
+        /// Name of the skill to pin.
+        skill: Option<String>,
+    },
+    /// Move a local skill to the global skills directory.
+    #[command(alias = "uplift")]
+    Promote {
+        /// Name of the local skill to promote.
+        skill: String,
+        /// Specify tool when skill exists in several tools' local directories (claude, codex, or
+        /// gemini).
+        #[arg(long, value_enum)]
+        tool: Option<ToolFilter>,
+        /// Preview changes without moving.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Overwrite existing global skill without prompting.
         #[arg(long, short = 'f')]
         force: bool,
     },
@@ -131,6 +470,18 @@ enum Command {
         /// Target source directory when multiple are configured.
         #[arg(long)]
         to: Option<PathBuf>,
+        /// Show the full pull plan without prompting or writing.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Pull without prompting, resolving conflicts by the newest copy.
+        #[arg(long, short = 'f')]
+        force: bool,
+        /// Resolve conflicts by taking this tool's copy.
+        #[arg(long, value_enum)]
+        prefer: Option<ToolFilter>,
+        /// Remove source files the pulled copy lacks, after snapshotting the source.
+        #[arg(long)]
+        prune: bool,
     },
     /// Push source skills to tools.
     Push {
@@ -139,9 +490,9 @@ enum Command {
         /// Push all skills.
         #[arg(long)]
         all: bool,
-        /// Target tool (claude, codex, or all).
-        #[arg(long, value_enum, default_value = "all")]
-        tool: ToolFilter,
+        /// Target tool (claude, codex, gemini, or all) [default: all].
+        #[arg(long, value_enum)]
+        tool: Option<ToolFilter>,
         /// Preview changes without writing.
         #[arg(long, short = 'n')]
         dry_run: bool,
@@ -151,22 +502,30 @@ enum Command {
         /// Skip all prompts (requires --force).
         #[arg(long, short = 'y', requires = "force")]
         yes: bool,
+        /// Push into the current project's local skill directories, rendering with its context.
+        #[arg(long, alias = "local")]
+        project: bool,
+        /// Rename tool directories whose names differ from their skills' frontmatter names.
+        #[arg(long)]
+        reconcile: bool,
+        /// Push into this directory instead of a tool's, rendering as `--render-as`.
+        #[arg(long, value_name = "DIR", requires = "render_as", conflicts_with_all = ["tool", "project"])]
+        to: Option<PathBuf>,
+        /// Tool whose context renders skills pushed with `--to`.
+        #[arg(long, value_enum, value_name = "TOOL", requires = "to")]
+        render_as: Option<Tool>,
     },
-    /// Preview rendered skill output for a specific tool.
-    Render {
-        /// Name of the skill to render.
+    /// Push a source skill to an OCI registry.
+    PushOci {
+        /// Name of the skill to push.
         skill: String,
-        /// Target tool to render for.
-        #[arg(long, value_enum)]
-        tool: ToolFilter,
+        /// Registry reference, e.g. `ghcr.io/acme/skills/pdf:1.0`.
+        reference: String,
     },
-    /// Display a skill file with syntax highlighting.
-    Show {
-        /// Name of the skill to display.
-        skill: String,
-        /// Send output through a pager.
-        #[arg(long)]
-        pager: Option<String>,
+    /// Walk through out-of-sync skills one at a time, choosing which copy to keep.
+    Resolve {
+        /// Names of skills to resolve (omit for all out-of-sync skills).
+        skills: Vec<String>,
     },
     /// Sync skills between sources and tools based on timestamps.
     Sync {
@@ -178,6 +537,12 @@ enum Command {
         /// On conflict, prefer tool version (uses newest tool).
         #[arg(long, conflicts_with = "prefer_source")]
         prefer_tool: bool,
+        /// Direction for this run, overriding the configured `sync.default`.
+        #[arg(long, value_enum)]
+        direction: Option<SyncDirection>,
+        /// Write a JSON report (or Markdown, for a `.md` path) of every action taken or planned.
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
         /// Preview changes without writing.
         #[arg(long, short = 'n')]
         dry_run: bool,
@@ -185,8 +550,15 @@ enum Command {
     /// Remove a skill from tool directories.
     Unload {
         /// Name of the skill to unload.
-        skill: String,
-        /// Target tool (claude, codex, or all).
+        #[arg(
+            required_unless_present = "all_orphans",
+            conflicts_with = "all_orphans"
+        )]
+        skill: Option<String>,
+        /// Remove every installed skill that has no source.
+        #[arg(long)]
+        all_orphans: bool,
+        /// Target tool (claude, codex, gemini, or all).
         #[arg(long, value_enum, default_value = "all")]
         tool: ToolFilter,
         /// Preview changes without removing.
@@ -196,113 +568,806 @@ enum Command {
         #[arg(long, short = 'f')]
         force: bool,
     },
-    /// Move a local skill to the global skills directory.
-    #[command(alias = "uplift")]
-    Promote {
-        /// Name of the local skill to promote.
+    /// Let push and sync update a pinned skill again.
+    Unpin {
+        /// Name of the skill to unpin.
         skill: String,
-        /// Specify tool when skill exists in both .claude and .codex (claude or codex).
+    },
+    /// Push source skills to tools whenever their files change.
+    Watch {
+        /// Target tool (claude, codex, gemini, or all) [default: all].
         #[arg(long, value_enum)]
         tool: Option<ToolFilter>,
-        /// Preview changes without moving.
+        /// Milliseconds to wait for changes to settle before pushing.
+        #[arg(long, value_name = "MS", default_value_t = 300)]
+        debounce: u64,
+        /// Overwrite modified tool copies without prompting.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+}
+
+// Commands are ordered alphabetically - maintain this order.
+/// Commands that import, export, and package skills.
+#[derive(Debug, Subcommand)]
+enum ExchangeCommand {
+    /// Install quarantined skills into the targets they were imported for.
+    Approve {
+        /// Names of quarantined skills to install.
+        #[arg(required = true)]
+        skills: Vec<String>,
+        /// Overwrite existing skills without prompting.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// List the skills in a GitHub repository and pick which to import.
+    Browse {
+        /// Repository as `owner/repo[/path][@ref]` or a github.com tree URL.
+        repo: String,
+        /// Only list the skills, without prompting.
+        #[arg(long)]
+        list: bool,
+        /// Import to specific location: a tool, source, source:<name-or-path>, or a path.
+        #[arg(long)]
+        to: Option<String>,
+        /// Import as project-local skills (.claude/skills/, .codex/skills/, .gemini/skills/).
+        #[arg(long)]
+        project: bool,
+        /// Stage picked skills for `skills review` instead of installing them.
+        #[arg(long)]
+        quarantine: bool,
+    },
+    /// Preinstall skills in a devcontainer.
+    Devcontainer {
+        /// Devcontainer action.
+        #[command(subcommand)]
+        action: DevcontainerAction,
+    },
+    /// Export the skill catalog for publishing.
+    Export {
+        /// Export format.
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+    /// Bundle every source skill into a `.tar.zst` file for moving to another machine.
+    ExportCatalog {
+        /// Bundle file to write, e.g. `catalog.tar.zst`.
+        output: PathBuf,
+        /// How to handle symlinks: reject them, follow them, or store them as links.
+        #[arg(long, value_enum, default_value = "reject")]
+        symlinks: SymlinkPolicy,
+        /// Overwrite an existing bundle file.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Import skills from ZIP files, URLs, GitHub, or an OCI registry.
+    Import {
+        /// ZIP files, directories of ZIP files, URLs, GitHub URLs, `owner/repo/path[@ref]`
+        /// shorthand, or `oci://` references.
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// Import to specific location: a tool, source, source:<name-or-path>, or a path.
+        #[arg(long)]
+        to: Option<String>,
+        /// Import as project-local skill (.claude/skills/, .codex/skills/, .gemini/skills/).
+        #[arg(long, alias = "local")]
+        project: bool,
+        /// Overwrite existing skill without prompting.
+        #[arg(long, short = 'f')]
+        force: bool,
+        /// Preview what would be imported without extracting.
         #[arg(long, short = 'n')]
         dry_run: bool,
-        /// Overwrite existing global skill without prompting.
+        /// Stage skills for `skills review` instead of installing them.
+        #[arg(long)]
+        quarantine: bool,
+        /// Install skills even when the scan finds high-severity risky content.
+        #[arg(long)]
+        allow_risky: bool,
+        /// Import into a source, then push the imported skills to the tools.
+        #[arg(long, conflicts_with_all = ["project", "quarantine"])]
+        push: bool,
+        /// Tools to push to with --push (claude, codex, gemini, or all) [default: all].
+        #[arg(long, value_enum, requires = "push")]
+        tool: Option<ToolFilter>,
+    },
+    /// Merge the skills of a bundle written by `export-catalog` into a source.
+    ImportCatalog {
+        /// Bundle file to read.
+        file: PathBuf,
+        /// Source to add new skills to, by name or path (default: the only source, or ask).
+        #[arg(long)]
+        to: Option<String>,
+        /// What to do with skills that differ from the installed ones.
+        #[arg(long, value_enum, default_value = "ask")]
+        on_conflict: OnConflict,
+        /// Preview what would be imported without writing anything.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+    },
+    /// Package skills into ZIP files for sharing.
+    Pack {
+        /// Names of skills to pack (omit for all skills).
+        skills: Vec<String>,
+        /// Pack all skills.
+        #[arg(long)]
+        all: bool,
+        /// Output directory for ZIP files.
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+        /// Pack from project-local skills instead of sources.
+        #[arg(long, alias = "local")]
+        project: bool,
+        /// Output format: a ZIP archive or an OCI image layout.
+        #[arg(long, value_enum, default_value = "zip")]
+        format: PackFormat,
+        /// How to handle symlinks: reject them, follow them, or store them as links.
+        #[arg(long, value_enum, default_value = "reject")]
+        symlinks: SymlinkPolicy,
+        /// Preview what would be packed without creating files.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Overwrite existing ZIP files.
         #[arg(long, short = 'f')]
         force: bool,
     },
-    /// Validate skill files for correct structure and syntax.
-    Validate {
-        /// Name of skill to validate (omit for all skills).
-        skill: Option<String>,
+    /// Package all skills into ZIP files (deprecated: use `pack --all -o <dir>`).
+    #[command(hide = true)]
+    PackAll {
+        /// Output directory for ZIP files.
+        output: PathBuf,
+        /// Pack from project-local skills instead of sources.
+        #[arg(long, alias = "local")]
+        project: bool,
+        /// Output format: a ZIP archive or an OCI image layout.
+        #[arg(long, value_enum, default_value = "zip")]
+        format: PackFormat,
+        /// How to handle symlinks: reject them, follow them, or store them as links.
+        #[arg(long, value_enum, default_value = "reject")]
+        symlinks: SymlinkPolicy,
+        /// Preview what would be packed without creating files.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Overwrite existing ZIP files.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Show quarantined skills with their provenance, validation results, and renderings.
+    Review {
+        /// Names of quarantined skills to review (omit for all).
+        skills: Vec<String>,
+        /// Show complete renderings instead of previews.
+        #[arg(long)]
+        full: bool,
     },
 }
 
-/// Run the requested command.
+/// Actions supported by `skills config`.
+#[derive(Debug, Subcommand)]
+enum ConfigAction {
+    /// Upgrade the config file to the current format version, preserving comments.
+    Migrate {
+        /// Show the changes without writing.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+    },
+}
+
+/// Actions supported by `skills devcontainer`.
+#[derive(Debug, Subcommand)]
+enum DevcontainerAction {
+    /// Write rendered skills and an install script into a devcontainer folder.
+    Install {
+        /// Names of skills to write (omit for all).
+        skills: Vec<String>,
+        /// Tool the skills are rendered and installed for.
+        #[arg(long, value_enum)]
+        tool: Tool,
+        /// Devcontainer folder to write into [default: .devcontainer].
+        #[arg(long, value_name = "DIR")]
+        dir: Option<PathBuf>,
+        /// Preview changes without writing.
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Overwrite modified skills (shows diff before prompting).
+        #[arg(long, short = 'f')]
+        force: bool,
+        /// Skip all prompts (requires --force).
+        #[arg(long, short = 'y', requires = "force")]
+        yes: bool,
+    },
+}
+
+/// Formats supported by `skills export`.
+#[derive(Debug, Subcommand)]
+enum ExportFormat {
+    /// Render a static HTML site with per-skill pages, archives, and a catalog.json feed.
+    Site {
+        /// Directory to write the site to.
+        #[arg(long, short = 'o')]
+        output: PathBuf,
+        /// Tool to render skill pages for (all renders a section per tool).
+        #[arg(long, value_enum, default_value = "claude")]
+        tool: ToolFilter,
+        /// Write into a non-empty output directory, overwriting existing files.
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Write skill metadata (name, description, tags, version, targets) without file contents.
+    Metadata {
+        /// Output format.
+        #[arg(long, value_enum, default_value = "json")]
+        format: MetadataFormat,
+        /// File to write to instead of stdout.
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Run the requested command, reporting any error and its hint on stderr.
 pub async fn run() -> Result<()> {
-    let cli = Cli::parse();
-    let color = cli.color.into_choice();
+    let matches = Cli::parser().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let mut ctx = Context::from_env(ColorChoice::Auto, Verbosity::from_count(cli.verbose))
+        .inspect_err(|error| {
+            let palette = Palette::new(&Theme::default(), ColorChoice::Auto.enabled_for_stderr());
+            shell::report(error, &palette);
+        })?;
+    let result = run_command(&mut ctx, cli, &matches).await;
+    if let Err(error) = &result {
+        shell::report(error, &ctx.error_palette());
+    }
+    result
+}
+
+/// Apply the global options to `ctx` and run the chosen command, or `list` when none was given.
+async fn run_command(ctx: &mut Context, cli: Cli, matches: &ArgMatches) -> Result<()> {
+    let defaults = ctx.load_project_defaults()?;
+    ctx.color = cli
+        .color
+        .map(ColorMode::into_choice)
+        .or(ctx.overrides.color)
+        .or(defaults.color)
+        .unwrap_or(ColorChoice::Auto);
+    ctx.warnings = cli.warnings;
+    ctx.warnings_file = cli.warnings_file;
+    ctx.dates = cli.dates;
+
+    cli.command
+        .unwrap_or_else(Command::list)
+        .run_profiled(ctx, &defaults, matches, cli.profile_perf)
+        .await
+}
+
+impl Command {
+    /// Dispatch to the command, timing it when `--profile-perf` was given.
+    async fn run_profiled(
+        self,
+        ctx: &Context,
+        defaults: &ProjectDefaults,
+        matches: &ArgMatches,
+        format: Option<ProfileFormat>,
+    ) -> Result<()> {
+        let Some(format) = format else {
+            return self.run(ctx, defaults).await;
+        };
+        profile::start();
+        let result = self.run(ctx, defaults).await;
+        profile::finish(matches.subcommand_name().unwrap_or("list"), format);
+        result
+    }
+
+    /// The command run when none is given: `list` with its defaults.
+    fn list() -> Self {
+        Self::Inspection(InspectionCommand::List {
+            archived: false,
+            summary: false,
+            legend: false,
+            json: false,
+        })
+    }
+
+    /// Dispatch to the command implementation for its area.
+    async fn run(self, ctx: &Context, defaults: &ProjectDefaults) -> Result<()> {
+        match self {
+            Self::Setup(command) => command.run(ctx, defaults).await,
+            Self::Authoring(command) => command.run(ctx).await,
+            Self::Inspection(command) => command.run(ctx).await,
+            Self::Install(command) => command.run(ctx, defaults).await,
+            Self::Exchange(command) => command.run(ctx, defaults).await,
+        }
+    }
+
+    /// Whether the command leaves skill files untouched, so a shell can keep its catalog.
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Setup(SetupCommand::Doctor | SetupCommand::Env | SetupCommand::Exec { .. })
+                | Self::Authoring(
+                    AuthoringCommand::Compose { dry_run: true, .. }
+                        | AuthoringCommand::Describe { dry_run: true, .. }
+                        | AuthoringCommand::Fmt { check: true, .. }
+                        | AuthoringCommand::Render { .. }
+                        | AuthoringCommand::Split { dry_run: true, .. }
+                        | AuthoringCommand::Test { .. }
+                        | AuthoringCommand::Validate { .. }
+                )
+                | Self::Inspection(
+                    InspectionCommand::Diff { .. }
+                        | InspectionCommand::List { .. }
+                        | InspectionCommand::Scan { .. }
+                        | InspectionCommand::Search { .. }
+                        | InspectionCommand::Show { .. }
+                        | InspectionCommand::Stats
+                        | InspectionCommand::Status { fix: false, .. }
+                        | InspectionCommand::Verify { .. }
+                )
+                | Self::Install(
+                    InstallCommand::Gc { .. }
+                        | InstallCommand::Pin { .. }
+                        | InstallCommand::Unpin { .. }
+                )
+                | Self::Exchange(
+                    ExchangeCommand::ExportCatalog { .. } | ExchangeCommand::Review { .. }
+                )
+        )
+    }
+}
 
-    // Match arms are ordered alphabetically - maintain this order.
-    match cli.command.unwrap_or(Command::List) {
-        Command::Diff { skill, pager } => {
-            commands::diff::run(color, cli.verbose, skill, pager).await
+impl SetupCommand {
+    /// Dispatch to the command implementation.
+    async fn run(self, ctx: &Context, defaults: &ProjectDefaults) -> Result<()> {
+        // Match arms are ordered alphabetically - maintain this order.
+        match self {
+            Self::Bootstrap { url, name } => commands::bootstrap::run(ctx, url, name).await,
+            Self::Clone { url, name } => commands::clone::run(ctx, url, name).await,
+            Self::Config { action } => action.run(ctx).await,
+            Self::Doctor => commands::doctor::run(ctx).await,
+            Self::Env => commands::env::run(ctx).await,
+            Self::Exec { stdin: _, batch } => commands::exec::run(ctx, batch).await,
+            Self::Init {
+                sources,
+                yes,
+                force,
+                print,
+                git,
+                remote,
+            } => {
+                let git = commands::init::GitSetup {
+                    enabled: git || remote.is_some(),
+                    remote,
+                };
+                commands::init::run(ctx, sources, yes, force, print, git).await
+            }
+            Self::Lsp => commands::lsp::run(ctx).await,
+            Self::Shell => shell(ctx, defaults).await,
+            Self::UpdateSources { sync } => commands::update_sources::run(ctx, sync).await,
+        }
+    }
+}
+
+impl AuthoringCommand {
+    /// Dispatch to the command implementation.
+    async fn run(self, ctx: &Context) -> Result<()> {
+        // Match arms are ordered alphabetically - maintain this order.
+        match self {
+            Self::Archive {
+                skill,
+                dry_run,
+                force,
+            } => commands::archive::run(ctx, skill, dry_run, force).await,
+            Self::Bump {
+                skill,
+                message,
+                part,
+                dry_run,
+            } => commands::bump::run(ctx, skill, message, part, dry_run).await,
+            Self::Compose {
+                name,
+                from,
+                description,
+                dry_run,
+                force,
+            } => commands::compose::run(ctx, name, from, description, dry_run, force).await,
+            Self::Describe {
+                skill,
+                llm_cmd,
+                dry_run,
+                force,
+            } => commands::describe::run(ctx, skill, llm_cmd, dry_run, force).await,
+            Self::Edit { skill } => commands::edit::run(ctx, skill).await,
+            Self::Fmt { skills, check } => commands::fmt::run(ctx, skills, check).await,
+            Self::Mv {
+                old_name,
+                new_name,
+                dry_run,
+                force,
+            } => commands::mv::run(ctx, old_name, new_name, dry_run, force).await,
+            Self::New { path, with_script } => commands::new::run(ctx, path, with_script).await,
+            Self::Render {
+                skill,
+                tool,
+                annotate,
+                check,
+                context,
+                project,
+                list_filters,
+            } => match skill.zip(tool) {
+                Some((skill, tool)) if !list_filters => {
+                    commands::render::run(ctx, skill, tool, annotate, check, context, project).await
+                }
+                _ => commands::render::list_filters(ctx).await,
+            },
+            Self::Split { skill, dry_run } => commands::split::run(ctx, skill, dry_run).await,
+            Self::Test {
+                skills,
+                update_snapshots,
+            } => commands::test::run(ctx, skills, update_snapshots).await,
+            Self::Unarchive {
+                skill,
+                dry_run,
+                force,
+            } => commands::archive::run_unarchive(ctx, skill, dry_run, force).await,
+            Self::Validate {
+                skill,
+                prose,
+                fix,
+                json,
+            } => commands::validate::run(ctx, skill, prose, fix, json).await,
         }
-        Command::Edit { skill } => commands::edit::run(cli.verbose, skill).await,
-        Command::Import {
-            source,
-            to,
-            project,
-            force,
-            dry_run,
-        } => commands::import::run(color, cli.verbose, source, to, project, force, dry_run).await,
-        Command::Init => commands::init::run().await,
-        Command::List => commands::list::run(color, cli.verbose).await,
-        Command::Mv {
-            old_name,
-            new_name,
-            dry_run,
-            force,
-        } => commands::mv::run(color, cli.verbose, old_name, new_name, dry_run, force).await,
-        Command::New { path } => commands::new::run(path).await,
-        Command::Pack {
-            skills,
-            all,
-            output,
-            project,
-            dry_run,
-            force,
-        } => commands::pack::run(color, cli.verbose, skills, all, output, project, dry_run, force).await,
-        Command::PackAll {
-            output,
-            project,
-            dry_run,
-            force,
-        } => commands::pack::run_all(color, cli.verbose, output, project, dry_run, force).await,
-        Command::Pull { skill, to } => commands::pull::run(color, cli.verbose, skill, to).await,
-        Command::Push {
-            skills,
-            all,
-            tool,
-            dry_run,
-            force,
-            yes,
-        } => commands::push::run(color, cli.verbose, skills, all, tool, dry_run, force, yes).await,
-        Command::Render { skill, tool } => {
-            commands::render::run(color, cli.verbose, skill, tool).await
+    }
+}
+
+impl InspectionCommand {
+    /// Dispatch to the command implementation.
+    async fn run(self, ctx: &Context) -> Result<()> {
+        // Match arms are ordered alphabetically - maintain this order.
+        match self {
+            Self::Diff {
+                skill,
+                between,
+                at,
+                pager,
+                exit_code,
+                json,
+            } => match (between.as_deref(), at, skill) {
+                (Some([first, second]), _, _) => {
+                    commands::diff::run_between(ctx, first, second, pager, exit_code).await
+                }
+                (_, Some(at), Some(skill)) => {
+                    commands::diff::run_at(ctx, &skill, &at, pager, exit_code).await
+                }
+                (_, _, skill) => commands::diff::run(ctx, skill, pager, exit_code, json).await,
+            },
+            Self::List {
+                archived,
+                summary,
+                legend,
+                json,
+            } => commands::list::run(ctx, archived, summary, legend, json).await,
+            Self::Scan { skills } => commands::scan::run(ctx, skills).await,
+            Self::Search { pattern, options } => {
+                commands::search::run(ctx, &pattern, options).await
+            }
+            Self::Show {
+                skill,
+                at,
+                history,
+                pager,
+            } => match at {
+                _ if history => commands::show::run_history(ctx, skill).await,
+                Some(at) => commands::show::run_at(ctx, skill, at, pager).await,
+                None => commands::show::run(ctx, skill, pager).await,
+            },
+            Self::Stats => commands::stats::run(ctx).await,
+            Self::Status { all, fix } => commands::status::run(ctx, all, fix).await,
+            Self::Verify { skills } => commands::verify::run(ctx, skills).await,
+        }
+    }
+}
+
+impl InstallCommand {
+    /// Dispatch to the command implementation.
+    async fn run(self, ctx: &Context, defaults: &ProjectDefaults) -> Result<()> {
+        // Match arms are ordered alphabetically - maintain this order.
+        match self {
+            Self::Gc {
+                older_than,
+                dry_run,
+            } => commands::gc::run(ctx, older_than, dry_run).await,
+            Self::Pin { skill } => commands::pin::run(ctx, skill).await,
+            Self::Promote {
+                skill,
+                tool,
+                dry_run,
+                force,
+            } => commands::promote::run(ctx, skill, tool, dry_run, force).await,
+            Self::Pull {
+                skill,
+                to,
+                dry_run,
+                force,
+                prefer,
+                prune,
+            } => commands::pull::run(ctx, skill, to, dry_run, force, prefer, prune).await,
+            Self::Push {
+                skills,
+                all,
+                tool,
+                dry_run,
+                force,
+                yes,
+                project,
+                reconcile,
+                to,
+                render_as,
+            } => {
+                let tool = tool
+                    .or(defaults.push.default_tool)
+                    .unwrap_or(ToolFilter::All);
+                commands::push::run(
+                    ctx,
+                    skills,
+                    tool.to_tools(),
+                    PushOptions {
+                        all,
+                        dry_run,
+                        force,
+                        yes,
+                        project,
+                        reconcile,
+                        to: to.zip(render_as),
+                    },
+                )
+                .await
+            }
+            Self::PushOci { skill, reference } => {
+                commands::push_oci::run(ctx, skill, reference).await
+            }
+            Self::Resolve { skills } => commands::resolve::run(ctx, skills).await,
+            Self::Sync {
+                skills,
+                prefer_source,
+                prefer_tool,
+                direction,
+                report,
+                dry_run,
+            } => {
+                let (prefer_source, prefer_tool) =
+                    defaults.sync.prefer_flags(prefer_source, prefer_tool);
+                commands::sync::run(
+                    ctx,
+                    skills,
+                    prefer_source,
+                    prefer_tool,
+                    direction,
+                    report,
+                    dry_run,
+                )
+                .await
+            }
+            Self::Unload {
+                skill,
+                all_orphans: _,
+                tool,
+                dry_run,
+                force,
+            } => commands::unload::run(ctx, skill, tool, dry_run, force).await,
+            Self::Unpin { skill } => commands::pin::run_unpin(ctx, skill).await,
+            Self::Watch {
+                tool,
+                debounce,
+                force,
+            } => {
+                let tool = tool
+                    .or(defaults.push.default_tool)
+                    .unwrap_or(ToolFilter::All);
+                commands::watch::run(ctx, tool.to_tools(), Duration::from_millis(debounce), force)
+                    .await
+            }
         }
-        Command::Show { skill, pager } => {
-            commands::show::run(color, cli.verbose, skill, pager).await
+    }
+}
+
+impl ExchangeCommand {
+    /// Dispatch to the command implementation.
+    async fn run(self, ctx: &Context, defaults: &ProjectDefaults) -> Result<()> {
+        // Match arms are ordered alphabetically - maintain this order.
+        match self {
+            Self::Approve { skills, force } => commands::approve::run(ctx, skills, force).await,
+            Self::Browse {
+                repo,
+                list,
+                to,
+                project,
+                quarantine,
+            } => commands::browse::run(ctx, repo, list, to, project, quarantine).await,
+            Self::Devcontainer { action } => action.run(ctx).await,
+            Self::Export { format } => format.run(ctx).await,
+            Self::ExportCatalog {
+                output,
+                symlinks,
+                force,
+            } => commands::bundle::export(ctx, output, symlinks, force).await,
+            Self::Import {
+                sources,
+                to,
+                project,
+                force,
+                dry_run,
+                quarantine,
+                allow_risky,
+                push,
+                tool,
+            } => {
+                let push = push.then(|| {
+                    tool.or(defaults.push.default_tool)
+                        .unwrap_or(ToolFilter::All)
+                });
+                commands::import::run(
+                    ctx,
+                    sources,
+                    ImportOptions {
+                        to,
+                        local: project,
+                        force,
+                        dry_run,
+                        quarantine,
+                        allow_risky,
+                        push,
+                    },
+                )
+                .await
+            }
+            Self::ImportCatalog {
+                file,
+                to,
+                on_conflict,
+                dry_run,
+            } => commands::bundle::import(ctx, file, to, on_conflict, dry_run).await,
+            Self::Pack {
+                skills,
+                all,
+                output,
+                project,
+                format,
+                symlinks,
+                dry_run,
+                force,
+            } => {
+                commands::pack::run(
+                    ctx, skills, all, output, project, format, symlinks, dry_run, force,
+                )
+                .await
+            }
+            Self::PackAll {
+                output,
+                project,
+                format,
+                symlinks,
+                dry_run,
+                force,
+            } => {
+                commands::pack::run_all(ctx, output, project, format, symlinks, dry_run, force)
+                    .await
+            }
+            Self::Review { skills, full } => commands::review::run(ctx, skills, full).await,
         }
-        Command::Sync {
-            skills,
-            prefer_source,
-            prefer_tool,
-            dry_run,
-        } => commands::sync::run(color, cli.verbose, skills, prefer_source, prefer_tool, dry_run).await,
-        Command::Unload {
-            skill,
-            tool,
-            dry_run,
-            force,
-        } => commands::unload::run(color, cli.verbose, skill, tool, dry_run, force).await,
-        Command::Promote {
-            skill,
-            tool,
-            dry_run,
-            force,
-        } => commands::promote::run(color, cli.verbose, skill, tool, dry_run, force).await,
-        Command::Validate { skill } => commands::validate::run(color, cli.verbose, skill).await,
     }
 }
 
+/// Run commands read from an interactive prompt against one session catalog.
+///
+/// The catalog is loaded on the first command and reused until a command that may change skill
+/// files runs. Errors are printed and the session continues.
+async fn shell(ctx: &Context, defaults: &ProjectDefaults) -> Result<()> {
+    commands::init::ensure(ctx).await?;
+    let session = ctx.with_session();
+    let cli = Cli::parser();
+    let mut prompt = Shell::new(
+        cli.get_subcommands()
+            .flat_map(|command| iter::once(command.get_name()).chain(command.get_all_aliases())),
+    );
+    prompt.set_skills(&session.load_catalog(&session.load_config()?, &mut session.diagnostics()));
+
+    while let Some(words) = prompt.read()? {
+        if words.is_empty() {
+            continue;
+        }
+        let parsed = Cli::parser()
+            .try_get_matches_from(iter::once("skills".to_string()).chain(words))
+            .and_then(|matches| Ok((Cli::from_arg_matches(&matches)?, matches)));
+        let (parsed, matches) = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                error.print().ok();
+                continue;
+            }
+        };
+        let command = parsed.command.unwrap_or_else(Command::list);
+        if matches!(command, Command::Setup(SetupCommand::Shell)) {
+            eprintln!("Already in a shell; type exit to leave it");
+            continue;
+        }
+
+        let mut ctx = session.clone();
+        ctx.verbosity = ctx.verbosity.max(Verbosity::from_count(parsed.verbose));
+        if let Some(color) = parsed.color {
+            ctx.color = color.into_choice();
+        }
+        if parsed.warnings != WarningFormat::Text {
+            ctx.warnings = parsed.warnings;
+        }
+        if parsed.warnings_file.is_some() {
+            ctx.warnings_file = parsed.warnings_file;
+        }
+        if parsed.dates.is_some() {
+            ctx.dates = parsed.dates;
+        }
+        let read_only = command.is_read_only();
+        if let Err(error) =
+            Box::pin(command.run_profiled(&ctx, defaults, &matches, parsed.profile_perf)).await
+        {
+            shell::report(&error, &ctx.error_palette());
+        }
+        if !read_only {
+            session.invalidate_catalog();
+            if let Ok(config) = session.load_config() {
+                prompt.set_skills(&session.load_catalog(&config, &mut session.diagnostics()));
+            }
+        }
+    }
+    Ok(())
+}
+
 impl ColorMode {
     /// Convert a CLI color mode into a color choice.
-    fn into_choice(self) -> commands::ColorChoice {
+    fn into_choice(self) -> ColorChoice {
+        match self {
+            Self::Auto => ColorChoice::Auto,
+            Self::Always => ColorChoice::Always,
+            Self::Never => ColorChoice::Never,
+        }
+    }
+}
+
+impl ConfigAction {
+    /// Run the config subcommand.
+    async fn run(self, ctx: &Context) -> Result<()> {
+        match self {
+            Self::Migrate { dry_run } => commands::config::migrate(ctx, dry_run).await,
+        }
+    }
+}
+
+impl DevcontainerAction {
+    /// Run the devcontainer subcommand.
+    async fn run(self, ctx: &Context) -> Result<()> {
+        match self {
+            Self::Install {
+                skills,
+                tool,
+                dir,
+                dry_run,
+                force,
+                yes,
+            } => commands::devcontainer::install(ctx, skills, tool, dir, dry_run, force, yes).await,
+        }
+    }
+}
+
+impl ExportFormat {
+    /// Run the export subcommand.
+    async fn run(self, ctx: &Context) -> Result<()> {
         match self {
-            Self::Auto => commands::ColorChoice::Auto,
-            Self::Always => commands::ColorChoice::Always,
-            Self::Never => commands::ColorChoice::Never,
+            Self::Site {
+                output,
+                tool,
+                force,
+            } => commands::export::site(ctx, output, tool, force).await,
+            Self::Metadata { format, output } => {
+                commands::export::metadata(ctx, format, output).await
+            }
         }
     }
 }
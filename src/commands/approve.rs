@@ -0,0 +1,164 @@
+//! Implementation of the `skills approve` command.
+
+use std::{fs, path::Path};
+
+use crate::{
+    commands::init::{self, copy_dir},
+    config::Config,
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+    quarantine::{Quarantine, QuarantinedSkill},
+    state::{InstallOrigin, State},
+};
+
+/// Execute the approve command, installing each named quarantined skill into its targets.
+///
+/// Every target is checked before anything is written, so a skill whose target already exists is
+/// left in quarantine unless `force` is set.
+pub async fn run(ctx: &Context, skills: Vec<String>, force: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let quarantine = Quarantine::new(&ctx.quarantine_dir);
+
+    let approved = skills
+        .iter()
+        .map(|name| quarantine.get(name))
+        .collect::<Result<Vec<_>>>()?;
+    if !force {
+        for skill in &approved {
+            if let Some(target) = skill.record.targets.iter().find(|target| target.exists()) {
+                return Err(Error::SkillExists {
+                    name: skill.name.clone(),
+                    path: target.clone(),
+                });
+            }
+        }
+    }
+
+    let mut state = ctx.load_state()?;
+    for skill in &approved {
+        install(&config, skill, &mut state)?;
+        quarantine.remove(&skill.name)?;
+        println!(
+            "{} {}",
+            palette.paint(Role::Success, "Approved"),
+            palette.paint(Role::SkillName, &skill.name)
+        );
+        for target in &skill.record.targets {
+            println!("  {}", display_path(target));
+        }
+    }
+    state.save(&ctx.state_path)?;
+
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Copy a quarantined skill into each of its targets, snapshotting and recording installs
+/// outside the sources.
+fn install(config: &Config, skill: &QuarantinedSkill, state: &mut State) -> Result<()> {
+    for target in &skill.record.targets {
+        let tracked = !config
+            .sources()
+            .iter()
+            .any(|source| target.starts_with(source));
+        if tracked {
+            state.snapshot(target, InstallOrigin::Import)?;
+        }
+        install_copy(&skill.skill_dir, target)?;
+        if tracked {
+            state.record(target, InstallOrigin::Import)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a skill into a staging directory beside the target, then move it into place.
+fn install_copy(from: &Path, target: &Path) -> Result<()> {
+    let file_name = target.file_name().ok_or_else(|| Error::InvalidPath {
+        path: target.to_path_buf(),
+    })?;
+    let staging = target.with_file_name(format!(".{}.partial", file_name.to_string_lossy()));
+    remove_dir(&staging)?;
+    if let Err(error) = copy_dir(from, &staging) {
+        fs::remove_dir_all(&staging).ok();
+        return Err(error);
+    }
+    remove_dir(target)?;
+    fs::rename(&staging, target).map_err(|source| Error::SkillMove {
+        from: staging.clone(),
+        to: target.to_path_buf(),
+        source,
+    })
+}
+
+/// Remove a directory tree if it exists.
+fn remove_dir(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    fs::remove_dir_all(path).map_err(|source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::run;
+    use crate::{
+        commands::import::{self, ImportOptions},
+        error::Error,
+        quarantine::Quarantine,
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn installs_quarantined_skills_on_approval() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        let archive = fixture.work_dir().join("alpha.zip");
+        let mut zip = ZipWriter::new(fs::File::create(&archive).expect("create"));
+        zip.start_file("alpha/SKILL.md", SimpleFileOptions::default())
+            .expect("start");
+        zip.write_all(simple_skill("alpha").as_bytes())
+            .expect("write");
+        zip.finish().expect("finish");
+
+        import::run(
+            &ctx,
+            vec![archive.display().to_string()],
+            ImportOptions {
+                to: Some("claude".to_string()),
+                quarantine: true,
+                ..ImportOptions::default()
+            },
+        )
+        .await
+        .expect("import");
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "alpha"));
+        let quarantine = Quarantine::new(&ctx.quarantine_dir);
+        assert_eq!(quarantine.list().expect("list").len(), 1);
+
+        run(&ctx, vec!["alpha".to_string()], false)
+            .await
+            .expect("approve");
+        assert!(fixture.tool_skill_exists(Tool::Claude, "alpha"));
+        assert!(quarantine.list().expect("list").is_empty());
+
+        let missing = run(&ctx, vec!["alpha".to_string()], false).await;
+        assert!(matches!(
+            missing,
+            Err(Error::QuarantinedSkillNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+//! Implementation of the `skills archive` and `skills unarchive` commands.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use inquire::{Confirm, error::InquireError};
+
+use crate::{
+    catalog::load_archived,
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+    skill::ARCHIVE_DIR,
+    store::FsStore,
+    tool::Tool,
+};
+
+/// Execute the archive command, removing a skill from every tool and moving its source into the
+/// source's archive.
+pub async fn run(ctx: &Context, skill: String, dry_run: bool, force: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let skill = catalog.resolve_name(&skill);
+
+    let template = catalog
+        .sources
+        .get(&skill)
+        .ok_or_else(|| Error::SkillNotFound {
+            name: skill.clone(),
+        })?;
+    let source_dir = template.skill_dir.clone();
+    let dir_name = source_dir.file_name().ok_or_else(|| Error::InvalidPath {
+        path: source_dir.clone(),
+    })?;
+    let archived_dir = template.source_root.join(ARCHIVE_DIR).join(dir_name);
+    if archived_dir.exists() && !force {
+        return Err(Error::SkillExists {
+            name: skill,
+            path: archived_dir,
+        });
+    }
+    let installs: Vec<(Tool, PathBuf)> = Tool::all()
+        .into_iter()
+        .filter(|tool| {
+            catalog
+                .tools
+                .get(tool)
+                .is_some_and(|skills| skills.contains_key(&skill))
+        })
+        .map(|tool| (tool, ctx.tool_dir(tool).join(&skill)))
+        .collect();
+
+    println!(
+        "{} '{}'",
+        palette.paint(
+            Role::Action,
+            if dry_run {
+                "Would archive"
+            } else {
+                "Archiving"
+            }
+        ),
+        palette.paint(Role::SkillName, &skill)
+    );
+    println!();
+    println!(
+        "  source: {} -> {}",
+        display_path(&source_dir),
+        display_path(&archived_dir)
+    );
+    for (tool, dir) in &installs {
+        println!("  {}: remove {}", tool.id(), display_path(dir));
+    }
+
+    if dry_run {
+        println!();
+        println!("Dry run - no changes made.");
+        return Ok(());
+    }
+    if !force {
+        println!();
+        if !confirm(&format!("Archive '{skill}'?"))? {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut state = ctx.load_state()?;
+    for (_, dir) in &installs {
+        fs::remove_dir_all(dir).map_err(|source| Error::SkillWrite {
+            path: dir.clone(),
+            source,
+        })?;
+        state.forget(dir);
+    }
+    state.save(&ctx.state_path)?;
+    move_dir(&source_dir, &archived_dir)?;
+
+    println!();
+    println!("Done. Run `skills unarchive {skill}` to restore it.");
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Execute the unarchive command, moving an archived skill back into its source.
+///
+/// Tool copies are not restored; push the skill to reinstall them.
+pub async fn run_unarchive(ctx: &Context, skill: String, dry_run: bool, force: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let archived = load_archived(&FsStore, &ctx.catalog_roots(&config), &mut diagnostics);
+    let template = archived
+        .iter()
+        .find(|template| template.name == skill || template.dir_name() == skill)
+        .ok_or_else(|| Error::SkillNotFound {
+            name: skill.clone(),
+        })?;
+    let archived_dir = template.skill_dir.clone();
+    let dir_name = archived_dir.file_name().ok_or_else(|| Error::InvalidPath {
+        path: archived_dir.clone(),
+    })?;
+    let source_dir = template.source_root.join(dir_name);
+    let skill = template.name.clone();
+    if let Some(existing) = catalog.sources.get(&skill) {
+        return Err(Error::SkillExists {
+            name: skill,
+            path: existing.skill_dir.clone(),
+        });
+    }
+    if source_dir.exists() && !force {
+        return Err(Error::SkillExists {
+            name: skill,
+            path: source_dir,
+        });
+    }
+
+    println!(
+        "{} '{}'",
+        palette.paint(
+            Role::Action,
+            if dry_run {
+                "Would restore"
+            } else {
+                "Restoring"
+            }
+        ),
+        palette.paint(Role::SkillName, &skill)
+    );
+    println!();
+    println!(
+        "  source: {} -> {}",
+        display_path(&archived_dir),
+        display_path(&source_dir)
+    );
+    if dry_run {
+        println!();
+        println!("Dry run - no changes made.");
+        return Ok(());
+    }
+
+    move_dir(&archived_dir, &source_dir)?;
+
+    println!();
+    println!("Done. Run `skills push {skill}` to install it again.");
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Move a skill directory, replacing anything already at the destination.
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    let move_error = |source| Error::SkillMove {
+        from: from.to_path_buf(),
+        to: to.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(move_error)?;
+    }
+    if to.exists() {
+        fs::remove_dir_all(to).map_err(move_error)?;
+    }
+    fs::rename(from, to).map_err(move_error)
+}
+
+/// Prompt for confirmation.
+fn confirm(message: &str) -> Result<bool> {
+    match Confirm::new(message).with_default(false).prompt() {
+        Ok(value) => Ok(value),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, run_unarchive};
+    use crate::{
+        error::Error,
+        skill::ARCHIVE_DIR,
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn archives_and_restores_skills() {
+        let fixture = TestFixture::new()
+            .with_source_skill("old", &simple_skill("old"))
+            .with_tool_skill(Tool::Claude, "old", &simple_skill("old"))
+            .with_tool_skill(Tool::Codex, "old", &simple_skill("old"));
+        let ctx = fixture.context();
+
+        run(&ctx, "old".to_string(), true, false)
+            .await
+            .expect("dry run");
+        assert!(fixture.source_skill_exists("old"));
+
+        run(&ctx, "old".to_string(), false, true)
+            .await
+            .expect("archive");
+        assert!(!fixture.source_skill_exists("old"));
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "old"));
+        assert!(!fixture.tool_skill_exists(Tool::Codex, "old"));
+        assert!(fixture.source_dir().join(ARCHIVE_DIR).join("old").is_dir());
+        assert!(fixture.catalog().sources.is_empty());
+
+        let error = run(&ctx, "old".to_string(), false, true)
+            .await
+            .expect_err("already archived");
+        assert!(matches!(error, Error::SkillNotFound { .. }));
+
+        run_unarchive(&ctx, "old".to_string(), false, false)
+            .await
+            .expect("unarchive");
+        assert!(fixture.source_skill_exists("old"));
+        assert!(!fixture.source_dir().join(ARCHIVE_DIR).join("old").exists());
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "old"));
+    }
+}
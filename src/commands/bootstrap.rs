@@ -0,0 +1,167 @@
+//! Implementation of the `skills bootstrap` command.
+
+use std::{path::Path, slice};
+
+use crate::{
+    catalog::{Catalog, CatalogRoots},
+    commands::{
+        clone, init,
+        push::{self, PushOptions},
+    },
+    context::Context,
+    error::Result,
+    palette::Role,
+    paths::display_path,
+    tool::Tool,
+    units::format_count,
+};
+
+/// Execute the bootstrap command: clone a skills repository, make it a source, and push every
+/// skill to the tools installed on this machine, without prompting.
+///
+/// A clone already in place is reused, so the command can run again from a dotfiles script. A
+/// missing config is created with the clone as its only source. Copies that differ from the
+/// source are overwritten; `skills show --history` keeps what they held.
+pub async fn run(ctx: &Context, url: String, name: Option<String>) -> Result<()> {
+    let target = clone::clone_target(ctx, &url, name)?;
+    let cloned = if target.exists() {
+        println!("Using existing clone at {}", display_path(&target));
+        false
+    } else {
+        clone::clone_into(ctx, &url, &target)?;
+        true
+    };
+
+    let config_status = if !ctx.config_path.is_file() {
+        init::write_config(
+            &ctx.config_path,
+            &init::render_config(slice::from_ref(&target))?,
+        )?;
+        "created"
+    } else if ctx.load_config()?.sources().contains(&target) {
+        "unchanged"
+    } else {
+        clone::add_to_config(ctx, &target)?;
+        "source added"
+    };
+
+    let tools = detect_tools(ctx);
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let roots = CatalogRoots {
+        sources: vec![target.canonicalize().unwrap_or_else(|_| target.clone())],
+        skill_files: config.skill_files.clone(),
+        include_paths: config.include_paths.clone(),
+        ..CatalogRoots::default()
+    };
+    let provided = Catalog::load(&roots, &mut diagnostics).sources.len();
+
+    println!();
+    if tools.is_empty() {
+        println!("No tools detected; run `skills push` once one is installed.");
+    } else {
+        push::run(
+            ctx,
+            Vec::new(),
+            tools.clone(),
+            PushOptions {
+                all: true,
+                force: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await?;
+    }
+
+    let tool_names: Vec<&str> = tools.iter().map(|tool| tool.id()).collect();
+    println!();
+    println!("{}", palette.paint(Role::Heading, "Bootstrap:"));
+    println!(
+        "  {} {} ({}, {})",
+        palette.paint(Role::Label, "source:"),
+        display_path(&target),
+        if cloned { "cloned" } else { "existing" },
+        format_count(provided, "skill")
+    );
+    println!(
+        "  {} {} ({config_status})",
+        palette.paint(Role::Label, "config:"),
+        display_path(&ctx.config_path)
+    );
+    println!(
+        "  {} {}",
+        palette.paint(Role::Label, "tools: "),
+        if tool_names.is_empty() {
+            "none detected".to_string()
+        } else {
+            tool_names.join(", ")
+        }
+    );
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Return the tools installed on this machine: those whose configuration directory, the parent of
+/// their skill directory, exists.
+fn detect_tools(ctx: &Context) -> Vec<Tool> {
+    Tool::all()
+        .into_iter()
+        .filter(|&tool| ctx.tool_dir(tool).parent().is_some_and(Path::is_dir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::run;
+    use crate::{
+        config::Config,
+        git,
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn clones_configures_and_pushes() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        fs::remove_file(&ctx.config_path).expect("remove config");
+        let upstream = fixture.work_dir().join("team-skills");
+        fs::create_dir_all(upstream.join("shared")).expect("mkdir");
+        fs::write(
+            upstream.join("shared").join("SKILL.md"),
+            simple_skill("shared"),
+        )
+        .expect("write");
+        git::run(&upstream, &["init", "--quiet"]).expect("init");
+        git::run(&upstream, &["add", "-A"]).expect("add");
+        git::run(
+            &upstream,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "Add shared",
+            ],
+        )
+        .expect("commit");
+        let url = upstream.to_string_lossy().to_string();
+
+        run(&ctx, url.clone(), None).await.expect("bootstrap");
+        let target = ctx.clone_dir.join("team-skills");
+        let config = Config::load(&ctx.config_path, None).expect("load");
+        assert_eq!(config.sources(), [target]);
+        for tool in Tool::all() {
+            assert!(fixture.tool_skill_exists(tool, "shared"));
+        }
+
+        run(&ctx, url, None).await.expect("bootstrap again");
+    }
+}
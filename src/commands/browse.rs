@@ -0,0 +1,145 @@
+//! Implementation of the `skills browse` command.
+
+use std::io::{self, IsTerminal};
+
+use inquire::{MultiSelect, error::InquireError};
+
+use crate::{
+    commands::{
+        import::{self, ImportOptions},
+        init,
+    },
+    context::Context,
+    error::{Error, Result},
+    github::{GithubApi, GithubSource, RemoteSkill},
+    http::{build_client, cancellable},
+    palette::Role,
+};
+
+/// Execute the browse command, listing the skills in a GitHub repository and importing the ones
+/// picked interactively.
+///
+/// With `list`, or when standard input is not a terminal, the skills are only listed. Picked
+/// skills are imported at the resolved ref, so every pick comes from the same tree.
+pub async fn run(
+    ctx: &Context,
+    repo: String,
+    list: bool,
+    to: Option<String>,
+    project: bool,
+    quarantine: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+
+    let mut source = GithubSource::from_tree_url(&repo)
+        .or_else(|| GithubSource::from_shorthand(&repo))
+        .ok_or_else(|| Error::InvalidGithubRepo { repo: repo.clone() })?;
+    let client = build_client(&config.network, &config.import)?;
+    cancellable(source.resolve_ref(&client, &config.import)).await?;
+    let api = GithubApi::new(&client, &config.import);
+    let skills = cancellable(api.list_skills(&source, &config.skill_files)).await?;
+    if skills.is_empty() {
+        println!("No skills found in {source}.");
+        return Ok(());
+    }
+
+    println!(
+        "{} skill(s) in {}:",
+        skills.len(),
+        palette.paint(Role::Label, &source.to_string())
+    );
+    let width = skills
+        .iter()
+        .map(|skill| skill.name.len())
+        .max()
+        .unwrap_or(0);
+    for skill in &skills {
+        println!(
+            "  {}  {}",
+            palette.paint(Role::SkillName, &format!("{:<width$}", skill.name)),
+            palette.paint(Role::Path, &skill.path)
+        );
+    }
+
+    if list || !io::stdin().is_terminal() {
+        println!();
+        println!(
+            "Import one with `skills import {}`.",
+            skill_source(&source, &skills[0])
+        );
+        diagnostics.print_summary();
+        return Ok(());
+    }
+
+    println!();
+    let picked = prompt_skills(&skills)?;
+    if picked.is_empty() {
+        println!("Nothing selected.");
+        return Ok(());
+    }
+    diagnostics.print_summary();
+    let sources = picked
+        .iter()
+        .map(|skill| skill_source(&source, skill))
+        .collect();
+    import::run(
+        ctx,
+        sources,
+        ImportOptions {
+            to,
+            local: project,
+            quarantine,
+            ..ImportOptions::default()
+        },
+    )
+    .await
+}
+
+/// Return the import shorthand for a skill in the browsed repository.
+fn skill_source(repo: &GithubSource, skill: &RemoteSkill) -> String {
+    GithubSource {
+        path: skill.path.clone(),
+        ..repo.clone()
+    }
+    .to_string()
+}
+
+/// Ask which skills to import.
+fn prompt_skills(skills: &[RemoteSkill]) -> Result<Vec<RemoteSkill>> {
+    let labels: Vec<String> = skills
+        .iter()
+        .map(|skill| format!("{} ({})", skill.name, skill.path))
+        .collect();
+    match MultiSelect::new("Import which skills?", labels).raw_prompt() {
+        Ok(picked) => Ok(picked
+            .into_iter()
+            .map(|option| skills[option.index].clone())
+            .collect()),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::skill_source;
+    use crate::github::{GithubSource, RemoteSkill};
+
+    #[test]
+    fn pins_imports_to_the_resolved_ref() {
+        let mut repo = GithubSource::from_shorthand("acme/skills").expect("shorthand");
+        repo.git_ref = Some("main".to_string());
+        let skill = RemoteSkill {
+            name: "pdf".to_string(),
+            path: "skills/pdf".to_string(),
+        };
+        assert_eq!(skill_source(&repo, &skill), "acme/skills/skills/pdf@main");
+    }
+}
@@ -0,0 +1,222 @@
+//! Implementation of the `skills bump` command.
+
+use std::{fs, io::ErrorKind, time::SystemTime};
+
+use clap::ValueEnum;
+
+use crate::{
+    changelog::{CHANGELOG_FILE, Release, add_release},
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    frontmatter::{parse_metadata, set_field},
+    palette::Role,
+    paths::display_path,
+    units::format_date,
+};
+
+/// Part of a `MAJOR.MINOR.PATCH` version to increment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum VersionPart {
+    /// Incompatible changes; resets minor and patch.
+    Major,
+    /// New behavior; resets patch.
+    Minor,
+    /// Fixes.
+    #[default]
+    Patch,
+}
+
+/// Execute the bump command, incrementing a source skill's frontmatter version and recording
+/// `message` for the new version in its changelog.
+pub async fn run(
+    ctx: &Context,
+    skill: String,
+    message: String,
+    part: VersionPart,
+    dry_run: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let skill = catalog.resolve_name(&skill);
+
+    let template = catalog
+        .sources
+        .get(&skill)
+        .ok_or_else(|| Error::SkillNotFound {
+            name: skill.clone(),
+        })?;
+    let current = parse_metadata(&template.contents).version;
+    let version = next_version(&skill, current.as_deref(), part)?;
+    let contents = set_field(&template.contents, "version", &version).ok_or_else(|| {
+        Error::InvalidSkillFile {
+            path: template.skill_path.clone(),
+            message: "missing YAML frontmatter".to_string(),
+        }
+    })?;
+
+    let changelog_path = template.skill_dir.join(CHANGELOG_FILE);
+    let changelog = match fs::read_to_string(&changelog_path) {
+        Ok(changelog) => changelog,
+        Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+        Err(source) => {
+            return Err(Error::SkillRead {
+                path: changelog_path,
+                source,
+            });
+        }
+    };
+    let changelog = add_release(
+        &changelog,
+        &Release {
+            version: version.clone(),
+            date: Some(format_date(SystemTime::now())),
+            changes: vec![message.trim().to_string()],
+        },
+    );
+
+    println!(
+        "{} '{}' {} -> {}",
+        palette.paint(Role::Action, if dry_run { "Would bump" } else { "Bumping" }),
+        palette.paint(Role::SkillName, &skill),
+        current.as_deref().unwrap_or("(none)"),
+        version
+    );
+    println!(
+        "  {}",
+        palette.paint(Role::Path, &display_path(&template.skill_path))
+    );
+    println!(
+        "  {}",
+        palette.paint(Role::Path, &display_path(&changelog_path))
+    );
+    if dry_run {
+        println!();
+        println!("Dry run - no changes made.");
+        return Ok(());
+    }
+
+    fs::write(&template.skill_path, contents).map_err(|source| Error::SkillWrite {
+        path: template.skill_path.clone(),
+        source,
+    })?;
+    fs::write(&changelog_path, changelog).map_err(|source| Error::SkillWrite {
+        path: changelog_path.clone(),
+        source,
+    })?;
+
+    println!();
+    println!("Done. Run `skills push {skill}` to install the new version.");
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Return the version after `current`, treating a missing version as `0.0.0`.
+///
+/// Versions with fewer than three parts, like `1.2`, are padded with zeros.
+fn next_version(name: &str, current: Option<&str>, part: VersionPart) -> Result<String> {
+    let invalid = || Error::InvalidVersion {
+        name: name.to_string(),
+        version: current.unwrap_or_default().to_string(),
+    };
+    let mut parts = [0u64; 3];
+    if let Some(current) = current {
+        let fields: Vec<&str> = current.trim().split('.').collect();
+        if fields.len() > parts.len() {
+            return Err(invalid());
+        }
+        for (slot, field) in parts.iter_mut().zip(fields) {
+            *slot = field.parse().map_err(|_| invalid())?;
+        }
+    }
+    let [major, minor, patch] = parts;
+    Ok(match part {
+        VersionPart::Major => format!("{}.0.0", major + 1),
+        VersionPart::Minor => format!("{major}.{}.0", minor + 1),
+        VersionPart::Patch => format!("{major}.{minor}.{}", patch + 1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{VersionPart, next_version, run};
+    use crate::{
+        changelog::{CHANGELOG_FILE, read_releases},
+        error::Error,
+        frontmatter::parse_metadata,
+        testutil::{TestFixture, simple_skill},
+    };
+
+    #[test]
+    fn increments_versions() {
+        let next = |current, part| next_version("pdf", current, part);
+        assert_eq!(next(None, VersionPart::Patch).expect("next"), "0.0.1");
+        assert_eq!(
+            next(Some("1.2"), VersionPart::Patch).expect("next"),
+            "1.2.1"
+        );
+        assert_eq!(
+            next(Some("1.2.9"), VersionPart::Minor).expect("next"),
+            "1.3.0"
+        );
+        assert_eq!(
+            next(Some("1.2.9"), VersionPart::Major).expect("next"),
+            "2.0.0"
+        );
+        assert!(matches!(
+            next(Some("1.0.0-beta"), VersionPart::Patch),
+            Err(Error::InvalidVersion { .. })
+        ));
+        assert!(next(Some("1.2.3.4"), VersionPart::Patch).is_err());
+    }
+
+    #[tokio::test]
+    async fn bumps_version_and_records_changes() {
+        let fixture = TestFixture::new().with_source_skill("alpha", &simple_skill("alpha"));
+        let ctx = fixture.context();
+        let skill_dir = fixture.source_dir().join("alpha");
+
+        run(
+            &ctx,
+            "alpha".to_string(),
+            "First release".to_string(),
+            VersionPart::Minor,
+            false,
+        )
+        .await
+        .expect("bump");
+        run(
+            &ctx,
+            "alpha".to_string(),
+            "Fix typos".to_string(),
+            VersionPart::Patch,
+            false,
+        )
+        .await
+        .expect("bump");
+
+        let contents = fs::read_to_string(skill_dir.join("SKILL.md")).expect("read");
+        assert_eq!(parse_metadata(&contents).version.as_deref(), Some("0.1.1"));
+        let releases = read_releases(&skill_dir);
+        let versions: Vec<&str> = releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, ["0.1.1", "0.1.0"]);
+        assert_eq!(releases[0].changes, ["Fix typos"]);
+
+        run(
+            &ctx,
+            "alpha".to_string(),
+            "Unwritten".to_string(),
+            VersionPart::Major,
+            true,
+        )
+        .await
+        .expect("dry run");
+        let changelog = fs::read_to_string(skill_dir.join(CHANGELOG_FILE)).expect("read");
+        assert!(!changelog.contains("Unwritten"));
+    }
+}
@@ -0,0 +1,457 @@
+//! Implementation of the `skills export-catalog` and `skills import-catalog` commands.
+//!
+//! A catalog bundle is a `.tar.zst` archive holding every source skill below `skills/<name>/`,
+//! plus a `catalog.json` manifest recording each skill's description, version, skill file, and
+//! directory within its source, so a library can be moved to a machine without network access.
+
+use std::{
+    fmt, fs,
+    path::{Component, Path, PathBuf},
+    time::SystemTime,
+};
+
+use clap::ValueEnum;
+use inquire::{InquireError, Select};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    archive::{self, ArchiveFormat, Entry, EntryKind, SymlinkPolicy, TarZst, read_dir},
+    commands::{init, pull::prompt_source},
+    context::Context,
+    error::{Error, Result},
+    frontmatter::parse_metadata,
+    palette::Role,
+    paths::display_path,
+    policy::Policy,
+    units::{format_count, format_size, format_time},
+};
+
+/// Name of the manifest at the root of a bundle.
+const MANIFEST: &str = "catalog.json";
+
+/// Directory in a bundle holding one directory per skill.
+const SKILLS_DIR: &str = "skills";
+
+/// Manifest format version written by this build.
+const BUNDLE_VERSION: u32 = 1;
+
+/// What to do with a bundled skill that differs from one already installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnConflict {
+    /// Ask for each conflicting skill.
+    #[default]
+    Ask,
+    /// Keep the installed skill.
+    Keep,
+    /// Replace the installed skill with the bundled one.
+    Replace,
+}
+
+/// Manifest written to `catalog.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// Manifest format version.
+    version: u32,
+    /// When the bundle was written, for display.
+    exported_at: String,
+    /// Bundled skills, by name.
+    skills: Vec<BundledSkill>,
+}
+
+/// A skill recorded in the manifest.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledSkill {
+    /// Skill name, also its directory under `skills/`.
+    name: String,
+    /// Skill description.
+    description: String,
+    /// Declared skill version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// Name of the skill file within the skill directory.
+    file: String,
+    /// Directory of the skill relative to its source, with `/` separators.
+    path: String,
+}
+
+/// An answer to the prompt for a conflicting skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    /// Keep the installed skill.
+    Keep,
+    /// Replace the installed skill.
+    Replace,
+    /// Keep this and every remaining conflicting skill.
+    KeepAll,
+    /// Replace this and every remaining conflicting skill.
+    ReplaceAll,
+}
+
+impl Resolution {
+    /// Every answer, in prompt order.
+    const ALL: [Self; 4] = [Self::Keep, Self::Replace, Self::KeepAll, Self::ReplaceAll];
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keep => write!(f, "Keep installed"),
+            Self::Replace => write!(f, "Replace with bundled"),
+            Self::KeepAll => write!(f, "Keep installed for all remaining"),
+            Self::ReplaceAll => write!(f, "Replace with bundled for all remaining"),
+        }
+    }
+}
+
+/// Execute the `export-catalog` command, bundling every source skill into one `.tar.zst` file.
+pub async fn export(
+    ctx: &Context,
+    output: PathBuf,
+    symlinks: SymlinkPolicy,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    if output.exists() && !force {
+        return Err(Error::PathExists { path: output });
+    }
+
+    let mut skills: Vec<_> = catalog.sources.values().collect();
+    skills.sort_by_key(|skill| skill.name.to_lowercase());
+    let mut manifest = Manifest {
+        version: BUNDLE_VERSION,
+        exported_at: format_time(SystemTime::now()),
+        skills: Vec::new(),
+    };
+    let mut entries = Vec::new();
+    for skill in skills {
+        let path = skill
+            .skill_dir
+            .strip_prefix(&skill.source_root)
+            .unwrap_or(&skill.skill_dir)
+            .to_string_lossy()
+            .replace('\\', "/");
+        entries.extend(read_dir(
+            &format!("{SKILLS_DIR}/{}", skill.name),
+            &skill.skill_dir,
+            symlinks,
+        )?);
+        manifest.skills.push(BundledSkill {
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            version: parse_metadata(&skill.contents).version,
+            file: skill
+                .skill_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+            path: if path.is_empty() {
+                skill.name.clone()
+            } else {
+                path
+            },
+        });
+    }
+
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|error| Error::TemplateRender {
+        message: error.to_string(),
+    })?;
+    entries.insert(
+        0,
+        Entry {
+            path: MANIFEST.to_string(),
+            kind: EntryKind::File {
+                contents: json,
+                executable: false,
+            },
+        },
+    );
+    let data = TarZst.write(&entries).map_err(|e| Error::ZipCreate {
+        path: output.clone(),
+        message: e.to_string(),
+    })?;
+    fs::write(&output, &data).map_err(|e| Error::SkillWrite {
+        path: output.clone(),
+        source: e,
+    })?;
+
+    println!(
+        "{} {} to {} ({})",
+        palette.paint(Role::Label, "Exported:"),
+        format_count(manifest.skills.len(), "skill"),
+        display_path(&output),
+        format_size(data.len() as u64)
+    );
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Execute the `import-catalog` command, merging the skills of a bundle into a source.
+///
+/// New skills are placed at their recorded directory within the source `to` names, or the only
+/// source, or one chosen at a prompt. A skill that is already installed is updated where it is:
+/// identical copies are left alone and differing ones are kept or replaced as `on_conflict`
+/// says. Skills that break the team policy are refused.
+pub async fn import(
+    ctx: &Context,
+    file: PathBuf,
+    to: Option<String>,
+    on_conflict: OnConflict,
+    dry_run: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let policy = Policy::load(ctx, &config, &mut diagnostics).await?;
+
+    let data = fs::read(&file).map_err(|e| Error::SkillRead {
+        path: file.clone(),
+        source: e,
+    })?;
+    let entries = TarZst.read(&data)?;
+    let manifest = read_manifest(&entries)?;
+    let source = match to {
+        Some(name) => config
+            .find_source(&name, &ctx.work_dir)
+            .cloned()
+            .ok_or(Error::SourceNotFound { name })?,
+        None => match config.sources() {
+            [] => {
+                return Err(Error::NoSources {
+                    config_path: ctx.config_path.clone(),
+                });
+            }
+            [source] => source.clone(),
+            sources => prompt_source(sources)?,
+        },
+    };
+
+    println!(
+        "{} {} from {}, exported {}",
+        palette.paint(Role::Heading, "Bundle:"),
+        format_count(manifest.skills.len(), "skill"),
+        display_path(&file),
+        manifest.exported_at
+    );
+    println!(
+        "{} {}",
+        palette.paint(Role::Label, "Source:"),
+        display_path(&source)
+    );
+    println!();
+
+    let mut on_conflict = on_conflict;
+    let (mut added, mut replaced, mut kept, mut unchanged) = (0, 0, 0, 0);
+    for skill in &manifest.skills {
+        let root = format!("{SKILLS_DIR}/{}", skill.name);
+        let bundled: Vec<Entry> = entries
+            .iter()
+            .filter(|entry| entry.path_below(&root).is_some())
+            .cloned()
+            .collect();
+        if let Some(policy) = &policy {
+            let contents = bundled
+                .iter()
+                .find_map(|entry| match &entry.kind {
+                    EntryKind::File { contents, .. }
+                        if entry.path_below(&root) == Some(skill.file.as_str()) =>
+                    {
+                        Some(String::from_utf8_lossy(contents).into_owned())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default();
+            let size = bundled.iter().map(Entry::size).sum();
+            policy.check_skill(&skill.name, &contents, size)?;
+        }
+
+        let target = match catalog.sources.get(&skill.name) {
+            Some(installed) => installed.skill_dir.clone(),
+            None => source.join(placement(skill)?),
+        };
+        let name = palette.paint(Role::SkillName, &skill.name);
+        if !target.exists() {
+            if !dry_run {
+                archive::install(&bundled, &root, &target)?;
+            }
+            println!("  {name}: added at {}", display_path(&target));
+            added += 1;
+            continue;
+        }
+        if read_dir(&root, &target, SymlinkPolicy::Preserve).is_ok_and(|ours| ours == bundled) {
+            println!("  {name}: unchanged");
+            unchanged += 1;
+            continue;
+        }
+
+        let replace = match on_conflict {
+            OnConflict::Keep => false,
+            OnConflict::Replace => true,
+            OnConflict::Ask if dry_run => {
+                println!("  {name}: differs from {}", display_path(&target));
+                continue;
+            }
+            OnConflict::Ask => {
+                let message = format!(
+                    "'{}' differs from the installed copy at {}",
+                    skill.name,
+                    display_path(&target)
+                );
+                match ask_resolution(&message)? {
+                    Resolution::Keep => false,
+                    Resolution::Replace => true,
+                    Resolution::KeepAll => {
+                        on_conflict = OnConflict::Keep;
+                        false
+                    }
+                    Resolution::ReplaceAll => {
+                        on_conflict = OnConflict::Replace;
+                        true
+                    }
+                }
+            }
+        };
+        if replace {
+            if !dry_run {
+                archive::install(&bundled, &root, &target)?;
+            }
+            println!("  {name}: replaced at {}", display_path(&target));
+            replaced += 1;
+        } else {
+            println!("  {name}: kept installed copy");
+            kept += 1;
+        }
+    }
+
+    println!();
+    println!(
+        "{} {added} added, {replaced} replaced, {kept} kept, {unchanged} unchanged",
+        palette.paint(
+            Role::Label,
+            if dry_run {
+                "Would import:"
+            } else {
+                "Imported:"
+            }
+        ),
+    );
+    if !dry_run && added + replaced > 0 {
+        println!("Run `skills push` to install them in your tools.");
+    }
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Find and parse the manifest of a bundle.
+fn read_manifest(entries: &[Entry]) -> Result<Manifest> {
+    let invalid = |message: String| Error::InvalidTar { message };
+    let contents = entries
+        .iter()
+        .find_map(|entry| match &entry.kind {
+            EntryKind::File { contents, .. } if entry.path == MANIFEST => Some(contents),
+            _ => None,
+        })
+        .ok_or_else(|| invalid(format!("not a catalog bundle: no {MANIFEST}")))?;
+    let manifest: Manifest =
+        serde_json::from_slice(contents).map_err(|e| invalid(format!("{MANIFEST}: {e}")))?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(invalid(format!(
+            "bundle version {} is newer than this build supports ({BUNDLE_VERSION})",
+            manifest.version
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Return the directory a bundled skill is placed at within a source, refusing paths that would
+/// leave it.
+fn placement(skill: &BundledSkill) -> Result<&Path> {
+    let path = Path::new(&skill.path);
+    if path.as_os_str().is_empty()
+        || !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(Error::InvalidTar {
+            message: format!("path traversal detected: {}", skill.path),
+        });
+    }
+    Ok(path)
+}
+
+/// Prompt for what to do with a conflicting skill.
+fn ask_resolution(message: &str) -> Result<Resolution> {
+    match Select::new(message, Resolution::ALL.to_vec()).prompt() {
+        Ok(answer) => Ok(answer),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{OnConflict, export, import};
+    use crate::{
+        archive::SymlinkPolicy,
+        skill::SKILL_FILE_NAME,
+        testutil::{TestFixture, simple_skill, skill_content},
+    };
+
+    #[tokio::test]
+    async fn moves_a_catalog_between_machines() {
+        let from = TestFixture::new()
+            .with_source_skill("alpha", &skill_content("alpha", "Alpha", "new"))
+            .with_source_skill("beta", &simple_skill("beta"));
+        let bundle = from.work_dir().join("catalog.tar.zst");
+        export(
+            &from.context(),
+            bundle.clone(),
+            SymlinkPolicy::Reject,
+            false,
+        )
+        .await
+        .expect("export");
+        assert!(
+            export(
+                &from.context(),
+                bundle.clone(),
+                SymlinkPolicy::Reject,
+                false
+            )
+            .await
+            .is_err()
+        );
+
+        let to =
+            TestFixture::new().with_source_skill("alpha", &skill_content("alpha", "Alpha", "old"));
+        let ctx = to.context();
+        let source = to.source_dir();
+        import(&ctx, bundle.clone(), None, OnConflict::Keep, false)
+            .await
+            .expect("import");
+        let alpha = || fs::read_to_string(source.join("alpha").join(SKILL_FILE_NAME));
+        assert!(alpha().expect("alpha").ends_with("old"));
+        assert_eq!(
+            fs::read_to_string(source.join("beta").join(SKILL_FILE_NAME)).expect("beta"),
+            simple_skill("beta")
+        );
+
+        import(&ctx, bundle, None, OnConflict::Replace, false)
+            .await
+            .expect("import");
+        assert!(alpha().expect("alpha").ends_with("new"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Implementation of the `skills clone` command.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    catalog::{Catalog, CatalogRoots},
+    commands::init,
+    config::add_source,
+    context::Context,
+    error::{Error, Result},
+    git,
+    palette::Role,
+    paths::display_path,
+    skill::SkillTemplate,
+};
+
+/// Execute the clone command, cloning a git repository of skills into the clone directory, adding
+/// it to the config's sources, and listing the skills it provides.
+pub async fn run(ctx: &Context, url: String, name: Option<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let target = clone_target(ctx, &url, name)?;
+    if target.exists() {
+        return Err(Error::PathExists { path: target });
+    }
+    clone_into(ctx, &url, &target)?;
+    add_to_config(ctx, &target)?;
+
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let root = target.canonicalize().unwrap_or(target);
+    let roots = CatalogRoots {
+        sources: vec![root.clone()],
+        skill_files: config.skill_files.clone(),
+        include_paths: config.include_paths.clone(),
+        ..CatalogRoots::default()
+    };
+    let cloned = Catalog::load(&roots, &mut diagnostics);
+    let mut provided: Vec<&SkillTemplate> = cloned.sources.values().collect();
+    provided.sort_by(|a, b| a.name.cmp(&b.name));
+
+    println!();
+    if provided.is_empty() {
+        println!("No skills found in {}.", display_path(&root));
+        diagnostics.print_summary();
+        return Ok(());
+    }
+    println!("{}", palette.paint(Role::Heading, "Skills:"));
+    for skill in &provided {
+        println!(
+            "  {} - {}",
+            palette.paint(Role::SkillName, &skill.name),
+            skill.description
+        );
+        if let Some(winner) = catalog.sources.get(&skill.name)
+            && winner.source_root != root
+        {
+            println!(
+                "    {}",
+                palette.paint(
+                    Role::Warning,
+                    &format!(
+                        "shadowed by {}, an earlier source",
+                        display_path(&winner.skill_dir)
+                    )
+                )
+            );
+        }
+    }
+    println!();
+    println!("Run `skills push` to install them.");
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Return the directory in the clone directory that a repository is cloned into, named `name` or
+/// after the repository.
+pub fn clone_target(ctx: &Context, url: &str, name: Option<String>) -> Result<PathBuf> {
+    let name = match name {
+        Some(name) => name,
+        None => repo_name(url).ok_or_else(|| Error::InvalidPath {
+            path: PathBuf::from(url),
+        })?,
+    };
+    if matches!(name.as_str(), "" | "." | "..") || name.contains(['/', '\\']) {
+        return Err(Error::InvalidPath {
+            path: ctx.clone_dir.join(name),
+        });
+    }
+    Ok(ctx.clone_dir.join(name))
+}
+
+/// Clone a git repository into a directory of the clone directory.
+pub fn clone_into(ctx: &Context, url: &str, target: &Path) -> Result<()> {
+    println!("Cloning {url} into {}", display_path(target));
+    fs::create_dir_all(&ctx.clone_dir).map_err(|source| Error::ConfigWrite {
+        path: ctx.clone_dir.clone(),
+        source,
+    })?;
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    git::run(&ctx.clone_dir, &["clone", "--quiet", url, &name])?;
+    Ok(())
+}
+
+/// Add a directory to the sources listed in the config file.
+pub fn add_to_config(ctx: &Context, source: &Path) -> Result<()> {
+    let path = &ctx.config_path;
+    let contents = fs::read_to_string(path).map_err(|source| Error::ConfigRead {
+        path: path.clone(),
+        source,
+    })?;
+    let updated = add_source(path, &contents, &display_path(source))?;
+    fs::write(path, updated).map_err(|source| Error::ConfigWrite {
+        path: path.clone(),
+        source,
+    })?;
+    println!("Added source to {}", display_path(path));
+    Ok(())
+}
+
+/// Derive a directory name from a git URL: its last path segment without a `.git` suffix.
+fn repo_name(url: &str) -> Option<String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let last = trimmed.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{repo_name, run};
+    use crate::{
+        config::Config,
+        git,
+        testutil::{TestFixture, simple_skill},
+    };
+
+    #[test]
+    fn derives_names_from_urls() {
+        assert_eq!(
+            repo_name("https://github.com/acme/team-skills.git").as_deref(),
+            Some("team-skills")
+        );
+        assert_eq!(
+            repo_name("git@github.com:acme/skills").as_deref(),
+            Some("skills")
+        );
+        assert_eq!(repo_name("/srv/git/shared/").as_deref(), Some("shared"));
+        assert_eq!(repo_name("https://example.com/.git"), None);
+    }
+
+    #[tokio::test]
+    async fn clones_repository_and_adds_source() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        let upstream = fixture.work_dir().join("upstream");
+        fs::create_dir_all(upstream.join("shared")).expect("mkdir");
+        fs::write(
+            upstream.join("shared").join("SKILL.md"),
+            simple_skill("shared"),
+        )
+        .expect("write");
+        git::run(&upstream, &["init", "--quiet"]).expect("init");
+        git::run(&upstream, &["add", "-A"]).expect("add");
+        git::run(
+            &upstream,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "Add shared",
+            ],
+        )
+        .expect("commit");
+
+        let url = upstream.to_string_lossy().to_string();
+        run(&ctx, url.clone(), None).await.expect("clone");
+
+        let target = ctx.clone_dir.join("upstream");
+        assert!(target.join("shared").join("SKILL.md").is_file());
+        let config = Config::load(&ctx.config_path, None).expect("load");
+        assert!(config.sources().contains(&target));
+        let catalog = ctx.load_catalog(&config, &mut ctx.diagnostics());
+        assert!(catalog.sources.contains_key("shared"));
+
+        let error = run(&ctx, url, None).await.expect_err("target exists");
+        assert!(error.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,417 @@
+//! Implementation of the `skills compose` command.
+//!
+//! A composed skill is generated from several source skills: one frontmatter merging theirs, then
+//! each member's body under a heading of its own. The members and a digest of their skill files
+//! are recorded in the frontmatter, so the composition can be regenerated once a member changes.
+
+use std::{fs, path::Path};
+
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    catalog::Catalog,
+    commands::{init, new::title_case},
+    context::Context,
+    error::{Error, Result},
+    format::shift_headings,
+    frontmatter::split_frontmatter,
+    name::normalize_name,
+    palette::{Palette, Role},
+    paths::display_path,
+    skill::SkillTemplate,
+    state::hash_bytes,
+};
+
+/// Frontmatter field listing the skills a composed skill was generated from.
+const COMPOSED_FROM: &str = "composed-from";
+
+/// Frontmatter field holding a digest of the member skill files it was generated from.
+const COMPOSED_HASH: &str = "composed-hash";
+
+/// Member frontmatter fields that are not carried over into a composition.
+const MEMBER_ONLY_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "version",
+    COMPOSED_FROM,
+    COMPOSED_HASH,
+];
+
+/// Hex digits of the member digest recorded in the frontmatter.
+const HASH_LENGTH: usize = 16;
+
+/// Execute the compose command.
+///
+/// With `from`, writes the composed skill `name` from those members, creating it beside the first
+/// member or rewriting an earlier composition. With only a name, regenerates that composition
+/// from its recorded members. With neither, regenerates every composition whose members changed.
+pub async fn run(
+    ctx: &Context,
+    name: Option<String>,
+    from: Vec<String>,
+    description: Option<String>,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let file_name = config.skill_files.primary();
+
+    let Some(name) = name else {
+        let (mut regenerated, mut current) = (0, 0);
+        for skill in catalog.sources.values() {
+            let Some(members) = recorded_members(&skill.contents) else {
+                continue;
+            };
+            let composed = palette.paint(Role::SkillName, &skill.name);
+            let members = match find_members(&catalog, &skill.name, &members) {
+                Ok(members) => members,
+                Err(error) => {
+                    println!(
+                        "  {} {composed}: {error}",
+                        palette.paint(Role::Failure, "✗")
+                    );
+                    continue;
+                }
+            };
+            if recorded_hash(&skill.contents).as_deref() == Some(&members_hash(&members)) {
+                current += 1;
+                continue;
+            }
+            write_composition(
+                &skill.skill_dir,
+                file_name,
+                &skill.name,
+                Some(&skill.description),
+                &members,
+                dry_run,
+                &palette,
+            )?;
+            regenerated += 1;
+        }
+        println!(
+            "{} {regenerated} regenerated, {current} up to date",
+            palette.paint(Role::Label, "Compositions:")
+        );
+        diagnostics.print_summary();
+        return Ok(());
+    };
+
+    let name = normalize_name(&name).map_err(|source| Error::InvalidSkillName {
+        name: name.clone(),
+        source,
+    })?;
+    let existing = catalog.sources.get(&name);
+    let recorded = existing.and_then(|skill| recorded_members(&skill.contents));
+    let members = match (from.is_empty(), &recorded, existing) {
+        (false, _, Some(skill)) if recorded.is_none() && !force => {
+            return Err(Error::SkillExists {
+                name,
+                path: skill.skill_dir.clone(),
+            });
+        }
+        (false, ..) => from,
+        (true, Some(recorded), _) => recorded.clone(),
+        (true, None, _) => {
+            return Err(Error::ComposeFailed {
+                name,
+                message: "it is not a composed skill; name its members with --from".to_string(),
+            });
+        }
+    };
+    let members = find_members(&catalog, &name, &members)?;
+    if let Some(skill) = existing
+        && description.is_none()
+        && recorded.as_ref().is_some_and(|recorded| {
+            recorded
+                .iter()
+                .eq(members.iter().map(|member| &member.name))
+        })
+        && recorded_hash(&skill.contents).as_deref() == Some(&members_hash(&members))
+        && !force
+    {
+        println!(
+            "{} is up to date",
+            palette.paint(Role::SkillName, &skill.name)
+        );
+        return Ok(());
+    }
+
+    let dir = match existing {
+        Some(skill) => skill.skill_dir.clone(),
+        None => members[0].source_root.join(&name),
+    };
+    let description = description.or_else(|| {
+        existing
+            .filter(|_| recorded.is_some())
+            .map(|skill| skill.description.clone())
+    });
+    write_composition(
+        &dir,
+        file_name,
+        &name,
+        description.as_deref(),
+        &members,
+        dry_run,
+        &palette,
+    )?;
+    if !dry_run {
+        println!();
+        println!("Run `skills push {name}` to install it.");
+    }
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Look up the members of a composition, refusing missing skills, the composition itself, and
+/// members that extend a shared template.
+fn find_members<'a>(
+    catalog: &'a Catalog,
+    name: &str,
+    members: &[String],
+) -> Result<Vec<&'a SkillTemplate>> {
+    let failed = |message: String| Error::ComposeFailed {
+        name: name.to_string(),
+        message,
+    };
+    let mut found: Vec<&SkillTemplate> = Vec::new();
+    for member in members {
+        let member = catalog.resolve_name(member.trim());
+        if member == name {
+            return Err(failed(
+                "a skill cannot be one of its own members".to_string(),
+            ));
+        }
+        let skill = catalog
+            .sources
+            .get(&member)
+            .ok_or_else(|| failed(format!("member '{member}' is not a source skill")))?;
+        if skill.contents.contains("{% extends") {
+            return Err(failed(format!(
+                "member '{member}' extends a shared template and cannot be combined"
+            )));
+        }
+        if !found.iter().any(|skill| skill.name == member) {
+            found.push(skill);
+        }
+    }
+    if found.len() < 2 {
+        return Err(failed(
+            "a composition needs at least two members".to_string(),
+        ));
+    }
+    Ok(found)
+}
+
+/// Generate a composition and write it to `dir`, reporting what was written.
+fn write_composition(
+    dir: &Path,
+    file_name: &str,
+    name: &str,
+    description: Option<&str>,
+    members: &[&SkillTemplate],
+    dry_run: bool,
+    palette: &Palette,
+) -> Result<()> {
+    let contents = compose(name, description, members)?;
+    let path = dir.join(file_name);
+    let names: Vec<&str> = members.iter().map(|member| member.name.as_str()).collect();
+    println!(
+        "{} {} from {} at {}",
+        palette.paint(
+            Role::Action,
+            if dry_run { "Would compose" } else { "Composed" }
+        ),
+        palette.paint(Role::SkillName, name),
+        names.join(", "),
+        display_path(&path)
+    );
+    if dry_run {
+        return Ok(());
+    }
+    fs::create_dir_all(dir).map_err(|source| Error::SkillWrite {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    fs::write(&path, contents).map_err(|source| Error::SkillWrite { path, source })
+}
+
+/// Generate the skill file of a composition.
+///
+/// List fields of the members' frontmatter are merged without repeats, and other fields take the
+/// first member's value. Without a description, the members' descriptions are joined.
+fn compose(name: &str, description: Option<&str>, members: &[&SkillTemplate]) -> Result<String> {
+    let description = description.map_or_else(
+        || {
+            members
+                .iter()
+                .map(|member| member.description.trim())
+                .collect::<Vec<_>>()
+                .join(" ")
+        },
+        str::to_string,
+    );
+    let mut frontmatter = Mapping::new();
+    frontmatter.insert("name".into(), name.into());
+    frontmatter.insert("description".into(), description.into());
+    for member in members {
+        let fields = split_frontmatter(&member.contents)
+            .and_then(|(yaml, _)| serde_yaml::from_str::<Mapping>(yaml).ok())
+            .unwrap_or_default();
+        for (key, value) in fields {
+            if key
+                .as_str()
+                .is_some_and(|key| MEMBER_ONLY_FIELDS.contains(&key))
+            {
+                continue;
+            }
+            match (frontmatter.get_mut(&key), value) {
+                (Some(Value::Sequence(merged)), Value::Sequence(items)) => {
+                    for item in items {
+                        if !merged.contains(&item) {
+                            merged.push(item);
+                        }
+                    }
+                }
+                (Some(_), _) => {}
+                (None, value) => {
+                    frontmatter.insert(key, value);
+                }
+            }
+        }
+    }
+    let names: Vec<&str> = members.iter().map(|member| member.name.as_str()).collect();
+    frontmatter.insert(
+        COMPOSED_FROM.into(),
+        Value::Sequence(names.iter().map(|&name| name.into()).collect()),
+    );
+    frontmatter.insert(COMPOSED_HASH.into(), members_hash(members).into());
+    let yaml = serde_yaml::to_string(&frontmatter).map_err(|error| Error::ComposeFailed {
+        name: name.to_string(),
+        message: error.to_string(),
+    })?;
+
+    let mut contents = format!(
+        "---\n{yaml}---\n\n{{# Generated by `skills compose` from {}. Edit the members and run \
+         `skills compose {name}` to regenerate. #}}\n",
+        names.join(", ")
+    );
+    for member in members {
+        let body =
+            split_frontmatter(&member.contents).map_or(member.contents.as_str(), |(_, body)| body);
+        contents.push_str(&format!(
+            "\n## {}\n\n{}\n",
+            title_case(&member.name),
+            shift_headings(body.trim(), 2)
+        ));
+    }
+    Ok(contents)
+}
+
+/// Return the members a composed skill file records, or `None` for other skills.
+fn recorded_members(contents: &str) -> Option<Vec<String>> {
+    let (yaml, _) = split_frontmatter(contents)?;
+    let fields: Mapping = serde_yaml::from_str(yaml).ok()?;
+    let members = fields.get(COMPOSED_FROM)?.as_sequence()?;
+    Some(
+        members
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Return the member digest a composed skill file records.
+fn recorded_hash(contents: &str) -> Option<String> {
+    let (yaml, _) = split_frontmatter(contents)?;
+    let fields: Mapping = serde_yaml::from_str(yaml).ok()?;
+    fields.get(COMPOSED_HASH)?.as_str().map(str::to_string)
+}
+
+/// Digest the names and skill files of the members, in order.
+fn members_hash(members: &[&SkillTemplate]) -> String {
+    let mut bytes = Vec::new();
+    for member in members {
+        bytes.extend_from_slice(member.name.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(member.contents.as_bytes());
+        bytes.push(0);
+    }
+    let mut hash = hash_bytes(&bytes);
+    hash.truncate(HASH_LENGTH);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::run;
+    use crate::{skill::SKILL_FILE_NAME, testutil::TestFixture};
+
+    #[tokio::test]
+    async fn composes_and_regenerates_when_members_change() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "pdf",
+                "---\nname: pdf\ndescription: Read PDFs.\ntags: [docs, pdf]\n---\n# PDF\n\nUse pdftotext.\n",
+            )
+            .with_source_skill(
+                "xlsx",
+                "---\nname: xlsx\ndescription: Read spreadsheets.\ntags: [docs]\n---\n# XLSX\n\nUse pandas.\n",
+            );
+        let ctx = fixture.context();
+        let composed = || {
+            fs::read_to_string(fixture.source_dir().join("office").join(SKILL_FILE_NAME))
+                .expect("composed")
+        };
+
+        run(
+            &ctx,
+            Some("office".to_string()),
+            vec!["pdf".to_string(), "xlsx".to_string()],
+            None,
+            false,
+            false,
+        )
+        .await
+        .expect("compose");
+        let first = composed();
+        assert!(first.contains("description: Read PDFs. Read spreadsheets.\n"));
+        assert!(first.contains("tags:\n- docs\n- pdf\n"));
+        assert!(first.contains("composed-from:\n- pdf\n- xlsx\n"));
+        assert!(first.contains("## Pdf\n\n### PDF\n\nUse pdftotext.\n\n## Xlsx\n\n### XLSX"));
+
+        run(&ctx, None, Vec::new(), None, false, false)
+            .await
+            .expect("regenerate");
+        assert_eq!(composed(), first);
+
+        fs::write(
+            fixture.source_dir().join("xlsx").join(SKILL_FILE_NAME),
+            "---\nname: xlsx\ndescription: Read spreadsheets.\n---\n# XLSX\n\nUse openpyxl.\n",
+        )
+        .expect("write");
+        run(&ctx, None, Vec::new(), None, false, false)
+            .await
+            .expect("regenerate");
+        let second = composed();
+        assert!(second.contains("Use openpyxl."));
+        assert!(second.contains("description: Read PDFs. Read spreadsheets.\n"));
+
+        let refused = run(
+            &ctx,
+            Some("pdf".to_string()),
+            vec!["xlsx".to_string(), "office".to_string()],
+            None,
+            false,
+            false,
+        )
+        .await;
+        assert!(refused.is_err());
+    }
+}
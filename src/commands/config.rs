@@ -0,0 +1,96 @@
+//! Implementation of the `skills config` commands.
+
+use std::fs;
+
+use crate::{
+    config::{CONFIG_VERSION, migrate_config},
+    context::Context,
+    diff::{colorize_diff, unified_diff, write_output},
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+};
+
+/// Execute the `config migrate` command.
+pub async fn migrate(ctx: &Context, dry_run: bool) -> Result<()> {
+    let path = &ctx.config_path;
+    let contents = fs::read_to_string(path).map_err(|source| Error::ConfigRead {
+        path: path.clone(),
+        source,
+    })?;
+    let (migrated, version) = migrate_config(path, &contents)?;
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+
+    if version == CONFIG_VERSION {
+        println!(
+            "Config at {} is already at version {CONFIG_VERSION}.",
+            display_path(path)
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        let label = display_path(path);
+        let diff = unified_diff(&label, &label, &contents, &migrated);
+        write_output(&colorize_diff(&diff, &palette), None)?;
+        println!();
+        println!("Dry run - no changes made.");
+        return Ok(());
+    }
+
+    let backup = path.with_extension("toml.bak");
+    fs::write(&backup, &contents).map_err(|source| Error::ConfigWrite {
+        path: backup.clone(),
+        source,
+    })?;
+    fs::write(path, &migrated).map_err(|source| Error::ConfigWrite {
+        path: path.clone(),
+        source,
+    })?;
+
+    println!(
+        "{} {} from version {version} to {CONFIG_VERSION} (backup at {})",
+        palette.paint(Role::Label, "Migrated:"),
+        display_path(path),
+        display_path(&backup)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::migrate;
+    use crate::testutil::TestFixture;
+
+    #[tokio::test]
+    async fn rewrites_config_with_version_and_keeps_comments() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        let original = fs::read_to_string(&ctx.config_path).expect("config");
+        let commented = format!("# my skills\n{original}");
+        fs::write(&ctx.config_path, &commented).expect("write");
+
+        migrate(&ctx, true).await.expect("dry run");
+        assert_eq!(
+            fs::read_to_string(&ctx.config_path).expect("config"),
+            commented
+        );
+
+        migrate(&ctx, false).await.expect("migrate");
+        let migrated = fs::read_to_string(&ctx.config_path).expect("config");
+        assert!(migrated.starts_with("# my skills\nversion = 1\n"));
+        assert_eq!(
+            fs::read_to_string(ctx.config_path.with_extension("toml.bak")).expect("backup"),
+            commented
+        );
+
+        migrate(&ctx, false).await.expect("already current");
+        assert_eq!(
+            fs::read_to_string(&ctx.config_path).expect("config"),
+            migrated
+        );
+    }
+}
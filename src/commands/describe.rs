@@ -0,0 +1,368 @@
+//! Implementation of the `skills describe` command.
+
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use inquire::{Confirm, error::InquireError};
+
+use crate::{
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    frontmatter::{frontmatter_json, parse_frontmatter, set_field, strip_frontmatter, yaml_scalar},
+    palette::Role,
+    paths::display_path,
+};
+
+/// Most section topics and commands named in a suggested description.
+const MAX_TOPICS: usize = 3;
+
+/// Longest summary sentence taken from the body, in characters.
+const MAX_SUMMARY: usize = 160;
+
+/// Section headings too generic to say when a skill applies.
+const GENERIC_SECTIONS: &[&str] = &[
+    "examples",
+    "guidelines",
+    "instructions",
+    "notes",
+    "overview",
+    "scripts",
+    "steps",
+    "usage",
+    "when to use",
+];
+
+/// Instructions sent ahead of the skill file to a `--llm-cmd` command.
+const LLM_PROMPT: &str = "Write a description for the agent skill below. The description decides \
+     when an agent loads the skill, so say what the skill does and which requests, situations, \
+     and file types should trigger it, in one or two sentences under 300 characters. Reply with \
+     the description only.\n\n";
+
+/// Execute the describe command.
+///
+/// The description is suggested from the skill body, or generated by piping the skill file to
+/// `llm_cmd`, then written back after confirmation unless `dry_run` is set. `force` writes
+/// without asking.
+pub async fn run(
+    ctx: &Context,
+    skill: String,
+    llm_cmd: Option<String>,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let name = catalog.resolve_name(&skill);
+    let template = catalog
+        .sources
+        .get(&name)
+        .ok_or(Error::SkillNotFound { name: skill })?;
+    let path = &template.skill_path;
+    let contents = fs::read_to_string(path).map_err(|source| Error::SkillRead {
+        path: path.clone(),
+        source,
+    })?;
+
+    let suggestion = match &llm_cmd {
+        Some(command) => generate_with_command(command, &contents)?,
+        None => suggest_description(&name, &contents),
+    };
+    let current = parse_frontmatter(&contents)
+        .map(|frontmatter| frontmatter.description)
+        .unwrap_or_default();
+    diagnostics.print_summary();
+    println!(
+        "{} {}",
+        palette.paint(Role::Label, "current:  "),
+        if current.is_empty() { "-" } else { &current }
+    );
+    println!("{} {suggestion}", palette.paint(Role::Label, "suggested:"));
+
+    if dry_run || suggestion == current {
+        return Ok(());
+    }
+    let updated = with_description(path, &contents, &suggestion)?;
+    if !force
+        && !confirm(&format!(
+            "Write this description to {}?",
+            display_path(path)
+        ))?
+    {
+        println!("Description left unchanged.");
+        return Ok(());
+    }
+    fs::write(path, updated).map_err(|source| Error::SkillWrite {
+        path: path.clone(),
+        source,
+    })?;
+    println!(
+        "{} description of {}",
+        palette.paint(Role::Action, "Updated"),
+        palette.paint(Role::SkillName, &name)
+    );
+    Ok(())
+}
+
+/// Suggest a description that says what a skill does and when it should be used.
+///
+/// The summary is the first sentence of prose in the body; the triggers are its specific section
+/// headings and the commands it names in backticks. Code blocks and template tags are skipped.
+fn suggest_description(name: &str, contents: &str) -> String {
+    let mut title = None;
+    let mut summary = None;
+    let mut topics: Vec<String> = Vec::new();
+    let mut commands: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for line in strip_frontmatter(contents).lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line.is_empty() || line.starts_with(['{', '<', '|', '>']) {
+            continue;
+        }
+        if line.starts_with('#') {
+            let level = line.chars().take_while(|char| *char == '#').count();
+            let text = line[level..].trim().trim_end_matches('#').trim();
+            if level == 1 && title.is_none() {
+                title = Some(text.to_string());
+            } else if level == 2
+                && topics.len() < MAX_TOPICS
+                && !GENERIC_SECTIONS.contains(&text.to_lowercase().as_str())
+            {
+                topics.push(text.to_lowercase());
+            }
+            continue;
+        }
+        for command in backticked_commands(line) {
+            if commands.len() < MAX_TOPICS && !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+        let is_list = line.starts_with(['-', '*', '+']) || line.starts_with(char::is_numeric);
+        if summary.is_none() && !is_list {
+            summary = Some(first_sentence(line));
+        }
+    }
+
+    let subject = title.unwrap_or_else(|| name.replace(['-', '_'], " "));
+    let lead = summary.unwrap_or_else(|| subject.clone());
+    let mut trigger = if topics.is_empty() {
+        format!("Use when working with {}", subject.to_lowercase())
+    } else {
+        format!("Use when the task involves {}", join_or(&topics))
+    };
+    if !commands.is_empty() {
+        let commands: Vec<String> = commands
+            .iter()
+            .map(|command| format!("`{command}`"))
+            .collect();
+        trigger.push_str(&format!(" or calls for {}", join_or(&commands)));
+    }
+    format!("{}. {trigger}.", lead.trim_end_matches('.'))
+}
+
+/// Return the first word of each backticked span in a line that looks like a command name.
+fn backticked_commands(line: &str) -> Vec<String> {
+    line.split('`')
+        .skip(1)
+        .step_by(2)
+        .filter_map(|span| span.split_whitespace().next())
+        .filter(|word| {
+            word.len() > 1
+                && word.starts_with(|char: char| char.is_ascii_lowercase())
+                && word
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Return the first sentence of a line of prose without Markdown emphasis, cut at a word
+/// boundary when it is long.
+fn first_sentence(line: &str) -> String {
+    let plain: String = line
+        .chars()
+        .filter(|char| !matches!(char, '*' | '`'))
+        .collect();
+    let sentence = plain.split(". ").next().unwrap_or_default().trim();
+    if sentence.chars().count() <= MAX_SUMMARY {
+        return sentence.to_string();
+    }
+    let cut: String = sentence.chars().take(MAX_SUMMARY).collect();
+    match cut.rsplit_once(' ') {
+        Some((head, _)) => head.to_string(),
+        None => cut,
+    }
+}
+
+/// Join items as an English list ending in "or".
+fn join_or(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{first} or {second}"),
+        [rest @ .., last] => format!("{}, or {last}", rest.join(", ")),
+    }
+}
+
+/// Pipe a skill file to a command and return the description it prints, with whitespace collapsed.
+fn generate_with_command(command: &str, contents: &str) -> Result<String> {
+    let failed = |message: String| Error::DescribeCommandFailed {
+        command: command.to_string(),
+        message,
+    };
+    let parts = shell_words::split(command).map_err(|error| failed(error.to_string()))?;
+    let (program, args) = parts
+        .split_first()
+        .ok_or_else(|| failed("command is empty".to_string()))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|error| failed(error.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(format!("{LLM_PROMPT}{contents}").as_bytes())
+            .map_err(|error| failed(error.to_string()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| failed(error.to_string()))?;
+    if !output.status.success() {
+        return Err(failed(format!("exited with status {}", output.status)));
+    }
+    let description = String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let description = description.trim_matches(['"', '\'']).trim().to_string();
+    if description.is_empty() {
+        return Err(failed("printed no description".to_string()));
+    }
+    Ok(description)
+}
+
+/// Replace the description in the contents of the skill file at `path`, refusing per-tool
+/// description maps.
+fn with_description(path: &Path, contents: &str, description: &str) -> Result<String> {
+    let invalid = |message: &str| Error::InvalidSkillFile {
+        path: path.to_path_buf(),
+        message: message.to_string(),
+    };
+    let is_map = frontmatter_json(contents)
+        .is_some_and(|(frontmatter, _)| frontmatter["description"].is_object());
+    if is_map {
+        return Err(invalid("description is a per-tool map; edit it by hand"));
+    }
+    set_field(contents, "description", &yaml_scalar(description))
+        .ok_or_else(|| invalid("no frontmatter to write the description into"))
+}
+
+/// Prompt for confirmation.
+fn confirm(message: &str) -> Result<bool> {
+    match Confirm::new(message).with_default(true).prompt() {
+        Ok(value) => Ok(value),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, suggest_description};
+    use crate::testutil::{TestFixture, skill_content};
+
+    #[test]
+    fn suggests_from_summary_sections_and_commands() {
+        let contents = "---\nname: pdf\ndescription: PDFs\n---\n# PDF Tools\n\n{% if tool == \"codex\" %}\nRead, fill, and merge **PDF** files. Works offline.\n{% endif %}\n\n## Overview\n\n## Form filling\n\n- Use `pdftk input.pdf` for merges.\n\n```\n`not-a-command`\n```\n\n## Merging documents\n\nRun `qpdf` to repair files.\n";
+        assert_eq!(
+            suggest_description("pdf", contents),
+            "Read, fill, and merge PDF files. Use when the task involves form filling or merging \
+             documents or calls for `pdftk` or `qpdf`."
+        );
+        assert_eq!(
+            suggest_description("release-notes", "---\nname: release-notes\n---\n"),
+            "release notes. Use when working with release notes."
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_the_suggested_description() {
+        let fixture = TestFixture::new().with_source_skill(
+            "notes",
+            &skill_content("notes", "Notes", "# Notes\n\nTake meeting notes.\n"),
+        );
+        let ctx = fixture.context();
+
+        run(&ctx, "notes".to_string(), None, true, true)
+            .await
+            .expect("dry run");
+        assert!(
+            fixture
+                .read_source_skill("notes")
+                .expect("notes")
+                .contains("description: Notes\n")
+        );
+
+        run(&ctx, "notes".to_string(), None, false, true)
+            .await
+            .expect("describe");
+        assert!(
+            fixture
+                .read_source_skill("notes")
+                .expect("notes")
+                .contains("description: Take meeting notes. Use when working with notes.\n")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_a_description_from_a_command() {
+        let fixture = TestFixture::new()
+            .with_source_skill("notes", &skill_content("notes", "Notes", "Body\n"));
+        let command = "sh -c 'cat > /dev/null; printf \"Use when: taking\\n notes\\n\"'";
+
+        run(
+            &fixture.context(),
+            "notes".to_string(),
+            Some(command.to_string()),
+            false,
+            true,
+        )
+        .await
+        .expect("describe");
+        assert!(
+            fixture
+                .read_source_skill("notes")
+                .expect("notes")
+                .contains("description: 'Use when: taking notes'\n")
+        );
+        let failing = run(
+            &fixture.context(),
+            "notes".to_string(),
+            Some("false".to_string()),
+            false,
+            true,
+        )
+        .await;
+        assert!(failing.is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Implementation of the `skills devcontainer` commands.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::push::{self, PushOptions},
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+    permissions::set_executable,
+    tool::Tool,
+};
+
+/// Directory the rendered skills are written to, inside the devcontainer folder.
+const SKILLS_DIR: &str = "skills";
+
+/// Name of the script that copies the skills into place when the container is created.
+const INSTALL_SCRIPT: &str = "install.sh";
+
+/// Execute the `devcontainer install` command.
+///
+/// Skills are rendered for `tool` and pushed into `skills/` under the devcontainer folder, which
+/// defaults to the project's `.devcontainer`. An install script beside them copies them into the
+/// tool's skill directory in the container, and the `postCreateCommand` that runs it is printed.
+/// With no names, every source skill is written.
+pub async fn install(
+    ctx: &Context,
+    skills: Vec<String>,
+    tool: Tool,
+    dir: Option<PathBuf>,
+    dry_run: bool,
+    force: bool,
+    yes: bool,
+) -> Result<()> {
+    let dir = dir.unwrap_or_else(|| ctx.project_dir.join(".devcontainer"));
+    let skills_dir = dir.join(SKILLS_DIR);
+    let all = skills.is_empty();
+    push::run(
+        ctx,
+        skills,
+        vec![tool],
+        PushOptions {
+            all,
+            dry_run,
+            force,
+            yes,
+            to: Some((skills_dir.clone(), tool)),
+            ..PushOptions::default()
+        },
+    )
+    .await?;
+
+    let script = skills_dir.join(INSTALL_SCRIPT);
+    let command = format!(
+        "sh {}",
+        script
+            .strip_prefix(&ctx.project_dir)
+            .unwrap_or(&script)
+            .to_string_lossy()
+            .replace('\\', "/")
+    );
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    println!();
+    if dry_run {
+        println!(
+            "{} {}",
+            palette.paint(Role::Action, "Would write"),
+            display_path(&script)
+        );
+        return Ok(());
+    }
+    write_install_script(&script, tool)?;
+    println!(
+        "{} {}",
+        palette.paint(Role::Action, "Wrote"),
+        display_path(&script)
+    );
+    println!();
+    println!("Add to {}:", display_path(&dir.join("devcontainer.json")));
+    println!("  \"postCreateCommand\": \"{command}\"");
+    Ok(())
+}
+
+/// Write the script that copies every skill beside it into a tool's skill directory.
+fn write_install_script(path: &Path, tool: Tool) -> Result<()> {
+    let target = tool.local_skills_dir().to_string_lossy().replace('\\', "/");
+    let script = format!(
+        "#!/bin/sh\n\
+         # Install these skills for {name}. Written by `skills devcontainer install`.\n\
+         set -eu\n\
+         here=\"$(cd \"$(dirname \"$0\")\" && pwd)\"\n\
+         target=\"$HOME/{target}\"\n\
+         mkdir -p \"$target\"\n\
+         for skill in \"$here\"/*/; do\n\
+         \x20   name=\"$(basename \"$skill\")\"\n\
+         \x20   rm -rf \"$target/$name\"\n\
+         \x20   cp -R \"$skill\" \"$target/$name\"\n\
+         done\n",
+        name = tool.display_name(),
+    );
+    let write_error = |source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    };
+    fs::write(path, script).map_err(write_error)?;
+    set_executable(path).map_err(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::install;
+    use crate::{skill::SKILL_FILE_NAME, testutil::TestFixture, tool::Tool};
+
+    #[tokio::test]
+    async fn writes_rendered_skills_and_install_script() {
+        let template = "---\nname: alpha\ndescription: Alpha\n---\n{{ tool }}\n";
+        let fixture = TestFixture::new().with_source_skill("alpha", template);
+        let ctx = fixture.context();
+
+        install(&ctx, Vec::new(), Tool::Codex, None, false, false, false)
+            .await
+            .expect("install");
+
+        let skills = ctx.project_dir.join(".devcontainer").join("skills");
+        let rendered =
+            fs::read_to_string(skills.join("alpha").join(SKILL_FILE_NAME)).expect("skill");
+        assert!(rendered.ends_with("codex"));
+        let script = fs::read_to_string(skills.join("install.sh")).expect("script");
+        assert!(script.contains("target=\"$HOME/.codex/skills\""));
+        assert!(!fixture.tool_skill_exists(Tool::Codex, "alpha"));
+    }
+}
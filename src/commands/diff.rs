@@ -1,112 +1,351 @@
 //! Implementation of the `skills diff` command.
 
-use owo_colors::OwoColorize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::ErrorKind,
+};
+
+use serde::Serialize;
 
 use crate::{
+    archive::{self, EntryKind, SymlinkPolicy},
     catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
+    commands::{init, print_json},
+    context::Context,
     diagnostics::Diagnostics,
     diff::{colorize_diff, resolve_pager, unified_diff, write_output},
     error::{Error, Result},
+    history::load_skill_file,
+    palette::Palette,
     paths::display_path,
-    skill::render_template,
-    status::{SyncStatus, normalize_line_endings},
+    skill::{RenderedSkill, SkillTemplate, ToolSkill},
+    status::{ContentHash, SyncStatus},
     tool::Tool,
 };
 
-/// Execute the diff command.
+/// How a skill's tool copies compare with its source.
+#[derive(Debug, Serialize)]
+struct SkillDiff {
+    /// Skill name.
+    name: String,
+    /// Each tool with a copy or a source to compare.
+    tools: Vec<ToolDiff>,
+}
+
+/// How one tool's copy of a skill compares with the source rendered for that tool.
+#[derive(Debug, Serialize)]
+struct ToolDiff {
+    /// Tool holding the copy.
+    tool: Tool,
+    /// Sync status of the copy.
+    status: SyncStatus,
+    /// Unified diff from the rendered source to the copy, when it was modified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+}
+
+/// Execute the diff command, failing with [`Error::Differences`] when `exit_code` is set and any
+/// skill is out of sync.
+///
+/// With `json`, each skill's per-tool statuses and uncolored diffs are printed as JSON instead.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill: Option<String>,
     pager: Option<String>,
+    exit_code: bool,
+    json: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
 
+    let skill = skill.map(|skill| catalog.resolve_name(&skill));
     let mut names = collect_names(&catalog, skill.as_deref())?;
     names.sort_by_key(|left| left.to_lowercase());
 
-    let mut output = String::new();
-    let use_color = color.enabled();
-
+    let mut diffs = Vec::new();
     for name in names {
-        let mut section = String::new();
+        diffs.extend(diff_skill(&catalog, &name, &mut diagnostics)?);
+    }
+    let differing = diffs
+        .iter()
+        .filter(|diff| {
+            diff.tools
+                .iter()
+                .any(|tool| tool.status != SyncStatus::Synced)
+        })
+        .count();
+
+    diagnostics.print_summary();
+    if json {
+        print_json(&diffs)?;
+        return check_differences(exit_code, differing);
+    }
+
+    let pager = resolve_pager(pager.as_deref(), ctx.overrides.pager.as_deref());
+    let palette = ctx.palette(&config);
+    let mut output = String::new();
+    for diff in &diffs {
         if !output.is_empty() {
-            section.push('\n');
+            output.push('\n');
         }
-        section.push_str(&format_header(&name));
-        section.push('\n');
-
-        let source = catalog.sources.get(&name);
-        let mut skip = false;
-
-        for tool in Tool::all() {
-            let tool_map = catalog.tools.get(&tool);
-            let tool_skill = tool_map.and_then(|skills| skills.get(&name));
-            let mut rendered = None;
-
-            let status = match (source, tool_skill) {
-                (Some(source), Some(tool_skill)) => {
-                    let rendered_value = match render_template(&source.contents, tool) {
-                        Ok(rendered) => rendered,
-                        Err(error) => {
-                            diagnostics.warn_skipped(&source.skill_path, error);
-                            skip = true;
-                            break;
-                        }
-                    };
-                    let synced = normalize_line_endings(&rendered_value)
-                        == normalize_line_endings(&tool_skill.contents);
-                    rendered = Some(rendered_value);
-                    if synced {
-                        SyncStatus::Synced
-                    } else {
-                        SyncStatus::Modified
-                    }
-                }
-                (Some(_), None) => SyncStatus::Missing,
-                (None, Some(_)) => SyncStatus::Orphan,
-                (None, None) => continue,
-            };
-
-            section.push_str(&format_tool_status(tool, status, use_color));
-            section.push('\n');
-
-            if status == SyncStatus::Modified
-                && let (Some(source), Some(tool_skill), Some(rendered)) =
-                    (source, tool_skill, rendered.as_ref())
-            {
-                let diff_text = unified_diff(
-                    &format!("source: {}", display_path(&source.skill_path)),
-                    &format!("tool: {}", display_path(&tool_skill.skill_path)),
-                    rendered,
-                    &tool_skill.contents,
-                );
-                let diff_text = colorize_diff(&diff_text, use_color);
-                section.push_str(&diff_text);
+        output.push_str(&format_header(&diff.name));
+        output.push('\n');
+        for tool in &diff.tools {
+            output.push_str(&format_tool_status(tool.tool, tool.status, &palette));
+            output.push('\n');
+            if let Some(diff_text) = &tool.diff {
+                let diff_text = colorize_diff(diff_text, &palette);
+                output.push_str(&diff_text);
                 if !diff_text.ends_with('\n') {
-                    section.push('\n');
+                    output.push('\n');
                 }
             }
         }
+    }
+    write_output(&output, pager.as_deref())?;
+    check_differences(exit_code, differing)
+}
 
-        if skip {
-            continue;
-        }
+/// Compare each tool's copy of a skill with its source, skipping the skill with a warning when
+/// the source fails to render.
+fn diff_skill(
+    catalog: &Catalog,
+    name: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<Option<SkillDiff>> {
+    let source = catalog.sources.get(name);
+    let mut tools = Vec::new();
+    for tool in Tool::all() {
+        let tool_skill = catalog.tools.get(&tool).and_then(|skills| skills.get(name));
+        let (status, diff) = match (source, tool_skill) {
+            (Some(source), Some(tool_skill)) => {
+                let rendered = match catalog.render_skill(source, tool) {
+                    Ok(rendered) => rendered,
+                    Err(error) => {
+                        diagnostics.warn_skipped(&source.skill_path, error);
+                        return Ok(None);
+                    }
+                };
+                if tool_skill.matches(source, &rendered) {
+                    (SyncStatus::Synced, None)
+                } else {
+                    let diff = diff_copy(
+                        source,
+                        &rendered,
+                        tool_skill,
+                        (
+                            &format!("source: {}", display_path(&source.skill_path)),
+                            &format!("tool: {}", display_path(&tool_skill.skill_path)),
+                        ),
+                    )?;
+                    (SyncStatus::Modified, Some(diff))
+                }
+            }
+            (Some(_), None) => (SyncStatus::Missing, None),
+            (None, Some(_)) => (SyncStatus::Orphan, None),
+            (None, None) => continue,
+        };
+        tools.push(ToolDiff { tool, status, diff });
+    }
+    Ok(Some(SkillDiff {
+        name: name.to_string(),
+        tools,
+    }))
+}
+
+/// Execute the diff command for two source skills, comparing every file in their directories.
+pub async fn run_between(
+    ctx: &Context,
+    first: &str,
+    second: &str,
+    pager: Option<String>,
+    exit_code: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let pager = resolve_pager(pager.as_deref(), ctx.overrides.pager.as_deref());
+    let palette = ctx.palette(&config);
+
+    let first_files = source_files(&catalog, first)?;
+    let second_files = source_files(&catalog, second)?;
+    let changes = colorize_diff(
+        &diff_files((first, &first_files), (second, &second_files)),
+        &palette,
+    );
+
+    diagnostics.print_summary();
+    if changes.is_empty() {
+        println!("Skills '{first}' and '{second}' have identical files.");
+        return Ok(());
+    }
+    let output = format!(
+        "{}\n{changes}",
+        format_header(&format!("{first} <-> {second}"))
+    );
+    write_output(&output, pager.as_deref())?;
+    check_differences(exit_code, 1)
+}
+
+/// Execute the diff command for a snapshot, comparing a tool copy as it was before the operation
+/// `at` selects with the copy now.
+pub async fn run_at(
+    ctx: &Context,
+    skill: &str,
+    at: &str,
+    pager: Option<String>,
+    exit_code: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let pager = resolve_pager(pager.as_deref(), ctx.overrides.pager.as_deref());
+    let palette = ctx.palette(&config);
+    let state = ctx.load_state()?;
 
-        output.push_str(&section);
+    let file = load_skill_file(&state, &ctx.state_path, skill, at, &config.skill_files)?;
+    let current_path = file
+        .path
+        .clone()
+        .unwrap_or_else(|| config.skill_files.path_in(&file.snapshot.dir));
+    let current = match fs::read_to_string(&current_path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+        Err(source) => {
+            return Err(Error::SkillRead {
+                path: current_path,
+                source,
+            });
+        }
+    };
+    let before = file.contents.unwrap_or_default();
+    let description = file.snapshot.describe();
+    if before == current {
+        println!(
+            "No changes to {} since before {description}.",
+            display_path(&current_path)
+        );
+        return Ok(());
     }
 
-    diagnostics.print_skipped_summary();
-    let pager = resolve_pager(pager.as_deref());
+    let diff_text = unified_diff(
+        &format!("before {description}: {}", display_path(&current_path)),
+        &format!("now: {}", display_path(&current_path)),
+        &before,
+        &current,
+    );
+    let output = format!(
+        "{}\n{}",
+        format_header(skill),
+        colorize_diff(&diff_text, &palette)
+    );
     write_output(&output, pager.as_deref())?;
+    check_differences(exit_code, 1)
+}
+
+/// Fail with [`Error::Differences`] when differences were found and `exit_code` asks for it.
+fn check_differences(exit_code: bool, count: usize) -> Result<()> {
+    if exit_code && count > 0 {
+        return Err(Error::Differences { count });
+    }
     Ok(())
 }
 
+/// Read every file of a source skill, keyed by its path in the skill directory.
+fn source_files(catalog: &Catalog, name: &str) -> Result<BTreeMap<String, Vec<u8>>> {
+    let skill = catalog
+        .sources
+        .get(&catalog.resolve_name(name))
+        .ok_or_else(|| Error::SkillNotFound {
+            name: name.to_string(),
+        })?;
+    let entries = archive::read_dir(name, &skill.skill_dir, SymlinkPolicy::Follow)?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            let path = entry.path_below(name)?.to_string();
+            match entry.kind {
+                EntryKind::File { contents, .. } => Some((path, contents)),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+/// Describe how a tool's copy of a skill differs from the source rendered for that tool: a
+/// unified diff of the skill file, labeled with `labels`, when it changed, then the support files
+/// that differ.
+pub fn diff_copy(
+    source: &SkillTemplate,
+    rendered: &RenderedSkill,
+    tool_skill: &ToolSkill,
+    labels: (&str, &str),
+) -> Result<String> {
+    let mut output = String::new();
+    if ContentHash::of(&rendered.contents) != tool_skill.hash {
+        output = unified_diff(
+            labels.0,
+            labels.1,
+            &rendered.contents,
+            &tool_skill.read_contents()?,
+        );
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    output.push_str(&diff_files(
+        (
+            &format!("source: {}", display_path(&source.skill_dir)),
+            &rendered.support_contents(source)?,
+        ),
+        (
+            &format!("tool: {}", display_path(&tool_skill.skill_dir)),
+            &tool_skill.read_support_files()?,
+        ),
+    ));
+    Ok(output)
+}
+
+/// Describe how two sets of skill files differ: a unified diff for each changed text file, and a
+/// line for each binary change and each file only one side has.
+fn diff_files(
+    (first, first_files): (&str, &BTreeMap<String, Vec<u8>>),
+    (second, second_files): (&str, &BTreeMap<String, Vec<u8>>),
+) -> String {
+    let paths: BTreeSet<&String> = first_files.keys().chain(second_files.keys()).collect();
+    let mut output = String::new();
+    for path in paths {
+        match (first_files.get(path), second_files.get(path)) {
+            (Some(old), Some(new)) if old != new => {
+                match (str::from_utf8(old), str::from_utf8(new)) {
+                    (Ok(old), Ok(new)) => {
+                        let diff_text = unified_diff(
+                            &format!("{first}/{path}"),
+                            &format!("{second}/{path}"),
+                            old,
+                            new,
+                        );
+                        output.push_str(&diff_text);
+                        if !output.ends_with('\n') {
+                            output.push('\n');
+                        }
+                    }
+                    _ => output.push_str(&format!(
+                        "Binary files {first}/{path} and {second}/{path} differ\n"
+                    )),
+                }
+            }
+            (Some(_), None) => output.push_str(&format!("Only in {first}: {path}\n")),
+            (None, Some(_)) => output.push_str(&format!("Only in {second}: {path}\n")),
+            _ => {}
+        }
+    }
+    output
+}
+
 /// Collect skill names for diffing.
 fn collect_names(catalog: &Catalog, skill: Option<&str>) -> Result<Vec<String>> {
     if let Some(skill) = skill {
@@ -139,26 +378,12 @@ fn collect_names(catalog: &Catalog, skill: Option<&str>) -> Result<Vec<String>>
 }
 
 /// Format a tool status line for diff output.
-fn format_tool_status(tool: Tool, status: SyncStatus, color: bool) -> String {
-    let label = match status {
-        SyncStatus::Synced => "synced",
-        SyncStatus::Modified => "modified",
-        SyncStatus::Missing => "missing",
-        SyncStatus::Orphan => "orphan",
-    };
-
-    let status_text = if !color {
-        label.to_string()
-    } else {
-        match status {
-            SyncStatus::Synced => label.green().to_string(),
-            SyncStatus::Modified => label.yellow().to_string(),
-            SyncStatus::Missing => label.red().to_string(),
-            SyncStatus::Orphan => label.red().to_string(),
-        }
-    };
-
-    format!("{}: {}", tool.display_name(), status_text)
+fn format_tool_status(tool: Tool, status: SyncStatus, palette: &Palette) -> String {
+    format!(
+        "{}: {}",
+        tool.display_name(),
+        palette.paint(status.role(), status.label())
+    )
 }
 
 /// Format a header for diff output.
@@ -170,3 +395,112 @@ fn format_header(name: &str) -> String {
     line.push_str("===");
     line
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{diff_files, diff_skill, run, source_files};
+    use crate::{
+        error::Error,
+        testutil::{TestFixture, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn compares_every_file_of_two_skills() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "pdf",
+                &skill_content("pdf", "Read PDFs", "Use pdftotext.\n"),
+            )
+            .with_source_skill(
+                "pdf-fork",
+                &skill_content("pdf-fork", "Read PDFs", "Use pdfplumber.\n"),
+            );
+        let notes = fixture.source_dir().join("pdf-fork").join("notes.md");
+        fs::write(notes, "Fork notes\n").expect("write notes");
+        let catalog = fixture.catalog();
+        let first = source_files(&catalog, "pdf").expect("pdf");
+        let second = source_files(&catalog, "pdf-fork").expect("fork");
+
+        let output = diff_files(("pdf", &first), ("pdf-fork", &second));
+        assert!(output.contains("--- pdf/SKILL.md\n+++ pdf-fork/SKILL.md\n"));
+        assert!(output.contains("-Use pdftotext.\n+Use pdfplumber.\n"));
+        assert!(output.ends_with("Only in pdf-fork: notes.md\n"));
+        assert!(diff_files(("pdf", &first), ("pdf", &first)).is_empty());
+        assert!(source_files(&catalog, "missing").is_err());
+    }
+
+    #[test]
+    fn reports_each_tool_as_json() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"))
+            .with_tool_skill(
+                Tool::Codex,
+                "alpha",
+                &skill_content("alpha", "Alpha", "Edited\n"),
+            );
+        let catalog = fixture.catalog();
+        let diff = diff_skill(&catalog, "alpha", &mut fixture.context().diagnostics())
+            .expect("diff")
+            .expect("rendered");
+
+        let value = serde_json::to_value(&diff).expect("json");
+        let tools = value["tools"].as_array().expect("tools");
+        assert_eq!(tools[0]["tool"], "claude");
+        assert_eq!(tools[0]["status"], "synced");
+        assert!(tools[0].get("diff").is_none());
+        assert_eq!(tools[1]["status"], "modified");
+        assert!(tools[1]["diff"].as_str().expect("diff").contains("+Edited"));
+        assert_eq!(tools[2]["status"], "missing");
+    }
+
+    #[test]
+    fn shows_support_file_changes() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"));
+        fs::write(
+            fixture.source_dir().join("alpha").join("guide.md"),
+            "{{ tool }}",
+        )
+        .expect("write guide");
+        let installed = fixture.tool_dir(Tool::Claude).join("alpha");
+        fs::write(installed.join("guide.md"), "edited").expect("write guide");
+        fs::write(installed.join("logo.png"), [0x89, 0xff]).expect("write logo");
+        let catalog = fixture.catalog();
+        let diff = diff_skill(&catalog, "alpha", &mut fixture.context().diagnostics())
+            .expect("diff")
+            .expect("rendered");
+
+        let value = serde_json::to_value(&diff).expect("json");
+        assert_eq!(value["tools"][0]["status"], "modified");
+        let output = value["tools"][0]["diff"].as_str().expect("diff");
+        assert!(!output.contains("SKILL.md"), "{output}");
+        assert!(output.contains("-claude\n"), "{output}");
+        assert!(output.contains("+edited\n"), "{output}");
+        assert!(output.contains(": logo.png"), "{output}");
+    }
+
+    #[tokio::test]
+    async fn exit_code_reports_differences() {
+        let fixture = TestFixture::new().with_source_skill("alpha", &simple_skill("alpha"));
+        let ctx = fixture.context();
+        let result = run(&ctx, Some("alpha".to_string()), None, true, false).await;
+        assert!(matches!(result, Err(Error::Differences { count: 1 })));
+        run(&ctx, Some("alpha".to_string()), None, false, false)
+            .await
+            .expect("differences are not an error without --exit-code");
+
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Codex, "alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Gemini, "alpha", &simple_skill("alpha"));
+        run(&fixture.context(), None, None, true, false)
+            .await
+            .expect("no differences");
+    }
+}
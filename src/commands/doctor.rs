@@ -0,0 +1,144 @@
+//! Implementation of the `skills doctor` command.
+
+use crate::{
+    context::Context,
+    error::{Error, Result},
+    palette::{Palette, Role, Theme},
+    paths::display_path,
+    permissions::find_access_problem,
+    policy::Policy,
+    tool::Tool,
+    units::format_size,
+};
+
+/// Indent for details under a check.
+const INDENT: &str = "    ";
+
+/// Execute the doctor command, checking the config, sources, tool directories, and state, and
+/// reporting the team policy in force.
+///
+/// Like `skills env` this never starts `init`, so it can diagnose a broken setup. Fails when any
+/// check does.
+pub async fn run(ctx: &Context) -> Result<()> {
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config();
+    let palette = match &config {
+        Ok(config) => ctx.palette(config),
+        Err(_) => Palette::new(&Theme::default(), ctx.color.enabled()),
+    };
+    let mut failed = 0;
+    let mut check = |ok: bool, subject: &str, detail: &str| {
+        let mark = if ok {
+            palette.paint(Role::Success, "✓")
+        } else {
+            failed += 1;
+            palette.paint(Role::Failure, "✗")
+        };
+        println!("{mark} {} {detail}", palette.paint(Role::Label, subject));
+    };
+
+    let config = match config {
+        Ok(config) => {
+            check(true, "config:", &display_path(&ctx.config_path));
+            config
+        }
+        Err(error) => {
+            check(false, "config:", &error.to_string());
+            return Err(Error::DoctorFailed { count: 1 });
+        }
+    };
+    for source in config.sources() {
+        if source.is_dir() {
+            check(true, "source:", &display_path(source));
+        } else {
+            check(
+                false,
+                "source:",
+                &format!("{} is not a directory", display_path(source)),
+            );
+        }
+    }
+    for tool in Tool::all() {
+        let dir = ctx.tool_dir(tool);
+        if !dir.is_dir() {
+            continue;
+        }
+        match find_access_problem(dir, true) {
+            None => check(true, "tool:  ", &display_path(dir)),
+            Some(problem) => {
+                check(false, "tool:  ", &problem.describe());
+                println!("{INDENT}run `{}`", problem.fix(dir));
+            }
+        }
+    }
+    match ctx.load_state() {
+        Ok(_) => check(true, "state: ", &display_path(&ctx.state_path)),
+        Err(error) => check(false, "state: ", &error.to_string()),
+    }
+    match Policy::load(ctx, &config, &mut diagnostics).await {
+        Ok(Some(policy)) => {
+            check(true, "policy:", &policy.origin);
+            print_policy(&policy, &palette);
+        }
+        Ok(None) => check(true, "policy:", "none configured"),
+        Err(error) => check(false, "policy:", &error.to_string()),
+    }
+
+    diagnostics.print_summary();
+    if failed > 0 {
+        return Err(Error::DoctorFailed { count: failed });
+    }
+    Ok(())
+}
+
+/// Print the rules a policy enforces.
+fn print_policy(policy: &Policy, palette: &Palette) {
+    let label = |text: &str| palette.paint(Role::Label, &format!("{text:<15}"));
+    let list = |items: &[String], empty: &str| {
+        if items.is_empty() {
+            empty.to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    println!(
+        "{INDENT}{} {}",
+        label("allowed hosts:"),
+        list(&policy.allowed_hosts, "any")
+    );
+    let keys = match policy.signing_keys.len() {
+        0 => "none (imports need not be signed)".to_string(),
+        count => format!("{count} (imports must be signed)"),
+    };
+    println!("{INDENT}{} {keys}", label("signing keys:"));
+    println!(
+        "{INDENT}{} {}",
+        label("banned fields:"),
+        list(&policy.banned_fields, "none")
+    );
+    let size = policy
+        .max_skill_size
+        .map_or_else(|| "none".to_string(), format_size);
+    println!("{INDENT}{} {size}", label("max skill size:"));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::run;
+    use crate::{error::Error, testutil::TestFixture};
+
+    #[tokio::test]
+    async fn reports_policy_errors() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        run(&ctx).await.expect("healthy setup");
+
+        let mut config = fs::read_to_string(&ctx.config_path).expect("read config");
+        config.push_str("[policy]\nsource = 'missing-policy.toml'\n");
+        fs::write(&ctx.config_path, config).expect("write config");
+        let result = run(&ctx).await;
+        assert!(matches!(result, Err(Error::DoctorFailed { count: 1 })));
+    }
+}
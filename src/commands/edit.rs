@@ -1,23 +1,21 @@
 //! Implementation of the `skills edit` command.
 
-use std::{env, process::Command};
+use std::{env, path::Path, process::Command};
 
 use crate::{
-    catalog::Catalog,
     commands::init,
-    config::Config,
-    diagnostics::Diagnostics,
+    context::Context,
     error::{Error, Result},
-    skill::SKILL_FILE_NAME,
     tool::Tool,
 };
 
 /// Execute the edit command.
-pub async fn run(verbose: bool, skill_name: String) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
+pub async fn run(ctx: &Context, skill_name: String) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let skill_name = catalog.resolve_name(&skill_name);
 
     // Find the skill file path - search sources first, then tools, then local
     let skill_path = if let Some(source) = catalog.sources.get(&skill_name) {
@@ -26,18 +24,18 @@ pub async fn run(verbose: bool, skill_name: String) -> Result<()> {
         // Check tool installations
         let mut found = None;
         for tool in Tool::all() {
-            if let Some(skills) = catalog.tools.get(&tool) {
-                if let Some(skill) = skills.get(&skill_name) {
-                    found = Some(skill.skill_path.clone());
-                    break;
-                }
+            if let Some(skills) = catalog.tools.get(&tool)
+                && let Some(skill) = skills.get(&skill_name)
+            {
+                found = Some(skill.skill_path.clone());
+                break;
             }
         }
         // Check local skills
         if found.is_none() {
             for skills in catalog.local.values() {
                 if let Some(skill) = skills.get(&skill_name) {
-                    found = Some(skill.skill_dir.join(SKILL_FILE_NAME));
+                    found = Some(skill.skill_path.clone());
                     break;
                 }
             }
@@ -47,14 +45,20 @@ pub async fn run(verbose: bool, skill_name: String) -> Result<()> {
         })?
     };
 
-    // Get editor from environment
+    open_in_editor(&skill_path)?;
+    diagnostics.print_summary();
+
+    Ok(())
+}
+
+/// Open a file in `$EDITOR`, falling back to `$VISUAL` and then `vi`, and wait for it to exit.
+pub fn open_in_editor(path: &Path) -> Result<()> {
     let editor = env::var("EDITOR")
         .or_else(|_| env::var("VISUAL"))
         .unwrap_or_else(|_| "vi".to_string());
 
-    // Open the skill in the editor
     let status = Command::new(&editor)
-        .arg(&skill_path)
+        .arg(path)
         .status()
         .map_err(|e| Error::EditorFailed {
             editor: editor.clone(),
@@ -67,6 +71,5 @@ pub async fn run(verbose: bool, skill_name: String) -> Result<()> {
             message: format!("exited with status {}", status),
         });
     }
-
     Ok(())
 }
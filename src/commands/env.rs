@@ -0,0 +1,127 @@
+//! Implementation of the `skills env` command.
+
+use std::path::Path;
+
+use crate::{
+    config::PROJECT_CONFIG_FILE,
+    context::Context,
+    error::Result,
+    palette::{Palette, Role, Theme},
+    paths::display_path,
+    tool::Tool,
+};
+
+/// Indent for listed values.
+const INDENT: &str = "    ";
+
+/// Execute the env command.
+///
+/// Unlike other commands this never starts `init` and reports config errors instead of failing,
+/// since it is the first thing to run when resolution goes wrong.
+pub async fn run(ctx: &Context) -> Result<()> {
+    let config = ctx.load_config();
+    let palette = match &config {
+        Ok(config) => ctx.palette(config),
+        Err(_) => Palette::new(&Theme::default(), ctx.color.enabled()),
+    };
+    let label = |text: &str| palette.paint(Role::Label, text);
+
+    println!(
+        "{} {}",
+        label("config: "),
+        describe_file(&ctx.config_path, &palette)
+    );
+    let project_config = ctx.project_dir.join(PROJECT_CONFIG_FILE);
+    println!(
+        "{} {}",
+        label("project:"),
+        palette.paint(Role::Path, &display_path(&ctx.project_dir))
+    );
+    println!(
+        "{} {}",
+        label("        "),
+        describe_file(&project_config, &palette)
+    );
+    println!(
+        "{} {}",
+        label("state:  "),
+        describe_file(&ctx.state_path, &palette)
+    );
+
+    println!();
+    let origin = if ctx.overrides.sources.is_some() {
+        " (from SKILLS_SOURCES)"
+    } else {
+        ""
+    };
+    println!("{}", label(&format!("sources{origin}:")));
+    match &config {
+        Ok(config) => {
+            for source in config.sources() {
+                let missing = if source.is_dir() { "" } else { " (missing)" };
+                println!(
+                    "{INDENT}{}{}",
+                    palette.paint(Role::Path, &display_path(source)),
+                    palette.paint(Role::Warning, missing)
+                );
+            }
+        }
+        Err(error) => println!(
+            "{INDENT}{}",
+            palette.paint(Role::Failure, &error.to_string())
+        ),
+    }
+
+    println!();
+    println!("{}", label("tools:"));
+    for tool in Tool::all() {
+        println!(
+            "{INDENT}{} {}",
+            label(&format!("{:<7}", format!("{}:", tool.id()))),
+            palette.paint(Role::Path, &display_path(ctx.tool_dir(tool)))
+        );
+        println!(
+            "{INDENT}{} {}",
+            label(&format!("{:<7}", "")),
+            palette.paint(Role::Path, &display_path(&ctx.local_dir(tool)))
+        );
+    }
+
+    println!();
+    println!("{}", label("environment:"));
+    if ctx.overrides.applied.is_empty() {
+        println!("{INDENT}(no SKILLS_* variables set)");
+    }
+    for (name, value) in &ctx.overrides.applied {
+        println!("{INDENT}{name}={value}");
+    }
+    Ok(())
+}
+
+/// Describe a file path and whether it exists.
+fn describe_file(path: &Path, palette: &Palette) -> String {
+    let status = if path.is_file() {
+        palette.paint(Role::Success, "found")
+    } else {
+        palette.paint(Role::Warning, "not found")
+    };
+    format!(
+        "{} ({status})",
+        palette.paint(Role::Path, &display_path(path))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::testutil::TestFixture;
+
+    #[tokio::test]
+    async fn reports_without_a_config() {
+        let fixture = TestFixture::new();
+        let mut ctx = fixture.context();
+        ctx.config_path = fixture.work_dir().join("missing.toml");
+
+        run(&ctx).await.expect("env never fails on config errors");
+    }
+}
@@ -0,0 +1,302 @@
+//! Implementation of the `skills exec` command.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    catalog::Catalog,
+    commands::{init, validate::check_skill},
+    context::Context,
+    diagnostics::Diagnostics,
+    error::{Error, Result},
+    schema::Schema,
+    skill::SkillTemplate,
+    status::{SkillEntry, build_entries},
+    tool::ToolFilter,
+};
+
+/// One line of batch input.
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Caller-chosen identifier echoed back in the response.
+    #[serde(default)]
+    id: Option<Value>,
+    /// Operation to run.
+    #[serde(flatten)]
+    operation: Operation,
+}
+
+/// An operation a batch can run against the loaded catalog.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Operation {
+    /// Every skill with its sync status per tool.
+    List,
+    /// Sync status of one skill per tool.
+    Status {
+        /// Skill name.
+        skill: String,
+    },
+    /// A source skill rendered for one tool or all of them.
+    Render {
+        /// Skill name.
+        skill: String,
+        /// Tool to render for.
+        #[serde(default = "all_tools")]
+        tool: ToolFilter,
+    },
+    /// A source skill's path and raw template.
+    Show {
+        /// Skill name.
+        skill: String,
+    },
+    /// Problems `skills validate` would report for a source skill.
+    Validate {
+        /// Skill name.
+        skill: String,
+    },
+}
+
+/// Default tool filter for operations that accept one.
+fn all_tools() -> ToolFilter {
+    ToolFilter::All
+}
+
+/// Result of one batch line.
+#[derive(Debug, Serialize)]
+struct Response {
+    /// Identifier from the request, if it had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    /// Whether the operation succeeded.
+    ok: bool,
+    /// Operation output on success.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    /// Error message on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Execute the exec command.
+///
+/// Requests are read as JSON lines from stdin, or from `batch` when given, and answered with one
+/// JSON line each on stdout. The catalog is loaded once for the whole batch, and a failed request
+/// is reported in its response without stopping the rest.
+pub async fn run(ctx: &Context, batch: Option<PathBuf>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+
+    let (input, input_path): (Box<dyn BufRead>, &Path) = match &batch {
+        Some(path) => {
+            let file = File::open(path).map_err(|source| Error::BatchRead {
+                path: path.clone(),
+                source,
+            })?;
+            (Box::new(BufReader::new(file)), path)
+        }
+        None => (Box::new(io::stdin().lock()), Path::new("<stdin>")),
+    };
+
+    let mut stdout = io::stdout().lock();
+    for line in input.lines() {
+        let line = line.map_err(|source| Error::BatchRead {
+            path: input_path.to_path_buf(),
+            source,
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = respond(&line, &catalog, &mut diagnostics);
+        let json = serde_json::to_string(&response).map_err(|error| Error::TemplateRender {
+            message: error.to_string(),
+        })?;
+        // Flush per line so callers can stream requests and read answers as they arrive; stop
+        // quietly once the reader has gone away.
+        if writeln!(stdout, "{json}")
+            .and_then(|()| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Parse and run one request line.
+fn respond(line: &str, catalog: &Catalog, diagnostics: &mut Diagnostics) -> Response {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(error) => {
+            return Response {
+                id: serde_json::from_str::<Value>(line)
+                    .ok()
+                    .and_then(|value| value.get("id").cloned()),
+                ok: false,
+                result: None,
+                error: Some(format!("invalid request: {error}")),
+            };
+        }
+    };
+    match execute(&request.operation, catalog, diagnostics) {
+        Ok(result) => Response {
+            id: request.id,
+            ok: true,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => Response {
+            id: request.id,
+            ok: false,
+            result: None,
+            error: Some(error.to_string()),
+        },
+    }
+}
+
+/// Run an operation against the catalog.
+fn execute(
+    operation: &Operation,
+    catalog: &Catalog,
+    diagnostics: &mut Diagnostics,
+) -> Result<Value> {
+    match operation {
+        Operation::List => Ok(Value::Array(
+            build_entries(catalog, diagnostics)
+                .iter()
+                .map(entry_json)
+                .collect(),
+        )),
+        Operation::Status { skill } => build_entries(catalog, diagnostics)
+            .iter()
+            .find(|entry| entry.name == catalog.resolve_name(skill))
+            .map(entry_json)
+            .ok_or_else(|| Error::SkillNotFound {
+                name: skill.clone(),
+            }),
+        Operation::Render { skill, tool } => {
+            let source = find_source(catalog, skill)?;
+            let mut rendered = BTreeMap::new();
+            for tool in tool.to_tools() {
+                let output = catalog
+                    .render(source, tool)
+                    .map_err(|message| Error::TemplateRender { message })?;
+                rendered.insert(tool.id(), output);
+            }
+            Ok(json!(rendered))
+        }
+        Operation::Show { skill } => {
+            let source = find_source(catalog, skill)?;
+            Ok(json!({
+                "path": source.skill_path,
+                "contents": source.contents,
+            }))
+        }
+        Operation::Validate { skill } => {
+            let source = find_source(catalog, skill)?;
+            let schema = Schema::load(&source.source_root)?;
+            let problems = check_skill(
+                source.dir_name(),
+                &source.contents,
+                &source.bases,
+                schema.as_ref(),
+                None,
+            );
+            Ok(json!({
+                "valid": problems.is_empty(),
+                "problems": problems,
+            }))
+        }
+    }
+}
+
+/// Find a source skill by name.
+fn find_source<'a>(catalog: &'a Catalog, name: &str) -> Result<&'a SkillTemplate> {
+    catalog
+        .sources
+        .get(&catalog.resolve_name(name))
+        .ok_or_else(|| Error::SkillNotFound {
+            name: name.to_string(),
+        })
+}
+
+/// Describe a skill's status per tool as JSON.
+fn entry_json(entry: &SkillEntry) -> Value {
+    let tools: BTreeMap<&str, &str> = entry
+        .tool_statuses
+        .iter()
+        .map(|status| (status.tool.id(), status.status.label()))
+        .collect();
+    json!({ "name": entry.name, "tools": tools })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::respond;
+    use crate::{
+        testutil::{TestFixture, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn answers_each_request_line() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "alpha",
+                &skill_content(
+                    "alpha",
+                    "Alpha",
+                    "{% if tool == \"codex\" %}Codex{% else %}Other{% endif %}",
+                ),
+            )
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"));
+        let catalog = fixture.catalog();
+        let mut diagnostics = fixture.context().diagnostics();
+        let mut answer = |line: &str| {
+            serde_json::to_value(respond(line, &catalog, &mut diagnostics)).expect("json")
+        };
+
+        let list = answer(r#"{"id": 1, "op": "list"}"#);
+        assert_eq!(list["id"], 1);
+        assert_eq!(list["result"][0]["tools"]["claude"], "modified");
+        assert_eq!(list["result"][0]["tools"]["codex"], "missing");
+
+        let render = answer(r#"{"op": "render", "skill": "alpha", "tool": "codex"}"#);
+        assert!(
+            render["result"]["codex"]
+                .as_str()
+                .expect("codex")
+                .ends_with("Codex")
+        );
+        assert_eq!(render["result"].as_object().expect("object").len(), 1);
+
+        let validate = answer(r#"{"op": "validate", "skill": "alpha"}"#);
+        assert_eq!(validate["result"]["valid"], true);
+
+        let missing = answer(r#"{"id": "m", "op": "show", "skill": "nope"}"#);
+        assert_eq!(missing["ok"], false);
+        assert_eq!(missing["id"], "m");
+        assert_eq!(missing.get("result"), None);
+
+        let invalid = answer(r#"{"id": 7, "op": "explode"}"#);
+        assert_eq!(invalid["ok"], false);
+        assert_eq!(invalid["id"], 7);
+        assert!(answer("not json")["error"].is_string());
+        assert_eq!(
+            answer(r#"{"op": "status", "skill": "alpha"}"#)["ok"],
+            Value::Bool(true)
+        );
+    }
+}
@@ -0,0 +1,496 @@
+//! Implementation of the `skills export` command.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use minijinja::{Environment, context};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use serde::Serialize;
+
+use crate::{
+    archive::SymlinkPolicy,
+    commands::{
+        init,
+        pack::{PackFormat, pack_skill},
+    },
+    context::Context,
+    error::{Error, Result},
+    frontmatter::{parse_metadata, strip_frontmatter},
+    palette::Role,
+    paths::display_path,
+    skill::render_template,
+    tool::ToolFilter,
+};
+
+/// Directory under the site root holding per-skill pages.
+const PAGES_DIR: &str = "skills";
+
+/// Directory under the site root holding packed skill archives.
+const ARCHIVES_DIR: &str = "archives";
+
+/// Shared page styling.
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;\
+padding:0 1rem;line-height:1.5}table{border-collapse:collapse;width:100%}td,th{text-align:left;\
+padding:.4rem;border-bottom:1px solid #ddd;vertical-align:top}pre{background:#f6f8fa;\
+padding:1rem;overflow:auto}code{font-size:.9em}.meta{color:#555}";
+
+/// Template for the catalog index page.
+const INDEX_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Skills</title>
+<style>{{ style }}</style>
+</head>
+<body>
+<h1>Skills</h1>
+<p class="meta">{{ skills|length }} skill(s) &middot; <a href="catalog.json">catalog.json</a></p>
+<table>
+<tr><th>Name</th><th>Description</th><th>Download</th></tr>
+{%- for skill in skills %}
+<tr><td><a href="{{ skill.page }}">{{ skill.name }}</a></td><td>{{ skill.description }}</td><td><a href="{{ skill.archive }}">zip</a></td></tr>
+{%- endfor %}
+</table>
+</body>
+</html>
+"#;
+
+/// Template for a single skill page.
+const SKILL_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{ skill.name }}</title>
+<style>{{ style }}</style>
+</head>
+<body>
+<p><a href="../index.html">&larr; All skills</a></p>
+<h1>{{ skill.name }}</h1>
+<p>{{ skill.description }}</p>
+<p class="meta"><a href="../{{ skill.archive }}">Download {{ skill.name }}.zip</a> &middot; install with <code>skills import {{ skill.name }}.zip</code></p>
+<h2>Files</h2>
+<ul>
+{%- for file in skill.files %}
+<li><code>{{ file }}</code></li>
+{%- endfor %}
+</ul>
+{%- for section in sections %}
+{%- if sections|length > 1 %}
+<h2>{{ section.tool }}</h2>
+{%- endif %}
+{{ section.html|safe }}
+{%- endfor %}
+</body>
+</html>
+"#;
+
+/// Output format for `export metadata`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MetadataFormat {
+    /// A JSON array of objects.
+    #[default]
+    Json,
+    /// A YAML list of mappings.
+    Yaml,
+    /// Comma-separated values with a header row; list fields are joined with `;`.
+    Csv,
+}
+
+/// Metadata for one skill written by `export metadata`.
+#[derive(Debug, Serialize)]
+struct SkillMetadata {
+    /// Skill name.
+    name: String,
+    /// Skill description.
+    description: String,
+    /// Free-form tags.
+    tags: Vec<String>,
+    /// Declared skill version.
+    version: Option<String>,
+    /// Tools the skill is intended for.
+    targets: Vec<String>,
+}
+
+/// Catalog entry written to `catalog.json` and used by the page templates.
+#[derive(Debug, Serialize)]
+struct SiteSkill {
+    /// Skill name.
+    name: String,
+    /// Skill description.
+    description: String,
+    /// Page path relative to the site root.
+    page: String,
+    /// Archive path relative to the site root.
+    archive: String,
+    /// Files included in the archive.
+    files: Vec<String>,
+}
+
+/// Rendered skill body for one tool.
+#[derive(Debug, Serialize)]
+struct Section {
+    /// Tool display name.
+    tool: &'static str,
+    /// Rendered HTML body.
+    html: String,
+}
+
+/// Execute the `export site` command.
+pub async fn site(
+    ctx: &Context,
+    output: PathBuf,
+    tool_filter: ToolFilter,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    if !force && is_non_empty_dir(&output) {
+        return Err(Error::PathExists { path: output });
+    }
+    for dir in [output.join(PAGES_DIR), output.join(ARCHIVES_DIR)] {
+        fs::create_dir_all(&dir).map_err(|source| Error::SkillWrite { path: dir, source })?;
+    }
+
+    let mut env = Environment::new();
+    env.add_template("index.html", INDEX_TEMPLATE)
+        .and_then(|()| env.add_template("skill.html", SKILL_TEMPLATE))
+        .map_err(|error| template_error(&error))?;
+
+    let mut skills: Vec<_> = catalog.sources.values().collect();
+    skills.sort_by_key(|skill| skill.name.to_lowercase());
+
+    let tools = tool_filter.to_tools();
+    let mut entries = Vec::new();
+    for skill in skills {
+        let mut sections = Vec::new();
+        for &tool in &tools {
+            match render_template(&skill.contents, tool, &skill.bases) {
+                Ok(rendered) => sections.push(Section {
+                    tool: tool.display_name(),
+                    html: markdown_to_html(strip_frontmatter(&rendered)),
+                }),
+                Err(error) => diagnostics.warn_skipped(&skill.skill_path, error),
+            }
+        }
+        if sections.len() < tools.len() {
+            continue;
+        }
+
+        let archive = format!("{ARCHIVES_DIR}/{}.zip", skill.name);
+        let packed = pack_skill(
+            &skill.name,
+            &skill.skill_dir,
+            &output.join(&archive),
+            PackFormat::Zip,
+            SymlinkPolicy::Reject,
+            &config.skill_files,
+        )?;
+        let entry = SiteSkill {
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            page: format!("{PAGES_DIR}/{}.html", skill.name),
+            archive,
+            files: packed.files,
+        };
+
+        let page = env
+            .get_template("skill.html")
+            .and_then(|template| {
+                template.render(context! { style => STYLE, skill => &entry, sections => sections })
+            })
+            .map_err(|error| template_error(&error))?;
+        write_file(&output.join(&entry.page), &page)?;
+        println!(
+            "  {} {}",
+            palette.paint(Role::Success, "✓"),
+            palette.paint(Role::SkillName, &entry.name)
+        );
+        entries.push(entry);
+    }
+
+    let index = env
+        .get_template("index.html")
+        .and_then(|template| template.render(context! { style => STYLE, skills => &entries }))
+        .map_err(|error| template_error(&error))?;
+    write_file(&output.join("index.html"), &index)?;
+
+    let feed = serde_json::to_string_pretty(&serde_json::json!({ "skills": &entries })).map_err(
+        |error| Error::TemplateRender {
+            message: error.to_string(),
+        },
+    )?;
+    write_file(&output.join("catalog.json"), &feed)?;
+
+    println!();
+    println!(
+        "{} {} skill(s) to {}",
+        palette.paint(Role::Label, "Exported:"),
+        entries.len(),
+        display_path(&output)
+    );
+
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Execute the `export metadata` command.
+///
+/// Writes to `output` when given, otherwise to stdout so the result can be piped.
+pub async fn metadata(
+    ctx: &Context,
+    format: MetadataFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let mut skills: Vec<_> = catalog.sources.values().collect();
+    skills.sort_by_key(|skill| skill.name.to_lowercase());
+    let entries: Vec<_> = skills
+        .into_iter()
+        .map(|skill| {
+            let metadata = parse_metadata(&skill.contents);
+            SkillMetadata {
+                name: skill.name.clone(),
+                description: skill.description.clone(),
+                tags: metadata.tags,
+                version: metadata.version,
+                targets: metadata.targets,
+            }
+        })
+        .collect();
+
+    let serialize_error = |message: String| Error::TemplateRender { message };
+    let rendered = match format {
+        MetadataFormat::Json => serde_json::to_string_pretty(&entries)
+            .map(|json| json + "\n")
+            .map_err(|error| serialize_error(error.to_string()))?,
+        MetadataFormat::Yaml => {
+            serde_yaml::to_string(&entries).map_err(|error| serialize_error(error.to_string()))?
+        }
+        MetadataFormat::Csv => metadata_csv(&entries),
+    };
+
+    match output {
+        Some(path) => {
+            write_file(&path, &rendered)?;
+            println!(
+                "{} {} skill(s) to {}",
+                palette.paint(Role::Label, "Exported:"),
+                entries.len(),
+                display_path(&path)
+            );
+        }
+        None => print!("{rendered}"),
+    }
+
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Render skill metadata as CSV.
+fn metadata_csv(entries: &[SkillMetadata]) -> String {
+    let mut csv = String::from("name,description,tags,version,targets\n");
+    for entry in entries {
+        let fields = [
+            entry.name.clone(),
+            entry.description.clone(),
+            entry.tags.join(";"),
+            entry.version.clone().unwrap_or_default(),
+            entry.targets.join(";"),
+        ];
+        let row: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render markdown to HTML, escaping any raw HTML in the source and neutralizing link and image
+/// targets that could run script.
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, Options::all()).map(|event| match event {
+        Event::Html(text) | Event::InlineHtml(text) => Event::Text(text),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// Keep a link target that is relative or uses http(s) or mailto, replacing any other, such as
+/// `javascript:`, with `#`.
+///
+/// Whitespace and control characters are ignored when reading the scheme, as browsers do.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    let scheme = cleaned
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        Some(scheme)
+            if !["http", "https", "mailto"]
+                .iter()
+                .any(|safe| scheme.eq_ignore_ascii_case(safe)) =>
+        {
+            CowStr::Borrowed("#")
+        }
+        _ => url,
+    }
+}
+
+/// Check whether a path is a directory with at least one entry.
+fn is_non_empty_dir(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Write a generated site file.
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Convert a site template failure into an error.
+fn template_error(error: &minijinja::Error) -> Error {
+    Error::TemplateRender {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{MetadataFormat, markdown_to_html, metadata, site};
+    use crate::{
+        error::Error,
+        testutil::{TestFixture, skill_content},
+        tool::ToolFilter,
+    };
+
+    #[test]
+    fn escapes_raw_html_in_markdown() {
+        let html = markdown_to_html("# Title\n\n<script>alert(1)</script>\n");
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn neutralizes_script_links() {
+        let html = markdown_to_html(
+            "[x](javascript:alert(1)) [y](<Java\tScript:alert(2)>) ![z](data:text/html,hi)\n",
+        );
+        assert!(!html.to_lowercase().contains("script:"), "{html}");
+        assert!(!html.contains("data:"), "{html}");
+        assert_eq!(html.matches("href=\"#\"").count(), 2, "{html}");
+
+        let html = markdown_to_html(
+            "[a](https://example.com) [b](mailto:me@example.com) [c](docs/guide.md#usage)\n",
+        );
+        assert!(html.contains("href=\"https://example.com\""), "{html}");
+        assert!(html.contains("href=\"mailto:me@example.com\""), "{html}");
+        assert!(html.contains("href=\"docs/guide.md#usage\""), "{html}");
+    }
+
+    #[tokio::test]
+    async fn site_writes_index_pages_archives_and_feed() {
+        let fixture = TestFixture::new().with_source_skill(
+            "alpha",
+            &skill_content("alpha", "Alpha <tools>", "# Alpha\n{{ tool }}\n"),
+        );
+        let output = fixture.work_dir().join("site");
+
+        site(&fixture.context(), output.clone(), ToolFilter::Codex, false)
+            .await
+            .expect("export");
+
+        let index = fs::read_to_string(output.join("index.html")).expect("index");
+        assert!(index.contains(r#"alpha.html">alpha</a>"#));
+        assert!(index.contains("Alpha &lt;tools&gt;"));
+
+        let page = fs::read_to_string(output.join("skills").join("alpha.html")).expect("page");
+        assert!(page.contains("<h1>Alpha</h1>"));
+        assert!(page.contains("<p>codex</p>"));
+        assert!(output.join("archives").join("alpha.zip").is_file());
+
+        let feed: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.join("catalog.json")).expect("feed"))
+                .expect("json");
+        assert_eq!(feed["skills"][0]["name"], "alpha");
+        assert_eq!(feed["skills"][0]["archive"], "archives/alpha.zip");
+
+        let error = site(&fixture.context(), output, ToolFilter::Codex, false)
+            .await
+            .expect_err("non-empty output");
+        assert!(matches!(error, Error::PathExists { .. }));
+    }
+
+    #[tokio::test]
+    async fn metadata_writes_csv_without_contents() {
+        let fixture = TestFixture::new().with_source_skill(
+            "alpha",
+            "---\nname: alpha\ndescription: Reads, writes\ntags: [pdf, docs]\nversion: 2\n---\n# Body\n",
+        );
+        let output = fixture.work_dir().join("skills.csv");
+
+        metadata(
+            &fixture.context(),
+            MetadataFormat::Csv,
+            Some(output.clone()),
+        )
+        .await
+        .expect("export");
+
+        assert_eq!(
+            fs::read_to_string(output).expect("csv"),
+            "name,description,tags,version,targets\nalpha,\"Reads, writes\",pdf;docs,2,\n"
+        );
+    }
+}
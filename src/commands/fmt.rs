@@ -0,0 +1,120 @@
+//! Implementation of the `skills fmt` command.
+
+use std::fs;
+
+use crate::{
+    commands::init,
+    config::LineEndings,
+    context::Context,
+    error::{Error, Result},
+    format::format_skill,
+    palette::Role,
+    paths::display_path,
+    skill::SkillTemplate,
+};
+
+/// Execute the fmt command over the named source skills, or every source skill.
+///
+/// With `check`, nothing is written and the command fails when any file needs formatting.
+pub async fn run(ctx: &Context, skills: Vec<String>, check: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let mut targets: Vec<&SkillTemplate> = if skills.is_empty() {
+        catalog.sources.values().collect()
+    } else {
+        skills
+            .iter()
+            .map(|name| {
+                catalog
+                    .sources
+                    .get(&catalog.resolve_name(name))
+                    .ok_or_else(|| Error::SkillNotFound { name: name.clone() })
+            })
+            .collect::<Result<_>>()?
+    };
+    targets.sort_by_key(|skill| skill.name.to_lowercase());
+
+    let mut changed = 0;
+    for skill in &targets {
+        let contents =
+            fs::read_to_string(&skill.skill_path).map_err(|source| Error::SkillRead {
+                path: skill.skill_path.clone(),
+                source,
+            })?;
+        let formatted = formatted(&contents, config.line_endings);
+        if formatted == contents {
+            continue;
+        }
+        changed += 1;
+        let path = display_path(&skill.skill_path);
+        if check {
+            println!("{} {path}", palette.paint(Role::Action, "Would reformat"));
+            continue;
+        }
+        fs::write(&skill.skill_path, formatted).map_err(|source| Error::SkillWrite {
+            path: skill.skill_path.clone(),
+            source,
+        })?;
+        println!("{} {path}", palette.paint(Role::Action, "Formatted"));
+    }
+
+    diagnostics.print_summary();
+    if check && changed > 0 {
+        return Err(Error::FormatCheckFailed { count: changed });
+    }
+    if changed == 0 {
+        println!("{} skill file(s) already formatted.", targets.len());
+    }
+    Ok(())
+}
+
+/// Format a skill file, keeping CRLF line endings when the policy preserves them.
+fn formatted(contents: &str, line_endings: LineEndings) -> String {
+    let formatted = format_skill(contents);
+    match line_endings {
+        LineEndings::Preserve if contents.contains("\r\n") => formatted.replace('\n', "\r\n"),
+        policy => policy.apply(&formatted).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::{
+        error::Error,
+        testutil::{TestFixture, skill_content},
+    };
+
+    #[tokio::test]
+    async fn checks_and_formats_source_skills() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "pdf",
+                "---\ndescription: Read PDFs\nname: pdf\n---\n\n# Pdf   \n\n* one\n\n\n",
+            )
+            .with_source_skill("tidy", &skill_content("tidy", "Tidy", "# Tidy\n"));
+        let ctx = fixture.context();
+
+        let result = run(&ctx, Vec::new(), true).await;
+        assert!(matches!(result, Err(Error::FormatCheckFailed { count: 1 })));
+        assert!(
+            fixture
+                .read_source_skill("pdf")
+                .expect("pdf")
+                .starts_with("---\ndescription")
+        );
+
+        run(&ctx, vec!["pdf".to_string()], false)
+            .await
+            .expect("fmt");
+        assert_eq!(
+            fixture.read_source_skill("pdf").expect("pdf"),
+            "---\nname: pdf\ndescription: Read PDFs\n---\n\n# Pdf\n\n- one\n"
+        );
+        run(&ctx, Vec::new(), true).await.expect("formatted");
+    }
+}
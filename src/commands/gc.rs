@@ -0,0 +1,105 @@
+//! Implementation of the `skills gc` command.
+
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    commands::init,
+    context::Context,
+    error::Result,
+    history::{self, StoredBlob},
+    palette::Role,
+    units::{format_count, format_size},
+};
+
+/// Seconds in a day.
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Execute the gc command, dropping snapshots older than `older_than` days and removing stored
+/// contents that no remaining snapshot refers to.
+pub async fn run(ctx: &Context, older_than: u64, dry_run: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let mut state = ctx.load_state()?;
+
+    let cutoff = SystemTime::now()
+        .checked_sub(Duration::from_secs(older_than.saturating_mul(DAY_SECS)))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let dropped = state.drop_snapshots_before(cutoff);
+    let (kept, unreferenced) = history::stored_blobs(&ctx.state_path, &state.snapshots)?;
+    if !dry_run {
+        state.save(&ctx.state_path)?;
+        history::prune_blobs(&ctx.state_path, &state.snapshots)?;
+    }
+
+    let total = |blobs: &[StoredBlob]| format_size(blobs.iter().map(|blob| blob.size).sum());
+    let verb = if dry_run { "would remove" } else { "removed" };
+    println!(
+        "{} {} older than {} {verb}, {} kept",
+        palette.paint(Role::Label, "Snapshots:"),
+        dropped,
+        format_count(older_than as usize, "day"),
+        state.snapshots.len()
+    );
+    println!(
+        "{} {} unreferenced {verb} ({}), {} kept ({})",
+        palette.paint(Role::Label, "Contents: "),
+        unreferenced.len(),
+        total(&unreferenced),
+        kept.len(),
+        total(&kept)
+    );
+    diagnostics.print_summary();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use super::run;
+    use crate::{
+        history::blob_dir,
+        state::{InstallOrigin, State},
+        testutil::TestFixture,
+    };
+
+    #[tokio::test]
+    async fn drops_old_snapshots_and_their_contents() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        let skill_dir = fixture.work_dir().join("pdf");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+
+        let mut state = State::default();
+        fs::write(skill_dir.join("SKILL.md"), "old").expect("write");
+        state
+            .snapshot(&skill_dir, InstallOrigin::Push)
+            .expect("snapshot");
+        state.snapshots[0].taken_at = SystemTime::now() - Duration::from_secs(40 * 24 * 60 * 60);
+        fs::write(skill_dir.join("SKILL.md"), "new").expect("write");
+        state
+            .snapshot(&skill_dir, InstallOrigin::Push)
+            .expect("snapshot");
+        state.save(&ctx.state_path).expect("save");
+        fs::write(blob_dir(&ctx.state_path).join(".abc.partial"), "x").expect("write");
+        let stored = || {
+            fs::read_dir(blob_dir(&ctx.state_path))
+                .expect("blobs")
+                .count()
+        };
+        assert_eq!(stored(), 3);
+
+        run(&ctx, 30, true).await.expect("dry run");
+        assert_eq!(stored(), 3);
+
+        run(&ctx, 30, false).await.expect("gc");
+        let state = State::load(&ctx.state_path).expect("load");
+        assert_eq!(state.snapshots.len(), 1);
+        assert_eq!(stored(), 1);
+    }
+}
@@ -1,58 +1,136 @@
 //! Implementation of the `skills init` command.
 
 use std::{
+    collections::BTreeMap,
     fs,
     path::{Path, PathBuf},
+    slice,
 };
 
 use inquire::{Confirm, Text, error::InquireError};
 use serde::Serialize;
+use walkdir::WalkDir;
 
 use crate::{
+    config::CONFIG_VERSION,
+    context::Context,
     error::{Error, Result},
-    paths::{default_config_path, display_path, expand_source_path},
+    git,
+    paths::{display_path, expand_path},
+    profile::{Phase, span},
+    skill::{SkillTemplate, ToolSkill},
 };
 
+/// File name of the generated skill index in a git source.
+const INDEX_FILE: &str = "INDEX.md";
+
+/// Starter `.gitignore` for a git source: editor droppings and interrupted installs.
+const GITIGNORE: &str = ".DS_Store\n*.swp\n*~\n.*.partial\n";
+
+/// Starter README for a git source.
+const README: &str = "# Skills
+
+Agent skills for Claude Code, Codex, and other tools, managed with
+[skills](https://github.com/cortesi/skills). Each directory holds one skill; see
+[INDEX.md](INDEX.md) for the list.
+
+To use them, clone this repository as a source and push the skills to your tools:
+
+```sh
+skills clone <repository URL>
+skills push
+```
+";
+
+/// Git repository setup requested for a new source.
+#[derive(Debug, Clone, Default)]
+pub struct GitSetup {
+    /// Whether to set the first source up as a git repository.
+    pub(crate) enabled: bool,
+    /// Remote to add as `origin`; prompted for when absent unless accepting all prompts.
+    pub(crate) remote: Option<String>,
+}
+
 /// Execute the init command.
-pub async fn run() -> Result<()> {
-    init(InitMode::Explicit).await
+///
+/// With `sources`, the config is built from them without prompting for a source directory; `yes`
+/// accepts every remaining prompt, `force` replaces an existing config, and `print` writes the
+/// would-be config to stdout instead of creating anything. `git` sets the first source up as a
+/// git repository.
+pub async fn run(
+    ctx: &Context,
+    sources: Vec<String>,
+    yes: bool,
+    force: bool,
+    print: bool,
+    git: GitSetup,
+) -> Result<()> {
+    let config_path = &ctx.config_path;
+    if config_path.is_file() && !force && !print {
+        println!(
+            "Config already exists at {} (use --force to replace it)",
+            display_path(config_path)
+        );
+        return Ok(());
+    }
+
+    let sources = if sources.is_empty() {
+        if yes {
+            vec![default_source_dir()?]
+        } else {
+            vec![prompt_source(config_path)?]
+        }
+    } else {
+        sources
+            .iter()
+            .map(|source| expand_path(source, &ctx.work_dir))
+            .collect::<Result<_>>()?
+    };
+
+    let contents = render_config(&sources)?;
+    if print {
+        print!("{contents}");
+        return Ok(());
+    }
+
+    for source in &sources {
+        ensure_source_dir(source, yes)?;
+    }
+    write_config(config_path, &contents)?;
+    adopt_existing(ctx, &sources[0], yes)?;
+    if git.enabled {
+        init_git(ctx, &sources[0], git.remote, yes)?;
+    }
+    Ok(())
 }
 
 /// Ensure a config exists, running init if needed.
-pub async fn ensure() -> Result<()> {
-    init(InitMode::Auto).await
-}
+///
+/// Sources given through `SKILLS_SOURCES` stand in for a config file.
+pub async fn ensure(ctx: &Context) -> Result<()> {
+    let config_path = &ctx.config_path;
+    if config_path.is_file() || ctx.overrides.sources.is_some() {
+        return Ok(());
+    }
 
-/// Mode for init execution.
-#[derive(Debug, Clone, Copy)]
-enum InitMode {
-    /// Run init because the user requested it.
-    Explicit,
-    /// Run init automatically when config is missing.
-    Auto,
+    println!("No config found. Starting `skills init`...");
+    let source = prompt_source(config_path)?;
+    ensure_source_dir(&source, false)?;
+    write_config(config_path, &render_config(slice::from_ref(&source))?)?;
+    adopt_existing(ctx, &source, false)
 }
 
 /// Serialized config payload for init.
 #[derive(Debug, Serialize)]
 struct InitConfig {
+    /// Config format version.
+    version: i64,
     /// Configured source directories.
     sources: Vec<String>,
 }
 
-/// Run init for the requested mode.
-async fn init(mode: InitMode) -> Result<()> {
-    let config_path = default_config_path()?;
-    if config_path.is_file() {
-        if matches!(mode, InitMode::Explicit) {
-            println!("Config already exists at {}", display_path(&config_path));
-        }
-        return Ok(());
-    }
-
-    if matches!(mode, InitMode::Auto) {
-        println!("No config found. Starting `skills init`...");
-    }
-
+/// Prompt for a source directory, resolving relative answers against the config directory.
+fn prompt_source(config_path: &Path) -> Result<PathBuf> {
     let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
     let default_source = default_source_dir()?;
     let default_label = display_path(&default_source);
@@ -78,21 +156,40 @@ async fn init(mode: InitMode) -> Result<()> {
     } else {
         trimmed
     };
+    expand_path(raw, config_dir)
+}
 
-    let expanded = expand_source_path(raw, config_dir)?;
-    if !expanded.is_dir() {
-        let prompt = format!("Create directory {}?", display_path(&expanded));
-        let create = confirm(&prompt)?;
-        if create {
-            fs::create_dir_all(&expanded).map_err(|error| Error::ConfigWrite {
-                path: expanded.clone(),
-                source: error,
-            })?;
-        } else {
-            return Err(Error::PathMissing { path: expanded });
-        }
+/// Create a missing source directory, asking first unless `yes` is set.
+fn ensure_source_dir(source: &Path, yes: bool) -> Result<()> {
+    if source.is_dir() {
+        return Ok(());
     }
+    let prompt = format!("Create directory {}?", display_path(source));
+    if !yes && !confirm(&prompt)? {
+        return Err(Error::PathMissing {
+            path: source.to_path_buf(),
+        });
+    }
+    fs::create_dir_all(source).map_err(|error| Error::ConfigWrite {
+        path: source.to_path_buf(),
+        source: error,
+    })
+}
+
+/// Serialize a config listing the given source directories.
+pub fn render_config(sources: &[PathBuf]) -> Result<String> {
+    let config = InitConfig {
+        version: CONFIG_VERSION,
+        sources: sources
+            .iter()
+            .map(|source| source.to_string_lossy().to_string())
+            .collect(),
+    };
+    toml::to_string(&config).map_err(|error| Error::ConfigSerialize { source: error })
+}
 
+/// Write the config file, creating its parent directory.
+pub fn write_config(config_path: &Path, contents: &str) -> Result<()> {
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent).map_err(|error| Error::ConfigWrite {
             path: parent.to_path_buf(),
@@ -100,18 +197,198 @@ async fn init(mode: InitMode) -> Result<()> {
         })?;
     }
 
-    let config = InitConfig {
-        sources: vec![expanded.to_string_lossy().to_string()],
+    fs::write(config_path, contents).map_err(|error| Error::ConfigWrite {
+        path: config_path.to_path_buf(),
+        source: error,
+    })?;
+
+    println!("Created config at {}", display_path(config_path));
+    Ok(())
+}
+
+/// Offer to copy skills already installed in tool directories into a new source directory.
+///
+/// When several tools hold a skill with the same name, the most recently modified copy is adopted.
+fn adopt_existing(ctx: &Context, source: &Path, yes: bool) -> Result<()> {
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+
+    let mut found: BTreeMap<String, &ToolSkill> = BTreeMap::new();
+    for skills in catalog.tools.values() {
+        for (name, skill) in skills {
+            if catalog.sources.contains_key(name) {
+                continue;
+            }
+            let newer = found
+                .get(name)
+                .is_none_or(|current| skill.modified > current.modified);
+            if newer {
+                found.insert(name.clone(), skill);
+            }
+        }
+    }
+    if found.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "Found {} skill(s) already installed in tool directories:",
+        found.len()
+    );
+    for skill in found.values() {
+        println!("  {} ({})", skill.name, display_path(&skill.skill_path));
+    }
+    let prompt = format!("Adopt them into {}?", display_path(source));
+    if !yes && !confirm(&prompt)? {
+        return Ok(());
+    }
+
+    for (name, skill) in &found {
+        let Some(skill_dir) = skill.skill_path.parent() else {
+            continue;
+        };
+        copy_dir(skill_dir, &source.join(name))?;
+    }
+    println!(
+        "Adopted {} skill(s) into {}",
+        found.len(),
+        display_path(source)
+    );
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Set a source directory up as a git repository with a starter README, `.gitignore`, and skill
+/// index, and add a remote.
+///
+/// An existing repository is kept, as are an existing README and `.gitignore`; the index is always
+/// regenerated. Nothing is committed.
+fn init_git(ctx: &Context, source: &Path, remote: Option<String>, yes: bool) -> Result<()> {
+    println!();
+    if git::is_repo(source) {
+        println!("{} is already a git repository", display_path(source));
+    } else {
+        git::run(source, &["init", "--quiet"])?;
+        println!("Initialized git repository in {}", display_path(source));
+    }
+    write_starter(&source.join("README.md"), README)?;
+    write_starter(&source.join(".gitignore"), GITIGNORE)?;
+
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let root = source
+        .canonicalize()
+        .unwrap_or_else(|_| source.to_path_buf());
+    let mut skills: Vec<&SkillTemplate> = catalog
+        .sources
+        .values()
+        .filter(|skill| skill.source_root == root)
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    let index_path = source.join(INDEX_FILE);
+    fs::write(&index_path, render_index(&skills, &root)).map_err(|error| Error::ConfigWrite {
+        path: index_path.clone(),
+        source: error,
+    })?;
+    println!("Wrote {}", display_path(&index_path));
+
+    let remote = match remote {
+        Some(remote) => Some(remote),
+        None if yes => None,
+        None => prompt_remote()?,
     };
-    let contents =
-        toml::to_string(&config).map_err(|error| Error::ConfigSerialize { source: error })?;
+    if let Some(remote) = remote {
+        if git::run(source, &["remote", "get-url", "origin"]).is_ok() {
+            println!("Remote 'origin' already exists; leaving it unchanged");
+        } else {
+            git::run(source, &["remote", "add", "origin", &remote])?;
+            println!("Added remote 'origin' -> {remote}");
+        }
+    }
+
+    let dir = display_path(source);
+    println!("Commit the starter files with `git -C {dir} add -A && git -C {dir} commit`");
+    diagnostics.print_summary();
+    Ok(())
+}
 
-    fs::write(&config_path, contents).map_err(|error| Error::ConfigWrite {
-        path: config_path.clone(),
+/// Write a starter file unless one already exists.
+fn write_starter(path: &Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    fs::write(path, contents).map_err(|error| Error::ConfigWrite {
+        path: path.to_path_buf(),
         source: error,
     })?;
+    println!("Wrote {}", display_path(path));
+    Ok(())
+}
 
-    println!("Created config at {}", display_path(&config_path));
+/// Render a Markdown index of a source's skills, linking each to its skill file.
+fn render_index(skills: &[&SkillTemplate], root: &Path) -> String {
+    let mut index = String::from("# Skill Index\n\n<!-- Generated by `skills init --git`. -->\n\n");
+    if skills.is_empty() {
+        index.push_str("No skills yet. Create one with `skills new <name>`.\n");
+        return index;
+    }
+    index.push_str("| Skill | Description |\n| --- | --- |\n");
+    for skill in skills {
+        let link = skill
+            .skill_path
+            .strip_prefix(root)
+            .unwrap_or(&skill.skill_path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let description = skill
+            .description
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|");
+        index.push_str(&format!("| [{}]({link}) | {description} |\n", skill.name));
+    }
+    index
+}
+
+/// Prompt for a git remote URL, returning `None` when left empty.
+fn prompt_remote() -> Result<Option<String>> {
+    match Text::new("Git remote URL (leave empty to skip)").prompt() {
+        Ok(value) if value.trim().is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value.trim().to_string())),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+/// Recursively copy a skill directory, preserving file permissions.
+pub fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    let _span = span(Phase::Io);
+    for entry in WalkDir::new(from) {
+        let entry = entry.map_err(|error| Error::SkillRead {
+            path: error.path().unwrap_or(from).to_path_buf(),
+            source: error.into(),
+        })?;
+        let target = to.join(entry.path().strip_prefix(from).unwrap_or(entry.path()));
+        let result = if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+        } else {
+            fs::copy(entry.path(), &target).map(|_| ())
+        };
+        result.map_err(|source| Error::SkillWrite {
+            path: target,
+            source,
+        })?;
+    }
     Ok(())
 }
 
@@ -133,3 +410,126 @@ fn default_source_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().ok_or(Error::HomeDirMissing)?;
     Ok(home.join("skills"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{GitSetup, run};
+    use crate::{
+        config::Config,
+        git,
+        skill::SKILL_FILE_NAME,
+        testutil::{TestFixture, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn writes_config_from_flags_without_prompting() {
+        let fixture = TestFixture::new();
+        let ctx = fixture.context();
+        let original = fs::read_to_string(&ctx.config_path).expect("config");
+
+        run(
+            &ctx,
+            vec!["team".to_string()],
+            true,
+            false,
+            false,
+            GitSetup::default(),
+        )
+        .await
+        .expect("existing config is kept");
+        assert_eq!(
+            fs::read_to_string(&ctx.config_path).expect("config"),
+            original
+        );
+
+        run(
+            &ctx,
+            vec!["team".to_string()],
+            true,
+            false,
+            true,
+            GitSetup::default(),
+        )
+        .await
+        .expect("print");
+        assert!(!fixture.work_dir().join("team").exists());
+
+        run(
+            &ctx,
+            vec!["team".to_string()],
+            true,
+            true,
+            false,
+            GitSetup::default(),
+        )
+        .await
+        .expect("force");
+        let team = fixture.work_dir().join("team");
+        assert!(team.is_dir());
+        let config = Config::load(&ctx.config_path, None).expect("load");
+        assert_eq!(config.sources(), [team.canonicalize().expect("canonical")]);
+    }
+
+    #[tokio::test]
+    async fn adopts_existing_tool_skills_into_new_source() {
+        let fixture = TestFixture::new()
+            .with_source_skill("managed", &simple_skill("managed"))
+            .with_tool_skill(Tool::Claude, "managed", &simple_skill("managed"))
+            .with_tool_skill(Tool::Codex, "orphan", &simple_skill("orphan"));
+        let orphan_dir = fixture.tool_dir(Tool::Codex).join("orphan");
+        fs::write(orphan_dir.join("run.sh"), "echo").expect("write");
+        let ctx = fixture.context();
+
+        run(
+            &ctx,
+            vec!["team".to_string()],
+            true,
+            true,
+            false,
+            GitSetup::default(),
+        )
+        .await
+        .expect("init");
+
+        let adopted = fixture.work_dir().join("team").join("orphan");
+        assert_eq!(
+            fs::read_to_string(adopted.join(SKILL_FILE_NAME)).expect("skill"),
+            simple_skill("orphan")
+        );
+        assert!(adopted.join("run.sh").is_file());
+        assert!(fixture.work_dir().join("team").join("managed").is_dir());
+        assert!(orphan_dir.is_dir());
+    }
+
+    #[tokio::test]
+    async fn sets_up_source_as_git_repository() {
+        let fixture = TestFixture::new().with_tool_skill(
+            Tool::Claude,
+            "pdf",
+            &skill_content("pdf", "Read | split PDFs", "Body"),
+        );
+        let ctx = fixture.context();
+        let git = GitSetup {
+            enabled: true,
+            remote: Some("https://example.com/team/skills.git".to_string()),
+        };
+
+        run(&ctx, vec!["team".to_string()], true, true, false, git)
+            .await
+            .expect("init");
+
+        let team = fixture.work_dir().join("team");
+        assert!(git::is_repo(&team));
+        assert!(team.join("README.md").is_file());
+        assert!(team.join(".gitignore").is_file());
+        let index = fs::read_to_string(team.join("INDEX.md")).expect("index");
+        assert!(index.contains("| [pdf](pdf/SKILL.md) | Read \\| split PDFs |"));
+        assert_eq!(
+            git::run(&team, &["remote", "get-url", "origin"]).expect("remote"),
+            "https://example.com/team/skills.git"
+        );
+    }
+}
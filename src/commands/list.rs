@@ -1,38 +1,137 @@
 //! Implementation of the `skills list` command.
 
-use std::{collections::HashSet, env, path::Path};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
-use textwrap::{Options, wrap};
+use serde::Serialize;
 
 use crate::{
-    catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
+    catalog::{Catalog, load_archived},
+    changelog::{Release, read_releases},
+    commands::{init, print_json},
+    context::Context,
+    diagnostics::Verbosity,
     error::Result,
-    palette::{
-        fmt_description, fmt_heading, fmt_label, fmt_path, fmt_skill_name, fmt_tool_tag,
-        fmt_warning, fmt_warning_heading, status_error, status_modified, status_synced,
-    },
+    frontmatter::parse_frontmatter,
+    layout::{output_width, wrap_lines},
+    palette::{Palette, Role},
     paths::display_path,
-    skill::LocalSkill,
-    status::{SkillEntry, SyncStatus, build_entries},
+    skill::{LocalSkill, SkillTemplate},
+    state::State,
+    status::{OrphanKind, SkillEntry, StatusCounts, SyncStatus, build_entries},
+    store::{DirStats, FsStore},
     tool::Tool,
+    units::{DateFormat, format_size},
 };
 
 /// Indent for subordinate information.
 const INDENT: &str = "    ";
 /// Double indent for nested subordinate information.
 const INDENT2: &str = "        ";
+/// Changelog releases shown per skill in verbose listings.
+const RECENT_RELEASES: usize = 3;
+
+/// Everything `list --json` prints.
+#[derive(Debug, Serialize)]
+struct Listing<'a> {
+    /// Source and installed skills, with their status in each tool.
+    skills: Vec<ListedSkill<'a>>,
+    /// Project-local skills.
+    local: Vec<OtherSkill<'a>>,
+    /// Project-local skills that shadow a tool's global skill of the same name.
+    conflicts: Vec<ListedConflict>,
+    /// Archived skills, when they were asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<Vec<OtherSkill<'a>>>,
+    /// Number of skills in each status.
+    counts: StatusCounts,
+}
+
+/// A source or installed skill in a JSON listing.
+#[derive(Debug, Serialize)]
+struct ListedSkill<'a> {
+    /// Skill name.
+    name: &'a str,
+    /// Status describing the skill as a whole.
+    status: SyncStatus,
+    /// Status of each tool's copy, keyed by tool id.
+    tools: BTreeMap<&'static str, SyncStatus>,
+    /// How an orphan came about, for skills without a source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orphan: Option<OrphanKind>,
+    /// Source directory holding the skill.
+    source: Option<&'a Path>,
+    /// Description from the source frontmatter.
+    description: Option<&'a str>,
+}
+
+/// A project-local or archived skill in a JSON listing.
+#[derive(Debug, Serialize)]
+struct OtherSkill<'a> {
+    /// Skill name.
+    name: &'a str,
+    /// Tool a project-local skill belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool: Option<Tool>,
+    /// Description from the frontmatter.
+    description: &'a str,
+    /// Skill directory.
+    path: &'a Path,
+}
+
+/// A project-local skill shadowing a global one in a JSON listing.
+#[derive(Debug, Serialize)]
+struct ListedConflict {
+    /// Skill name.
+    skill: String,
+    /// Tool whose global skill is shadowed.
+    tool: Tool,
+}
 
 /// Execute the list command.
-pub async fn run(color: ColorChoice, verbose: bool) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
+///
+/// With `archived`, archived skills are listed after the others. With `summary`, the listing ends
+/// with a count of skills in each status; with `legend`, it ends with an explanation of each
+/// status. With `json`, the listing is printed as JSON instead, always with the counts.
+pub async fn run(
+    ctx: &Context,
+    archived: bool,
+    summary: bool,
+    legend: bool,
+    json: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
     let entries = build_entries(&catalog, &mut diagnostics);
-    let use_color = color.enabled();
+    let palette = ctx.palette(&config);
+    let width = output_width();
+    let verbose = ctx.verbosity >= Verbosity::Verbose;
+    let dates = ctx.dates(&config);
+    let has_orphans = entries
+        .iter()
+        .any(|entry| entry.overall() == SyncStatus::Orphan);
+    let state = if verbose || has_orphans {
+        Some(ctx.load_state()?)
+    } else {
+        None
+    };
+    if json {
+        let archived = archived
+            .then(|| load_archived(&FsStore, &ctx.catalog_roots(&config), &mut diagnostics));
+        print_json(&listing(
+            &catalog,
+            &entries,
+            state.as_ref(),
+            archived.as_deref(),
+        ))?;
+        diagnostics.print_summary();
+        return Ok(());
+    }
 
     // Print source/tool skills
     for entry in &entries {
@@ -40,57 +139,88 @@ pub async fn run(color: ColorChoice, verbose: bool) -> Result<()> {
         let source_path = skill
             .map(|s| display_path(&s.source_root))
             .unwrap_or_else(|| "-".to_string());
-        let description = skill.map(|s| s.description.as_str()).unwrap_or("-");
 
-        println!("{}", fmt_skill_name(&entry.name, use_color));
+        println!("{}", palette.paint(Role::SkillName, &entry.name));
         println!(
             "{}{} {}",
             INDENT,
-            fmt_label("source:", use_color),
-            fmt_path(&source_path, use_color)
+            palette.paint(Role::Label, "source:"),
+            palette.paint(Role::Path, &source_path)
         );
 
         let mut tool_output = String::new();
         for tool in Tool::all() {
-            let status = format_status(status_for_tool(entry, tool), use_color);
+            let status = format_status(status_for_tool(entry, tool), &palette);
             let label = format!("{}:", tool.id());
-            use std::fmt::Write;
-            let _ = write!(
-                &mut tool_output,
+            tool_output.push_str(&format!(
                 "{} {:<9} ",
-                fmt_label(&label, use_color),
+                palette.paint(Role::Label, &label),
                 status
-            );
+            ));
         }
         println!("{}{}", INDENT, tool_output.trim_end());
+        if entry.overall() == SyncStatus::Orphan
+            && let Some(state) = &state
+        {
+            let kind = OrphanKind::of(&catalog, state, &entry.name);
+            println!(
+                "{}{} {}, {}",
+                INDENT,
+                palette.paint(Role::Label, "orphan:"),
+                kind.label(),
+                kind.suggestion(&entry.name)
+            );
+        }
+        if verbose && let Some(state) = &state {
+            let installs: Vec<_> = Tool::all()
+                .into_iter()
+                .map(|tool| ctx.tool_dir(tool).join(&entry.name))
+                .collect();
+            let pushed = state.last_recorded(installs.iter().map(PathBuf::as_path));
+            print_ages(skill.map(|s| s.modified), pushed, INDENT, dates, &palette);
+            if let Some(skill) = skill {
+                print_stats(skill.stats, config.limits.skill_size, INDENT, &palette);
+                print_releases(&read_releases(&skill.skill_dir), &palette);
+            }
+        }
 
-        println!("{}", wrap_styled(description, INDENT, use_color));
+        match skill {
+            Some(skill) => print_descriptions(&catalog, skill, width, &palette),
+            None => println!("{}", wrap_styled("-", INDENT, width, &palette)),
+        }
     }
 
     // Collect and print local skills
     let local_skills = collect_local_skills(&catalog);
-    let cwd = env::current_dir().ok();
     if !local_skills.is_empty() {
         if !entries.is_empty() {
             println!();
         }
-        println!("{}", fmt_heading("Local Skills:", use_color));
+        println!("{}", palette.paint(Role::Heading, "Local Skills:"));
         for skill in &local_skills {
             let tool_label = format!("[{}]", skill.tool.id());
-            let path_display = display_relative_path(&skill.skill_dir, cwd.as_deref());
+            let path_display = display_relative_path(&skill.skill_dir, &ctx.work_dir);
             println!(
                 "{}{} {}",
                 INDENT,
-                fmt_skill_name(&skill.name, use_color),
-                fmt_tool_tag(&tool_label, use_color)
+                palette.paint(Role::SkillName, &skill.name),
+                palette.paint(Role::ToolTag, &tool_label)
+            );
+            println!(
+                "{}",
+                wrap_styled(&skill.description, INDENT2, width, &palette)
             );
-            println!("{}", wrap_styled(&skill.description, INDENT2, use_color));
             println!(
                 "{}{} {}",
                 INDENT2,
-                fmt_label("path:", use_color),
-                fmt_path(&path_display, use_color)
+                palette.paint(Role::Label, "path:"),
+                palette.paint(Role::Path, &path_display)
             );
+            if verbose && let Some(state) = &state {
+                let pushed = state.last_recorded([skill.skill_dir.as_path()]);
+                print_ages(Some(skill.modified), pushed, INDENT2, dates, &palette);
+                print_stats(skill.stats, config.limits.skill_size, INDENT2, &palette);
+            }
         }
     }
 
@@ -98,26 +228,269 @@ pub async fn run(color: ColorChoice, verbose: bool) -> Result<()> {
     let conflicts = find_conflicts(&catalog);
     if !conflicts.is_empty() {
         println!();
-        println!("{}", fmt_warning_heading("Conflicts:", use_color));
+        println!("{}", palette.paint(Role::WarningHeading, "Conflicts:"));
         for (name, tool) in &conflicts {
             let warning = format!(
                 "{}⚠ '{}' exists locally and in {} global skills",
-                INDENT, name, tool.id()
+                INDENT,
+                name,
+                tool.id()
             );
-            println!("{}", fmt_warning(&warning, use_color));
+            println!("{}", palette.paint(Role::Warning, &warning));
             println!("{}Local takes precedence in this project", INDENT2);
         }
     }
 
-    if entries.is_empty() && local_skills.is_empty() {
+    let archived = if archived {
+        load_archived(&FsStore, &ctx.catalog_roots(&config), &mut diagnostics)
+    } else {
+        Vec::new()
+    };
+    if !archived.is_empty() {
+        if !entries.is_empty() || !local_skills.is_empty() {
+            println!();
+        }
+        println!("{}", palette.paint(Role::Heading, "Archived Skills:"));
+        for skill in &archived {
+            println!("{}{}", INDENT, palette.paint(Role::SkillName, &skill.name));
+            println!(
+                "{}",
+                wrap_styled(&skill.description, INDENT2, width, &palette)
+            );
+            println!(
+                "{}{} {}",
+                INDENT2,
+                palette.paint(Role::Label, "path:"),
+                palette.paint(Role::Path, &display_path(&skill.skill_dir))
+            );
+        }
+    }
+
+    if entries.is_empty() && local_skills.is_empty() && archived.is_empty() {
         println!("No skills found.");
         println!();
     }
+    if summary {
+        println!();
+        println!(
+            "{}",
+            format_counts(&StatusCounts::tally(&entries, &catalog), &palette)
+        );
+    }
+    if legend {
+        println!();
+        print_legend(&palette);
+    }
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
+/// Gather what `list --json` prints.
+fn listing<'a>(
+    catalog: &'a Catalog,
+    entries: &'a [SkillEntry],
+    state: Option<&State>,
+    archived: Option<&'a [SkillTemplate]>,
+) -> Listing<'a> {
+    let skills = entries
+        .iter()
+        .map(|entry| {
+            let skill = catalog.sources.get(&entry.name);
+            let status = entry.overall();
+            ListedSkill {
+                name: &entry.name,
+                status,
+                tools: Tool::all()
+                    .into_iter()
+                    .map(|tool| (tool.id(), status_for_tool(entry, tool)))
+                    .collect(),
+                orphan: state
+                    .filter(|_| status == SyncStatus::Orphan)
+                    .map(|state| OrphanKind::of(catalog, state, &entry.name)),
+                source: skill.map(|skill| skill.source_root.as_path()),
+                description: skill.map(|skill| skill.description.as_str()),
+            }
+        })
+        .collect();
+    let local = collect_local_skills(catalog)
+        .into_iter()
+        .map(|skill| OtherSkill {
+            name: &skill.name,
+            tool: Some(skill.tool),
+            description: &skill.description,
+            path: &skill.skill_dir,
+        })
+        .collect();
+    Listing {
+        skills,
+        local,
+        conflicts: find_conflicts(catalog)
+            .into_iter()
+            .map(|(skill, tool)| ListedConflict { skill, tool })
+            .collect(),
+        archived: archived.map(|archived| {
+            archived
+                .iter()
+                .map(|skill| OtherSkill {
+                    name: &skill.name,
+                    tool: None,
+                    description: &skill.description,
+                    path: &skill.skill_dir,
+                })
+                .collect()
+        }),
+        counts: StatusCounts::tally(entries, catalog),
+    }
+}
+
+/// Format status counts as a one-line footer, coloring each count like its status.
+fn format_counts(counts: &StatusCounts, palette: &Palette) -> String {
+    let parts = [
+        (
+            counts.synced,
+            SyncStatus::Synced.label(),
+            SyncStatus::Synced.role(),
+        ),
+        (
+            counts.modified,
+            SyncStatus::Modified.label(),
+            SyncStatus::Modified.role(),
+        ),
+        (
+            counts.missing,
+            SyncStatus::Missing.label(),
+            SyncStatus::Missing.role(),
+        ),
+        (counts.orphans, "orphans", SyncStatus::Orphan.role()),
+        (counts.local, "local", Role::ToolTag),
+    ];
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|(count, label, role)| palette.paint(*role, &format!("{count} {label}")))
+        .collect();
+    format!("{} skills: {}", counts.skills(), parts.join(", "))
+}
+
+/// Print what each status means, in its color.
+fn print_legend(palette: &Palette) {
+    println!("{}", palette.paint(Role::Heading, "Legend:"));
+    for status in SyncStatus::ALL {
+        let label = format!("{:<9}", status.label());
+        println!(
+            "{}{} {}",
+            INDENT,
+            palette.paint(status.role(), &label),
+            status.description()
+        );
+    }
+}
+
+/// Print when a skill was last modified and last pushed.
+fn print_ages(
+    modified: Option<SystemTime>,
+    pushed: Option<SystemTime>,
+    indent: &str,
+    dates: DateFormat,
+    palette: &Palette,
+) {
+    let age = |time: Option<SystemTime>, missing: &str| {
+        time.map(|time| dates.age(time))
+            .unwrap_or_else(|| missing.to_string())
+    };
+    println!(
+        "{}{} {}    {} {}",
+        indent,
+        palette.paint(Role::Label, "modified:"),
+        age(modified, "-"),
+        palette.paint(Role::Label, "pushed:"),
+        age(pushed, "never")
+    );
+}
+
+/// Print a skill's size and file count, flagging skills over the size budget.
+fn print_stats(stats: DirStats, budget: u64, indent: &str, palette: &Palette) {
+    let mut line = format!(
+        "{}{} {}",
+        indent,
+        palette.paint(Role::Label, "size:"),
+        stats.summary()
+    );
+    if stats.exceeds(budget) {
+        let warning = format!("(over the {} budget)", format_size(budget));
+        line.push(' ');
+        line.push_str(&palette.paint(Role::Warning, &warning));
+    }
+    println!("{line}");
+}
+
+/// Print the most recent releases from a skill's changelog, if it has one.
+fn print_releases(releases: &[Release], palette: &Palette) {
+    if releases.is_empty() {
+        return;
+    }
+    println!("{}{}", INDENT, palette.paint(Role::Label, "changes:"));
+    for release in releases.iter().take(RECENT_RELEASES) {
+        let date = release
+            .date
+            .as_ref()
+            .map(|date| format!(" ({date})"))
+            .unwrap_or_default();
+        println!(
+            "{}{}{}: {}",
+            INDENT2,
+            release.version,
+            date,
+            release.changes.join("; ")
+        );
+    }
+}
+
+/// Print a source skill's description, with one labeled line per variant when tools see
+/// different descriptions.
+fn print_descriptions(
+    catalog: &Catalog,
+    skill: &SkillTemplate,
+    width: Option<usize>,
+    palette: &Palette,
+) {
+    let variants = tool_descriptions(catalog, skill);
+    if let [(_, description)] = variants.as_slice() {
+        println!("{}", wrap_styled(description, INDENT, width, palette));
+        return;
+    }
+    for (tools, description) in &variants {
+        let ids: Vec<&str> = tools.iter().map(|tool| tool.id()).collect();
+        println!(
+            "{}{}",
+            INDENT,
+            palette.paint(Role::Label, &format!("{}:", ids.join(", ")))
+        );
+        println!("{}", wrap_styled(description, INDENT2, width, palette));
+    }
+}
+
+/// Return the description each tool sees for a source skill, grouping tools that see the same
+/// one.
+///
+/// Descriptions can be templates or per-tool maps, so they are read from each tool's rendering;
+/// a tool whose rendering fails is shown the source description.
+fn tool_descriptions(catalog: &Catalog, skill: &SkillTemplate) -> Vec<(Vec<Tool>, String)> {
+    let mut variants: Vec<(Vec<Tool>, String)> = Vec::new();
+    for tool in Tool::all() {
+        let description = catalog
+            .render(skill, tool)
+            .ok()
+            .and_then(|rendered| parse_frontmatter(&rendered).ok())
+            .map_or_else(|| skill.description.clone(), |fm| fm.description);
+        match variants.iter_mut().find(|(_, text)| *text == description) {
+            Some((tools, _)) => tools.push(tool),
+            None => variants.push((vec![tool], description)),
+        }
+    }
+    variants
+}
+
 /// Find the status for a tool within an entry.
 fn status_for_tool(entry: &SkillEntry, tool: Tool) -> SyncStatus {
     entry
@@ -129,26 +502,8 @@ fn status_for_tool(entry: &SkillEntry, tool: Tool) -> SyncStatus {
 }
 
 /// Format a status string with optional color.
-fn format_status(status: SyncStatus, use_color: bool) -> String {
-    use owo_colors::OwoColorize;
-
-    let label = match status {
-        SyncStatus::Synced => "synced",
-        SyncStatus::Modified => "modified",
-        SyncStatus::Missing => "missing",
-        SyncStatus::Orphan => "orphan",
-    };
-
-    if !use_color {
-        return label.to_string();
-    }
-
-    match status {
-        SyncStatus::Synced => label.style(status_synced()).to_string(),
-        SyncStatus::Modified => label.style(status_modified()).to_string(),
-        SyncStatus::Missing => label.style(status_error()).to_string(),
-        SyncStatus::Orphan => label.style(status_error()).to_string(),
-    }
+fn format_status(status: SyncStatus, palette: &Palette) -> String {
+    palette.paint(status.role(), status.label())
 }
 
 /// Collect all local skills from the catalog, sorted by name.
@@ -181,26 +536,18 @@ fn find_conflicts(catalog: &Catalog) -> Vec<(String, Tool)> {
     conflicts
 }
 
-/// Maximum width for wrapped text.
-const WRAP_WIDTH: usize = 80;
-
-/// Wrap text to a given width with an indent prefix and optional styling.
-fn wrap_styled(text: &str, indent: &str, use_color: bool) -> String {
-    let options = Options::new(WRAP_WIDTH.saturating_sub(indent.len()))
-        .initial_indent("")
-        .subsequent_indent("");
-    wrap(text, options)
+/// Wrap description text to the output width with an indent prefix and styling.
+fn wrap_styled(text: &str, indent: &str, width: Option<usize>, palette: &Palette) -> String {
+    wrap_lines(text, indent, width)
         .iter()
-        .map(|line| format!("{}{}", indent, fmt_description(line, use_color)))
+        .map(|line| format!("{}{}", indent, palette.paint(Role::Description, line)))
         .collect::<Vec<_>>()
         .join("\n")
 }
 
 /// Display a path relative to cwd if it's under cwd, otherwise use display_path.
-fn display_relative_path(path: &Path, cwd: Option<&Path>) -> String {
-    if let Some(cwd) = cwd
-        && let Ok(relative) = path.strip_prefix(cwd)
-    {
+fn display_relative_path(path: &Path, cwd: &Path) -> String {
+    if let Ok(relative) = path.strip_prefix(cwd) {
         let rel_str = relative.display().to_string();
         if rel_str.is_empty() {
             return ".".to_string();
@@ -212,12 +559,64 @@ fn display_relative_path(path: &Path, cwd: Option<&Path>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::format_status;
-    use crate::status::SyncStatus;
+    use serde_json::json;
+
+    use super::{format_status, listing, tool_descriptions};
+    use crate::{
+        palette::Palette,
+        status::{SyncStatus, build_entries},
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
 
     #[test]
     fn disables_color_output() {
-        let formatted = format_status(SyncStatus::Modified, false);
+        let formatted = format_status(SyncStatus::Modified, &Palette::plain());
         assert_eq!(formatted, "modified");
     }
+
+    #[test]
+    fn lists_skills_as_json() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Codex, "alpha", &simple_skill("alpha"));
+        let catalog = fixture.catalog();
+        let entries = build_entries(&catalog, &mut fixture.context().diagnostics());
+
+        let value = serde_json::to_value(listing(&catalog, &entries, None, None)).expect("json");
+        assert_eq!(value["skills"][0]["name"], "alpha");
+        assert_eq!(value["skills"][0]["status"], "missing");
+        assert_eq!(
+            value["skills"][0]["tools"],
+            json!({ "claude": "missing", "codex": "synced", "gemini": "missing" })
+        );
+        assert_eq!(value["counts"]["missing"], 1);
+        assert!(value.get("archived").is_none());
+    }
+
+    #[test]
+    fn groups_tools_by_rendered_description() {
+        let catalog = TestFixture::new()
+            .with_source_skill("plain", &simple_skill("plain"))
+            .with_source_skill(
+                "mapped",
+                "---\nname: mapped\ndescription:\n  default: Shared\n  codex: For Codex\n---\nBody\n",
+            )
+            .with_source_skill(
+                "templated",
+                "---\nname: templated\ndescription: \"Use in {{ tool }}\"\n---\nBody\n",
+            )
+            .catalog();
+        let variants = |name: &str| tool_descriptions(&catalog, &catalog.sources[name]);
+
+        assert_eq!(variants("plain").len(), 1);
+        assert_eq!(
+            variants("mapped"),
+            [
+                (vec![Tool::Claude, Tool::Gemini], "Shared".to_string()),
+                (vec![Tool::Codex], "For Codex".to_string()),
+            ]
+        );
+        assert_eq!(variants("templated")[1].1, "Use in codex");
+    }
 }
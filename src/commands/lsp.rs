@@ -0,0 +1,494 @@
+//! Implementation of the `skills lsp` command, a minimal language server for skill files.
+//!
+//! The server speaks JSON-RPC over stdio with full-document sync. It publishes diagnostics from
+//! the same checks as `skills validate`, completes frontmatter keys, tags, and tool ids, and shows
+//! each tool's rendering of the document on hover.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+use serde_json::{Value, json};
+use url::Url;
+
+use crate::{
+    commands::validate::check_skill,
+    context::Context,
+    error::{Error, Result},
+    frontmatter::{parse_metadata, strip_frontmatter},
+    schema::Schema,
+    skill::{BaseTemplates, SkillFileNames, render_template},
+    store::FsStore,
+    tool::Tool,
+};
+
+/// Frontmatter keys offered for completion, with their descriptions.
+const FRONTMATTER_KEYS: &[(&str, &str)] = &[
+    ("name", "Skill name; must match the directory name"),
+    ("description", "When an agent should use this skill"),
+    ("tags", "Free-form tags for catalogs"),
+    ("version", "Skill version"),
+    ("targets", "Tools the skill is intended for"),
+];
+
+/// JSON-RPC error code for an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Completion item kinds from the LSP specification.
+mod kind {
+    /// A variable.
+    pub const VARIABLE: u8 = 6;
+    /// A property, used for frontmatter keys.
+    pub const PROPERTY: u8 = 10;
+    /// A value, used for tags and tool ids.
+    pub const VALUE: u8 = 12;
+}
+
+/// Execute the lsp command, serving requests on stdin and stdout until the client exits.
+pub async fn run(ctx: &Context) -> Result<()> {
+    // The server must not prompt or print: stdout carries the protocol.
+    let mut server = match ctx.load_config() {
+        Ok(config) => {
+            let catalog = ctx.load_catalog(&config, &mut ctx.diagnostics());
+            let tags = catalog
+                .sources
+                .values()
+                .flat_map(|skill| parse_metadata(&skill.contents).tags)
+                .collect();
+            Server::new(config.skill_files, config.include_paths, tags)
+        }
+        Err(_) => Server::new(SkillFileNames::default(), Vec::new(), BTreeSet::new()),
+    };
+
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut writer = io::stdout().lock();
+    while let Some(message) = read_message(&mut reader)? {
+        let (replies, exit) = server.handle(&message);
+        for reply in replies {
+            write_message(&mut writer, &reply)?;
+        }
+        if exit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Language server state.
+struct Server {
+    /// Names a skill file may have.
+    skill_files: SkillFileNames,
+    /// Directories outside the sources that shared templates may link into.
+    include_paths: Vec<PathBuf>,
+    /// Tags used across source skills, offered for completion.
+    tags: BTreeSet<String>,
+    /// Open skill documents keyed by URI.
+    documents: HashMap<String, String>,
+}
+
+impl Server {
+    /// Create a server with no open documents.
+    fn new(
+        skill_files: SkillFileNames,
+        include_paths: Vec<PathBuf>,
+        tags: BTreeSet<String>,
+    ) -> Self {
+        Self {
+            skill_files,
+            include_paths,
+            tags,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Handle one incoming message, returning messages to send and whether to exit.
+    fn handle(&mut self, message: &Value) -> (Vec<Value>, bool) {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id");
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let result = match method {
+            "initialize" => capabilities(),
+            "shutdown" => Value::Null,
+            "exit" => return (Vec::new(), true),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                return (self.update(uri, text.to_string()), false);
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                let Some(text) = changes
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return (Vec::new(), false);
+                };
+                return (self.update(uri, text.to_string()), false);
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return (vec![publish(uri, &[])], false);
+            }
+            "textDocument/completion" => json!(self.completion(uri, &params["position"])),
+            "textDocument/hover" => self.hover(uri),
+            _ => {
+                let reply = id.map(|id| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": METHOD_NOT_FOUND, "message": format!("unknown method {method}") },
+                    })
+                });
+                return (reply.into_iter().collect(), false);
+            }
+        };
+        let reply = id.map(|id| json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+        (reply.into_iter().collect(), false)
+    }
+
+    /// Store a document's text and publish its diagnostics.
+    fn update(&mut self, uri: &str, text: String) -> Vec<Value> {
+        let Some(name) = self.skill_name(uri) else {
+            return Vec::new();
+        };
+        let diagnostics: Vec<Value> =
+            check_skill(&name, &text, &self.bases(uri), schema(uri).as_ref(), None)
+                .into_iter()
+                .map(|problem| {
+                    let line = problem.line.map_or(0, |line| line.saturating_sub(1));
+                    let width = text
+                        .lines()
+                        .nth(line)
+                        .map_or(0, |text| text.encode_utf16().count());
+                    json!({
+                        "range": {
+                            "start": { "line": line, "character": 0 },
+                            "end": { "line": line, "character": width },
+                        },
+                        "severity": 1,
+                        "source": "skills",
+                        "message": problem.message,
+                    })
+                })
+                .collect();
+        self.documents.insert(uri.to_string(), text);
+        vec![publish(uri, &diagnostics)]
+    }
+
+    /// Return the skill name for a document URI, or `None` if it is not a skill file.
+    fn skill_name(&self, uri: &str) -> Option<String> {
+        let path: PathBuf = Url::parse(uri).ok()?.to_file_path().ok()?;
+        let file_name = path.file_name()?.to_str()?;
+        if !self.skill_files.matches(file_name) {
+            return None;
+        }
+        Some(path.parent()?.file_name()?.to_str()?.to_string())
+    }
+
+    /// Return completion items for a position in a document.
+    fn completion(&self, uri: &str, position: &Value) -> Vec<Value> {
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let line_number = position["line"].as_u64().unwrap_or_default() as usize;
+        let character = position["character"].as_u64().unwrap_or_default() as usize;
+        let lines: Vec<&str> = text.lines().collect();
+        let Some(line) = lines.get(line_number) else {
+            return Vec::new();
+        };
+        let prefix = utf16_prefix(line, character);
+
+        let frontmatter_end = (lines.first() == Some(&"---"))
+            .then(|| lines.iter().skip(1).position(|line| *line == "---"))
+            .flatten()
+            .map(|index| index + 1);
+        if frontmatter_end.is_some_and(|end| line_number > 0 && line_number < end) {
+            return self.frontmatter_completion(&lines[..line_number], prefix);
+        }
+        if inside_template_tag(prefix) {
+            let quoted = !prefix.ends_with('"');
+            let mut items = vec![item("tool", kind::VARIABLE, "The tool being rendered for")];
+            items.extend(Tool::all().into_iter().map(|tool| {
+                let mut value = item(tool.id(), kind::VALUE, tool.display_name());
+                if quoted {
+                    value["insertText"] = json!(format!("\"{}\"", tool.id()));
+                }
+                value
+            }));
+            return items;
+        }
+        Vec::new()
+    }
+
+    /// Complete a frontmatter key, or the value of the key the cursor is under.
+    fn frontmatter_completion(&self, previous: &[&str], prefix: &str) -> Vec<Value> {
+        let key = if let Some((key, _)) = prefix.split_once(':') {
+            key.trim()
+        } else if prefix.trim_start().starts_with('-') {
+            previous
+                .iter()
+                .rev()
+                .find(|line| !line.starts_with([' ', '\t', '-']))
+                .and_then(|line| line.split_once(':'))
+                .map_or("", |(key, _)| key.trim())
+        } else if prefix.starts_with([' ', '\t']) {
+            return Vec::new();
+        } else {
+            return FRONTMATTER_KEYS
+                .iter()
+                .map(|(key, detail)| {
+                    let mut value = item(key, kind::PROPERTY, detail);
+                    value["insertText"] = json!(format!("{key}: "));
+                    value
+                })
+                .collect();
+        };
+        match key {
+            "tags" => self
+                .tags
+                .iter()
+                .map(|tag| item(tag, kind::VALUE, "tag"))
+                .collect(),
+            "targets" => Tool::all()
+                .into_iter()
+                .map(|tool| item(tool.id(), kind::VALUE, tool.display_name()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Load the base templates of the source a document's skill lives in, read fresh so edits to a
+    /// base show up without restarting the server.
+    fn bases(&self, uri: &str) -> BaseTemplates {
+        source_root(uri)
+            .map(|source_root| BaseTemplates::load(&FsStore, &source_root, &self.include_paths))
+            .unwrap_or_default()
+    }
+
+    /// Return hover content showing the document body rendered for each tool.
+    fn hover(&self, uri: &str) -> Value {
+        let Some(text) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+        let bases = self.bases(uri);
+        let sections: Vec<String> = Tool::all()
+            .into_iter()
+            .map(|tool| {
+                let body = match render_template(text, tool, &bases) {
+                    Ok(rendered) => {
+                        format!("```markdown\n{}\n```", strip_frontmatter(&rendered).trim())
+                    }
+                    Err(error) => format!("Render failed: {error}"),
+                };
+                format!("**{}** (`{}`)\n\n{}", tool.display_name(), tool.id(), body)
+            })
+            .collect();
+        json!({ "contents": { "kind": "markdown", "value": sections.join("\n\n---\n\n") } })
+    }
+}
+
+/// Return the source directory a document's skill lives in.
+fn source_root(uri: &str) -> Option<PathBuf> {
+    let path = Url::parse(uri).ok()?.to_file_path().ok()?;
+    Some(path.parent()?.parent()?.to_path_buf())
+}
+
+/// Load the frontmatter schema of the source a document's skill lives in, read fresh like
+/// [`Server::bases`]. A schema that cannot be loaded is left to `skills validate` to report.
+fn schema(uri: &str) -> Option<Schema> {
+    Schema::load(&source_root(uri)?).ok().flatten()
+}
+
+/// Return the capabilities advertised in the `initialize` response.
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": 1,
+            "completionProvider": { "triggerCharacters": ["\"", " ", ":"] },
+            "hoverProvider": true,
+        },
+        "serverInfo": { "name": "skills", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/// Build a `publishDiagnostics` notification.
+fn publish(uri: &str, diagnostics: &[Value]) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+/// Build a completion item.
+fn item(label: &str, kind: u8, detail: &str) -> Value {
+    json!({ "label": label, "kind": kind, "detail": detail })
+}
+
+/// Return the part of a line before a UTF-16 character offset.
+fn utf16_prefix(line: &str, character: usize) -> &str {
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= character {
+            return &line[..index];
+        }
+        units += c.len_utf16();
+    }
+    line
+}
+
+/// Return whether text ends inside an unclosed `{{` or `{%` template tag.
+fn inside_template_tag(prefix: &str) -> bool {
+    let open = [prefix.rfind("{{"), prefix.rfind("{%")]
+        .into_iter()
+        .flatten()
+        .max();
+    let close = [prefix.rfind("}}"), prefix.rfind("%}")]
+        .into_iter()
+        .flatten()
+        .max();
+    open.is_some_and(|open| close.is_none_or(|close| close < open))
+}
+
+/// Read one framed message, returning `None` at end of input.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader
+            .read_line(&mut header)
+            .map_err(|error| lsp_error(&error))?
+            == 0
+        {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length.ok_or_else(|| Error::Lsp {
+        message: "message without Content-Length header".to_string(),
+    })?;
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|error| lsp_error(&error))?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|error| Error::Lsp {
+            message: error.to_string(),
+        })
+}
+
+/// Write one framed message.
+fn write_message(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+        .map_err(|error| lsp_error(&error))?;
+    writer.flush().map_err(|error| lsp_error(&error))
+}
+
+/// Convert an IO error on the protocol streams into a crate error.
+fn lsp_error(error: &io::Error) -> Error {
+    Error::Lsp {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, io::Cursor};
+
+    use serde_json::{Value, json};
+
+    use super::{Server, read_message, write_message};
+    use crate::skill::SkillFileNames;
+
+    const URI: &str = "file:///skills/pdf/SKILL.md";
+
+    fn labels(reply: &Value) -> Vec<&str> {
+        reply["result"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .map(|item| item["label"].as_str().expect("label"))
+            .collect()
+    }
+
+    #[test]
+    fn serves_diagnostics_completion_and_hover() {
+        let tags = BTreeSet::from(["documents".to_string()]);
+        let mut server = Server::new(SkillFileNames::default(), Vec::new(), tags);
+        let text = "---\nname: pdf\ndescription: PDFs\ntags:\n  - \n---\n{% if tool == \"codex\" %}\nCodex\n{% endif %}\n{% if %}\n";
+        let (replies, _) = server.handle(&json!({
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": URI, "text": text } },
+        }));
+        let diagnostics = replies[0]["params"]["diagnostics"]
+            .as_array()
+            .expect("list");
+        assert!(!diagnostics.is_empty());
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 9);
+
+        let complete = |server: &mut Server, line: u64, character: u64| {
+            let (replies, _) = server.handle(&json!({
+                "id": 1,
+                "method": "textDocument/completion",
+                "params": {
+                    "textDocument": { "uri": URI },
+                    "position": { "line": line, "character": character },
+                },
+            }));
+            replies.into_iter().next().expect("reply")
+        };
+        assert!(labels(&complete(&mut server, 1, 0)).contains(&"description"));
+        assert_eq!(labels(&complete(&mut server, 4, 4)), vec!["documents"]);
+        assert_eq!(
+            labels(&complete(&mut server, 6, 14)),
+            vec!["tool", "claude", "codex", "gemini"]
+        );
+        assert!(labels(&complete(&mut server, 7, 2)).is_empty());
+
+        let fixed =
+            "---\nname: pdf\ndescription: PDFs\n---\n{% if tool == \"codex\" %}Codex{% endif %}\n";
+        let (replies, _) = server.handle(&json!({
+            "method": "textDocument/didChange",
+            "params": { "textDocument": { "uri": URI }, "contentChanges": [{ "text": fixed }] },
+        }));
+        assert_eq!(replies[0]["params"]["diagnostics"], json!([]));
+        let (replies, _) = server.handle(&json!({
+            "id": 2,
+            "method": "textDocument/hover",
+            "params": { "textDocument": { "uri": URI }, "position": { "line": 4, "character": 0 } },
+        }));
+        let hover = replies[0]["result"]["contents"]["value"]
+            .as_str()
+            .expect("hover");
+        assert!(hover.contains("**Codex** (`codex`)\n\n```markdown\nCodex\n```"));
+
+        let (replies, exit) = server.handle(&json!({ "id": 3, "method": "workspace/symbol" }));
+        assert_eq!(replies[0]["error"]["code"], -32601);
+        assert!(!exit);
+        assert!(server.handle(&json!({ "method": "exit" })).1);
+    }
+
+    #[test]
+    fn frames_messages_with_content_length() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({ "id": 1, "method": "shutdown" })).expect("write");
+        let mut reader = Cursor::new(buffer);
+        let message = read_message(&mut reader).expect("read").expect("message");
+        assert_eq!(message["method"], "shutdown");
+        assert!(read_message(&mut reader).expect("read").is_none());
+    }
+}
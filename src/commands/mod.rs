@@ -2,8 +2,13 @@
 
 use std::io::{self, IsTerminal};
 
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
 /// Output color handling selection.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ColorChoice {
     /// Colorize only when output is a TTY.
     Auto,
@@ -22,38 +27,120 @@ impl ColorChoice {
             Self::Never => false,
         }
     }
+
+    /// Determine whether error output on stderr should be colorized.
+    pub(crate) fn enabled_for_stderr(self) -> bool {
+        match self {
+            Self::Auto => io::stderr().is_terminal(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Print a value as pretty JSON, for commands' `--json` output.
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|error| Error::TemplateRender {
+        message: error.to_string(),
+    })?;
+    println!("{json}");
+    Ok(())
 }
 
 // Command modules are ordered alphabetically - maintain this order.
+/// Approve command implementation.
+pub mod approve;
+/// Archive and unarchive command implementation.
+pub mod archive;
+/// Bootstrap command implementation.
+pub mod bootstrap;
+/// Browse command implementation.
+pub mod browse;
+/// Bump command implementation.
+pub mod bump;
+/// Export-catalog and import-catalog command implementation.
+pub mod bundle;
+/// Clone command implementation.
+pub mod clone;
+/// Compose command implementation.
+pub mod compose;
+/// Config command implementation.
+pub mod config;
+/// Describe command implementation.
+pub mod describe;
+/// Devcontainer command implementation.
+pub mod devcontainer;
 /// Diff command implementation.
 pub mod diff;
+/// Doctor command implementation.
+pub mod doctor;
 /// Edit command implementation.
 pub mod edit;
+/// Env command implementation.
+pub mod env;
+/// Exec command implementation.
+pub mod exec;
+/// Export command implementation.
+pub mod export;
+/// Fmt command implementation.
+pub mod fmt;
+/// Gc command implementation.
+pub mod gc;
 /// Import command implementation.
 pub mod import;
 /// Init command implementation.
 pub mod init;
 /// List command implementation.
 pub mod list;
+/// Lsp command implementation.
+pub mod lsp;
 /// Mv command implementation.
 pub mod mv;
 /// New command implementation.
 pub mod new;
 /// Pack command implementation.
 pub mod pack;
+/// Pin command implementation.
+pub mod pin;
 /// Promote command implementation.
 pub mod promote;
 /// Pull command implementation.
 pub mod pull;
 /// Push command implementation.
 pub mod push;
+/// Push-oci command implementation.
+pub mod push_oci;
 /// Render command implementation.
 pub mod render;
+/// Resolve command implementation.
+pub mod resolve;
+/// Review command implementation.
+pub mod review;
+/// Scan command implementation.
+pub mod scan;
+/// Search command implementation.
+pub mod search;
+/// Shell command implementation.
+pub mod shell;
 /// Show command implementation.
 pub mod show;
+/// Split command implementation.
+pub mod split;
+/// Stats command implementation.
+pub mod stats;
+/// Status command implementation.
+pub mod status;
 /// Sync command implementation.
 pub mod sync;
+/// Test command implementation.
+pub mod test;
 /// Unload command implementation.
 pub mod unload;
+/// Update-sources command implementation.
+pub mod update_sources;
 /// Validate command implementation.
 pub mod validate;
+/// Verify command implementation.
+pub mod verify;
+/// Watch command implementation.
+pub mod watch;
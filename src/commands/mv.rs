@@ -3,35 +3,37 @@
 use std::fs;
 
 use inquire::{Confirm, error::InquireError};
-use owo_colors::OwoColorize;
 
 use crate::{
-    catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
+    commands::init,
+    context::Context,
     error::{Error, Result},
-    paths::display_path,
-    skill::SKILL_FILE_NAME,
+    name::normalize_name,
+    palette::Role,
+    paths::{self, display_path},
     tool::Tool,
 };
 
 /// Execute the mv command.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     old_name: String,
     new_name: String,
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let new_name = normalize_name(&new_name).map_err(|source| Error::InvalidSkillName {
+        name: new_name,
+        source,
+    })?;
 
     // Check if old skill exists
+    let old_name = catalog.resolve_name(&old_name);
     let source_skill = catalog.sources.get(&old_name);
     if source_skill.is_none() {
         return Err(Error::SkillNotFound {
@@ -46,43 +48,37 @@ pub async fn run(
     // Check if new name already exists
     if catalog.sources.contains_key(&new_name) && !force {
         return Err(Error::SkillExists {
-            name: new_name.clone(),
-            path: new_source_dir.clone(),
+            name: new_name,
+            path: new_source_dir,
         });
     }
 
     // Print what we're doing
-    if use_color {
-        println!(
-            "{} '{}' -> '{}'",
-            if dry_run { "Would rename" } else { "Renaming" }.bold(),
-            old_name.cyan(),
-            new_name.cyan()
-        );
-    } else {
-        println!(
-            "{} '{}' -> '{}'",
-            if dry_run { "Would rename" } else { "Renaming" },
-            old_name,
-            new_name
-        );
-    }
+    println!(
+        "{} '{}' -> '{}'",
+        palette.paint(
+            Role::Action,
+            if dry_run { "Would rename" } else { "Renaming" }
+        ),
+        palette.paint(Role::SkillName, &old_name),
+        palette.paint(Role::SkillName, &new_name)
+    );
 
     // Collect all locations to rename
     let mut rename_ops = Vec::new();
 
     // Source directory
-    rename_ops.push((old_source_dir.clone(), new_source_dir.clone(), "source"));
+    rename_ops.push((old_source_dir, new_source_dir.clone(), "source"));
 
     // Tool directories
     for tool in Tool::all() {
-        if let Some(skills) = catalog.tools.get(&tool) {
-            if skills.contains_key(&old_name) {
-                let tool_dir = tool.skills_dir()?;
-                let old_tool_dir = tool_dir.join(&old_name);
-                let new_tool_dir = tool_dir.join(&new_name);
-                rename_ops.push((old_tool_dir, new_tool_dir, tool.id()));
-            }
+        if let Some(skills) = catalog.tools.get(&tool)
+            && skills.contains_key(&old_name)
+        {
+            let tool_dir = ctx.tool_dir(tool);
+            let old_tool_dir = tool_dir.join(&old_name);
+            let new_tool_dir = tool_dir.join(&new_name);
+            rename_ops.push((old_tool_dir, new_tool_dir, tool.id()));
         }
     }
 
@@ -114,9 +110,11 @@ pub async fn run(
     }
 
     // Perform renames
+    let mut state = ctx.load_state()?;
     for (old_path, new_path, _label) in &rename_ops {
-        // Remove destination if it exists and force is set
-        if new_path.exists() && force {
+        // Remove destination if it exists and force is set. A case-only rename's destination is
+        // the skill itself on a case-insensitive filesystem.
+        if new_path.exists() && force && !paths::is_case_only_rename(old_path, new_path) {
             fs::remove_dir_all(new_path).map_err(|e| Error::SkillMove {
                 from: old_path.clone(),
                 to: new_path.clone(),
@@ -124,15 +122,18 @@ pub async fn run(
             })?;
         }
 
-        fs::rename(old_path, new_path).map_err(|e| Error::SkillMove {
+        paths::rename(old_path, new_path).map_err(|e| Error::SkillMove {
             from: old_path.clone(),
             to: new_path.clone(),
             source: e,
         })?;
+        state.rename(old_path, new_path);
     }
+    state.rename_skill(&old_name, &new_name);
+    state.save(&ctx.state_path)?;
 
-    // Update the SKILL.md frontmatter name field in source
-    let new_skill_path = new_source_dir.join(SKILL_FILE_NAME);
+    // Update the skill file's frontmatter name field in source
+    let new_skill_path = config.skill_files.path_in(&new_source_dir);
     if new_skill_path.exists() {
         let contents = fs::read_to_string(&new_skill_path).map_err(|e| Error::SkillRead {
             path: new_skill_path.clone(),
@@ -150,7 +151,7 @@ pub async fn run(
     println!();
     println!("Done. Renamed {} location(s).", rename_ops.len());
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
@@ -196,3 +197,69 @@ fn confirm(message: &str) -> Result<bool> {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::run;
+    use crate::{
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn run_renames_source_and_tool_copies() {
+        let fixture = TestFixture::new()
+            .with_source_skill("old", &simple_skill("old"))
+            .with_tool_skill(Tool::Claude, "old", &simple_skill("old"));
+
+        run(
+            &fixture.context(),
+            "old".to_string(),
+            "new".to_string(),
+            false,
+            true,
+        )
+        .await
+        .expect("mv");
+
+        assert!(!fixture.source_skill_exists("old"));
+        let renamed = fixture.read_source_skill("new").expect("renamed source");
+        assert!(renamed.contains("name: new"));
+        assert!(fixture.tool_skill_exists(Tool::Claude, "new"));
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "old"));
+    }
+
+    #[tokio::test]
+    async fn run_renames_when_only_case_changes() {
+        let fixture = TestFixture::new()
+            .with_source_skill("Notes", &simple_skill("Notes"))
+            .with_tool_skill(Tool::Claude, "Notes", &simple_skill("Notes"));
+
+        run(
+            &fixture.context(),
+            "Notes".to_string(),
+            "notes".to_string(),
+            false,
+            true,
+        )
+        .await
+        .expect("mv");
+
+        let renamed = fixture.read_source_skill("notes").expect("renamed source");
+        assert!(renamed.contains("name: notes"));
+        assert!(fixture.tool_skill_exists(Tool::Claude, "notes"));
+        let entries: Vec<String> = fs::read_dir(fixture.source_dir())
+            .expect("read source")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(entries, ["notes"]);
+    }
+}
@@ -5,46 +5,149 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::error::{Error, Result};
+use crate::{
+    config::NewSkillConfig,
+    context::Context,
+    error::{Error, Result},
+    frontmatter::yaml_scalar,
+    name::normalize_name,
+    permissions::set_executable,
+    skill::{SCRIPTS_DIR, SKILL_FILE_NAME},
+};
 
-/// Execute the new command.
-pub async fn run(path: PathBuf) -> Result<()> {
-    create_skill_template(&path)?;
-    println!("Created skill at {}/SKILL.md", path.display());
-    println!("\nEdit the SKILL.md file, then run `skills push` to sync.");
+/// Execute the new command, scaffolding an executable helper script when `with_script` is given.
+pub async fn run(ctx: &Context, path: PathBuf, with_script: Option<String>) -> Result<()> {
+    // Creating a skill does not require a config, so fall back to the default file name.
+    let (file_name, defaults) = ctx
+        .load_config()
+        .map(|config| (config.skill_files.primary().to_string(), config.new_skill))
+        .unwrap_or_else(|_| (SKILL_FILE_NAME.to_string(), NewSkillConfig::default()));
+    let path = create_skill_template(
+        &ctx.work_dir.join(path),
+        &file_name,
+        with_script.as_deref(),
+        &defaults,
+    )?;
+    let shown = path.strip_prefix(&ctx.work_dir).unwrap_or(&path);
+    println!("Created skill at {}/{file_name}", shown.display());
+    if let Some(script) = &with_script {
+        println!(
+            "Created script at {}/{SCRIPTS_DIR}/{script}",
+            shown.display()
+        );
+    }
+    println!("\nEdit the {file_name} file, then run `skills push` to sync.");
     Ok(())
 }
 
-/// Create a new skill directory and template.
-fn create_skill_template(path: &Path) -> Result<()> {
+/// Create a new skill directory and template, returning the normalized skill directory.
+///
+/// The configured defaults add frontmatter fields and can replace the placeholder body.
+fn create_skill_template(
+    path: &Path,
+    file_name: &str,
+    script: Option<&str>,
+    defaults: &NewSkillConfig,
+) -> Result<PathBuf> {
+    let name = skill_name_from_path(path)?;
+    if let Some(script) = script {
+        validate_script_name(script)?;
+    }
+    let path = path.with_file_name(&name);
     if path.exists() {
-        return Err(Error::PathExists {
-            path: path.to_path_buf(),
-        });
+        return Err(Error::PathExists { path });
     }
 
-    let name = skill_name_from_path(path)?;
     let title = title_case(&name);
+    let body = match &defaults.template {
+        Some(template) => fs::read_to_string(template)
+            .map_err(|source| Error::SkillRead {
+                path: template.clone(),
+                source,
+            })?
+            .replace("{{ name }}", &name)
+            .replace("{{ title }}", &title),
+        None => format!("# {title}\n\n<instructions for the AI assistant>\n"),
+    };
 
-    fs::create_dir_all(path).map_err(|error| Error::SkillWrite {
-        path: path.to_path_buf(),
+    fs::create_dir_all(&path).map_err(|error| Error::SkillWrite {
+        path: path.clone(),
         source: error,
     })?;
 
-    let skill_path = path.join("SKILL.md");
-    let template = format!(
-        "---\nname: {name}\ndescription: <describe when this skill should be used>\n---\n\n# {title}\n\n<instructions for the AI assistant>\n"
+    let skill_path = path.join(file_name);
+    let mut template = format!(
+        "---\nname: {name}\ndescription: <describe when this skill should be used>\n{}---\n\n{body}",
+        default_fields(defaults)
     );
+    if let Some(script) = script {
+        template.push_str(&format!(
+            "\n## Scripts\n\n- `{SCRIPTS_DIR}/{script}`: <describe what the script does and when to run it>\n"
+        ));
+        create_script(&path.join(SCRIPTS_DIR), script)?;
+    }
 
     fs::write(&skill_path, template).map_err(|error| Error::SkillWrite {
         path: skill_path,
         source: error,
     })?;
 
-    Ok(())
+    Ok(path)
+}
+
+/// Format the configured default frontmatter fields as YAML lines.
+pub fn default_fields(defaults: &NewSkillConfig) -> String {
+    let mut fields = String::new();
+    let scalars = [
+        ("version", &defaults.version),
+        ("author", &defaults.author),
+        ("license", &defaults.license),
+    ];
+    for (key, value) in scalars {
+        if let Some(value) = value {
+            fields.push_str(&format!("{key}: {}\n", yaml_scalar(value)));
+        }
+    }
+    if !defaults.tags.is_empty() {
+        fields.push_str("tags:\n");
+        for tag in &defaults.tags {
+            fields.push_str(&format!("  - {}\n", yaml_scalar(tag)));
+        }
+    }
+    fields
+}
+
+/// Write an executable script skeleton into a skill's scripts directory.
+fn create_script(scripts_dir: &Path, script: &str) -> Result<()> {
+    let write_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| Error::SkillWrite { path, source }
+    };
+    fs::create_dir_all(scripts_dir).map_err(write_error(scripts_dir))?;
+    let script_path = scripts_dir.join(script);
+    fs::write(
+        &script_path,
+        "#!/usr/bin/env bash\nset -euo pipefail\n\n# <what this script does>\n",
+    )
+    .map_err(write_error(&script_path))?;
+    set_executable(&script_path).map_err(write_error(&script_path))
 }
 
-/// Extract a skill name from the destination path.
+/// Check that a script name is a plain file name.
+fn validate_script_name(script: &str) -> Result<()> {
+    let plain = Path::new(script)
+        .file_name()
+        .is_some_and(|name| name == script && script != "." && script != "..");
+    if plain {
+        Ok(())
+    } else {
+        Err(Error::InvalidPath {
+            path: PathBuf::from(script),
+        })
+    }
+}
+
+/// Extract and normalize a skill name from the destination path.
 fn skill_name_from_path(path: &Path) -> Result<String> {
     let name = path
         .file_name()
@@ -52,11 +155,14 @@ fn skill_name_from_path(path: &Path) -> Result<String> {
         .ok_or_else(|| Error::InvalidPath {
             path: path.to_path_buf(),
         })?;
-    Ok(name.to_string())
+    normalize_name(name).map_err(|source| Error::InvalidSkillName {
+        name: name.to_string(),
+        source,
+    })
 }
 
 /// Convert a hyphenated name into title case for headings.
-fn title_case(name: &str) -> String {
+pub fn title_case(name: &str) -> String {
     name.split('-')
         .filter(|part| !part.is_empty())
         .map(capitalize)
@@ -72,3 +178,50 @@ fn capitalize(word: &str) -> String {
         None => String::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::create_skill_template;
+    use crate::{config::NewSkillConfig, frontmatter::parse_metadata};
+
+    #[test]
+    fn applies_configured_defaults() {
+        let dir = tempdir().expect("tempdir");
+        let body = dir.path().join("body.md");
+        fs::write(&body, "# {{ title }}\n\nOwned by the {{ name }} team.\n").expect("write");
+        let defaults = NewSkillConfig {
+            author: Some("Platform: Tools".to_string()),
+            license: Some("MIT".to_string()),
+            tags: vec!["internal".to_string(), "yes".to_string()],
+            version: Some("0.1.0".to_string()),
+            template: Some(body),
+        };
+
+        let path =
+            create_skill_template(&dir.path().join("pdf-tools"), "SKILL.md", None, &defaults)
+                .expect("create");
+        let contents = fs::read_to_string(path.join("SKILL.md")).expect("read");
+        let metadata = parse_metadata(&contents);
+        assert_eq!(metadata.tags, ["internal", "yes"]);
+        assert_eq!(metadata.version.as_deref(), Some("0.1.0"));
+        assert!(contents.contains("author: 'Platform: Tools'\n"));
+        assert!(contents.contains("license: MIT\n"));
+        assert!(contents.ends_with("---\n\n# Pdf Tools\n\nOwned by the pdf-tools team.\n"));
+
+        let plain = create_skill_template(
+            &dir.path().join("plain"),
+            "SKILL.md",
+            None,
+            &NewSkillConfig::default(),
+        )
+        .expect("create");
+        let contents = fs::read_to_string(plain.join("SKILL.md")).expect("read");
+        assert!(contents.starts_with(
+            "---\nname: plain\ndescription: <describe when this skill should be used>\n---\n\n# Plain\n"
+        ));
+    }
+}
@@ -1,55 +1,105 @@
 //! Implementation of the `skills pack` command.
 
 use std::{
-    fs::{self, File},
-    io::{self, Read, Write},
+    fs, io,
     path::{Path, PathBuf},
 };
 
-use owo_colors::OwoColorize;
+use clap::ValueEnum;
 use walkdir::WalkDir;
-use zip::{ZipWriter, write::SimpleFileOptions};
 
 use crate::{
+    archive::{SymlinkPolicy, Zip, pack_dir},
     catalog::Catalog,
-    commands::{ColorChoice, init},
+    commands::init,
     config::Config,
+    context::Context,
     diagnostics::Diagnostics,
     error::{Error, Result},
+    frontmatter::parse_frontmatter,
+    oci::Artifact,
+    palette::{Palette, Role},
     paths::display_path,
+    skill::SkillFileNames,
+    store::DirStats,
+    units::format_size,
 };
 
+/// Output format for packed skills.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PackFormat {
+    /// A ZIP archive (`<name>.zip`).
+    #[default]
+    Zip,
+    /// An OCI image layout directory (`<name>.oci/`) holding the ZIP as an artifact layer.
+    Oci,
+}
+
+impl PackFormat {
+    /// Return the output file or directory name for a skill.
+    fn file_name(self, name: &str) -> String {
+        match self {
+            Self::Zip => format!("{name}.zip"),
+            Self::Oci => format!("{name}.oci"),
+        }
+    }
+}
+
 /// Result of packing a single skill.
-struct PackResult {
+pub struct PackResult {
     /// Skill name.
-    name: String,
+    pub(crate) name: String,
     /// Output path.
-    path: PathBuf,
+    pub(crate) path: PathBuf,
     /// Size in bytes.
-    size: u64,
+    pub(crate) size: u64,
     /// Files included.
-    files: Vec<String>,
+    pub(crate) files: Vec<String>,
+}
+
+/// Options shared by every skill in one pack.
+struct PackOptions {
+    /// Whether to pack project-local skills instead of source skills.
+    local: bool,
+    /// Output format.
+    format: PackFormat,
+    /// How to handle symlinks in skill directories.
+    symlinks: SymlinkPolicy,
+    /// Whether to report what would be packed without writing anything.
+    dry_run: bool,
+    /// Whether to overwrite existing outputs.
+    force: bool,
+    /// Palette for output.
+    palette: Palette,
 }
 
 /// Execute the pack command for specific skills.
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill_names: Vec<String>,
     all: bool,
     output: Option<PathBuf>,
     local: bool,
+    format: PackFormat,
+    symlinks: SymlinkPolicy,
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let options = PackOptions {
+        local,
+        format,
+        symlinks,
+        dry_run,
+        force,
+        palette: ctx.palette(&config),
+    };
 
-    let output_dir = output.unwrap_or_else(|| PathBuf::from("."));
+    let output_dir = output.unwrap_or_else(|| ctx.work_dir.clone());
 
     // Ensure output directory exists
     if !output_dir.exists() {
@@ -65,34 +115,51 @@ pub async fn run(
 
     // If --all or no skills specified, pack all skills
     if all || skill_names.is_empty() {
-        return pack_all(&catalog, &output_dir, dry_run, force, use_color, local, &mut diagnostics);
+        return pack_all(&catalog, &output_dir, &options, &config, &mut diagnostics);
     }
 
     if skill_names.len() == 1 {
         // Single skill - use detailed output
-        pack_single(&catalog, &skill_names[0], &output_dir, dry_run, force, use_color, local)
+        pack_single(&catalog, &skill_names[0], &output_dir, &options, &config)?;
+        diagnostics.print_summary();
+        Ok(())
     } else {
         // Multiple skills - use summary output
-        pack_multiple(&catalog, &skill_names, &output_dir, dry_run, force, use_color, local, &mut diagnostics)
+        pack_multiple(
+            &catalog,
+            &skill_names,
+            &output_dir,
+            &options,
+            &config,
+            &mut diagnostics,
+        )
     }
 }
 
 /// Execute the pack-all command.
 pub async fn run_all(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     output: PathBuf,
     local: bool,
+    format: PackFormat,
+    symlinks: SymlinkPolicy,
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let options = PackOptions {
+        local,
+        format,
+        symlinks,
+        dry_run,
+        force,
+        palette: ctx.palette(&config),
+    };
 
-    pack_all(&catalog, &output, dry_run, force, use_color, local, &mut diagnostics)
+    pack_all(&catalog, &output, &options, &config, &mut diagnostics)
 }
 
 /// Pack a single skill with detailed output.
@@ -100,32 +167,48 @@ fn pack_single(
     catalog: &Catalog,
     name: &str,
     output_dir: &Path,
-    dry_run: bool,
-    force: bool,
-    use_color: bool,
-    local: bool,
+    options: &PackOptions,
+    config: &Config,
 ) -> Result<()> {
+    let PackOptions {
+        local,
+        format,
+        symlinks,
+        dry_run,
+        force,
+        ref palette,
+    } = *options;
     // Find the skill
-    let skill_dir = if local {
+    let (skill_dir, stats) = if local {
         find_local_skill(catalog, name)?
     } else {
         find_source_skill(catalog, name)?
     };
 
     // Determine output path
-    let output_path = output_dir.join(format!("{}.zip", name));
+    let output_path = output_dir.join(format.file_name(name));
 
     // Check if output exists
     if output_path.exists() && !force {
-        return Err(Error::PathExists {
-            path: output_path,
-        });
+        return Err(Error::PathExists { path: output_path });
+    }
+
+    println!(
+        "{} {}",
+        palette.paint(Role::Label, "Size:"),
+        stats.summary()
+    );
+    if let Some(warning) = budget_warning(name, stats, config.limits.skill_size) {
+        println!(
+            "{}",
+            palette.paint(Role::Warning, &format!("Warning: {warning}"))
+        );
     }
 
     if dry_run {
         println!(
             "{} '{}' from {}",
-            "Would pack".bold(),
+            palette.paint(Role::Action, "Would pack"),
             name,
             display_path(&skill_dir)
         );
@@ -139,19 +222,22 @@ fn pack_single(
     }
 
     // Pack the skill
-    let result = pack_skill(name, &skill_dir, &output_path)?;
+    let result = pack_skill(
+        name,
+        &skill_dir,
+        &output_path,
+        format,
+        symlinks,
+        &config.skill_files,
+    )?;
 
     // Print result
-    if use_color {
-        println!(
-            "{} '{}' from {}",
-            "Packing".bold(),
-            result.name.cyan(),
-            display_path(&skill_dir)
-        );
-    } else {
-        println!("Packing '{}' from {}", result.name, display_path(&skill_dir));
-    }
+    println!(
+        "{} '{}' from {}",
+        palette.paint(Role::Action, "Packing"),
+        palette.paint(Role::SkillName, &result.name),
+        display_path(&skill_dir)
+    );
     println!();
     println!(
         "Created: {} ({} bytes)",
@@ -162,26 +248,41 @@ fn pack_single(
         println!("  - {}", file);
     }
     println!();
-    println!(
-        "Share this file or import with: skills import {}",
-        result.path.file_name().unwrap_or_default().to_string_lossy()
-    );
+    match format {
+        PackFormat::Zip => println!(
+            "Share this file or import with: skills import {}",
+            result
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+        ),
+        PackFormat::Oci => println!(
+            "Publish to a registry with: skills push-oci {} <registry>/<repository>:<tag>",
+            result.name
+        ),
+    }
 
     Ok(())
 }
 
 /// Pack multiple named skills with summary output.
-#[allow(clippy::too_many_arguments)]
 fn pack_multiple(
     catalog: &Catalog,
     names: &[String],
     output_dir: &Path,
-    dry_run: bool,
-    force: bool,
-    use_color: bool,
-    local: bool,
+    options: &PackOptions,
+    config: &Config,
     diagnostics: &mut Diagnostics,
 ) -> Result<()> {
+    let PackOptions {
+        local,
+        format,
+        symlinks,
+        dry_run,
+        force,
+        ref palette,
+    } = *options;
     println!(
         "Packing {} skills{}...",
         names.len(),
@@ -193,16 +294,16 @@ fn pack_multiple(
     let mut skip_count = 0;
 
     for name in names {
-        let skill_dir = if local {
+        let (skill_dir, stats) = if local {
             match find_local_skill(catalog, name) {
                 Ok(dir) => dir,
                 Err(e) => {
                     diagnostics.warn(format!("Skill '{}': {}", name, e));
-                    if use_color {
-                        println!("  {} {} (not found)", "✗".red(), name);
-                    } else {
-                        println!("  ✗ {} (not found)", name);
-                    }
+                    println!(
+                        "  {} {} (not found)",
+                        palette.paint(Role::Failure, "✗"),
+                        name
+                    );
                     skip_count += 1;
                     continue;
                 }
@@ -212,56 +313,65 @@ fn pack_multiple(
                 Ok(dir) => dir,
                 Err(e) => {
                     diagnostics.warn(format!("Skill '{}': {}", name, e));
-                    if use_color {
-                        println!("  {} {} (not found)", "✗".red(), name);
-                    } else {
-                        println!("  ✗ {} (not found)", name);
-                    }
+                    println!(
+                        "  {} {} (not found)",
+                        palette.paint(Role::Failure, "✗"),
+                        name
+                    );
                     skip_count += 1;
                     continue;
                 }
             }
         };
 
-        let output_path = output_dir.join(format!("{}.zip", name));
+        if let Some(warning) = budget_warning(name, stats, config.limits.skill_size) {
+            diagnostics.warn(warning);
+        }
+
+        let output_path = output_dir.join(format.file_name(name));
 
         // Check if output exists
         if output_path.exists() && !force {
-            if use_color {
-                println!("  {} {} (already exists)", "✗".red(), name);
-            } else {
-                println!("  ✗ {} (already exists)", name);
-            }
+            println!(
+                "  {} {} (already exists)",
+                palette.paint(Role::Failure, "✗"),
+                name
+            );
             skip_count += 1;
             continue;
         }
 
         if dry_run {
-            if use_color {
-                println!("  {} {}.zip", "✓".green(), name);
-            } else {
-                println!("  ✓ {}.zip", name);
-            }
+            println!(
+                "  {} {} ({})",
+                palette.paint(Role::Success, "✓"),
+                format.file_name(name),
+                stats.summary()
+            );
             success_count += 1;
             continue;
         }
 
-        match pack_skill(name, &skill_dir, &output_path) {
+        match pack_skill(
+            name,
+            &skill_dir,
+            &output_path,
+            format,
+            symlinks,
+            &config.skill_files,
+        ) {
             Ok(result) => {
-                if use_color {
-                    println!("  {} {}.zip ({} bytes)", "✓".green(), name, result.size);
-                } else {
-                    println!("  ✓ {}.zip ({} bytes)", name, result.size);
-                }
+                println!(
+                    "  {} {} ({} bytes)",
+                    palette.paint(Role::Success, "✓"),
+                    format.file_name(name),
+                    result.size
+                );
                 success_count += 1;
             }
             Err(e) => {
                 diagnostics.warn(format!("Failed to pack '{}': {}", name, e));
-                if use_color {
-                    println!("  {} {} ({})", "✗".red(), name, e);
-                } else {
-                    println!("  ✗ {} ({})", name, e);
-                }
+                println!("  {} {} ({})", palette.paint(Role::Failure, "✗"), name, e);
                 skip_count += 1;
             }
         }
@@ -285,7 +395,7 @@ fn pack_multiple(
         println!("Skipped {} skills", skip_count);
     }
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
@@ -293,12 +403,18 @@ fn pack_multiple(
 fn pack_all(
     catalog: &Catalog,
     output_dir: &Path,
-    dry_run: bool,
-    force: bool,
-    use_color: bool,
-    local: bool,
+    options: &PackOptions,
+    config: &Config,
     diagnostics: &mut Diagnostics,
 ) -> Result<()> {
+    let PackOptions {
+        local,
+        format,
+        symlinks,
+        dry_run,
+        force,
+        ref palette,
+    } = *options;
     // Ensure output directory exists
     if !output_dir.exists() {
         if dry_run {
@@ -311,20 +427,20 @@ fn pack_all(
         }
     }
 
-    let skills: Vec<(&String, PathBuf)> = if local {
+    let skills: Vec<(&String, PathBuf, DirStats)> = if local {
         // Collect local skills
         catalog
             .local
             .values()
             .flat_map(|skills| skills.iter())
-            .map(|(name, skill)| (name, skill.skill_dir.clone()))
+            .map(|(name, skill)| (name, skill.skill_dir.clone(), skill.stats))
             .collect()
     } else {
         // Collect source skills
         catalog
             .sources
             .iter()
-            .map(|(name, skill)| (name, skill.skill_dir.clone()))
+            .map(|(name, skill)| (name, skill.skill_dir.clone(), skill.stats))
             .collect()
     };
 
@@ -343,46 +459,55 @@ fn pack_all(
     let mut success_count = 0;
     let mut skip_count = 0;
 
-    for (name, skill_dir) in skills {
-        let output_path = output_dir.join(format!("{}.zip", name));
+    for (name, skill_dir, stats) in skills {
+        if let Some(warning) = budget_warning(name, stats, config.limits.skill_size) {
+            diagnostics.warn(warning);
+        }
+
+        let output_path = output_dir.join(format.file_name(name));
 
         // Check if output exists
         if output_path.exists() && !force {
-            if use_color {
-                println!("  {} {} (already exists)", "✗".red(), name);
-            } else {
-                println!("  ✗ {} (already exists)", name);
-            }
+            println!(
+                "  {} {} (already exists)",
+                palette.paint(Role::Failure, "✗"),
+                name
+            );
             skip_count += 1;
             continue;
         }
 
         if dry_run {
-            if use_color {
-                println!("  {} {}.zip", "✓".green(), name);
-            } else {
-                println!("  ✓ {}.zip", name);
-            }
+            println!(
+                "  {} {} ({})",
+                palette.paint(Role::Success, "✓"),
+                format.file_name(name),
+                stats.summary()
+            );
             success_count += 1;
             continue;
         }
 
-        match pack_skill(name, &skill_dir, &output_path) {
+        match pack_skill(
+            name,
+            &skill_dir,
+            &output_path,
+            format,
+            symlinks,
+            &config.skill_files,
+        ) {
             Ok(result) => {
-                if use_color {
-                    println!("  {} {}.zip ({} bytes)", "✓".green(), name, result.size);
-                } else {
-                    println!("  ✓ {}.zip ({} bytes)", name, result.size);
-                }
+                println!(
+                    "  {} {} ({} bytes)",
+                    palette.paint(Role::Success, "✓"),
+                    format.file_name(name),
+                    result.size
+                );
                 success_count += 1;
             }
             Err(e) => {
                 diagnostics.warn(format!("Failed to pack '{}': {}", name, e));
-                if use_color {
-                    println!("  {} {} ({})", "✗".red(), name, e);
-                } else {
-                    println!("  ✗ {} ({})", name, e);
-                }
+                println!("  {} {} ({})", palette.paint(Role::Failure, "✗"), name, e);
                 skip_count += 1;
             }
         }
@@ -406,26 +531,27 @@ fn pack_all(
         println!("Skipped {} skills", skip_count);
     }
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
-/// Find a source skill by name.
-fn find_source_skill(catalog: &Catalog, name: &str) -> Result<PathBuf> {
+/// Find a source skill's directory and stats by name.
+fn find_source_skill(catalog: &Catalog, name: &str) -> Result<(PathBuf, DirStats)> {
     catalog
         .sources
-        .get(name)
-        .map(|s| s.skill_dir.clone())
+        .get(&catalog.resolve_name(name))
+        .map(|s| (s.skill_dir.clone(), s.stats))
         .ok_or_else(|| Error::SkillNotFound {
             name: name.to_string(),
         })
 }
 
-/// Find a local skill by name.
-fn find_local_skill(catalog: &Catalog, name: &str) -> Result<PathBuf> {
+/// Find a local skill's directory and stats by name.
+fn find_local_skill(catalog: &Catalog, name: &str) -> Result<(PathBuf, DirStats)> {
+    let resolved = catalog.resolve_name(name);
     for skills in catalog.local.values() {
-        if let Some(skill) = skills.get(name) {
-            return Ok(skill.skill_dir.clone());
+        if let Some(skill) = skills.get(&resolved) {
+            return Ok((skill.skill_dir.clone(), skill.stats));
         }
     }
     Err(Error::LocalSkillNotFound {
@@ -433,6 +559,18 @@ fn find_local_skill(catalog: &Catalog, name: &str) -> Result<PathBuf> {
     })
 }
 
+/// Describe a skill that is larger than the size budget.
+fn budget_warning(name: &str, stats: DirStats, budget: u64) -> Option<String> {
+    stats.exceeds(budget).then(|| {
+        format!(
+            "Skill '{}' is {}, over the {} size budget",
+            name,
+            format_size(stats.size),
+            format_size(budget)
+        )
+    })
+}
+
 /// Collect relative file paths in a directory.
 fn collect_files(dir: &Path) -> Result<Vec<String>> {
     let mut files = Vec::new();
@@ -453,75 +591,30 @@ fn collect_files(dir: &Path) -> Result<Vec<String>> {
     Ok(files)
 }
 
-/// Pack a skill directory into a ZIP file.
-fn pack_skill(name: &str, skill_dir: &Path, output_path: &Path) -> Result<PackResult> {
-    let file = File::create(output_path).map_err(|e| Error::ZipCreate {
-        path: output_path.to_path_buf(),
-        message: e.to_string(),
-    })?;
-
-    let mut zip = ZipWriter::new(file);
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .unix_permissions(0o644);
-
-    let mut files = Vec::new();
-
-    for entry in WalkDir::new(skill_dir).min_depth(1) {
-        let entry = entry.map_err(|e| Error::ZipCreate {
+/// Pack a skill directory into a ZIP file or OCI image layout.
+pub fn pack_skill(
+    name: &str,
+    skill_dir: &Path,
+    output_path: &Path,
+    format: PackFormat,
+    symlinks: SymlinkPolicy,
+    skill_files: &SkillFileNames,
+) -> Result<PackResult> {
+    let (archive, files) = pack_dir(&Zip, name, skill_dir, symlinks)?;
+    let size = archive.len() as u64;
+
+    match format {
+        PackFormat::Zip => fs::write(output_path, &archive).map_err(|e| Error::ZipCreate {
             path: output_path.to_path_buf(),
             message: e.to_string(),
-        })?;
-
-        let path = entry.path();
-        let rel_path = path.strip_prefix(skill_dir).map_err(|_| Error::ZipCreate {
-            path: output_path.to_path_buf(),
-            message: "failed to compute relative path".to_string(),
-        })?;
-
-        // Build archive path with skill name as root directory
-        let archive_path = format!("{}/{}", name, rel_path.display());
-
-        if entry.file_type().is_dir() {
-            zip.add_directory(&archive_path, options).map_err(|e| Error::ZipCreate {
-                path: output_path.to_path_buf(),
-                message: e.to_string(),
-            })?;
-        } else if entry.file_type().is_file() {
-            zip.start_file(&archive_path, options).map_err(|e| Error::ZipCreate {
-                path: output_path.to_path_buf(),
-                message: e.to_string(),
-            })?;
-
-            let mut f = File::open(path).map_err(|e| Error::SkillRead {
-                path: path.to_path_buf(),
-                source: e,
-            })?;
-            let mut buffer = Vec::new();
-            f.read_to_end(&mut buffer).map_err(|e| Error::SkillRead {
-                path: path.to_path_buf(),
-                source: e,
-            })?;
-            zip.write_all(&buffer).map_err(|e| Error::ZipCreate {
-                path: output_path.to_path_buf(),
-                message: e.to_string(),
-            })?;
-
-            files.push(rel_path.display().to_string());
+        })?,
+        PackFormat::Oci => {
+            let description = read_description(skill_dir, skill_files)?;
+            let artifact = Artifact::new(name, &description, archive);
+            artifact.write_layout(output_path, name)?;
         }
     }
 
-    zip.finish().map_err(|e| Error::ZipCreate {
-        path: output_path.to_path_buf(),
-        message: e.to_string(),
-    })?;
-
-    let size = fs::metadata(output_path)
-        .map(|m| m.len())
-        .unwrap_or(0);
-
-    files.sort();
-
     Ok(PackResult {
         name: name.to_string(),
         path: output_path.to_path_buf(),
@@ -529,3 +622,18 @@ fn pack_skill(name: &str, skill_dir: &Path, output_path: &Path) -> Result<PackRe
         files,
     })
 }
+
+/// Read the description from a skill directory's frontmatter.
+fn read_description(skill_dir: &Path, skill_files: &SkillFileNames) -> Result<String> {
+    let skill_path = skill_files.path_in(skill_dir);
+    let contents = fs::read_to_string(&skill_path).map_err(|e| Error::SkillRead {
+        path: skill_path.clone(),
+        source: e,
+    })?;
+    parse_frontmatter(&contents)
+        .map(|frontmatter| frontmatter.description)
+        .map_err(|e| Error::InvalidSkillFile {
+            path: skill_path,
+            message: e.message,
+        })
+}
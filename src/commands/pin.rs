@@ -0,0 +1,174 @@
+//! Implementation of the `skills pin` and `skills unpin` commands.
+
+use std::{collections::BTreeMap, time::SystemTime};
+
+use crate::{
+    catalog::Catalog,
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    state::{Pin, hash_bytes},
+    tool::Tool,
+};
+
+/// Number of hex digits of a hash shown in listings.
+const SHORT_HASH: usize = 12;
+
+/// Execute the pin command, pinning a skill at its current tool copies, or listing pinned skills
+/// when no skill is named.
+pub async fn run(ctx: &Context, skill: Option<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let dates = ctx.dates(&config);
+    let mut state = ctx.load_state()?;
+
+    let Some(name) = skill else {
+        if state.pins.is_empty() {
+            println!("No pinned skills.");
+            return Ok(());
+        }
+        for (name, pin) in &state.pins {
+            println!(
+                "{} pinned {}",
+                palette.paint(Role::SkillName, name),
+                dates.age(pin.pinned_at)
+            );
+            let current = copy_hashes(&catalog, name)?;
+            for (tool, hash) in &pin.hashes {
+                let drift = if current.get(tool) == Some(hash) {
+                    ""
+                } else {
+                    " (copy changed since pinning)"
+                };
+                println!("    {tool:<6}: {}{drift}", &hash[..SHORT_HASH]);
+            }
+        }
+        diagnostics.print_summary();
+        return Ok(());
+    };
+
+    let name = catalog.resolve_name(&name);
+    let hashes = copy_hashes(&catalog, &name)?;
+    if hashes.is_empty() {
+        return Err(Error::SkillNotFound { name });
+    }
+    let verb = if state.is_pinned(&name) {
+        "Re-pinned"
+    } else {
+        "Pinned"
+    };
+    println!(
+        "{} {}",
+        palette.paint(Role::Action, verb),
+        palette.paint(Role::SkillName, &name)
+    );
+    for (tool, hash) in &hashes {
+        println!("    {tool:<6}: {}", &hash[..SHORT_HASH]);
+    }
+    state.pins.insert(
+        name.clone(),
+        Pin {
+            hashes,
+            pinned_at: SystemTime::now(),
+        },
+    );
+    state.save(&ctx.state_path)?;
+    println!();
+    println!("Push and sync will skip '{name}' until `skills unpin {name}`.");
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Execute the unpin command, letting push and sync update a skill again.
+pub async fn run_unpin(ctx: &Context, skill: String) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let mut state = ctx.load_state()?;
+
+    let name = catalog.resolve_name(&skill);
+    if state.pins.remove(&name).is_none() {
+        println!("'{name}' is not pinned.");
+        return Ok(());
+    }
+    state.save(&ctx.state_path)?;
+    println!(
+        "{} {}",
+        palette.paint(Role::Action, "Unpinned"),
+        palette.paint(Role::SkillName, &name)
+    );
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Hash the skill file of each tool's copy of a skill, keyed by tool id.
+fn copy_hashes(catalog: &Catalog, name: &str) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for tool in Tool::all() {
+        if let Some(copy) = catalog.tools.get(&tool).and_then(|skills| skills.get(name)) {
+            let contents = copy.read_contents()?;
+            hashes.insert(tool.id().to_string(), hash_bytes(contents.as_bytes()));
+        }
+    }
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, run_unpin};
+    use crate::{
+        commands::push::{self, PushOptions},
+        state::State,
+        testutil::{TestFixture, skill_content},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn pinned_skills_are_left_alone_until_unpinned() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &skill_content("alpha", "Alpha", "v2"))
+            .with_tool_skill(
+                Tool::Claude,
+                "alpha",
+                &skill_content("alpha", "Alpha", "v1"),
+            );
+        let ctx = fixture.context();
+        let push = || {
+            push::run(
+                &ctx,
+                Vec::new(),
+                vec![Tool::Claude],
+                PushOptions {
+                    all: true,
+                    force: true,
+                    yes: true,
+                    ..PushOptions::default()
+                },
+            )
+        };
+
+        run(&ctx, Some("alpha".to_string())).await.expect("pin");
+        let state = State::load(&ctx.state_path).expect("state");
+        assert!(state.pins["alpha"].hashes.contains_key("claude"));
+        run(&ctx, None).await.expect("list");
+
+        push().await.expect("push");
+        let copy = fixture
+            .read_tool_skill(Tool::Claude, "alpha")
+            .expect("copy");
+        assert!(copy.ends_with("v1"));
+
+        run_unpin(&ctx, "alpha".to_string()).await.expect("unpin");
+        push().await.expect("push");
+        let copy = fixture
+            .read_tool_skill(Tool::Claude, "alpha")
+            .expect("copy");
+        assert!(copy.ends_with("v2"));
+    }
+}
@@ -2,14 +2,12 @@
 
 use std::fs;
 
-use owo_colors::OwoColorize;
-
 use crate::{
     catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
+    commands::init,
+    context::Context,
     error::{Error, Result},
+    palette::Role,
     paths::display_path,
     skill::LocalSkill,
     tool::{Tool, ToolFilter},
@@ -17,18 +15,17 @@ use crate::{
 
 /// Execute the promote command.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill_name: String,
     tool_filter: Option<ToolFilter>,
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
 
     // Convert ToolFilter to Option<Tool> for filtering
     let tool_filter: Option<Tool> = match tool_filter {
@@ -51,7 +48,7 @@ pub async fn run(
 
     // Process each match (usually just one)
     for skill in matches {
-        let target_dir = skill.tool.skills_dir()?;
+        let target_dir = ctx.tool_dir(skill.tool);
         let target_skill_dir = target_dir.join(&skill.name);
 
         // Check if skill already exists at target
@@ -63,21 +60,21 @@ pub async fn run(
         }
 
         // Print what we're doing
-        let action = if dry_run { "Would promote" } else { "Promoting" };
+        let action = if dry_run {
+            "Would promote"
+        } else {
+            "Promoting"
+        };
         let from = display_path(&skill.skill_dir);
         let to = display_path(&target_skill_dir);
 
-        if use_color {
-            println!(
-                "{} '{}' from {} to {}",
-                action.bold(),
-                skill.name.cyan(),
-                from,
-                to
-            );
-        } else {
-            println!("{} '{}' from {} to {}", action, skill.name, from, to);
-        }
+        println!(
+            "{} '{}' from {} to {}",
+            palette.paint(Role::Action, action),
+            palette.paint(Role::SkillName, &skill.name),
+            from,
+            to
+        );
 
         if dry_run {
             println!();
@@ -117,7 +114,7 @@ pub async fn run(
         println!("  skills pull {} --to <source-dir>", skill.name);
     }
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
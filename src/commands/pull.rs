@@ -1,24 +1,26 @@
 //! Implementation of the `skills pull` command.
 
 use std::{
-    fs,
+    collections::BTreeMap,
     path::{Path, PathBuf},
-    time::{Duration, SystemTime},
 };
 
 use inquire::{Confirm, Text, error::InquireError};
 
 use crate::{
     catalog::Catalog,
-    commands::{ColorChoice, init},
+    commands::init,
     config::Config,
+    context::Context,
     diagnostics::Diagnostics,
     diff::{colorize_diff, unified_diff, write_output},
     error::{Error, Result},
-    paths::{display_path, expand_source_path},
-    skill::{SKILL_FILE_NAME, SkillTemplate, ToolSkill, render_template},
-    status::normalize_line_endings,
-    tool::Tool,
+    palette::{Palette, Role},
+    paths::{display_path, expand_path},
+    skill::{SkillTemplate, ToolSkill, pull_support_files, write_skill_file},
+    state::InstallOrigin,
+    tool::{Tool, ToolFilter},
+    units::DateFormat,
 };
 
 /// Source of a tool skill variant (global or local).
@@ -31,65 +33,86 @@ enum VariantSource {
 }
 
 /// Execute the pull command.
+///
+/// Pulls add and update source files. With `prune`, source files the pulled copy lacks are removed
+/// too, after the source directory is snapshotted so they can be recovered.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill: Option<String>,
     to: Option<PathBuf>,
+    dry_run: bool,
+    force: bool,
+    prefer: Option<ToolFilter>,
+    prune: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
     let target_override = resolve_target_override(&to)?;
 
+    let skill = skill.map(|skill| catalog.resolve_name(&skill));
     let mut plans = collect_pull_plans(&catalog, skill.as_deref(), &mut diagnostics)?;
     if plans.is_empty() {
         println!("No modified skills found.");
         return Ok(());
     }
 
-    plans.sort_by(|left, right| left.name.to_lowercase().cmp(&right.name.to_lowercase()));
+    plans.sort_by_key(|left| left.name.to_lowercase());
 
     print_plan_summary(&plans);
 
-    let use_color = color.enabled();
+    if dry_run {
+        print_dry_run(&config, &plans, target_override.as_ref());
+        diagnostics.print_summary();
+        return Ok(());
+    }
 
+    let palette = ctx.palette(&config);
+    // --force resolves conflicts by the newest copy unless --prefer names a tool.
+    let prefer = prefer.or(force.then_some(ToolFilter::All));
+
+    let mut pulled = 0;
+    let mut skipped = Vec::new();
     for plan in plans.drain(..) {
-        match plan.variants.as_slice() {
+        let selected = match plan.variants.as_slice() {
             [] => continue,
-            [variant] => {
-                if !confirm_pull(&plan, variant)? {
-                    continue;
-                }
-                let target = select_target_source(&config, &plan, target_override.as_ref())?;
-                apply_pull(&plan, variant, &target)?;
-                println!(
-                    "Pulled {} from {} -> {}",
-                    plan.name,
-                    format_variant_source(variant),
-                    display_path(&target)
-                );
-            }
-            variants => {
-                let selected = resolve_conflict(&plan, variants, use_color)?;
-                let Some(selected) = selected else {
-                    continue;
-                };
-                let target = select_target_source(&config, &plan, target_override.as_ref())?;
-                apply_pull(&plan, &selected, &target)?;
-                println!(
-                    "Pulled {} from {} -> {}",
-                    plan.name,
-                    format_variant_source(&selected),
-                    display_path(&target)
-                );
-            }
+            [variant] => (force || confirm_pull(&plan, variant)?).then(|| variant.clone()),
+            variants => match prefer {
+                Some(prefer) => preferred_variant(variants, prefer).cloned(),
+                None => resolve_conflict(&plan, variants, ctx.dates(&config), &palette)?,
+            },
+        };
+        let Some(selected) = selected else {
+            skipped.push(plan.name);
+            continue;
+        };
+        let target = select_target_source(&config, &plan, target_override.as_ref(), force)?;
+        if prune {
+            snapshot_pruned(ctx, &plan, &selected)?;
         }
+        apply_pull(&plan, &selected, &target, &config, prune)?;
+        println!(
+            "Pulled {} from {} -> {}",
+            plan.name,
+            format_variant_source(&selected),
+            display_path(&target)
+        );
+        pulled += 1;
     }
 
-    diagnostics.print_skipped_summary();
-    diagnostics.print_warning_summary();
+    println!();
+    println!(
+        "{} {} pulled, {} skipped.",
+        palette.paint(Role::Label, "Done:"),
+        pulled,
+        skipped.len()
+    );
+    if !skipped.is_empty() {
+        println!("Skipped: {}", skipped.join(", "));
+    }
+
+    diagnostics.print_summary();
     Ok(())
 }
 
@@ -164,7 +187,7 @@ fn collect_pull_plans(
 
             if let Some(tool_skill) = tool_skill {
                 if let Some(source) = &source {
-                    let rendered = match render_template(&source.contents, tool) {
+                    let rendered = match catalog.render_skill(source, tool) {
                         Ok(rendered) => rendered,
                         Err(error) => {
                             diagnostics.warn_skipped(&source.skill_path, error);
@@ -173,9 +196,7 @@ fn collect_pull_plans(
                         }
                     };
 
-                    let modified = normalize_line_endings(&rendered)
-                        != normalize_line_endings(&tool_skill.contents);
-                    if modified {
+                    if !tool_skill.matches(source, &rendered) {
                         variants.push(PullVariant {
                             tool,
                             skill: tool_skill.clone(),
@@ -198,16 +219,10 @@ fn collect_pull_plans(
             let local_skill = local_map.and_then(|skills| skills.get(&name));
 
             if let Some(local_skill) = local_skill {
-                // Convert LocalSkill to ToolSkill for variant
-                let tool_skill = ToolSkill {
-                    name: local_skill.name.clone(),
-                    skill_path: local_skill.skill_path.clone(),
-                    contents: local_skill.contents.clone(),
-                    modified: local_skill.modified,
-                };
+                let tool_skill = local_skill.to_tool_skill();
 
                 if let Some(source) = &source {
-                    let rendered = match render_template(&source.contents, tool) {
+                    let rendered = match catalog.render_skill(source, tool) {
                         Ok(rendered) => rendered,
                         Err(error) => {
                             diagnostics.warn_skipped(&source.skill_path, error);
@@ -215,9 +230,7 @@ fn collect_pull_plans(
                         }
                     };
 
-                    let modified = normalize_line_endings(&rendered)
-                        != normalize_line_endings(&local_skill.contents);
-                    if modified {
+                    if !tool_skill.matches(source, &rendered) {
                         variants.push(PullVariant {
                             tool,
                             skill: tool_skill,
@@ -275,6 +288,38 @@ fn print_plan_summary(plans: &[PullPlan]) {
     }
 }
 
+/// Print what each pull would do without prompting.
+fn print_dry_run(config: &Config, plans: &[PullPlan], override_path: Option<&PathBuf>) {
+    println!("Planned pulls:");
+    for plan in plans {
+        let target = match planned_target(config, plan, override_path) {
+            Some(target) => display_path(&target),
+            None => format!("one of {} sources (prompt)", config.sources().len()),
+        };
+        match plan.variants.as_slice() {
+            [] => {}
+            [variant] => {
+                let action = if variant.orphan { "create" } else { "update" };
+                println!(
+                    "  {}: {action} from {} -> {target}",
+                    plan.name,
+                    format_variant_source(variant)
+                );
+            }
+            variants => {
+                let candidates: Vec<_> = variants.iter().map(format_variant_source).collect();
+                println!(
+                    "  {}: conflict between {} (prompt) -> {target}",
+                    plan.name,
+                    candidates.join(", ")
+                );
+            }
+        }
+    }
+    println!();
+    println!("Dry run - no changes made.");
+}
+
 /// Confirm pulling a single variant.
 fn confirm_pull(plan: &PullPlan, variant: &PullVariant) -> Result<bool> {
     let source_label = match variant.source {
@@ -293,7 +338,8 @@ fn confirm_pull(plan: &PullPlan, variant: &PullVariant) -> Result<bool> {
 fn resolve_conflict(
     plan: &PullPlan,
     variants: &[PullVariant],
-    color: bool,
+    dates: DateFormat,
+    palette: &Palette,
 ) -> Result<Option<PullVariant>> {
     loop {
         println!(
@@ -309,7 +355,7 @@ fn resolve_conflict(
                 "  [{}] {}  (modified {})",
                 index + 1,
                 source_label,
-                format_age(variant.skill.modified)
+                dates.age(variant.skill.modified)
             );
         }
         println!("  [d] Show diff between versions");
@@ -334,7 +380,7 @@ fn resolve_conflict(
         match choice.as_str() {
             "s" | "S" => return Ok(None),
             "d" | "D" => {
-                show_variant_diff(variants, color)?;
+                show_variant_diff(variants, palette)?;
                 continue;
             }
             _ => {
@@ -348,8 +394,18 @@ fn resolve_conflict(
     }
 }
 
+/// Pick the newest variant from the preferred tools, falling back to the newest overall.
+fn preferred_variant(variants: &[PullVariant], prefer: ToolFilter) -> Option<&PullVariant> {
+    let tools = prefer.to_tools();
+    variants
+        .iter()
+        .filter(|variant| tools.contains(&variant.tool))
+        .max_by_key(|variant| variant.skill.modified)
+        .or_else(|| variants.iter().max_by_key(|variant| variant.skill.modified))
+}
+
 /// Show a diff between the first two variants.
-fn show_variant_diff(variants: &[PullVariant], color: bool) -> Result<()> {
+fn show_variant_diff(variants: &[PullVariant], palette: &Palette) -> Result<()> {
     if variants.len() < 2 {
         return Ok(());
     }
@@ -359,33 +415,39 @@ fn show_variant_diff(variants: &[PullVariant], color: bool) -> Result<()> {
     let diff_text = unified_diff(
         &display_path(&left.skill.skill_path),
         &display_path(&right.skill.skill_path),
-        &left.skill.contents,
-        &right.skill.contents,
+        &left.skill.read_contents()?,
+        &right.skill.read_contents()?,
     );
-    let diff_text = colorize_diff(&diff_text, color);
+    let diff_text = colorize_diff(&diff_text, palette);
     write_output(&diff_text, None)?;
     Ok(())
 }
 
 /// Select the target source directory for a pull.
+///
+/// Without a single obvious target, `force` picks the highest-priority source instead of
+/// prompting.
 fn select_target_source(
     config: &Config,
     plan: &PullPlan,
     override_path: Option<&PathBuf>,
+    force: bool,
 ) -> Result<PathBuf> {
-    if let Some(source) = &plan.source {
-        return Ok(source.source_root.clone());
-    }
-
-    if let Some(path) = override_path {
-        return Ok(path.clone());
+    if let Some(target) = planned_target(config, plan, override_path) {
+        return Ok(target);
     }
 
     let sources = config.sources();
-    if sources.len() == 1 {
+    if force {
         return Ok(sources[0].clone());
     }
+    prompt_source(sources)
+}
 
+/// Ask which of several sources to write to, defaulting to the first.
+///
+/// The answer is a source's number or a path to another directory.
+pub fn prompt_source(sources: &[PathBuf]) -> Result<PathBuf> {
     println!("Available sources:");
     for (index, source) in sources.iter().enumerate() {
         println!("  [{}] {}", index + 1, display_path(source));
@@ -416,29 +478,76 @@ fn select_target_source(
         return Ok(source.clone());
     }
 
-    let normalized = expand_source_path(trimmed, Path::new("."))?;
+    let normalized = expand_path(trimmed, Path::new("."))?;
     Ok(normalized)
 }
 
-/// Apply a pull variant to the selected source.
-fn apply_pull(plan: &PullPlan, variant: &PullVariant, target: &Path) -> Result<()> {
-    let skill_dir = if let Some(source) = &plan.source {
-        source.skill_dir.clone()
-    } else {
-        target.join(&plan.name)
-    };
+/// Return the target source for a pull when it can be decided without prompting.
+fn planned_target(
+    config: &Config,
+    plan: &PullPlan,
+    override_path: Option<&PathBuf>,
+) -> Option<PathBuf> {
+    if let Some(source) = &plan.source {
+        return Some(source.source_root.clone());
+    }
+    if let Some(path) = override_path {
+        return Some(path.clone());
+    }
+    match config.sources() {
+        [source] => Some(source.clone()),
+        _ => None,
+    }
+}
 
-    fs::create_dir_all(&skill_dir).map_err(|error| Error::SkillWrite {
-        path: skill_dir.clone(),
-        source: error,
-    })?;
+/// Snapshot the source of a pull that would remove files, saving the state before anything is
+/// written so the removed files can be recovered.
+fn snapshot_pruned(ctx: &Context, plan: &PullPlan, variant: &PullVariant) -> Result<()> {
+    let Some(source) = &plan.source else {
+        return Ok(());
+    };
+    if source
+        .files
+        .keys()
+        .all(|path| variant.skill.files.contains_key(path))
+    {
+        return Ok(());
+    }
+    let mut state = ctx.load_state()?;
+    state.snapshot(&source.skill_dir, InstallOrigin::Pull)?;
+    state.save(&ctx.state_path)
+}
 
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    fs::write(&skill_path, &variant.skill.contents).map_err(|error| Error::SkillWrite {
-        path: skill_path,
-        source: error,
-    })?;
+/// Apply a pull variant to the selected source, copying its support files along with the skill
+/// file and, with `prune`, removing the source files the variant lacks.
+fn apply_pull(
+    plan: &PullPlan,
+    variant: &PullVariant,
+    target: &Path,
+    config: &Config,
+    prune: bool,
+) -> Result<()> {
+    let (skill_path, existing) = match &plan.source {
+        Some(source) => (source.skill_path.clone(), source.files.clone()),
+        None => (
+            target.join(&plan.name).join(config.skill_files.primary()),
+            BTreeMap::new(),
+        ),
+    };
 
+    write_skill_file(
+        &skill_path,
+        &variant.skill.read_contents()?,
+        config.line_endings,
+    )?;
+    let source_dir = skill_path.parent().unwrap_or(target);
+    pull_support_files(
+        &variant.skill,
+        source_dir,
+        &existing,
+        config.line_endings,
+        prune,
+    )?;
     Ok(())
 }
 
@@ -448,7 +557,7 @@ fn resolve_target_override(to: &Option<PathBuf>) -> Result<Option<PathBuf>> {
         return Ok(None);
     };
 
-    let normalized = expand_source_path(
+    let normalized = expand_path(
         path.to_str()
             .ok_or_else(|| Error::PathNotUnicode { path: path.clone() })?,
         Path::new("."),
@@ -474,30 +583,6 @@ fn confirm(message: &str) -> Result<bool> {
     }
 }
 
-/// Format a relative age from a timestamp.
-fn format_age(modified: SystemTime) -> String {
-    let elapsed = modified.elapsed().unwrap_or(Duration::from_secs(0));
-    let seconds = elapsed.as_secs();
-    if seconds < 60 {
-        return "moments ago".to_string();
-    }
-    if seconds < 60 * 60 {
-        let minutes = seconds / 60;
-        return format!("{} minute{} ago", minutes, plural(minutes));
-    }
-    if seconds < 60 * 60 * 24 {
-        let hours = seconds / 60 / 60;
-        return format!("{} hour{} ago", hours, plural(hours));
-    }
-    let days = seconds / 60 / 60 / 24;
-    format!("{days} day{} ago", plural(days))
-}
-
-/// Return a plural suffix for counts.
-fn plural(count: u64) -> &'static str {
-    if count == 1 { "" } else { "s" }
-}
-
 /// Format the source label for a variant (e.g., "Claude Code" or "Claude Code local").
 fn format_variant_source(variant: &PullVariant) -> String {
     match variant.source {
@@ -508,14 +593,16 @@ fn format_variant_source(variant: &PullVariant) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use super::{VariantSource, collect_pull_plans, run};
     use crate::{
         diagnostics::Diagnostics,
+        state::InstallOrigin,
         testutil::{TestFixture, simple_skill, skill_content},
-        tool::Tool,
+        tool::{Tool, ToolFilter},
     };
 
-    use super::{VariantSource, collect_pull_plans};
-
     #[test]
     fn detects_modified_global_tool_skill() {
         let source_content = skill_content("my-skill", "desc", "original");
@@ -526,7 +613,7 @@ mod tests {
             .with_tool_skill(Tool::Claude, "my-skill", &tool_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -539,11 +626,14 @@ mod tests {
 
     #[test]
     fn detects_orphaned_global_tool_skill() {
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Codex, "orphan-skill", &simple_skill("orphan-skill"));
+        let fixture = TestFixture::new().with_tool_skill(
+            Tool::Codex,
+            "orphan-skill",
+            &simple_skill("orphan-skill"),
+        );
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -564,7 +654,7 @@ mod tests {
             .with_local_skill(Tool::Claude, "local-skill", &local_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -577,11 +667,14 @@ mod tests {
 
     #[test]
     fn detects_orphaned_local_skill() {
-        let fixture = TestFixture::new()
-            .with_local_skill(Tool::Codex, "local-orphan", &simple_skill("local-orphan"));
+        let fixture = TestFixture::new().with_local_skill(
+            Tool::Codex,
+            "local-orphan",
+            &simple_skill("local-orphan"),
+        );
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -602,7 +695,7 @@ mod tests {
             .with_local_skill(Tool::Codex, "synced-skill", &content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert!(plans.is_empty());
@@ -615,7 +708,7 @@ mod tests {
             .with_tool_skill(Tool::Claude, "skill-b", &simple_skill("skill-b"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, Some("skill-a"), &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -624,11 +717,11 @@ mod tests {
 
     #[test]
     fn errors_on_nonexistent_skill_filter() {
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Claude, "exists", &simple_skill("exists"));
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Claude, "exists", &simple_skill("exists"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let result = collect_pull_plans(&catalog, Some("nonexistent"), &mut diagnostics);
 
         assert!(result.is_err());
@@ -646,7 +739,7 @@ mod tests {
             .with_local_skill(Tool::Claude, "multi", &local_modified);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
@@ -676,7 +769,7 @@ mod tests {
             .with_local_skill(Tool::Codex, "local-codex", &simple_skill("local-codex"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let plans = collect_pull_plans(&catalog, None, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 4);
@@ -687,4 +780,112 @@ mod tests {
         assert!(names.contains(&"local-claude"));
         assert!(names.contains(&"local-codex"));
     }
+
+    #[tokio::test]
+    async fn dry_run_writes_nothing() {
+        let fixture = TestFixture::new()
+            .with_source_skill("edited", &skill_content("edited", "desc", "original"))
+            .with_tool_skill(
+                Tool::Claude,
+                "edited",
+                &skill_content("edited", "desc", "changed"),
+            )
+            .with_tool_skill(Tool::Codex, "orphan", &simple_skill("orphan"));
+
+        run(&fixture.context(), None, None, true, false, None, false)
+            .await
+            .expect("dry run");
+
+        assert_eq!(
+            fixture.read_source_skill("edited"),
+            Some(skill_content("edited", "desc", "original"))
+        );
+        assert!(!fixture.source_skill_exists("orphan"));
+    }
+
+    #[tokio::test]
+    async fn pulls_every_file_in_the_skill_directory() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"));
+        let source = fixture.source_dir().join("alpha");
+        fs::write(source.join("guide.md"), "original").expect("write");
+        fs::write(source.join("data.json"), "{}").expect("write");
+        let installed = fixture.tool_dir(Tool::Claude).join("alpha");
+        fs::create_dir_all(installed.join("scripts")).expect("mkdir");
+        fs::write(installed.join("guide.md"), "edited").expect("write");
+        fs::write(installed.join("scripts").join("run.sh"), "echo").expect("write");
+
+        run(&fixture.context(), None, None, false, true, None, false)
+            .await
+            .expect("pull");
+
+        let guide = fs::read_to_string(source.join("guide.md")).expect("guide");
+        assert_eq!(guide, "edited");
+        let script = fs::read_to_string(source.join("scripts").join("run.sh")).expect("script");
+        assert_eq!(script, "echo");
+        assert!(source.join("data.json").exists());
+    }
+
+    #[tokio::test]
+    async fn prunes_only_when_asked_and_after_a_snapshot() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &skill_content("alpha", "desc", "original"))
+            .with_tool_skill(
+                Tool::Claude,
+                "alpha",
+                &skill_content("alpha", "desc", "edited"),
+            );
+        let source = fixture.source_dir().join("alpha");
+        fs::write(source.join("data.json"), "{}").expect("write");
+        let ctx = fixture.context();
+
+        run(&ctx, None, None, false, true, None, true)
+            .await
+            .expect("pull");
+
+        assert!(!source.join("data.json").exists());
+        let state = ctx.load_state().expect("state");
+        let snapshot = state.snapshots_of("alpha").last().expect("snapshot");
+        assert_eq!(snapshot.origin, InstallOrigin::Pull);
+        assert!(snapshot.files.contains_key("data.json"));
+    }
+
+    #[tokio::test]
+    async fn force_resolves_conflicts_by_preferred_tool() {
+        let fixture = TestFixture::new()
+            .with_source_skill("edited", &skill_content("edited", "desc", "original"))
+            .with_tool_skill(
+                Tool::Claude,
+                "edited",
+                &skill_content("edited", "desc", "claude"),
+            )
+            .with_tool_skill(
+                Tool::Codex,
+                "edited",
+                &skill_content("edited", "desc", "codex"),
+            )
+            .with_tool_skill(Tool::Codex, "orphan", &simple_skill("orphan"));
+
+        run(
+            &fixture.context(),
+            None,
+            None,
+            false,
+            true,
+            Some(ToolFilter::Codex),
+            false,
+        )
+        .await
+        .expect("pull");
+
+        assert_eq!(
+            fixture.read_source_skill("edited"),
+            Some(skill_content("edited", "desc", "codex"))
+        );
+        assert_eq!(
+            fixture.read_source_skill("orphan"),
+            Some(simple_skill("orphan"))
+        );
+    }
 }
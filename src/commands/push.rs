@@ -1,44 +1,123 @@
 //! Implementation of the `skills push` command.
 
 use std::{
-    fs,
+    collections::{BTreeSet, HashMap},
+    fmt, fs,
     path::{Path, PathBuf},
+    result::Result as StdResult,
 };
 
-use inquire::{Confirm, error::InquireError};
+use inquire::{Confirm, Select, error::InquireError};
 use similar::{ChangeTag, TextDiff};
 
 use crate::{
-    catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
+    catalog::{Catalog, CatalogRoots},
+    commands::init,
+    config::LineEndings,
+    context::Context,
     diagnostics::Diagnostics,
     error::{Error, Result},
-    palette::{fmt_label, fmt_skill_name},
-    skill::{SKILL_FILE_NAME, SkillTemplate, render_template},
-    status::normalize_line_endings,
-    tool::{Tool, ToolFilter},
+    machine::Probe,
+    palette::{Palette, Role},
+    paths::display_path,
+    policy::Policy,
+    project::ProjectContext,
+    skill::{
+        RenderedSkill, SKILL_FILE_NAME, SkillTemplate, ToolSkill, push_support_files,
+        render_template_probed, write_skill_file,
+    },
+    state::{InstallOrigin, OverwriteChoice, State},
+    tool::Tool,
+    transaction::{self, PlannedWrite, Transaction, backup_dir},
+    units::format_count,
 };
 
+/// Options for one push.
+#[derive(Debug, Default)]
+pub struct PushOptions {
+    /// Push every source skill rather than the named or out-of-sync ones.
+    pub all: bool,
+    /// Report what would be pushed without writing anything.
+    pub dry_run: bool,
+    /// Overwrite modified tool copies, showing their diffs before prompting.
+    pub force: bool,
+    /// Skip the prompts `force` would otherwise show.
+    pub yes: bool,
+    /// Push into the current project's local skill directories.
+    pub project: bool,
+    /// Rename tool directories whose names differ from their skills' frontmatter names.
+    pub reconcile: bool,
+    /// Directory to push into instead of a tool's, with the tool whose context renders skills.
+    pub to: Option<(PathBuf, Tool)>,
+}
+
 /// Execute the push command.
-#[allow(clippy::too_many_arguments)]
+///
+/// With `project`, skills are pushed into the current project's local skill directories and
+/// rendered with the project's context. Nothing is pushed if any skill breaks the team policy.
+///
+/// Every write is planned, and every prompt answered, before anything is written. The planned
+/// writes are then checked together and applied as one transaction, so a failure part way through
+/// restores the copies already replaced.
+///
+/// A copy whose directory name differs from its frontmatter name is not written, since the push
+/// would otherwise leave a second copy or overwrite another skill. With `reconcile`, such
+/// directories are renamed to match their names first.
+///
+/// Answers of "always" or "never" to the prompt to overwrite a modified copy are remembered per
+/// skill and used instead of asking again, unless `force` is set.
+///
+/// Pinned skills are reported and left alone.
+///
+/// With `to`, skills are pushed into that directory instead of any tool's, rendered as if for the
+/// given tool.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skills: Vec<String>,
-    all: bool,
-    tool_filter: ToolFilter,
-    dry_run: bool,
-    force: bool,
-    yes: bool,
+    tools: Vec<Tool>,
+    options: PushOptions,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
-
-    let tools = tool_filter.to_tools();
+    let PushOptions {
+        all,
+        dry_run,
+        force,
+        yes,
+        project,
+        reconcile,
+        to,
+    } = options;
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let tools = match &to {
+        Some((_, tool)) => vec![*tool],
+        None => tools,
+    };
+    let project = if project {
+        Some(ProjectContext::detect(&ctx.project_dir)?)
+    } else {
+        None
+    };
+    let destination = match (&to, &project) {
+        (Some((dir, tool)), _) => {
+            let roots = CatalogRoots {
+                tools: vec![(*tool, dir.clone())],
+                skill_files: config.skill_files.clone(),
+                ..CatalogRoots::default()
+            };
+            let mut copies = Catalog::load(&roots, &mut diagnostics);
+            Destination::Dir {
+                dir,
+                copies: copies.tools.remove(tool).unwrap_or_default(),
+            }
+        }
+        (None, Some(project)) => Destination::Project(project),
+        (None, None) => Destination::Tools,
+    };
+    let mut state = ctx.load_state()?;
 
     // Determine which skills to push
     let skill_names: Vec<String> = if all {
@@ -46,7 +125,9 @@ pub async fn run(
         catalog.sources.keys().cloned().collect()
     } else if skills.is_empty() {
         // No skills specified - find out-of-sync skills and confirm
-        let out_of_sync = find_out_of_sync_skills(&catalog, &tools, &mut diagnostics);
+        let mut out_of_sync =
+            find_out_of_sync_skills(&catalog, &tools, &destination, &mut diagnostics);
+        out_of_sync.retain(|name| !state.is_pinned(name));
         if out_of_sync.is_empty() {
             println!("All skills are in sync.");
             return Ok(());
@@ -54,7 +135,7 @@ pub async fn run(
         if !force && !dry_run {
             println!("Skills needing push:");
             for name in &out_of_sync {
-                println!("  {}", fmt_skill_name(name, use_color));
+                println!("  {}", palette.paint(Role::SkillName, name));
             }
             println!();
             let prompt = format!("Push {} skill(s)?", out_of_sync.len());
@@ -67,6 +148,10 @@ pub async fn run(
         out_of_sync
     } else {
         // Validate that all specified skills exist
+        let skills: Vec<String> = skills
+            .iter()
+            .map(|name| catalog.resolve_name(name))
+            .collect();
         for name in &skills {
             if !catalog.sources.contains_key(name) {
                 return Err(Error::SkillNotFound { name: name.clone() });
@@ -80,21 +165,62 @@ pub async fn run(
         return Ok(());
     }
 
-    // Sort skills for consistent output
+    // Sort skills for consistent output, setting pinned skills aside
     let mut skill_names = skill_names;
-    skill_names.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+    skill_names.sort_by_key(|a| a.to_lowercase());
+    let (pinned, skill_names): (Vec<String>, Vec<String>) = skill_names
+        .into_iter()
+        .partition(|name| state.is_pinned(name));
+
+    if let Some(policy) = Policy::load(ctx, &config, &mut diagnostics).await? {
+        check_policy(&policy, &catalog, &skill_names, &tools, &destination)?;
+    }
+
+    let (renames, mismatches) = plan_renames(
+        find_mismatches(ctx, &catalog, &tools, &destination, &skill_names),
+        reconcile,
+    );
+    if !renames.is_empty() {
+        let heading = if dry_run {
+            "Would rename:"
+        } else {
+            "Renaming:"
+        };
+        println!("{}", palette.paint(Role::Action, heading));
+        for rename in &renames {
+            println!(
+                "  {}: {} -> {}",
+                rename.tool.id(),
+                display_path(&rename.dir),
+                display_path(&rename.target())
+            );
+        }
+        println!();
+    }
 
     let total = skill_names.len();
     let mut pushed_count = 0;
-    let mut skipped_count = 0;
+    let mut skipped_count = pinned.len();
+    let mut plan = Vec::new();
+    let planning = PushPlanning {
+        ctx,
+        catalog: &catalog,
+        tools: &tools,
+        destination: &destination,
+        mismatches: &mismatches,
+        dry_run,
+        force,
+        yes,
+        palette: &palette,
+    };
 
     for name in &skill_names {
         let template = catalog.sources.get(name).unwrap();
-        let results = push_skill(&catalog, template, &tools, dry_run, force, yes, use_color, &mut diagnostics)?;
+        let results = push_skill(&planning, template, &mut plan, &mut state, &mut diagnostics)?;
 
         // Check if any actual push happened
         let any_pushed = results.iter().any(|r| r.marker == '+' || r.marker == '~');
-        let any_skipped = results.iter().any(|r| r.marker == '!');
+        let any_skipped = results.iter().any(|r| r.marker == '!' || r.marker == '?');
 
         if any_pushed {
             pushed_count += 1;
@@ -104,7 +230,7 @@ pub async fn run(
         }
 
         // Print results
-        println!("{}", fmt_skill_name(name, use_color));
+        println!("{}", palette.paint(Role::SkillName, name));
         for result in results {
             println!(
                 "    {:<6}: {} ({})",
@@ -112,25 +238,47 @@ pub async fn run(
             );
         }
     }
+    for name in &pinned {
+        println!("{}", palette.paint(Role::SkillName, name));
+        println!("    pinned (run `skills unpin {name}` to push it)");
+    }
+
+    if !dry_run {
+        apply_plan(ctx, &renames, &plan, config.line_endings, &mut state)?;
+        state.save(&ctx.state_path)?;
+    }
 
     println!();
     if dry_run {
         println!(
             "{} {} skill(s) would be pushed.",
-            fmt_label("Dry run:", use_color),
+            palette.paint(Role::Label, "Dry run:"),
             total
         );
     } else {
         println!(
             "{} {} pushed, {} skipped.",
-            fmt_label("Done:", use_color),
+            palette.paint(Role::Label, "Done:"),
             pushed_count,
             skipped_count
         );
     }
+    if !mismatches.is_empty() {
+        println!();
+        if reconcile {
+            println!(
+                "Some copies could not be renamed because a directory with their skill's name \
+                 already exists; resolve them by hand."
+            );
+        } else {
+            println!(
+                "Some copies are in directories not named after their skills; run `skills push \
+                 --reconcile` to rename them."
+            );
+        }
+    }
 
-    diagnostics.print_skipped_summary();
-    diagnostics.print_warning_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
@@ -138,17 +286,17 @@ pub async fn run(
 fn find_out_of_sync_skills(
     catalog: &Catalog,
     tools: &[Tool],
+    destination: &Destination<'_>,
     diagnostics: &mut Diagnostics,
 ) -> Vec<String> {
     let mut out_of_sync = Vec::new();
 
     for (name, source) in &catalog.sources {
         for &tool in tools {
-            let tool_map = catalog.tools.get(&tool);
-            let tool_skill = tool_map.and_then(|skills| skills.get(name));
+            let tool_skill = installed(catalog, tool, name, destination);
 
             // Render the template for this tool
-            let rendered = match render_template(&source.contents, tool) {
+            let rendered = match render(catalog, source, tool, destination) {
                 Ok(rendered) => rendered,
                 Err(error) => {
                     diagnostics.warn_skipped(&source.skill_path, error);
@@ -163,9 +311,7 @@ fn find_out_of_sync_skills(
                     break;
                 }
                 Some(installed) => {
-                    if normalize_line_endings(&rendered)
-                        != normalize_line_endings(&installed.contents)
-                    {
+                    if !installed.matches(source, &rendered) {
                         // Skill differs
                         out_of_sync.push(name.clone());
                         break;
@@ -175,28 +321,270 @@ fn find_out_of_sync_skills(
         }
     }
 
-    out_of_sync.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+    out_of_sync.sort_by_key(|a| a.to_lowercase());
     out_of_sync
 }
 
+/// A tool copy whose directory name differs from the skill name in its frontmatter.
+#[derive(Debug)]
+struct Mismatch {
+    /// Tool the copy belongs to.
+    tool: Tool,
+    /// Directory holding the copy.
+    dir: PathBuf,
+    /// Skill name from the copy's frontmatter.
+    name: String,
+}
+
+impl Mismatch {
+    /// Return the directory the copy belongs in.
+    fn target(&self) -> PathBuf {
+        self.dir.with_file_name(&self.name)
+    }
+
+    /// Return the name of the directory holding the copy.
+    fn dir_name(&self) -> &str {
+        self.dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    /// Return whether the mismatch involves a skill, by name or by directory.
+    fn involves(&self, tool: Tool, skill: &str) -> bool {
+        self.tool == tool && (self.name == skill || self.dir_name() == skill)
+    }
+
+    /// Describe the mismatch as seen when pushing a skill.
+    fn describe(&self, skill: &str) -> String {
+        if self.name == skill {
+            format!("name mismatch: installed in '{}'", self.dir_name())
+        } else {
+            format!("name mismatch: '{skill}' holds '{}'", self.name)
+        }
+    }
+}
+
+/// Find the copies in each tool's push target whose directory and frontmatter names differ, when
+/// either name is one of the skills being pushed.
+fn find_mismatches(
+    ctx: &Context,
+    catalog: &Catalog,
+    tools: &[Tool],
+    destination: &Destination<'_>,
+    skill_names: &[String],
+) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for &tool in tools {
+        let target = target_dir(ctx, tool, destination);
+        let copies: Vec<(&String, &Path)> = match destination {
+            Destination::Project(_) => catalog
+                .local
+                .get(&tool)
+                .into_iter()
+                .flatten()
+                .map(|(name, skill)| (name, skill.skill_dir.as_path()))
+                .collect(),
+            Destination::Tools => catalog
+                .tools
+                .get(&tool)
+                .into_iter()
+                .flatten()
+                .filter_map(|(name, skill)| Some((name, skill.skill_path.parent()?)))
+                .collect(),
+            Destination::Dir { copies, .. } => copies
+                .iter()
+                .filter_map(|(name, skill)| Some((name, skill.skill_path.parent()?)))
+                .collect(),
+        };
+        for (name, dir) in copies {
+            let Some(dir_name) = dir.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if dir_name == name || dir.parent() != Some(target.as_path()) {
+                continue;
+            }
+            if skill_names
+                .iter()
+                .any(|skill| skill == name || skill == dir_name)
+            {
+                mismatches.push(Mismatch {
+                    tool,
+                    dir: dir.to_path_buf(),
+                    name: name.clone(),
+                });
+            }
+        }
+    }
+    mismatches.sort_by(|a, b| (a.tool.id(), &a.dir).cmp(&(b.tool.id(), &b.dir)));
+    mismatches
+}
+
+/// Split mismatches into those to rename and those left in place.
+///
+/// Without `reconcile` nothing is renamed. A copy is only renamed when no directory of its name
+/// exists and no other copy claims one.
+fn plan_renames(mismatches: Vec<Mismatch>, reconcile: bool) -> (Vec<Mismatch>, Vec<Mismatch>) {
+    if !reconcile {
+        return (Vec::new(), mismatches);
+    }
+    let mut claimed = BTreeSet::new();
+    mismatches.into_iter().partition(|mismatch| {
+        let target = mismatch.target();
+        !target.exists() && claimed.insert(target)
+    })
+}
 
-/// Push a skill to specified tools.
-#[allow(clippy::too_many_arguments)]
-fn push_skill(
+/// Where a push writes its copies.
+enum Destination<'a> {
+    /// Each tool's global skill directory.
+    Tools,
+    /// The current project's local skill directories.
+    Project(&'a ProjectContext),
+    /// A directory no tool owns, such as a mounted container volume.
+    Dir {
+        /// Directory the copies are written into.
+        dir: &'a Path,
+        /// Copies already in the directory, keyed by name.
+        copies: HashMap<String, ToolSkill>,
+    },
+}
+
+/// Return the directory a tool's copies are pushed to: its global directory, the project's local
+/// skill directory, or the directory given with `--to`.
+fn target_dir(ctx: &Context, tool: Tool, destination: &Destination<'_>) -> PathBuf {
+    match destination {
+        Destination::Tools => ctx.tool_dir(tool).to_path_buf(),
+        Destination::Project(_) => ctx.local_dir(tool),
+        Destination::Dir { dir, .. } => dir.to_path_buf(),
+    }
+}
+
+/// Return the copy of a skill already in a tool's push target.
+fn installed(
+    catalog: &Catalog,
+    tool: Tool,
+    name: &str,
+    destination: &Destination<'_>,
+) -> Option<ToolSkill> {
+    match destination {
+        Destination::Tools => catalog
+            .tools
+            .get(&tool)
+            .and_then(|skills| skills.get(name))
+            .cloned(),
+        Destination::Project(_) => catalog
+            .local
+            .get(&tool)
+            .and_then(|skills| skills.get(name))
+            .map(|skill| skill.to_tool_skill()),
+        Destination::Dir { copies, .. } => copies.get(name).cloned(),
+    }
+}
+
+/// Render a skill and its markdown support files for a tool, with the project's context when
+/// pushing to a project.
+///
+/// Project renderings depend on the project, so they bypass the render cache.
+fn render(
     catalog: &Catalog,
     skill: &SkillTemplate,
+    tool: Tool,
+    destination: &Destination<'_>,
+) -> StdResult<RenderedSkill, String> {
+    match destination {
+        Destination::Project(project) => skill.render_with(|template| {
+            render_template_probed(
+                template,
+                tool,
+                &skill.bases,
+                Some(project),
+                &Probe::default(),
+            )
+            .map_err(|(_, message)| message)
+        }),
+        Destination::Tools | Destination::Dir { .. } => catalog.render_skill(skill, tool),
+    }
+}
+
+/// Refuse to push when any skill's rendering for any tool breaks the team policy.
+///
+/// Skills that fail to render are left for the push itself to report.
+fn check_policy(
+    policy: &Policy,
+    catalog: &Catalog,
+    skill_names: &[String],
     tools: &[Tool],
+    destination: &Destination<'_>,
+) -> Result<()> {
+    for name in skill_names {
+        let skill = &catalog.sources[name];
+        for &tool in tools {
+            if let Ok(rendered) = render(catalog, skill, tool, destination) {
+                policy.check_skill(name, &rendered.contents, skill.stats.size)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Settings shared by every skill planned in one push.
+struct PushPlanning<'a> {
+    /// Command context.
+    ctx: &'a Context,
+    /// Catalog the skills are pushed from.
+    catalog: &'a Catalog,
+    /// Tools to push to.
+    tools: &'a [Tool],
+    /// Where the copies are written.
+    destination: &'a Destination<'a>,
+    /// Copies whose directory names differ from their frontmatter names, left unwritten.
+    mismatches: &'a [Mismatch],
+    /// Whether to report what would be pushed without writing anything.
     dry_run: bool,
+    /// Whether to overwrite modified tool copies, showing their diffs before prompting.
     force: bool,
+    /// Whether to skip the prompts `force` would otherwise show.
     yes: bool,
-    use_color: bool,
+    /// Palette for output.
+    palette: &'a Palette,
+}
+
+/// Plan pushing a skill to specified tools, adding the writes to make to `plan`.
+fn push_skill<'a>(
+    planning: &PushPlanning<'_>,
+    skill: &'a SkillTemplate,
+    plan: &mut Vec<PlannedPush<'a>>,
+    state: &mut State,
     diagnostics: &mut Diagnostics,
 ) -> Result<Vec<PushLine>> {
+    let PushPlanning {
+        ctx,
+        catalog,
+        tools,
+        destination,
+        mismatches,
+        dry_run,
+        force,
+        yes,
+        palette,
+    } = *planning;
     let mut results = Vec::new();
 
     for &tool in tools {
-        let tool_dir = tool.skills_dir()?;
-        let rendered = match render_template(&skill.contents, tool) {
+        if let Some(mismatch) = mismatches
+            .iter()
+            .find(|mismatch| mismatch.involves(tool, &skill.name))
+        {
+            results.push(PushLine {
+                tool_label: tool.id().to_string(),
+                marker: '?',
+                summary: mismatch.describe(&skill.name),
+            });
+            continue;
+        }
+        let tool_dir = target_dir(ctx, tool, destination);
+        let rendered = match render(catalog, skill, tool, destination) {
             Ok(rendered) => rendered,
             Err(error) => {
                 diagnostics.warn_skipped(&skill.skill_path, error);
@@ -209,13 +597,11 @@ fn push_skill(
             }
         };
 
-        let tool_map = catalog.tools.get(&tool);
-        let tool_skill = tool_map.and_then(|skills| skills.get(&skill.name));
-        let existing = tool_skill.map(|installed| installed.contents.clone());
-        let status = match &existing {
+        let tool_skill = installed(catalog, tool, &skill.name, destination);
+        let status = match &tool_skill {
             None => PushStatus::New,
-            Some(contents) => {
-                if normalize_line_endings(&rendered) == normalize_line_endings(contents) {
+            Some(installed) => {
+                if installed.matches(skill, &rendered) {
                     PushStatus::Unchanged
                 } else {
                     PushStatus::Modified
@@ -226,12 +612,28 @@ fn push_skill(
         let request = PushRequest {
             skill,
             tool,
-            tool_dir: &tool_dir,
-            rendered: &rendered,
-            existing: existing.as_deref(),
+            rendered: &rendered.contents,
+            existing: tool_skill.as_ref(),
             status,
+            remembered: if force {
+                None
+            } else {
+                state.overwrite_choice(&skill.name)
+            },
         };
-        let result = apply_push(&request, dry_run, force, yes, use_color)?;
+        let result = apply_push(&request, dry_run, force, yes, palette)?;
+        if let Some(answer) = result.answer {
+            state.set_overwrite_choice(&skill.name, answer.remembered());
+        }
+        if !dry_run && matches!(result.marker, '+' | '~') {
+            plan.push(PlannedPush {
+                skill,
+                tool,
+                skill_dir: tool_dir.join(&skill.name),
+                rendered,
+                existing: tool_skill,
+            });
+        }
 
         results.push(PushLine {
             tool_label: tool.id().to_string(),
@@ -249,14 +651,28 @@ struct PushRequest<'a> {
     skill: &'a SkillTemplate,
     /// Target tool.
     tool: Tool,
-    /// Tool root directory.
-    tool_dir: &'a PathBuf,
     /// Rendered template content.
     rendered: &'a str,
-    /// Existing content in tool (if any).
-    existing: Option<&'a str>,
+    /// Existing skill in the tool (if any).
+    existing: Option<&'a ToolSkill>,
     /// Precomputed push status.
     status: PushStatus,
+    /// Remembered answer to use instead of prompting before overwriting a modified copy.
+    remembered: Option<OverwriteChoice>,
+}
+
+/// A write decided on while planning a push, applied once every write has been checked.
+struct PlannedPush<'a> {
+    /// Source skill to install.
+    skill: &'a SkillTemplate,
+    /// Target tool.
+    tool: Tool,
+    /// Skill directory in the tool.
+    skill_dir: PathBuf,
+    /// Rendered skill and support files.
+    rendered: RenderedSkill,
+    /// Copy being replaced, whose support files the source no longer has are removed.
+    existing: Option<ToolSkill>,
 }
 
 /// Push status for a tool skill.
@@ -286,47 +702,105 @@ struct PushResult {
     marker: char,
     /// Summary label.
     summary: String,
+    /// Answer given to the overwrite prompt, when it was shown.
+    answer: Option<Answer>,
+}
+
+/// An answer to the prompt to overwrite a modified copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    /// Overwrite this copy.
+    Yes,
+    /// Leave this copy alone.
+    No,
+    /// Overwrite modified copies of the skill from now on.
+    Always,
+    /// Leave modified copies of the skill alone from now on.
+    Never,
+}
+
+impl Answer {
+    /// Every answer, in prompt order.
+    const ALL: [Self; 4] = [Self::Yes, Self::No, Self::Always, Self::Never];
+
+    /// Whether the answer overwrites the copy.
+    fn overwrites(self) -> bool {
+        matches!(self, Self::Yes | Self::Always)
+    }
+
+    /// The choice to remember for later prompts; a one-off answer forgets any earlier one.
+    fn remembered(self) -> Option<OverwriteChoice> {
+        match self {
+            Self::Yes | Self::No => None,
+            Self::Always => Some(OverwriteChoice::Always),
+            Self::Never => Some(OverwriteChoice::Never),
+        }
+    }
 }
 
-/// Apply push logic and write skill files if needed.
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Yes => write!(f, "Yes"),
+            Self::No => write!(f, "No"),
+            Self::Always => write!(f, "Always for this skill"),
+            Self::Never => write!(f, "Never for this skill"),
+        }
+    }
+}
+
+/// Decide whether to write a skill copy, prompting before overwriting a modified one.
 fn apply_push(
     request: &PushRequest<'_>,
     dry_run: bool,
     force: bool,
     yes: bool,
-    use_color: bool,
+    palette: &Palette,
 ) -> Result<PushResult> {
     match request.status {
         PushStatus::Unchanged => Ok(PushResult {
             marker: '=',
             summary: "unchanged".to_string(),
+            answer: None,
+        }),
+        PushStatus::New => Ok(PushResult {
+            marker: '+',
+            summary: "new".to_string(),
+            answer: None,
         }),
-        PushStatus::New => {
-            if !dry_run {
-                write_tool_skill(request.tool_dir, &request.skill.name, request.rendered)?;
-            }
-            Ok(PushResult {
-                marker: '+',
-                summary: "new".to_string(),
-            })
-        }
         PushStatus::Modified => {
+            match request.remembered {
+                Some(OverwriteChoice::Always) => {
+                    return Ok(PushResult {
+                        marker: '~',
+                        summary: "pushed (always overwrite)".to_string(),
+                        answer: None,
+                    });
+                }
+                Some(OverwriteChoice::Never) => {
+                    return Ok(PushResult {
+                        marker: '!',
+                        summary: "skipped (never overwrite)".to_string(),
+                        answer: None,
+                    });
+                }
+                None => {}
+            }
+            let mut answer = None;
             if !dry_run {
                 // Always prompt unless --yes is specified
                 let skip_prompt = force && yes;
 
                 if !skip_prompt {
                     // Show diff if force is specified (so user sees what's changing)
-                    if force {
-                        if let Some(existing) = request.existing {
-                            println!();
-                            println!(
-                                "Diff for '{}' in {}:",
-                                request.skill.name,
-                                request.tool.display_name()
-                            );
-                            print_diff(existing, request.rendered, use_color);
-                        }
+                    if force && let Some(existing) = request.existing {
+                        println!();
+                        println!(
+                            "Diff for '{}' in {}:",
+                            request.skill.name,
+                            request.tool.display_name()
+                        );
+                        print_diff(&existing.read_contents()?, request.rendered, palette);
                     }
 
                     let prompt = format!(
@@ -334,52 +808,170 @@ fn apply_push(
                         request.skill.name,
                         request.tool.display_name()
                     );
-                    let confirmed = confirm(&prompt)?;
-                    if !confirmed {
+                    let chosen = ask_overwrite(&prompt)?;
+                    if !chosen.overwrites() {
                         return Ok(PushResult {
                             marker: '!',
                             summary: "skipped".to_string(),
+                            answer: Some(chosen),
                         });
                     }
+                    answer = Some(chosen);
                 }
-
-                write_tool_skill(request.tool_dir, &request.skill.name, request.rendered)?;
             }
 
             Ok(PushResult {
                 marker: '~',
                 summary: "pushed".to_string(),
+                answer,
             })
         }
     }
 }
 
-/// Print a unified diff between two strings.
-fn print_diff(old: &str, new: &str, use_color: bool) {
-    use owo_colors::OwoColorize;
+/// Install a source skill, with its support files, into a tool that has no copy of it.
+pub fn install_missing(
+    ctx: &Context,
+    catalog: &Catalog,
+    skill: &SkillTemplate,
+    tool: Tool,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
+    let rendered = catalog
+        .render_skill(skill, tool)
+        .map_err(|message| Error::TemplateRender { message })?;
+    let skill_dir = ctx.tool_dir(tool).join(&skill.name);
+    install(skill, &skill_dir, &rendered, None, line_endings, state)
+}
 
+/// Check every planned write, then rename mismatched directories and apply the writes, restoring
+/// everything already changed if any step fails.
+fn apply_plan(
+    ctx: &Context,
+    renames: &[Mismatch],
+    plan: &[PlannedPush<'_>],
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
+    let backups = backup_dir(&ctx.state_path);
+    let writes: Vec<PlannedWrite> = plan
+        .iter()
+        .map(|push| PlannedWrite {
+            dir: push.skill_dir.clone(),
+            label: format!("'{}' for {}", push.skill.name, push.tool.display_name()),
+            digest: push.rendered.digest(),
+            bytes: push.rendered.contents.len() as u64 + push.skill.stats.size,
+        })
+        .collect();
+    transaction::check(&writes, &backups)?;
+
+    let mut transaction = Transaction::begin(&backups);
+    if let Err(error) = apply_steps(&mut transaction, renames, plan, line_endings, state) {
+        let restored = match transaction.rollback() {
+            Ok(restored) => restored,
+            Err(rollback) => {
+                eprintln!("{error}");
+                return Err(rollback);
+            }
+        };
+        eprintln!(
+            "Push failed; rolled back {}.",
+            format_count(restored, "write")
+        );
+        return Err(error);
+    }
+    transaction.commit()
+}
+
+/// Rename mismatched directories, then write every planned copy, staging each directory in the
+/// transaction before it changes.
+fn apply_steps(
+    transaction: &mut Transaction,
+    renames: &[Mismatch],
+    plan: &[PlannedPush<'_>],
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
+    for rename in renames {
+        let target = rename.target();
+        transaction.stage(&rename.dir)?;
+        transaction.stage(&target)?;
+        fs::rename(&rename.dir, &target).map_err(|source| Error::SkillMove {
+            from: rename.dir.clone(),
+            to: target.clone(),
+            source,
+        })?;
+        state.rename(&rename.dir, &target);
+    }
+    for push in plan {
+        transaction.stage(&push.skill_dir)?;
+        install(
+            push.skill,
+            &push.skill_dir,
+            &push.rendered,
+            push.existing.as_ref(),
+            line_endings,
+            state,
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the rendered skill and its support files into a tool's skill directory and record what
+/// was installed.
+///
+/// Support files of the `existing` copy that the source no longer has are removed.
+fn install(
+    skill: &SkillTemplate,
+    skill_dir: &Path,
+    rendered: &RenderedSkill,
+    existing: Option<&ToolSkill>,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
+    state.snapshot(skill_dir, InstallOrigin::Push)?;
+    write_skill_file(
+        &skill_dir.join(SKILL_FILE_NAME),
+        &rendered.contents,
+        line_endings,
+    )?;
+    push_support_files(skill, rendered, skill_dir, existing, line_endings)?;
+    state.record(skill_dir, InstallOrigin::Push)
+}
+
+/// Print a unified diff between two strings.
+fn print_diff(old: &str, new: &str, palette: &Palette) {
     let diff = TextDiff::from_lines(old, new);
 
     for change in diff.iter_all_changes() {
-        let sign = match change.tag() {
-            ChangeTag::Delete => "-",
-            ChangeTag::Insert => "+",
-            ChangeTag::Equal => " ",
-        };
-
-        if use_color {
-            match change.tag() {
-                ChangeTag::Delete => print!("{}", format!("{}{}", sign, change).red()),
-                ChangeTag::Insert => print!("{}", format!("{}{}", sign, change).green()),
-                ChangeTag::Equal => print!(" {}", change),
+        match change.tag() {
+            ChangeTag::Delete => {
+                print!("{}", palette.paint(Role::DiffRemove, &format!("-{change}")))
             }
-        } else {
-            print!("{}{}", sign, change);
+            ChangeTag::Insert => print!("{}", palette.paint(Role::DiffAdd, &format!("+{change}"))),
+            ChangeTag::Equal => print!(" {}", change),
         }
     }
     println!();
 }
 
+/// Ask whether to overwrite a modified copy, offering to remember the answer for the skill.
+fn ask_overwrite(message: &str) -> Result<Answer> {
+    match Select::new(message, Answer::ALL.to_vec())
+        .with_starting_cursor(1)
+        .prompt()
+    {
+        Ok(answer) => Ok(answer),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
 /// Prompt for confirmation.
 fn confirm(message: &str) -> Result<bool> {
     match Confirm::new(message).with_default(false).prompt() {
@@ -393,41 +985,315 @@ fn confirm(message: &str) -> Result<bool> {
     }
 }
 
-/// Write a rendered skill to the tool directory.
-fn write_tool_skill(tool_dir: &Path, name: &str, rendered: &str) -> Result<()> {
-    let skill_dir = tool_dir.join(name);
-    fs::create_dir_all(&skill_dir).map_err(|error| Error::SkillWrite {
-        path: skill_dir.clone(),
-        source: error,
-    })?;
-
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    fs::write(&skill_path, rendered).map_err(|error| Error::SkillWrite {
-        path: skill_path,
-        source: error,
-    })?;
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use super::{Destination, PushOptions, find_out_of_sync_skills, run};
     use crate::{
         diagnostics::Diagnostics,
+        error::Error,
+        project::VARS_FILE,
+        skill::SKILL_FILE_NAME,
+        state::{OverwriteChoice, State},
         testutil::{TestFixture, simple_skill, skill_content},
         tool::Tool,
     };
 
-    use super::find_out_of_sync_skills;
+    #[tokio::test]
+    async fn run_pushes_source_skills_to_every_tool() {
+        let template = "---\nname: alpha\ndescription: Alpha\n---\n{{ tool }}\n";
+        let fixture = TestFixture::new().with_source_skill("alpha", template);
+
+        run(
+            &fixture.context(),
+            Vec::new(),
+            Tool::all().to_vec(),
+            PushOptions {
+                all: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await
+        .expect("push");
+
+        for tool in Tool::all() {
+            let pushed = fixture
+                .read_tool_skill(tool, "alpha")
+                .expect("pushed skill");
+            assert!(pushed.ends_with(tool.id()));
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_skills_that_break_the_policy() {
+        let banned = "---\nname: beta\ndescription: Beta\nallowed-tools: Bash\n---\nBody\n";
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_source_skill("beta", banned);
+        let ctx = fixture.context();
+        let policy = fixture.work_dir().join("policy.toml");
+        fs::write(&policy, "banned_fields = [\"allowed-tools\"]").expect("write policy");
+        let mut config = fs::read_to_string(&ctx.config_path).expect("read config");
+        config.push_str(&format!("[policy]\nsource = '{}'\n", policy.display()));
+        fs::write(&ctx.config_path, config).expect("write config");
+
+        let result = run(
+            &ctx,
+            Vec::new(),
+            Tool::all().to_vec(),
+            PushOptions {
+                all: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(Error::PolicyViolation { .. })));
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "alpha"));
+    }
+
+    #[tokio::test]
+    async fn remembered_answers_replace_the_overwrite_prompt() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &skill_content("alpha", "Alpha", "new"))
+            .with_tool_skill(
+                Tool::Claude,
+                "alpha",
+                &skill_content("alpha", "Alpha", "edited"),
+            );
+        let ctx = fixture.context();
+        let remember = |choice| {
+            let mut state = State::load(&ctx.state_path).expect("state");
+            state.set_overwrite_choice("alpha", Some(choice));
+            state.save(&ctx.state_path).expect("save");
+        };
+        let push = || {
+            run(
+                &ctx,
+                vec!["alpha".to_string()],
+                vec![Tool::Claude],
+                PushOptions::default(),
+            )
+        };
+
+        remember(OverwriteChoice::Never);
+        push().await.expect("push");
+        let copy = fixture
+            .read_tool_skill(Tool::Claude, "alpha")
+            .expect("copy");
+        assert!(copy.ends_with("edited"));
+
+        remember(OverwriteChoice::Always);
+        push().await.expect("push");
+        let copy = fixture
+            .read_tool_skill(Tool::Claude, "alpha")
+            .expect("copy");
+        assert!(copy.ends_with("new"));
+    }
+
+    #[tokio::test]
+    async fn project_push_renders_with_project_context() {
+        let template = "---\nname: alpha\ndescription: Alpha\n---\n{{ project.languages | join(\",\") }} {{ project.vars.team }}\n";
+        let fixture = TestFixture::new().with_source_skill("alpha", template);
+        fs::write(fixture.work_dir().join("Cargo.toml"), "").expect("write");
+        fs::write(fixture.work_dir().join(VARS_FILE), "team = \"core\"\n").expect("write");
+
+        run(
+            &fixture.context(),
+            vec!["alpha".to_string()],
+            vec![Tool::Codex],
+            PushOptions {
+                yes: true,
+                project: true,
+                ..PushOptions::default()
+            },
+        )
+        .await
+        .expect("push");
+
+        let pushed = fixture
+            .read_local_skill(Tool::Codex, "alpha")
+            .expect("local skill");
+        assert!(pushed.ends_with("rust core"));
+        assert!(!fixture.tool_skill_exists(Tool::Codex, "alpha"));
+    }
+
+    #[tokio::test]
+    async fn pushes_into_a_directory_rendering_as_a_tool() {
+        let template = "---\nname: alpha\ndescription: Alpha\n---\n{{ tool }}\n";
+        let fixture = TestFixture::new().with_source_skill("alpha", template);
+        let ctx = fixture.context();
+        let dir = fixture.work_dir().join("mnt");
+        let push = |skills| {
+            run(
+                &ctx,
+                skills,
+                Tool::all().to_vec(),
+                PushOptions {
+                    to: Some((dir.clone(), Tool::Codex)),
+                    ..PushOptions::default()
+                },
+            )
+        };
+
+        push(vec!["alpha".to_string()]).await.expect("push");
+        let pushed = fs::read_to_string(dir.join("alpha").join(SKILL_FILE_NAME)).expect("read");
+        assert!(pushed.ends_with("codex"));
+        for tool in Tool::all() {
+            assert!(!fixture.tool_skill_exists(tool, "alpha"));
+        }
+
+        // The copy just written is in sync, so pushing out-of-sync skills has nothing to ask.
+        push(Vec::new()).await.expect("push again");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pushes_every_file_in_the_skill_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "alpha", &simple_skill("alpha"));
+        let source = fixture.source_dir().join("alpha");
+        fs::create_dir_all(source.join("scripts")).expect("mkdir");
+        fs::write(source.join("guide.md"), "{{ tool }}\n").expect("write");
+        let script = source.join("scripts").join("run.sh");
+        fs::write(&script, "echo {{ tool }}\n").expect("write");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("chmod");
+        let installed = fixture.tool_dir(Tool::Claude).join("alpha");
+        fs::create_dir_all(installed.join("old")).expect("mkdir");
+        fs::write(installed.join("old").join("stale.md"), "stale").expect("write");
+
+        run(
+            &fixture.context(),
+            vec!["alpha".to_string()],
+            vec![Tool::Claude],
+            PushOptions {
+                force: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await
+        .expect("push");
+
+        let guide = fs::read_to_string(installed.join("guide.md")).expect("guide");
+        assert_eq!(guide, "claude");
+        let copied = installed.join("scripts").join("run.sh");
+        assert_eq!(
+            fs::read_to_string(&copied).expect("script"),
+            "echo {{ tool }}\n"
+        );
+        let mode = fs::metadata(&copied)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert!(!installed.join("old").exists());
+    }
+
+    #[tokio::test]
+    async fn rolls_back_every_write_when_one_fails() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &skill_content("alpha", "Alpha", "new"))
+            .with_source_skill("beta", &simple_skill("beta"))
+            .with_tool_skill(
+                Tool::Claude,
+                "alpha",
+                &skill_content("alpha", "Alpha", "old"),
+            );
+        let scripts = fixture.source_dir().join("beta").join("scripts");
+        fs::create_dir_all(&scripts).expect("mkdir");
+        fs::write(scripts.join("run.sh"), "echo").expect("write");
+        // A file where the copy's scripts directory belongs makes the second write fail.
+        let installed = fixture.tool_dir(Tool::Claude).join("beta");
+        fs::create_dir_all(&installed).expect("mkdir");
+        fs::write(installed.join("scripts"), "in the way").expect("write");
+
+        let result = run(
+            &fixture.context(),
+            vec!["alpha".to_string(), "beta".to_string()],
+            vec![Tool::Claude],
+            PushOptions {
+                force: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(Error::SkillWrite { .. })));
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "alpha")
+                .expect("restored")
+                .contains("old")
+        );
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "beta"));
+    }
+
+    #[tokio::test]
+    async fn reconciles_directories_with_skill_names() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_source_skill("beta", &simple_skill("beta"))
+            .with_tool_skill(Tool::Claude, "old-alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Claude, "beta", &simple_skill("gamma"));
+        let ctx = fixture.context();
+        let push = |reconcile| {
+            run(
+                &ctx,
+                vec!["alpha".to_string(), "beta".to_string()],
+                vec![Tool::Claude],
+                PushOptions {
+                    force: true,
+                    yes: true,
+                    reconcile,
+                    ..PushOptions::default()
+                },
+            )
+        };
+
+        push(false).await.expect("push");
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "alpha"));
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "beta")
+                .expect("untouched")
+                .contains("name: gamma")
+        );
+
+        push(true).await.expect("reconcile");
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "old-alpha"));
+        assert!(fixture.tool_skill_exists(Tool::Claude, "alpha"));
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "gamma")
+                .expect("renamed")
+                .contains("name: gamma")
+        );
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "beta")
+                .expect("pushed")
+                .contains("name: beta")
+        );
+    }
 
     #[test]
     fn finds_missing_skill() {
-        let fixture = TestFixture::new()
-            .with_source_skill("new-skill", &simple_skill("new-skill"));
+        let fixture = TestFixture::new().with_source_skill("new-skill", &simple_skill("new-skill"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let mut diagnostics = Diagnostics::default();
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
 
         assert_eq!(out_of_sync, vec!["new-skill"]);
     }
@@ -442,8 +1308,13 @@ mod tests {
             .with_tool_skill(Tool::Claude, "modified", &tool_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let mut diagnostics = Diagnostics::default();
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
 
         assert_eq!(out_of_sync, vec!["modified"]);
     }
@@ -457,8 +1328,13 @@ mod tests {
             .with_tool_skill(Tool::Claude, "synced", &content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let mut diagnostics = Diagnostics::default();
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
 
         assert!(out_of_sync.is_empty());
     }
@@ -473,19 +1349,33 @@ mod tests {
             .with_tool_skill(Tool::Claude, "partial", &content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
 
         // Check only Claude - should be synced
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
         assert!(out_of_sync.is_empty());
 
         // Check only Codex - should be out of sync (missing)
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Codex], &mut diagnostics);
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Codex],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
         assert_eq!(out_of_sync, vec!["partial"]);
 
         // Check both - should be out of sync
-        let out_of_sync =
-            find_out_of_sync_skills(&catalog, &[Tool::Claude, Tool::Codex], &mut diagnostics);
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude, Tool::Codex],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
         assert_eq!(out_of_sync, vec!["partial"]);
     }
 
@@ -497,8 +1387,13 @@ mod tests {
             .with_source_skill("Banana", &simple_skill("Banana"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let mut diagnostics = Diagnostics::default();
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
 
         assert_eq!(out_of_sync, vec!["apple", "Banana", "Zebra"]);
     }
@@ -506,12 +1401,17 @@ mod tests {
     #[test]
     fn ignores_orphan_tool_skills() {
         // Tool has a skill that source doesn't - should not appear in out_of_sync
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Claude, "orphan", &simple_skill("orphan"));
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Claude, "orphan", &simple_skill("orphan"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let out_of_sync = find_out_of_sync_skills(&catalog, &[Tool::Claude], &mut diagnostics);
+        let mut diagnostics = Diagnostics::default();
+        let out_of_sync = find_out_of_sync_skills(
+            &catalog,
+            &[Tool::Claude],
+            &Destination::Tools,
+            &mut diagnostics,
+        );
 
         assert!(out_of_sync.is_empty());
     }
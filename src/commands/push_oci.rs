@@ -0,0 +1,49 @@
+//! Implementation of the `skills push-oci` command.
+
+use crate::{
+    archive::{SymlinkPolicy, Zip, pack_dir},
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    http::cancellable,
+    oci::Artifact,
+    palette::Role,
+};
+
+/// Execute the push-oci command.
+pub async fn run(ctx: &Context, skill: String, reference: String) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let skill = catalog.resolve_name(&skill);
+
+    let source = catalog
+        .sources
+        .get(&skill)
+        .ok_or_else(|| Error::SkillNotFound {
+            name: skill.clone(),
+        })?;
+
+    let (archive, files) = pack_dir(&Zip, &source.name, &source.skill_dir, SymlinkPolicy::Reject)?;
+    let size = archive.len();
+    let artifact = Artifact::new(&source.name, &source.description, archive);
+
+    println!(
+        "{} '{}' to {}",
+        palette.paint(Role::Action, "Pushing"),
+        palette.paint(Role::SkillName, &source.name),
+        reference
+    );
+    let manifest_url = cancellable(artifact.push(&config.network, &reference)).await?;
+
+    println!();
+    println!("Pushed: {} file(s), {} bytes", files.len(), size);
+    println!("Manifest: {manifest_url}");
+    println!();
+    println!("Import with: skills import oci://{reference}");
+
+    diagnostics.print_summary();
+    Ok(())
+}
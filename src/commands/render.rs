@@ -1,40 +1,110 @@
 //! Implementation of the `skills render` command.
 
+use clap::ValueEnum;
+use serde_json::{Value, json};
+
 use crate::{
-    catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
+    commands::init,
+    context::Context,
     error::{Error, Result},
-    skill::render_template,
-    tool::ToolFilter,
+    filters::FILTERS,
+    machine::{FUNCTIONS, Probe},
+    palette::{Palette, Role},
+    project::ProjectContext,
+    skill::{BaseTemplates, SkillTemplate, render_template_probed, template_context},
+    tool::{Tool, ToolFilter},
 };
 
+/// How `--context` prints the template context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ContextFormat {
+    /// One line per variable, function, and partial.
+    Text,
+    /// A JSON object.
+    Json,
+}
+
+/// Opens a marker recording which conditional block produced a piece of output.
+const MARKER_START: char = '\u{E000}';
+/// Closes a marker.
+const MARKER_END: char = '\u{E001}';
+
 /// Execute the render command.
+///
+/// With `annotate`, each output line is prefixed with the template conditions that produced it.
+/// With `check`, nothing is printed but whether each rendering succeeds and what it looked up
+/// about the machine. With `context`, nothing is rendered; the variables, functions, filters, and
+/// partials the template can use are printed instead. With `project`, templates also see the current
+/// project's context.
 pub async fn run(
-    _color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill_name: String,
     tool_filter: ToolFilter,
+    annotate: bool,
+    check: bool,
+    context: Option<ContextFormat>,
+    project: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
 
     // Find the skill in sources
-    let source = catalog.sources.get(&skill_name).ok_or_else(|| Error::SkillNotFound {
-        name: skill_name.clone(),
-    })?;
+    let skill_name = catalog.resolve_name(&skill_name);
+    let source = catalog
+        .sources
+        .get(&skill_name)
+        .ok_or_else(|| Error::SkillNotFound {
+            name: skill_name.clone(),
+        })?;
+
+    let project = if project {
+        Some(ProjectContext::detect(&ctx.project_dir)?)
+    } else {
+        None
+    };
 
     // Determine which tool(s) to render for
     let tools = tool_filter.to_tools();
     let multi = tools.len() > 1;
 
+    if check {
+        let result = check_renders(source, &tools, project.as_ref(), &palette);
+        diagnostics.print_summary();
+        return result;
+    }
+    if let Some(format) = context {
+        print_context(source, &tools, project.as_ref(), format, &palette);
+        diagnostics.print_summary();
+        return Ok(());
+    }
+
     for tool in &tools {
         // Render the template for this tool
-        let rendered = render_template(&source.contents, *tool)
-            .map_err(|e| Error::TemplateRender { message: e })?;
+        let rendered = if annotate {
+            render_annotated(
+                &source.contents,
+                *tool,
+                &source.bases,
+                project.as_ref(),
+                &palette,
+            )?
+        } else if let Some(project) = &project {
+            render_template_probed(
+                &source.contents,
+                *tool,
+                &source.bases,
+                Some(project),
+                &Probe::default(),
+            )
+            .map_err(|(_, message)| Error::TemplateRender { message })?
+        } else {
+            catalog
+                .render(source, *tool)
+                .map_err(|e| Error::TemplateRender { message: e })?
+        };
 
         if multi {
             println!("=== {} ===", tool.display_name());
@@ -47,6 +117,366 @@ pub async fn run(
             println!();
         }
     }
+    diagnostics.print_summary();
+
+    Ok(())
+}
+
+/// Render a skill for each tool, printing the outcome and the machine lookups each made.
+///
+/// Fails if any rendering fails.
+fn check_renders(
+    source: &SkillTemplate,
+    tools: &[Tool],
+    project: Option<&ProjectContext>,
+    palette: &Palette,
+) -> Result<()> {
+    let mut failed = 0;
+    let mut machine_dependent = false;
+    for &tool in tools {
+        let probe = Probe::default();
+        match render_template_probed(&source.contents, tool, &source.bases, project, &probe) {
+            Ok(_) => println!("{} {}", palette.paint(Role::Success, "✓"), tool.id()),
+            Err((line, message)) => {
+                failed += 1;
+                let location = line
+                    .map(|line| format!("line {line}: "))
+                    .unwrap_or_default();
+                println!(
+                    "{} {}: {location}{message}",
+                    palette.paint(Role::Failure, "✗"),
+                    tool.id()
+                );
+            }
+        }
+        for lookup in probe.lookups() {
+            machine_dependent = true;
+            println!("    {} = {}", lookup.call, lookup.value);
+        }
+    }
+    if machine_dependent {
+        println!("Output depends on this machine and is evaluated again on every push.");
+    }
+    if failed > 0 {
+        return Err(Error::TemplateRender {
+            message: format!("{failed} of {} rendering(s) failed", tools.len()),
+        });
+    }
+    Ok(())
+}
+
+/// Execute `render --list-filters`, describing the filters skill templates can use.
+pub async fn list_filters(ctx: &Context) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
 
+    println!("{}", palette.paint(Role::Label, "Filters:"));
+    let width = FILTERS
+        .iter()
+        .map(|(signature, _)| signature.len())
+        .max()
+        .unwrap_or_default();
+    for (signature, description) in FILTERS {
+        println!("  {signature:<width$}  {description}");
+    }
     Ok(())
 }
+
+/// Describe the variables a skill's template sees for each tool, with the functions, filters, and
+/// partials it can use.
+fn context_report(
+    source: &SkillTemplate,
+    tools: &[Tool],
+    project: Option<&ProjectContext>,
+) -> Value {
+    let contexts: Vec<Value> = tools
+        .iter()
+        .map(|&tool| {
+            let variables =
+                serde_json::to_value(template_context(tool, project)).unwrap_or(Value::Null);
+            json!({ "tool": tool.id(), "variables": variables })
+        })
+        .collect();
+    let partials: Vec<&str> = source.bases.names().collect();
+    json!({
+        "skill": source.name,
+        "contexts": contexts,
+        "functions": FUNCTIONS,
+        "filters": FILTERS.iter().map(|(signature, _)| signature).collect::<Vec<_>>(),
+        "partials": partials,
+    })
+}
+
+/// Print the template context of a skill for each tool.
+fn print_context(
+    source: &SkillTemplate,
+    tools: &[Tool],
+    project: Option<&ProjectContext>,
+    format: ContextFormat,
+    palette: &Palette,
+) {
+    let report = context_report(source, tools, project);
+    if format == ContextFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        return;
+    }
+
+    for (context, tool) in report["contexts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .zip(tools)
+    {
+        let heading = format!("Variables for {}:", tool.display_name());
+        println!("{}", palette.paint(Role::Label, &heading));
+        for (name, value) in context["variables"].as_object().into_iter().flatten() {
+            println!("  {name} = {value}");
+        }
+    }
+    println!("{}", palette.paint(Role::Label, "Functions:"));
+    for function in FUNCTIONS {
+        println!("  {function}");
+    }
+    println!("{}", palette.paint(Role::Label, "Filters:"));
+    for (signature, _) in FILTERS {
+        println!("  {signature}");
+    }
+    println!("{}", palette.paint(Role::Label, "Partials:"));
+    let partials = report["partials"].as_array().cloned().unwrap_or_default();
+    if partials.is_empty() {
+        println!("  (none)");
+    }
+    for partial in partials.iter().filter_map(Value::as_str) {
+        println!("  {partial}");
+    }
+}
+
+/// Render a template for a tool, prefixing each line with the conditions that produced it.
+fn render_annotated(
+    template: &str,
+    tool: Tool,
+    bases: &BaseTemplates,
+    project: Option<&ProjectContext>,
+    palette: &Palette,
+) -> Result<String> {
+    let (marked, labels) = mark_conditionals(template);
+    let rendered = render_template_probed(&marked, tool, bases, project, &Probe::default())
+        .map_err(|(_, message)| Error::TemplateRender { message })?;
+
+    let lines: Vec<(&str, String)> = rendered
+        .lines()
+        .map(|line| {
+            let label = first_marker(line).and_then(|index| labels.get(index));
+            (label.map_or("", String::as_str), strip_markers(line))
+        })
+        .collect();
+    let width = lines
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    for (label, line) in lines {
+        let gutter = format!("{label:<width$} │");
+        output.push_str(&palette.paint(Role::Template, &gutter));
+        if !line.is_empty() {
+            output.push(' ');
+            output.push_str(&line);
+        }
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Insert markers into a template before the first output of each line inside a conditional.
+///
+/// Returns the marked template and the label for each marker index, such as
+/// `if tool == "codex"` or, for nested blocks, `if a › else`.
+fn mark_conditionals(template: &str) -> (String, Vec<String>) {
+    let mut marked = String::with_capacity(template.len());
+    let mut labels: Vec<String> = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut line = 0;
+    let mut marked_line = None;
+
+    let mut mark = |marked: &mut String, stack: &[String], line: usize| {
+        if stack.is_empty() || marked_line == Some(line) {
+            return;
+        }
+        marked_line = Some(line);
+        marked.push(MARKER_START);
+        marked.push_str(&labels.len().to_string());
+        marked.push(MARKER_END);
+        labels.push(stack.join(" › "));
+    };
+
+    let mut rest = template;
+    loop {
+        let next = ["{%", "{{", "{#"]
+            .into_iter()
+            .filter_map(|open| rest.find(open).map(|start| (start, open)))
+            .min_by_key(|(start, _)| *start);
+        let text_end = next.map_or(rest.len(), |(start, _)| start);
+        for c in rest[..text_end].chars() {
+            if c == '\n' {
+                line += 1;
+            } else if !c.is_whitespace() {
+                mark(&mut marked, &stack, line);
+            }
+            marked.push(c);
+        }
+        let Some((start, open)) = next else {
+            break;
+        };
+        let close = match open {
+            "{%" => "%}",
+            "{{" => "}}",
+            _ => "#}",
+        };
+        let Some(end) = rest[start + 2..].find(close).map(|end| start + 2 + end + 2) else {
+            marked.push_str(&rest[start..]);
+            break;
+        };
+        let tag = &rest[start..end];
+        match open {
+            "{{" => mark(&mut marked, &stack, line),
+            "{%" => update_conditions(&mut stack, tag),
+            _ => {}
+        }
+        marked.push_str(tag);
+        line += tag.matches('\n').count();
+        rest = &rest[end..];
+    }
+    (marked, labels)
+}
+
+/// Track `if`/`elif`/`else`/`endif` block tags on the condition stack.
+fn update_conditions(stack: &mut Vec<String>, tag: &str) {
+    let inner = tag[2..tag.len() - 2].trim_matches(['-', '+', '~']).trim();
+    let (keyword, condition) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+    let condition = condition.trim();
+    match keyword {
+        "if" => stack.push(format!("if {condition}")),
+        "elif" => {
+            if let Some(top) = stack.last_mut() {
+                *top = format!("elif {condition}");
+            }
+        }
+        "else" => {
+            if let Some(top) = stack.last_mut() {
+                *top = "else".to_string();
+            }
+        }
+        "endif" => {
+            stack.pop();
+        }
+        _ => {}
+    }
+}
+
+/// Return the index of the first marker in a rendered line.
+fn first_marker(line: &str) -> Option<usize> {
+    let start = line.find(MARKER_START)? + MARKER_START.len_utf8();
+    let end = start + line[start..].find(MARKER_END)?;
+    line[start..end].parse().ok()
+}
+
+/// Remove every marker from a rendered line.
+fn strip_markers(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut in_marker = false;
+    for c in line.chars() {
+        match c {
+            MARKER_START => in_marker = true,
+            MARKER_END => in_marker = false,
+            _ if !in_marker => output.push(c),
+            _ => {}
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{context_report, render_annotated};
+    use crate::{
+        palette::{Palette, Theme},
+        project::ProjectContext,
+        skill::{BaseTemplates, TEMPLATES_DIR},
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[test]
+    fn annotates_lines_from_conditional_blocks() {
+        let template = "Intro\n{% if tool == \"codex\" %}\nUse apply_patch.\n{% else %}\nUse the editor.\n{% if tool == \"claude\" %}Claude only.{% endif %}\n{% endif %}\nOutro\n";
+        let palette = Palette::new(&Theme::default(), false);
+
+        let codex = render_annotated(
+            template,
+            Tool::Codex,
+            &BaseTemplates::default(),
+            None,
+            &palette,
+        )
+        .expect("render");
+        assert_eq!(
+            codex,
+            "                   │ Intro\n                   │\nif tool == \"codex\" │ Use apply_patch.\n                   │\n                   │ Outro\n"
+        );
+
+        let claude = render_annotated(
+            template,
+            Tool::Claude,
+            &BaseTemplates::default(),
+            None,
+            &palette,
+        )
+        .expect("render");
+        assert!(claude.contains(&format!("{:<26} │ Use the editor.\n", "else")));
+        assert!(claude.contains("else › if tool == \"claude\" │ Claude only.\n"));
+    }
+
+    #[test]
+    fn reports_template_context() {
+        let fixture = TestFixture::new().with_source_skill("alpha", &simple_skill("alpha"));
+        let templates = fixture.source_dir().join(TEMPLATES_DIR);
+        fs::create_dir_all(&templates).expect("mkdir");
+        fs::write(templates.join("base.md"), "Base").expect("write");
+        let catalog = fixture.catalog();
+        let project = ProjectContext {
+            name: "demo".to_string(),
+            ..ProjectContext::default()
+        };
+
+        let report = context_report(
+            &catalog.sources["alpha"],
+            &[Tool::Claude, Tool::Codex],
+            Some(&project),
+        );
+        assert_eq!(report["skill"], "alpha");
+        assert_eq!(report["contexts"][0]["variables"]["tool"], "claude");
+        assert_eq!(report["contexts"][1]["variables"]["tool"], "codex");
+        assert_eq!(
+            report["contexts"][1]["variables"]["project"]["name"],
+            "demo"
+        );
+        assert_eq!(report["partials"], serde_json::json!(["base.md"]));
+        assert!(
+            report["functions"]
+                .as_array()
+                .expect("functions")
+                .contains(&serde_json::json!("platform()"))
+        );
+
+        let report = context_report(&catalog.sources["alpha"], &[Tool::Claude], None);
+        assert!(report["contexts"][0]["variables"].get("project").is_none());
+    }
+}
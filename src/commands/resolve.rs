@@ -0,0 +1,416 @@
+//! Implementation of the `skills resolve` command.
+
+use std::{collections::HashMap, fmt, fs};
+
+use inquire::{Select, error::InquireError};
+use similar::{DiffTag, TextDiff};
+
+use crate::{
+    catalog::Catalog,
+    commands::{
+        diff::diff_copy,
+        edit::open_in_editor,
+        init,
+        sync::{SyncPlan, apply_pull, apply_push, build_sync_plans},
+    },
+    config::LineEndings,
+    context::Context,
+    diff::colorize_diff,
+    error::{Error, Result},
+    palette::{Palette, Role},
+    paths::display_path,
+    skill::{SkillTemplate, ToolSkill, write_skill_file},
+    state::State,
+    tool::Tool,
+};
+
+/// Opens the source side of a merge conflict block.
+const MARKER_SOURCE: &str = "<<<<<<< source";
+/// Separates the two sides of a merge conflict block.
+const MARKER_SPLIT: &str = "=======";
+/// Prefix of the line closing a merge conflict block, followed by the tool id.
+const MARKER_TOOL: &str = ">>>>>>> ";
+
+/// A decision for one out-of-sync skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    /// Overwrite the differing tool copies with the source.
+    KeepSource,
+    /// Pull a tool copy into the source and push it to every other tool.
+    KeepTool(Tool),
+    /// Combine the source and a tool copy in the editor.
+    Merge(Tool),
+    /// Edit the source in the editor, then push it.
+    Edit,
+    /// Leave the skill as it is.
+    Skip,
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeepSource => write!(f, "Keep source"),
+            Self::KeepTool(tool) => write!(f, "Keep {} copy", tool.id()),
+            Self::Merge(tool) => write!(f, "Merge source with {} copy", tool.id()),
+            Self::Edit => write!(f, "Edit source"),
+            Self::Skip => write!(f, "Skip"),
+        }
+    }
+}
+
+/// Execute the resolve command.
+///
+/// Each skill whose tool copies differ from the source is shown with its diffs and resolved by
+/// the user's choice, which is applied before the next skill is shown. With `skills`, only the
+/// named skills are offered.
+pub async fn run(ctx: &Context, skills: Vec<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+    let skills: Vec<String> = skills
+        .iter()
+        .map(|name| catalog.resolve_name(name))
+        .collect();
+
+    for name in &skills {
+        if !catalog.sources.contains_key(name) {
+            return Err(Error::SkillNotFound { name: name.clone() });
+        }
+    }
+    let mut plans = build_sync_plans(&catalog, config.sync.default, &mut diagnostics)?;
+    if !skills.is_empty() {
+        plans.retain(|plan| skills.contains(&plan.name));
+    }
+    if plans.is_empty() {
+        println!("Nothing to resolve.");
+        diagnostics.print_summary();
+        return Ok(());
+    }
+
+    let mut state = ctx.load_state()?;
+    let mut resolved = 0;
+    for (index, plan) in plans.iter().enumerate() {
+        println!(
+            "[{}/{}] {}",
+            index + 1,
+            plans.len(),
+            palette.paint(Role::SkillName, &plan.name)
+        );
+        show_diffs(&catalog, plan, &palette)?;
+
+        let choice = prompt_choice(&choices(plan))?;
+        if apply_choice(ctx, &catalog, plan, choice, config.line_endings, &mut state)? {
+            state.save(&ctx.state_path)?;
+            resolved += 1;
+        }
+        println!();
+    }
+    println!("Resolved {resolved} of {} skill(s).", plans.len());
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Print the diff between the rendered source and each differing tool copy.
+fn show_diffs(catalog: &Catalog, plan: &SyncPlan, palette: &Palette) -> Result<()> {
+    for tool in differing_tools(plan) {
+        let tool_skill = &plan.tool_skills[&tool];
+        let rendered = catalog
+            .render_skill(&plan.source, tool)
+            .map_err(|message| Error::TemplateRender { message })?;
+        let diff = diff_copy(
+            &plan.source,
+            &rendered,
+            tool_skill,
+            (
+                &format!("source (rendered for {})", tool.id()),
+                &format!("{}: {}", tool.id(), display_path(&tool_skill.skill_path)),
+            ),
+        )?;
+        let diff = colorize_diff(&diff, palette);
+        print!("{diff}");
+        if !diff.ends_with('\n') {
+            println!();
+        }
+    }
+    Ok(())
+}
+
+/// Return the choices offered for a skill, keeping and merging each differing tool copy.
+fn choices(plan: &SyncPlan) -> Vec<Choice> {
+    let tools = differing_tools(plan);
+    let mut choices = vec![Choice::KeepSource];
+    choices.extend(tools.iter().map(|&tool| Choice::KeepTool(tool)));
+    choices.extend(tools.iter().map(|&tool| Choice::Merge(tool)));
+    choices.extend([Choice::Edit, Choice::Skip]);
+    choices
+}
+
+/// Apply a choice, returning whether the skill was resolved.
+fn apply_choice(
+    ctx: &Context,
+    catalog: &Catalog,
+    plan: &SyncPlan,
+    choice: Choice,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<bool> {
+    let source_path = &plan.source.skill_path;
+    let contents = match choice {
+        Choice::KeepSource => {
+            let tools = differing_tools(plan);
+            apply_push(
+                ctx,
+                &plan.source,
+                &tools,
+                &plan.tool_skills,
+                line_endings,
+                state,
+            )?;
+            println!("Pushed source to {}.", tool_list(&tools));
+            return Ok(true);
+        }
+        Choice::KeepTool(tool) => {
+            let pulled = apply_pull(plan, tool, line_endings)?;
+            return push_updated(ctx, catalog, &pulled, line_endings, state);
+        }
+        Choice::Merge(tool) => {
+            let theirs = plan.tool_skills[&tool].read_contents()?;
+            let merge_path = ctx
+                .cache_dir
+                .join("resolve")
+                .join(format!("{}.md", plan.name));
+            write_skill_file(
+                &merge_path,
+                &merge_markers(&plan.source.contents, &theirs, tool),
+                line_endings,
+            )?;
+            open_in_editor(&merge_path)?;
+            let merged = fs::read_to_string(&merge_path).map_err(|source| Error::SkillRead {
+                path: merge_path.clone(),
+                source,
+            })?;
+            if has_markers(&merged) {
+                println!(
+                    "Conflict markers remain in {}; skipped {}.",
+                    display_path(&merge_path),
+                    plan.name
+                );
+                return Ok(false);
+            }
+            fs::remove_file(&merge_path).ok();
+            merged
+        }
+        Choice::Edit => {
+            open_in_editor(source_path)?;
+            fs::read_to_string(source_path).map_err(|source| Error::SkillRead {
+                path: source_path.clone(),
+                source,
+            })?
+        }
+        Choice::Skip => return Ok(false),
+    };
+
+    write_skill_file(source_path, &contents, line_endings)?;
+    let updated = SkillTemplate {
+        contents,
+        ..plan.source.clone()
+    };
+    push_updated(ctx, catalog, &updated, line_endings, state)
+}
+
+/// Push a changed source to every tool with a copy, returning that the skill was resolved.
+///
+/// The source changed, so every installed copy is now stale, not only the differing ones.
+fn push_updated(
+    ctx: &Context,
+    catalog: &Catalog,
+    skill: &SkillTemplate,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<bool> {
+    let installed = installed_copies(catalog, &skill.name);
+    let tools: Vec<Tool> = Tool::all()
+        .into_iter()
+        .filter(|tool| installed.contains_key(tool))
+        .collect();
+    apply_push(ctx, skill, &tools, &installed, line_endings, state)?;
+    println!("Updated source and pushed to {}.", tool_list(&tools));
+    Ok(true)
+}
+
+/// Combine a source template and a tool copy, wrapping each differing run of lines in
+/// git-style conflict markers.
+fn merge_markers(source: &str, theirs: &str, tool: Tool) -> String {
+    let diff = TextDiff::from_lines(source, theirs);
+    let old = diff.old_slices();
+    let new = diff.new_slices();
+    let mut output = String::new();
+    let mut ours_block = String::new();
+    let mut theirs_block = String::new();
+
+    let flush = |output: &mut String, ours: &mut String, theirs: &mut String| {
+        if ours.is_empty() && theirs.is_empty() {
+            return;
+        }
+        output.push_str(MARKER_SOURCE);
+        output.push('\n');
+        output.push_str(ours);
+        output.push_str(MARKER_SPLIT);
+        output.push('\n');
+        output.push_str(theirs);
+        output.push_str(MARKER_TOOL);
+        output.push_str(tool.id());
+        output.push('\n');
+        ours.clear();
+        theirs.clear();
+    };
+
+    for op in diff.ops() {
+        if op.tag() == DiffTag::Equal {
+            flush(&mut output, &mut ours_block, &mut theirs_block);
+            for line in &old[op.old_range()] {
+                output.push_str(line);
+            }
+            continue;
+        }
+        for line in &old[op.old_range()] {
+            push_line(&mut ours_block, line);
+        }
+        for line in &new[op.new_range()] {
+            push_line(&mut theirs_block, line);
+        }
+    }
+    flush(&mut output, &mut ours_block, &mut theirs_block);
+    output
+}
+
+/// Append a line to a conflict block, terminating it so the next marker starts a new line.
+fn push_line(block: &mut String, line: &str) {
+    block.push_str(line);
+    if !line.ends_with('\n') {
+        block.push('\n');
+    }
+}
+
+/// Whether merged text still contains conflict markers.
+fn has_markers(text: &str) -> bool {
+    text.lines()
+        .any(|line| line == MARKER_SOURCE || line == MARKER_SPLIT || line.starts_with(MARKER_TOOL))
+}
+
+/// Return the tools whose copies differ from the source, in canonical order.
+fn differing_tools(plan: &SyncPlan) -> Vec<Tool> {
+    Tool::all()
+        .into_iter()
+        .filter(|tool| plan.tool_skills.contains_key(tool))
+        .collect()
+}
+
+/// Return the installed copies of a skill, keyed by tool.
+fn installed_copies(catalog: &Catalog, name: &str) -> HashMap<Tool, ToolSkill> {
+    catalog
+        .tools
+        .iter()
+        .filter_map(|(tool, skills)| Some((*tool, skills.get(name)?.clone())))
+        .collect()
+}
+
+/// Join tool ids for display.
+fn tool_list(tools: &[Tool]) -> String {
+    tools
+        .iter()
+        .map(|tool| tool.id())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Prompt for a choice.
+fn prompt_choice(choices: &[Choice]) -> Result<Choice> {
+    match Select::new("Resolve with:", choices.to_vec()).prompt() {
+        Ok(choice) => Ok(choice),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Choice, apply_choice, choices, has_markers, merge_markers};
+    use crate::{
+        commands::sync::build_sync_plans,
+        config::{LineEndings, SyncDirection},
+        diagnostics::Diagnostics,
+        testutil::{TestFixture, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn wraps_differing_lines_in_conflict_markers() {
+        let merged = merge_markers("a\nb\nc\nd", "a\nB\nc\nd\ne\n", Tool::Codex);
+        assert_eq!(
+            merged,
+            "a\n<<<<<<< source\nb\n=======\nB\n>>>>>>> codex\nc\n<<<<<<< source\nd\n=======\nd\ne\n>>>>>>> codex\n"
+        );
+        assert!(has_markers(&merged));
+        assert!(!has_markers("a\n==\nb\n"));
+        assert_eq!(merge_markers("same\n", "same\n", Tool::Claude), "same\n");
+    }
+
+    #[test]
+    fn keeping_a_tool_copy_updates_source_and_other_tools() {
+        let fixture = TestFixture::new()
+            .with_source_skill("skill", &skill_content("skill", "desc", "source"))
+            .with_tool_skill(
+                Tool::Claude,
+                "skill",
+                &skill_content("skill", "desc", "claude"),
+            )
+            .with_tool_skill(
+                Tool::Codex,
+                "skill",
+                &skill_content("skill", "desc", "codex"),
+            );
+        let ctx = fixture.context();
+        let catalog = fixture.catalog();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).expect("plans");
+        let plan = &plans[0];
+        assert_eq!(
+            choices(plan),
+            [
+                Choice::KeepSource,
+                Choice::KeepTool(Tool::Claude),
+                Choice::KeepTool(Tool::Codex),
+                Choice::Merge(Tool::Claude),
+                Choice::Merge(Tool::Codex),
+                Choice::Edit,
+                Choice::Skip,
+            ]
+        );
+
+        let mut state = ctx.load_state().expect("state");
+        let resolved = apply_choice(
+            &ctx,
+            &catalog,
+            plan,
+            Choice::KeepTool(Tool::Codex),
+            LineEndings::default(),
+            &mut state,
+        )
+        .expect("apply");
+        assert!(resolved);
+        let codex = skill_content("skill", "desc", "codex");
+        assert_eq!(fixture.read_source_skill("skill").as_deref(), Some(&*codex));
+        assert_eq!(
+            fixture.read_tool_skill(Tool::Claude, "skill").as_deref(),
+            Some(&*codex)
+        );
+    }
+}
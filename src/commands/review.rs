@@ -0,0 +1,224 @@
+//! Implementation of the `skills review` command.
+
+use std::{fs, path::Path};
+
+use walkdir::WalkDir;
+
+use crate::{
+    commands::{init, scan::print_findings, validate::check_skill},
+    context::Context,
+    error::{Error, Result},
+    palette::{Palette, Role},
+    paths::display_path,
+    permissions::is_executable,
+    quarantine::{Quarantine, QuarantinedSkill},
+    scan::scan_dir,
+    skill::{BaseTemplates, SkillFileNames, render_template},
+    store::FsStore,
+    tool::Tool,
+    units::DateFormat,
+};
+
+/// Indent for subordinate information.
+const INDENT: &str = "    ";
+
+/// Lines of each rendering shown unless the full rendering is requested.
+const PREVIEW_LINES: usize = 12;
+
+/// Execute the review command, describing each quarantined skill, or only the named ones.
+///
+/// Each skill is shown with where it came from, where it will be installed, its files, its scan
+/// findings, its validation results, and a preview of each distinct tool rendering, or the whole rendering with
+/// `full`.
+pub async fn run(ctx: &Context, skills: Vec<String>, full: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let quarantine = Quarantine::new(&ctx.quarantine_dir);
+
+    let quarantined = if skills.is_empty() {
+        quarantine.list()?
+    } else {
+        skills
+            .iter()
+            .map(|name| quarantine.get(name))
+            .collect::<Result<Vec<_>>>()?
+    };
+    if quarantined.is_empty() {
+        println!("No skills in quarantine.");
+        return Ok(());
+    }
+
+    for (index, skill) in quarantined.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print_skill(
+            skill,
+            &config.skill_files,
+            full,
+            ctx.dates(&config),
+            &palette,
+        )?;
+    }
+    println!();
+    println!("Install with `skills approve <name>`.");
+
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Print one quarantined skill.
+fn print_skill(
+    skill: &QuarantinedSkill,
+    skill_files: &SkillFileNames,
+    full: bool,
+    dates: DateFormat,
+    palette: &Palette,
+) -> Result<()> {
+    let label = |text: &str| palette.paint(Role::Label, text);
+    println!("{}", palette.paint(Role::SkillName, &skill.name));
+    println!("{INDENT}{} {}", label("source:"), skill.record.source);
+    println!(
+        "{INDENT}{} {}",
+        label("quarantined:"),
+        dates.age(skill.record.quarantined_at)
+    );
+    println!("{INDENT}{}", label("installs to:"));
+    for target in &skill.record.targets {
+        println!(
+            "{INDENT}{INDENT}{}",
+            palette.paint(Role::Path, &display_path(target))
+        );
+    }
+    println!("{INDENT}{}", label("files:"));
+    for file in list_files(&skill.skill_dir) {
+        println!("{INDENT}{INDENT}{file}");
+    }
+    let findings = scan_dir(&skill.skill_dir)?;
+    if findings.is_empty() {
+        println!(
+            "{INDENT}{} {}",
+            label("scan:"),
+            palette.paint(Role::Success, "✓ no findings")
+        );
+    } else {
+        println!("{INDENT}{}", label("scan:"));
+        print_findings(&findings, &format!("{INDENT}{INDENT}"), palette);
+    }
+
+    let Some(skill_path) = skill_files.find(&FsStore, &skill.skill_dir) else {
+        println!(
+            "{INDENT}{} {}",
+            label("validation:"),
+            palette.paint(Role::Failure, "no skill file")
+        );
+        return Ok(());
+    };
+    let contents = fs::read_to_string(&skill_path).map_err(|source| Error::SkillRead {
+        path: skill_path.clone(),
+        source,
+    })?;
+
+    let bases = BaseTemplates::default();
+    let problems = check_skill(&skill.name, &contents, &bases, None, None);
+    if problems.is_empty() {
+        println!(
+            "{INDENT}{} {}",
+            label("validation:"),
+            palette.paint(Role::Success, "✓ valid")
+        );
+    } else {
+        println!(
+            "{INDENT}{} {}",
+            label("validation:"),
+            palette.paint(Role::Failure, "✗ invalid")
+        );
+        for problem in problems {
+            println!("{INDENT}{INDENT}- {}", problem.message);
+        }
+    }
+
+    for (tools, rendered) in renderings(&contents, &bases) {
+        let ids: Vec<&str> = tools.iter().map(|tool| tool.id()).collect();
+        println!("{INDENT}{}", label(&format!("{}:", ids.join(", "))));
+        let lines: Vec<&str> = rendered.lines().collect();
+        let shown = if full {
+            lines.len()
+        } else {
+            lines.len().min(PREVIEW_LINES)
+        };
+        for line in &lines[..shown] {
+            println!("{INDENT}{INDENT}│ {line}");
+        }
+        if shown < lines.len() {
+            println!(
+                "{INDENT}{INDENT}{}",
+                palette.paint(
+                    Role::Label,
+                    &format!("… {} more lines (--full)", lines.len() - shown)
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Render a skill for every tool, grouping tools whose renderings are identical.
+///
+/// A failed rendering is shown as its error, which validation also reports.
+fn renderings(contents: &str, bases: &BaseTemplates) -> Vec<(Vec<Tool>, String)> {
+    let mut groups: Vec<(Vec<Tool>, String)> = Vec::new();
+    for tool in Tool::all() {
+        let rendered = render_template(contents, tool, bases)
+            .unwrap_or_else(|error| format!("render failed: {error}"));
+        match groups.iter_mut().find(|(_, text)| *text == rendered) {
+            Some((tools, _)) => tools.push(tool),
+            None => groups.push((vec![tool], rendered)),
+        }
+    }
+    groups
+}
+
+/// List the files in a skill directory, marking executables and symlinks, which deserve the
+/// closest look.
+fn list_files(dir: &Path) -> Vec<String> {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_type().is_dir())
+        .map(|entry| {
+            let path = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            let path = path.display().to_string().replace('\\', "/");
+            if entry.path_is_symlink() {
+                let target = fs::read_link(entry.path()).unwrap_or_default();
+                format!("{path} -> {}", target.display())
+            } else if entry
+                .metadata()
+                .is_ok_and(|metadata| is_executable(&metadata))
+            {
+                format!("{path} (executable)")
+            } else {
+                path
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::renderings;
+    use crate::{skill::BaseTemplates, tool::Tool};
+
+    #[test]
+    fn groups_identical_renderings() {
+        let contents = "---\nname: a\ndescription: d\n---\n{% if tool == \"codex\" %}C{% else %}X{% endif %}\n";
+        let groups = renderings(contents, &BaseTemplates::default());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, [Tool::Claude, Tool::Gemini]);
+        assert_eq!(groups[1].0, [Tool::Codex]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Implementation of the `skills scan` command.
+
+use std::path::PathBuf;
+
+use crate::{
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    palette::{Palette, Role},
+    paths::display_path,
+    scan::{Finding, Severity, scan_dir},
+};
+
+/// Execute the scan command over the named skills, or every source skill when none are named.
+///
+/// A name that is not a source skill is treated as a path to a skill directory, so downloaded
+/// skills can be scanned before they are imported. Fails when any skill has high-severity
+/// findings.
+pub async fn run(ctx: &Context, skills: Vec<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let targets: Vec<(String, PathBuf)> = if skills.is_empty() {
+        catalog
+            .sources
+            .iter()
+            .map(|(name, skill)| (name.clone(), skill.skill_dir.clone()))
+            .collect()
+    } else {
+        skills
+            .iter()
+            .map(
+                |name| match catalog.sources.get(&catalog.resolve_name(name)) {
+                    Some(skill) => Ok((name.clone(), skill.skill_dir.clone())),
+                    None => {
+                        let path = ctx.work_dir.join(name);
+                        if path.is_dir() {
+                            Ok((display_path(&path), path))
+                        } else {
+                            Err(Error::SkillNotFound { name: name.clone() })
+                        }
+                    }
+                },
+            )
+            .collect::<Result<_>>()?
+    };
+    if targets.is_empty() {
+        println!("No skills to scan.");
+        return Ok(());
+    }
+
+    let mut flagged = 0;
+    for (name, dir) in &targets {
+        let findings = scan_dir(dir)?;
+        let mark = match findings.iter().map(|finding| finding.severity).max() {
+            Some(Severity::High) => {
+                flagged += 1;
+                palette.paint(Role::Failure, "✗")
+            }
+            Some(_) => palette.paint(Role::Warning, "!"),
+            None => palette.paint(Role::Success, "✓"),
+        };
+        println!("{mark} {}", palette.paint(Role::SkillName, name));
+        print_findings(&findings, "    ", &palette);
+    }
+
+    println!();
+    println!(
+        "{} scanned, {} with high-severity findings",
+        targets.len(),
+        flagged
+    );
+    diagnostics.print_summary();
+    if flagged > 0 {
+        return Err(Error::ScanFailed { count: flagged });
+    }
+    Ok(())
+}
+
+/// Print scan findings one per line, each led by its severity in the severity's color.
+pub fn print_findings(findings: &[Finding], indent: &str, palette: &Palette) {
+    for finding in findings {
+        let role = match finding.severity {
+            Severity::High => Role::Failure,
+            Severity::Medium => Role::Warning,
+            Severity::Low => Role::Label,
+        };
+        let label = format!("{:<6}", finding.severity.label());
+        println!("{indent}{} {finding}", palette.paint(role, &label));
+    }
+}
@@ -0,0 +1,298 @@
+//! Implementation of the `skills search` command.
+
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::{
+    catalog::Catalog,
+    commands::{init, print_json},
+    context::Context,
+    error::{Error, Result},
+    palette::{Palette, Role},
+    paths::display_path,
+    tool::Tool,
+};
+
+/// Options for the search command.
+#[derive(Debug, Clone, Copy, Default, Args)]
+pub struct SearchOptions {
+    /// Match letters regardless of case.
+    #[arg(long, short = 'i')]
+    pub ignore_case: bool,
+    /// Treat the pattern as literal text rather than a regular expression.
+    #[arg(long, short = 'F')]
+    pub fixed_strings: bool,
+    /// Print only the paths of files that contain a match.
+    #[arg(long, short = 'l')]
+    pub files_with_matches: bool,
+    /// Print results as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Where a searched skill lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Location {
+    /// A source skill.
+    Source,
+    /// A skill installed in a tool's global directory.
+    Global,
+    /// A skill in a project-local tool directory.
+    Local,
+}
+
+/// A skill directory to search.
+struct Target {
+    /// Skill name.
+    skill: String,
+    /// Location type of the skill.
+    location: Location,
+    /// Tool the skill is installed for, if any.
+    tool: Option<Tool>,
+    /// Skill directory.
+    dir: PathBuf,
+}
+
+/// A matching line in a skill file.
+#[derive(Debug, Serialize)]
+struct LineMatch {
+    /// Skill name.
+    skill: String,
+    /// Location type of the skill.
+    location: Location,
+    /// Tool id for installed skills.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool: Option<&'static str>,
+    /// Path of the file containing the match.
+    path: PathBuf,
+    /// One-based line number.
+    line: usize,
+    /// Full text of the line.
+    text: String,
+    /// Byte ranges of each match within the line, as `[start, end)` pairs.
+    matches: Vec<[usize; 2]>,
+}
+
+/// A file containing at least one match.
+#[derive(Debug, Serialize)]
+struct FileMatch {
+    /// Skill name.
+    skill: String,
+    /// Location type of the skill.
+    location: Location,
+    /// Tool id for installed skills.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool: Option<&'static str>,
+    /// Path of the matching file.
+    path: PathBuf,
+}
+
+/// Execute the search command.
+///
+/// Every text file in source, global tool, and project-local skill directories is searched.
+pub async fn run(ctx: &Context, pattern: &str, options: SearchOptions) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let regex = build_regex(pattern, options)?;
+    let matches = search(&targets(&catalog), &regex);
+
+    if options.files_with_matches {
+        let mut files: Vec<FileMatch> = Vec::new();
+        for found in matches {
+            if files.last().is_some_and(|file| file.path == found.path) {
+                continue;
+            }
+            files.push(FileMatch {
+                skill: found.skill,
+                location: found.location,
+                tool: found.tool,
+                path: found.path,
+            });
+        }
+        if options.json {
+            print_json(&files)?;
+        } else {
+            for file in &files {
+                println!("{}", palette.paint(Role::Path, &display_path(&file.path)));
+            }
+        }
+    } else if options.json {
+        print_json(&matches)?;
+    } else {
+        for found in &matches {
+            print_match(found, &palette);
+        }
+    }
+
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Compile the search pattern.
+fn build_regex(pattern: &str, options: SearchOptions) -> Result<Regex> {
+    let source = if options.fixed_strings {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    RegexBuilder::new(&source)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|error| Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// Collect every skill directory in the catalog, ordered by location, tool, and name.
+fn targets(catalog: &Catalog) -> Vec<Target> {
+    let mut targets: Vec<Target> = catalog
+        .sources
+        .values()
+        .map(|skill| Target {
+            skill: skill.name.clone(),
+            location: Location::Source,
+            tool: None,
+            dir: skill.skill_dir.clone(),
+        })
+        .collect();
+    for (tool, skills) in &catalog.tools {
+        targets.extend(skills.values().filter_map(|skill| {
+            Some(Target {
+                skill: skill.name.clone(),
+                location: Location::Global,
+                tool: Some(*tool),
+                dir: skill.skill_path.parent()?.to_path_buf(),
+            })
+        }));
+    }
+    for (tool, skills) in &catalog.local {
+        targets.extend(skills.values().map(|skill| Target {
+            skill: skill.name.clone(),
+            location: Location::Local,
+            tool: Some(*tool),
+            dir: skill.skill_dir.clone(),
+        }));
+    }
+    targets.sort_by(|a, b| {
+        (a.location, a.tool.map(Tool::id), a.skill.to_lowercase()).cmp(&(
+            b.location,
+            b.tool.map(Tool::id),
+            b.skill.to_lowercase(),
+        ))
+    });
+    targets
+}
+
+/// Search every text file in the target directories, skipping files that are not UTF-8.
+fn search(targets: &[Target], regex: &Regex) -> Vec<LineMatch> {
+    let mut matches = Vec::new();
+    for target in targets {
+        let files = WalkDir::new(&target.dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file());
+        for entry in files {
+            let Ok(contents) = fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            for (index, line) in contents.lines().enumerate() {
+                let ranges: Vec<[usize; 2]> = regex
+                    .find_iter(line)
+                    .filter(|found| !found.is_empty())
+                    .map(|found| [found.start(), found.end()])
+                    .collect();
+                if ranges.is_empty() {
+                    continue;
+                }
+                matches.push(LineMatch {
+                    skill: target.skill.clone(),
+                    location: target.location,
+                    tool: target.tool.map(Tool::id),
+                    path: entry.path().to_path_buf(),
+                    line: index + 1,
+                    text: line.to_string(),
+                    matches: ranges,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Print a matching line as `path:line: text`, highlighting the matches.
+fn print_match(found: &LineMatch, palette: &Palette) {
+    let mut text = String::new();
+    let mut last = 0;
+    for [start, end] in &found.matches {
+        text.push_str(&found.text[last..*start]);
+        text.push_str(&palette.paint(Role::Action, &found.text[*start..*end]));
+        last = *end;
+    }
+    text.push_str(&found.text[last..]);
+    println!(
+        "{}:{}: {}",
+        palette.paint(Role::Path, &display_path(&found.path)),
+        found.line,
+        text
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Location, SearchOptions, build_regex, search, targets};
+    use crate::{
+        testutil::{TestFixture, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn finds_matches_in_every_location() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "pdf",
+                &skill_content("pdf", "PDFs", "Use pdftotext.\nThen stop."),
+            )
+            .with_tool_skill(
+                Tool::Claude,
+                "pdf",
+                &skill_content("pdf", "PDFs", "Use PDFTOTEXT twice: pdftotext."),
+            );
+        let ctx = fixture.context();
+        let config = ctx.load_config().expect("config");
+        let catalog = ctx.load_catalog(&config, &mut ctx.diagnostics());
+
+        let options = SearchOptions {
+            ignore_case: true,
+            ..SearchOptions::default()
+        };
+        let regex = build_regex("pdftotext", options).expect("regex");
+        let matches = search(&targets(&catalog), &regex);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].location, Location::Source);
+        assert_eq!(matches[0].line, 5);
+        assert_eq!(matches[0].matches, vec![[4, 13]]);
+        assert_eq!(matches[1].location, Location::Global);
+        assert_eq!(matches[1].tool, Some("claude"));
+        assert_eq!(matches[1].matches.len(), 2);
+
+        let literal = SearchOptions {
+            fixed_strings: true,
+            ..SearchOptions::default()
+        };
+        let regex = build_regex("stop.", literal).expect("regex");
+        assert_eq!(search(&targets(&catalog), &regex).len(), 1);
+        assert!(build_regex("(", SearchOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Implementation of the `skills shell` command's prompt and completion.
+
+use std::{collections::BTreeSet, result::Result as StdResult};
+
+use inquire::{
+    Text,
+    autocompletion::{Autocomplete, Replacement},
+    error::{CustomUserError, InquireError},
+};
+
+use crate::{
+    catalog::Catalog,
+    error::{Error, Result},
+    palette::{Palette, Role},
+};
+
+/// Words that end the shell session.
+const EXIT_WORDS: [&str; 2] = ["exit", "quit"];
+
+/// Completes command names for the first word of a line and skill names after it.
+#[derive(Debug, Clone, Default)]
+struct Completer {
+    /// Subcommand names, sorted.
+    commands: Vec<String>,
+    /// Skill names from the loaded catalog, sorted.
+    skills: Vec<String>,
+}
+
+impl Completer {
+    /// Return every completion of the last word of a line, as whole replacement lines.
+    fn suggest(&self, input: &str) -> Vec<String> {
+        let (head, word) = match input.rfind(' ') {
+            Some(index) => input.split_at(index + 1),
+            None => ("", input),
+        };
+        let candidates = if head.trim().is_empty() {
+            if word.is_empty() {
+                return Vec::new();
+            }
+            &self.commands
+        } else {
+            &self.skills
+        };
+        candidates
+            .iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(|candidate| format!("{head}{candidate}"))
+            .collect()
+    }
+}
+
+impl Autocomplete for Completer {
+    fn get_suggestions(&mut self, input: &str) -> StdResult<Vec<String>, CustomUserError> {
+        Ok(self.suggest(input))
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted_suggestion: Option<String>,
+    ) -> StdResult<Replacement, CustomUserError> {
+        if highlighted_suggestion.is_some() {
+            return Ok(highlighted_suggestion);
+        }
+        let mut suggestions = self.suggest(input);
+        Ok((suggestions.len() == 1).then(|| suggestions.remove(0)))
+    }
+}
+
+/// Interactive prompt for `skills shell`.
+#[derive(Debug)]
+pub struct Shell {
+    /// Completion source for the prompt.
+    completer: Completer,
+}
+
+impl Shell {
+    /// Create a prompt that completes the given subcommand names.
+    pub fn new<'a>(commands: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut commands: Vec<String> = commands
+            .into_iter()
+            .chain(EXIT_WORDS)
+            .map(str::to_string)
+            .collect();
+        commands.sort();
+        commands.dedup();
+        Self {
+            completer: Completer {
+                commands,
+                skills: Vec::new(),
+            },
+        }
+    }
+
+    /// Complete skill names from a loaded catalog.
+    pub fn set_skills(&mut self, catalog: &Catalog) {
+        let names: BTreeSet<&String> = catalog
+            .sources
+            .keys()
+            .chain(catalog.tools.values().flat_map(|skills| skills.keys()))
+            .chain(catalog.local.values().flat_map(|skills| skills.keys()))
+            .collect();
+        self.completer.skills = names.into_iter().cloned().collect();
+    }
+
+    /// Read the next command line, split into arguments.
+    ///
+    /// Returns `None` when the session ends, through an exit word or Ctrl-C, and an empty list
+    /// for blank or unparseable lines, whose problem has already been printed.
+    pub fn read(&self) -> Result<Option<Vec<String>>> {
+        let line = match Text::new("skills>")
+            .with_autocomplete(self.completer.clone())
+            .prompt()
+        {
+            Ok(line) => line,
+            Err(InquireError::OperationCanceled) => return Ok(Some(Vec::new())),
+            Err(InquireError::OperationInterrupted) => return Ok(None),
+            Err(error) => {
+                return Err(Error::PromptFailed {
+                    message: error.to_string(),
+                });
+            }
+        };
+        parse_line(&line)
+    }
+}
+
+/// Split a shell line into arguments, returning `None` for an exit word.
+fn parse_line(line: &str) -> Result<Option<Vec<String>>> {
+    let line = line.trim();
+    if EXIT_WORDS.contains(&line) {
+        return Ok(None);
+    }
+    match shell_words::split(line) {
+        Ok(words) => Ok(Some(words)),
+        Err(error) => {
+            eprintln!("{error}");
+            Ok(Some(Vec::new()))
+        }
+    }
+}
+
+/// Print a command's error and its hint, if it has one, to stderr.
+///
+/// Differences found by `diff --exit-code` are a result rather than a failure, so only the exit
+/// status reports them.
+pub fn report(error: &Error, palette: &Palette) {
+    if matches!(error, Error::Differences { .. }) {
+        return;
+    }
+    eprintln!("{error}");
+    if let Some(hint) = error.hint() {
+        eprintln!("{} {hint}", palette.paint(Role::Label, "hint:"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shell, parse_line};
+    use crate::{
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[test]
+    fn completes_commands_then_skill_names() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_tool_skill(Tool::Codex, "alpine", &simple_skill("alpine"));
+        let mut shell = Shell::new(["push", "pull", "list"]);
+        shell.set_skills(&fixture.catalog());
+        let completer = &shell.completer;
+
+        assert_eq!(completer.suggest("pu"), ["pull", "push"]);
+        assert_eq!(completer.suggest("ex"), ["exit"]);
+        assert!(completer.suggest("").is_empty());
+        assert_eq!(completer.suggest("push al"), ["push alpha", "push alpine"]);
+        assert_eq!(completer.suggest("push alpha alpi"), ["push alpha alpine"]);
+        assert_eq!(completer.suggest("push ").len(), 2);
+    }
+
+    #[test]
+    fn splits_lines_and_recognizes_exit_words() {
+        assert_eq!(
+            parse_line("show 'my skill'").expect("parse"),
+            Some(vec!["show".to_string(), "my skill".to_string()])
+        );
+        assert_eq!(parse_line("  quit ").expect("parse"), None);
+        assert_eq!(parse_line("show 'open").expect("parse"), Some(Vec::new()));
+    }
+}
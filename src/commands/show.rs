@@ -2,43 +2,98 @@
 
 use std::fs;
 
-use owo_colors::OwoColorize;
-
 use crate::{
     catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
+    commands::init,
+    context::Context,
     diff::{resolve_pager, write_output},
     error::{Error, Result},
-    skill::SKILL_FILE_NAME,
+    history::load_skill_file,
+    layout::pad,
+    palette::{Palette, Role},
+    paths::display_path,
 };
 
 /// Execute the show command.
-pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
-    skill: String,
-    pager: Option<String>,
-) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+pub async fn run(ctx: &Context, skill: String, pager: Option<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let pager = resolve_pager(pager.as_deref(), ctx.overrides.pager.as_deref());
+    let palette = ctx.palette(&config);
 
     // Look for the skill in sources first, then tools, then local
+    let skill = catalog.resolve_name(&skill);
     let contents = find_skill_contents(&catalog, &skill)?;
 
-    let output = if use_color {
-        highlight_markdown(&contents)
+    let output = if palette.enabled() {
+        highlight_markdown(&contents, &palette)
     } else {
         contents
     };
 
-    let pager = resolve_pager(pager.as_deref());
     write_output(&output, pager.as_deref())?;
+    diagnostics.print_summary();
+
+    Ok(())
+}
+
+/// Execute the show command for a snapshot, displaying a tool copy as it was before the
+/// operation `at` selects.
+pub async fn run_at(ctx: &Context, skill: String, at: String, pager: Option<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let pager = resolve_pager(pager.as_deref(), ctx.overrides.pager.as_deref());
+    let palette = ctx.palette(&config);
+    let state = ctx.load_state()?;
+
+    let file = load_skill_file(&state, &ctx.state_path, &skill, &at, &config.skill_files)?;
+    let (Some(path), Some(contents)) = (file.path, file.contents) else {
+        println!(
+            "'{}' had no copy in {} before {}.",
+            skill,
+            display_path(&file.snapshot.dir),
+            file.snapshot.describe()
+        );
+        return Ok(());
+    };
+    eprintln!(
+        "{} as it was before {}",
+        palette.paint(Role::Path, &display_path(&path)),
+        file.snapshot.describe()
+    );
+
+    let output = if palette.enabled() {
+        highlight_markdown(&contents, &palette)
+    } else {
+        contents
+    };
+    write_output(&output, pager.as_deref())
+}
 
+/// Execute the show command's history listing: every snapshot of a skill's tool copies.
+pub async fn run_history(ctx: &Context, skill: String) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let dates = ctx.dates(&config);
+    let state = ctx.load_state()?;
+
+    let mut snapshots = state.snapshots_of(&skill).peekable();
+    if snapshots.peek().is_none() {
+        println!("No snapshots of '{skill}'.");
+        return Ok(());
+    }
+    for snapshot in snapshots {
+        println!(
+            "{:>5}  {}  {}  {}",
+            format!("#{}", snapshot.id),
+            dates.time(snapshot.taken_at),
+            palette.paint(Role::Action, &pad(snapshot.origin.label(), 6)),
+            palette.paint(Role::Path, &display_path(&snapshot.dir))
+        );
+    }
     Ok(())
 }
 
@@ -52,14 +107,14 @@ fn find_skill_contents(catalog: &Catalog, name: &str) -> Result<String> {
     // Check tool skills (any tool)
     for tool_skills in catalog.tools.values() {
         if let Some(skill) = tool_skills.get(name) {
-            return Ok(skill.contents.clone());
+            return skill.read_contents();
         }
     }
 
     // Check local skills
     for local_skills in catalog.local.values() {
         if let Some(skill) = local_skills.get(name) {
-            let skill_path = skill.skill_dir.join(SKILL_FILE_NAME);
+            let skill_path = skill.skill_path.clone();
             return fs::read_to_string(&skill_path).map_err(|e| Error::SkillRead {
                 path: skill_path,
                 source: e,
@@ -73,7 +128,7 @@ fn find_skill_contents(catalog: &Catalog, name: &str) -> Result<String> {
 }
 
 /// Highlight markdown content with syntax coloring.
-fn highlight_markdown(contents: &str) -> String {
+fn highlight_markdown(contents: &str, palette: &Palette) -> String {
     let mut output = String::new();
     let mut in_code_block = false;
     let mut in_frontmatter = false;
@@ -85,19 +140,19 @@ fn highlight_markdown(contents: &str) -> String {
             frontmatter_count += 1;
             if frontmatter_count == 1 {
                 in_frontmatter = true;
-                output.push_str(&line.dimmed().to_string());
+                output.push_str(&palette.paint(Role::Frontmatter, line));
                 output.push('\n');
                 continue;
             } else if frontmatter_count == 2 {
                 in_frontmatter = false;
-                output.push_str(&line.dimmed().to_string());
+                output.push_str(&palette.paint(Role::Frontmatter, line));
                 output.push('\n');
                 continue;
             }
         }
 
         if in_frontmatter {
-            output.push_str(&line.dimmed().to_string());
+            output.push_str(&palette.paint(Role::Frontmatter, line));
             output.push('\n');
             continue;
         }
@@ -105,20 +160,20 @@ fn highlight_markdown(contents: &str) -> String {
         // Handle code blocks
         if line.starts_with("```") {
             in_code_block = !in_code_block;
-            output.push_str(&line.cyan().to_string());
+            output.push_str(&paint_with_templates(line, Role::Code, palette));
             output.push('\n');
             continue;
         }
 
         if in_code_block {
-            output.push_str(&line.cyan().to_string());
+            output.push_str(&paint_with_templates(line, Role::Code, palette));
             output.push('\n');
             continue;
         }
 
         // Handle headers
         if line.starts_with('#') {
-            output.push_str(&line.bold().white().to_string());
+            output.push_str(&paint_with_templates(line, Role::MarkdownHeading, palette));
             output.push('\n');
             continue;
         }
@@ -126,8 +181,8 @@ fn highlight_markdown(contents: &str) -> String {
         // Handle bullet points
         if line.starts_with("- ") || line.starts_with("* ") {
             let (bullet, rest) = line.split_at(2);
-            output.push_str(&bullet.blue().to_string());
-            output.push_str(&format_inline(rest));
+            output.push_str(&palette.paint(Role::Bullet, bullet));
+            output.push_str(&format_inline(rest, palette));
             output.push('\n');
             continue;
         }
@@ -137,8 +192,8 @@ fn highlight_markdown(contents: &str) -> String {
             let prefix = &line[..pos];
             if prefix.chars().all(|c| c.is_ascii_digit()) {
                 let number_part: String = line[..=pos].to_string();
-                output.push_str(&number_part.blue().to_string());
-                output.push_str(&format_inline(&line[pos + 2..]));
+                output.push_str(&palette.paint(Role::Bullet, &number_part));
+                output.push_str(&format_inline(&line[pos + 2..], palette));
                 output.push('\n');
                 continue;
             }
@@ -146,13 +201,13 @@ fn highlight_markdown(contents: &str) -> String {
 
         // Handle blockquotes
         if line.starts_with('>') {
-            output.push_str(&line.yellow().to_string());
+            output.push_str(&paint_with_templates(line, Role::Quote, palette));
             output.push('\n');
             continue;
         }
 
         // Regular text with inline formatting
-        output.push_str(&format_inline(line));
+        output.push_str(&format_inline(line, palette));
         output.push('\n');
     }
 
@@ -165,18 +220,26 @@ fn highlight_markdown(contents: &str) -> String {
 }
 
 /// Format a line with inline markdown formatting (bold, italic, code, links).
-fn format_inline(text: &str) -> String {
+fn format_inline(text: &str, palette: &Palette) -> String {
     let chars: Vec<char> = text.chars().collect();
     let mut output = String::new();
     let mut i = 0;
 
     while i < chars.len() {
+        // Handle template tags
+        if let Some(end) = template_tag_end(&chars, i) {
+            let tag: String = chars[i..end].iter().collect();
+            output.push_str(&palette.paint(Role::Template, &tag));
+            i = end;
+            continue;
+        }
+
         // Handle inline code
         if chars[i] == '`'
             && let Some(end) = find_closing(&chars, i + 1, '`')
         {
             let code: String = chars[i + 1..end].iter().collect();
-            output.push_str(&format!("`{code}`").cyan().to_string());
+            output.push_str(&palette.paint(Role::Code, &format!("`{code}`")));
             i = end + 1;
             continue;
         }
@@ -188,7 +251,7 @@ fn format_inline(text: &str) -> String {
             && let Some(end) = find_double_closing(&chars, i + 2, '*')
         {
             let bold: String = chars[i + 2..end].iter().collect();
-            output.push_str(&bold.bold().to_string());
+            output.push_str(&palette.paint(Role::Strong, &bold));
             i = end + 2;
             continue;
         }
@@ -198,7 +261,7 @@ fn format_inline(text: &str) -> String {
             && let Some(end) = find_closing(&chars, i + 1, '*')
         {
             let italic: String = chars[i + 1..end].iter().collect();
-            output.push_str(&italic.italic().to_string());
+            output.push_str(&palette.paint(Role::Emphasis, &italic));
             i = end + 1;
             continue;
         }
@@ -212,8 +275,8 @@ fn format_inline(text: &str) -> String {
         {
             let link_text: String = chars[i + 1..bracket_end].iter().collect();
             let url: String = chars[bracket_end + 2..paren_end].iter().collect();
-            output.push_str(&link_text.blue().underline().to_string());
-            output.push_str(&format!(" ({url})").dimmed().to_string());
+            output.push_str(&palette.paint(Role::Link, &link_text));
+            output.push_str(&palette.paint(Role::LinkUrl, &format!(" ({url})")));
             i = paren_end + 1;
             continue;
         }
@@ -225,6 +288,64 @@ fn format_inline(text: &str) -> String {
     output
 }
 
+/// Paint a line in one role, with any template tags in it painted as templates.
+fn paint_with_templates(line: &str, role: Role, palette: &Palette) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut output = String::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(end) = template_tag_end(&chars, i) {
+            let text: String = chars[start..i].iter().collect();
+            let tag: String = chars[i..end].iter().collect();
+            if !text.is_empty() {
+                output.push_str(&palette.paint(role, &text));
+            }
+            output.push_str(&palette.paint(Role::Template, &tag));
+            i = end;
+            start = end;
+        } else {
+            i += 1;
+        }
+    }
+    let text: String = chars[start..].iter().collect();
+    if !text.is_empty() {
+        output.push_str(&palette.paint(role, &text));
+    }
+    output
+}
+
+/// Return the index just past a `{% %}`, `{{ }}`, or `{# #}` tag starting at `start`.
+fn template_tag_end(chars: &[char], start: usize) -> Option<usize> {
+    if chars.get(start) != Some(&'{') {
+        return None;
+    }
+    let close = match chars.get(start + 1) {
+        Some('%') => '%',
+        Some('{') => '}',
+        Some('#') => '#',
+        _ => return None,
+    };
+    find_double_closing_pair(chars, start + 2, close, '}').map(|end| end + 2)
+}
+
+/// Find a closing two-character delimiter such as `%}` in a char slice.
+fn find_double_closing_pair(
+    chars: &[char],
+    start: usize,
+    first: char,
+    second: char,
+) -> Option<usize> {
+    let mut i = start;
+    while i + 1 < chars.len() {
+        if chars[i] == first && chars[i + 1] == second {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
 /// Find closing delimiter in char slice.
 fn find_closing(chars: &[char], start: usize, delim: char) -> Option<usize> {
     for (i, &c) in chars.iter().enumerate().skip(start) {
@@ -237,12 +358,5 @@ fn find_closing(chars: &[char], start: usize, delim: char) -> Option<usize> {
 
 /// Find closing double delimiter (like **) in char slice.
 fn find_double_closing(chars: &[char], start: usize, delim: char) -> Option<usize> {
-    let mut i = start;
-    while i + 1 < chars.len() {
-        if chars[i] == delim && chars[i + 1] == delim {
-            return Some(i);
-        }
-        i += 1;
-    }
-    None
+    find_double_closing_pair(chars, start, delim, delim)
 }
@@ -0,0 +1,381 @@
+//! Implementation of the `skills split` command.
+//!
+//! Splitting is the inverse of composing: chosen top-level sections of a skill body become skills
+//! of their own in the same source, and each section in the original is cut down to its heading
+//! and a pointer to the new skill.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use inquire::{
+    MultiSelect, Text,
+    error::{InquireError, InquireResult},
+};
+
+use crate::{
+    catalog::Catalog,
+    commands::{init, new::default_fields},
+    config::NewSkillConfig,
+    context::Context,
+    error::{Error, Result},
+    format::{headings, shift_headings},
+    frontmatter::{strip_frontmatter, yaml_scalar},
+    name::normalize_name,
+    palette::Role,
+    paths::display_path,
+    skill::render_template_located,
+    tool::Tool,
+};
+
+/// A top-level section of a skill body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+    /// Heading text.
+    title: String,
+    /// Heading level.
+    level: usize,
+    /// Index of the heading line in the body.
+    start: usize,
+    /// Index of the first line after the section.
+    end: usize,
+}
+
+/// A section chosen to become a skill.
+#[derive(Debug, Clone)]
+struct Pick {
+    /// Index of the section.
+    section: usize,
+    /// Name of the new skill.
+    name: String,
+    /// Description of the new skill.
+    description: String,
+}
+
+/// Execute the split command, prompting for the sections of `skill` to move into new skills and
+/// for each new skill's name and description.
+pub async fn run(ctx: &Context, skill: String, dry_run: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let name = catalog.resolve_name(&skill);
+    let source = catalog
+        .sources
+        .get(&name)
+        .ok_or_else(|| Error::SkillNotFound { name: name.clone() })?;
+    let failed = |message: &str| Error::SplitFailed {
+        name: name.clone(),
+        message: message.to_string(),
+    };
+    if source.contents.contains("{% extends") {
+        return Err(failed(
+            "it extends a shared template, so its blocks cannot be moved",
+        ));
+    }
+    let sections = sections(strip_frontmatter(&source.contents));
+    if sections.len() < 2 {
+        return Err(failed("it has fewer than two top-level sections"));
+    }
+    if !io::stdin().is_terminal() {
+        return Err(Error::PromptFailed {
+            message: "choosing sections to split needs an interactive terminal".to_string(),
+        });
+    }
+
+    let labels: Vec<String> = sections
+        .iter()
+        .map(|section| {
+            let length = section.end - section.start;
+            format!("{} ({length} lines)", section.title)
+        })
+        .collect();
+    let chosen =
+        prompt(MultiSelect::new("Split which sections into skills?", labels).raw_prompt())?;
+    if chosen.is_empty() {
+        println!("Nothing selected.");
+        return Ok(());
+    }
+    let mut picks: Vec<Pick> = Vec::new();
+    for option in chosen {
+        let section = &sections[option.index];
+        let taken: BTreeSet<&str> = picks.iter().map(|pick| pick.name.as_str()).collect();
+        let name = prompt_name(&catalog, &source.source_root, section, &taken)?;
+        let description = prompt_description(&name)?;
+        picks.push(Pick {
+            section: option.index,
+            name,
+            description,
+        });
+    }
+
+    let (original, created) = split(&source.contents, &sections, &picks, &config.new_skill);
+    for contents in created
+        .iter()
+        .map(|(_, contents)| contents)
+        .chain([&original])
+    {
+        for tool in Tool::all() {
+            if let Err((_, error)) = render_template_located(contents, tool, &source.bases) {
+                return Err(failed(&format!(
+                    "a split file would not render for {}: {error}",
+                    tool.id()
+                )));
+            }
+        }
+    }
+
+    let file_name = config.skill_files.primary();
+    for (new_name, contents) in &created {
+        let dir = source.source_root.join(new_name);
+        let path = dir.join(file_name);
+        println!(
+            "{} {} at {}",
+            palette.paint(
+                Role::Action,
+                if dry_run { "Would create" } else { "Created" }
+            ),
+            palette.paint(Role::SkillName, new_name),
+            display_path(&path)
+        );
+        if dry_run {
+            continue;
+        }
+        fs::create_dir_all(&dir).map_err(|source| Error::SkillWrite {
+            path: dir.clone(),
+            source,
+        })?;
+        fs::write(&path, contents).map_err(|source| Error::SkillWrite { path, source })?;
+    }
+    println!(
+        "{} {}",
+        palette.paint(
+            Role::Action,
+            if dry_run { "Would update" } else { "Updated" }
+        ),
+        display_path(&source.skill_path)
+    );
+    if !dry_run {
+        fs::write(&source.skill_path, original).map_err(|error| Error::SkillWrite {
+            path: source.skill_path.clone(),
+            source: error,
+        })?;
+        println!();
+        println!("Run `skills push` to install the new skills and the shortened '{name}'.");
+    }
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Find the top-level sections of a skill body.
+///
+/// Sections start at the shallowest heading level, or at the next level down when a single title
+/// heading sits above everything else. Each runs until the next heading at its level or above.
+fn sections(body: &str) -> Vec<Section> {
+    let found = headings(body);
+    let levels: BTreeSet<usize> = found.iter().map(|(_, level, _)| *level).collect();
+    let mut levels = levels.into_iter();
+    let Some(top) = levels.next() else {
+        return Vec::new();
+    };
+    let titles = found.iter().filter(|(_, level, _)| *level == top).count();
+    let level = match levels.next() {
+        Some(next) if titles == 1 && found[0].1 == top => next,
+        _ => top,
+    };
+    let total = body.lines().count();
+    let starts: Vec<&(usize, usize, &str)> = found
+        .iter()
+        .filter(|(_, heading, _)| *heading == level)
+        .collect();
+    starts
+        .iter()
+        .map(|(start, _, title)| {
+            let end = found
+                .iter()
+                .find(|(index, heading, _)| index > start && *heading <= level)
+                .map_or(total, |(index, ..)| *index);
+            Section {
+                title: title.to_string(),
+                level,
+                start: *start,
+                end,
+            }
+        })
+        .collect()
+}
+
+/// Split the picked sections out of a skill file.
+///
+/// Returns the rewritten skill file, where each picked section keeps only its heading and a
+/// pointer to its new skill, and the name and contents of each new skill. A new skill's body is
+/// its section with headings moved up so the section heading is the title.
+fn split(
+    contents: &str,
+    sections: &[Section],
+    picks: &[Pick],
+    defaults: &NewSkillConfig,
+) -> (String, Vec<(String, String)>) {
+    let body = strip_frontmatter(contents);
+    let frontmatter = &contents[..contents.len() - body.len()];
+    let lines: Vec<&str> = body.lines().collect();
+    let mut original = Vec::new();
+    let mut created = Vec::new();
+    let mut next = 0;
+    for (index, section) in sections.iter().enumerate() {
+        let Some(pick) = picks.iter().find(|pick| pick.section == index) else {
+            continue;
+        };
+        original.extend(
+            lines[next..=section.start]
+                .iter()
+                .map(|line| line.to_string()),
+        );
+        original.push(String::new());
+        original.push(format!("See the `{}` skill.", pick.name));
+        original.push(String::new());
+        next = section.end;
+
+        let text = lines[section.start..section.end].join("\n");
+        let by = 1 - section.level as isize;
+        created.push((
+            pick.name.clone(),
+            format!(
+                "---\nname: {}\ndescription: {}\n{}---\n\n{}\n",
+                pick.name,
+                yaml_scalar(&pick.description),
+                default_fields(defaults),
+                shift_headings(text.trim_end(), by)
+            ),
+        ));
+    }
+    original.extend(lines[next..].iter().map(|line| line.to_string()));
+    let mut body = original.join("\n");
+    let trimmed = body.trim_end().len();
+    body.truncate(trimmed);
+    (format!("{frontmatter}{body}\n"), created)
+}
+
+/// Ask for the name of the skill a section becomes, until one is usable.
+fn prompt_name(
+    catalog: &Catalog,
+    source_root: &Path,
+    section: &Section,
+    taken: &BTreeSet<&str>,
+) -> Result<String> {
+    let suggested = slug(&section.title);
+    loop {
+        let answer = prompt(
+            Text::new(&format!("Name for '{}'", section.title))
+                .with_default(&suggested)
+                .prompt(),
+        )?;
+        let name = match normalize_name(&answer) {
+            Ok(name) => name,
+            Err(error) => {
+                eprintln!("Invalid skill name '{answer}': {error}");
+                continue;
+            }
+        };
+        if catalog.sources.contains_key(&name) || taken.contains(name.as_str()) {
+            eprintln!("A skill named '{name}' already exists.");
+        } else if source_root.join(&name).exists() {
+            eprintln!("A directory named '{name}' already exists in the source.");
+        } else {
+            return Ok(name);
+        }
+    }
+}
+
+/// Ask for the description of a new skill, until one is given.
+fn prompt_description(name: &str) -> Result<String> {
+    loop {
+        let answer = prompt(Text::new(&format!("Description for '{name}'")).prompt())?;
+        let answer = answer.trim();
+        if !answer.is_empty() {
+            return Ok(answer.to_string());
+        }
+        eprintln!("A description is required; it is how agents choose the skill.");
+    }
+}
+
+/// Map a prompt's answer, treating cancellation as [`Error::PromptCanceled`].
+fn prompt<T>(answer: InquireResult<T>) -> Result<T> {
+    match answer {
+        Ok(value) => Ok(value),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+/// Suggest a skill name for a section title: lowercase words joined by hyphens.
+fn slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pick, sections, slug, split};
+    use crate::config::NewSkillConfig;
+
+    #[test]
+    fn finds_sections_below_a_title() {
+        let body = "\n# PDF\n\nIntro.\n\n## Reading\n\n```sh\n# not a heading\n```\n\n### Tables\n\n## Forms\n\nFill them.\n";
+        let found: Vec<(String, usize, usize)> = sections(body)
+            .into_iter()
+            .map(|section| (section.title, section.start, section.end))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Reading".to_string(), 5, 13),
+                ("Forms".to_string(), 13, 16)
+            ]
+        );
+        assert_eq!(sections("# One\n\n# Two\n").len(), 2);
+        assert_eq!(slug("Reading & Writing PDFs"), "reading-writing-pdfs");
+    }
+
+    #[test]
+    fn moves_picked_sections_into_new_skills() {
+        let contents = "---\nname: pdf\ndescription: PDFs\n---\n\n# PDF\n\n## Reading\n\nUse pdftotext.\n\n### Tables\n\nUse camelot.\n\n## Forms\n\nFill them.\n";
+        let body = "\n# PDF\n\n## Reading\n\nUse pdftotext.\n\n### Tables\n\nUse camelot.\n\n## Forms\n\nFill them.\n";
+        let picks = [Pick {
+            section: 0,
+            name: "pdf-reading".to_string(),
+            description: "Extract text: tables too".to_string(),
+        }];
+        let defaults = NewSkillConfig {
+            version: Some("0.1.0".to_string()),
+            ..NewSkillConfig::default()
+        };
+
+        let (original, created) = split(contents, &sections(body), &picks, &defaults);
+        assert_eq!(
+            original,
+            "---\nname: pdf\ndescription: PDFs\n---\n\n# PDF\n\n## Reading\n\nSee the `pdf-reading` skill.\n\n## Forms\n\nFill them.\n"
+        );
+        assert_eq!(
+            created,
+            [(
+                "pdf-reading".to_string(),
+                "---\nname: pdf-reading\ndescription: 'Extract text: tables too'\nversion: 0.1.0\n---\n\n# Reading\n\nUse pdftotext.\n\n## Tables\n\nUse camelot.\n"
+                    .to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Implementation of the `skills stats` command.
+
+use crate::{
+    commands::{init, validate::print_similar},
+    context::Context,
+    error::Result,
+    layout::pad,
+    palette::Role,
+    similarity::similar_descriptions,
+    store::DirStats,
+    tool::Tool,
+    units::format_count,
+};
+
+/// Display columns taken by the labels in the summary.
+const LABEL_WIDTH: usize = 13;
+
+/// Execute the stats command, summarizing the catalog and reporting skills whose descriptions
+/// are too alike for an agent to choose between.
+pub async fn run(ctx: &Context) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    if catalog.sources.is_empty() {
+        println!("No source skills.");
+        diagnostics.print_summary();
+        return Ok(());
+    }
+
+    let total = catalog
+        .sources
+        .values()
+        .fold(DirStats::default(), |total, skill| DirStats {
+            size: total.size + skill.stats.size,
+            files: total.files + skill.stats.files,
+        });
+    println!(
+        "{} {}, {}",
+        palette.paint(Role::Label, &pad("sources:", LABEL_WIDTH)),
+        format_count(catalog.sources.len(), "skill"),
+        total.summary()
+    );
+
+    let words: Vec<usize> = catalog
+        .sources
+        .values()
+        .map(|skill| skill.description.split_whitespace().count())
+        .collect();
+    let shortest = words.iter().min().copied().unwrap_or_default();
+    let longest = words.iter().max().copied().unwrap_or_default();
+    println!(
+        "{} {} to {} words, {} on average",
+        palette.paint(Role::Label, &pad("descriptions:", LABEL_WIDTH)),
+        shortest,
+        longest,
+        (words.iter().sum::<usize>() as f64 / words.len() as f64).round()
+    );
+
+    for tool in Tool::all() {
+        if let Some(skills) = catalog.tools.get(&tool) {
+            println!(
+                "{} {} installed",
+                palette.paint(Role::Label, &pad(&format!("{}:", tool.id()), LABEL_WIDTH)),
+                format_count(skills.len(), "skill")
+            );
+        }
+    }
+
+    let descriptions: Vec<(&str, &str)> = catalog
+        .sources
+        .iter()
+        .map(|(name, skill)| (name.as_str(), skill.description.as_str()))
+        .collect();
+    let similar = similar_descriptions(&descriptions, None);
+    println!();
+    if similar.is_empty() {
+        println!("No similar descriptions.");
+    } else {
+        println!(
+            "{}",
+            palette.paint(Role::WarningHeading, "Similar descriptions:")
+        );
+        print_similar(&similar, &palette);
+    }
+    diagnostics.print_summary();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::{
+        testutil::{TestFixture, skill_content},
+        tool::Tool,
+    };
+
+    #[tokio::test]
+    async fn run_reports_similar_descriptions() {
+        let fixture = TestFixture::new()
+            .with_source_skill(
+                "pdf-tools",
+                &skill_content(
+                    "pdf-tools",
+                    "Extract text and tables from PDF files.",
+                    "Body",
+                ),
+            )
+            .with_source_skill(
+                "pdf-reader",
+                &skill_content("pdf-reader", "Extract text and tables from a PDF.", "Body"),
+            )
+            .with_tool_skill(
+                Tool::Claude,
+                "pdf-tools",
+                &skill_content(
+                    "pdf-tools",
+                    "Extract text and tables from PDF files.",
+                    "Body",
+                ),
+            );
+
+        run(&fixture.context()).await.expect("stats");
+    }
+}
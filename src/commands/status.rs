@@ -0,0 +1,407 @@
+//! Implementation of the `skills status` command.
+
+use inquire::{Confirm, error::InquireError};
+
+use crate::{
+    catalog::Catalog,
+    commands::{
+        init, push,
+        sync::{SyncAction, SyncPlan, apply_plan, build_sync_plans},
+    },
+    config::{LineEndings, SyncDirection},
+    context::Context,
+    error::{Error, Result},
+    palette::{Palette, Role},
+    state::State,
+    status::{OrphanKind, SkillEntry, SyncStatus, build_entries},
+    tool::Tool,
+};
+
+/// Indent for skills within a group.
+const INDENT: &str = "    ";
+
+/// What a skill needs to get back in sync, in the order groups are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Group {
+    /// Tool copies were edited differently and sync cannot pick one.
+    Conflict,
+    /// The source should be pushed to tools.
+    NeedsPush,
+    /// A tool copy should be pulled into the source.
+    NeedsPull,
+    /// Installed copies have no source.
+    Orphan,
+    /// Every tool copy matches the source.
+    Synced,
+}
+
+impl Group {
+    /// Every group, in print order.
+    const ALL: [Self; 5] = [
+        Self::Conflict,
+        Self::NeedsPush,
+        Self::NeedsPull,
+        Self::Orphan,
+        Self::Synced,
+    ];
+
+    /// Return the heading printed above the group.
+    fn heading(self) -> &'static str {
+        match self {
+            Self::Conflict => "Conflicts:",
+            Self::NeedsPush => "Needs push:",
+            Self::NeedsPull => "Needs pull:",
+            Self::Orphan => "Orphans:",
+            Self::Synced => "Synced:",
+        }
+    }
+
+    /// Return the palette role the group's heading is painted with.
+    fn role(self) -> Role {
+        match self {
+            Self::Conflict | Self::Orphan => SyncStatus::Orphan.role(),
+            Self::NeedsPush | Self::NeedsPull => SyncStatus::Modified.role(),
+            Self::Synced => SyncStatus::Synced.role(),
+        }
+    }
+}
+
+/// A skill's sync state and what would fix it.
+#[derive(Debug)]
+struct Item<'a> {
+    /// Skill name.
+    name: String,
+    /// Group the skill is listed under.
+    group: Group,
+    /// Description of the required action.
+    detail: String,
+    /// Sync plan for tool copies that differ from the source.
+    plan: Option<&'a SyncPlan>,
+    /// Tools with no copy of a source skill.
+    missing: Vec<Tool>,
+    /// How an installed skill without a source came about.
+    orphan: Option<OrphanKind>,
+}
+
+/// Execute the status command.
+///
+/// Only skills that are out of sync are listed unless `all` is set. With `fix`, each skill that
+/// needs a push or pull is offered for fixing, one at a time.
+pub async fn run(ctx: &Context, all: bool, fix: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let direction = config.sync.default;
+    let entries = build_entries(&catalog, &mut diagnostics);
+    let plans = build_sync_plans(&catalog, direction, &mut diagnostics)?;
+    let state = ctx.load_state()?;
+    let items = classify(&catalog, &state, &entries, &plans, direction);
+
+    let shown: Vec<&Item<'_>> = items
+        .iter()
+        .filter(|item| all || item.group != Group::Synced)
+        .collect();
+    if shown.iter().all(|item| item.group == Group::Synced) {
+        println!("All skills are in sync.");
+    }
+    print_groups(&shown, &palette);
+
+    if fix {
+        fix_items(ctx, &catalog, state, &items, config.line_endings)?;
+    }
+    diagnostics.print_summary();
+    Ok(())
+}
+
+/// Group every skill by the action it needs, telling orphans apart by the install records in
+/// `state`.
+fn classify<'a>(
+    catalog: &Catalog,
+    state: &State,
+    entries: &[SkillEntry],
+    plans: &'a [SyncPlan],
+    direction: SyncDirection,
+) -> Vec<Item<'a>> {
+    let mut items: Vec<Item<'a>> = entries
+        .iter()
+        .map(|entry| {
+            let missing: Vec<Tool> = entry
+                .tool_statuses
+                .iter()
+                .filter(|status| status.status == SyncStatus::Missing)
+                .map(|status| status.tool)
+                .collect();
+            let plan = plans.iter().find(|plan| plan.name == entry.name);
+            let orphan = (plan.is_none() && entry.overall() == SyncStatus::Orphan)
+                .then(|| OrphanKind::of(catalog, state, &entry.name));
+            let (group, detail) = match (plan, orphan) {
+                (Some(plan), _) if direction != SyncDirection::Push && plan.is_conflicted() => (
+                    Group::Conflict,
+                    format!(
+                        "tool copies differ: {}",
+                        tool_list(&sorted_tools(plan.tool_skills.keys().copied()))
+                    ),
+                ),
+                (Some(plan), _) => describe_action(&plan.action, &missing),
+                (None, Some(kind)) => {
+                    let tools: Vec<Tool> = entry
+                        .tool_statuses
+                        .iter()
+                        .map(|status| status.tool)
+                        .collect();
+                    (
+                        Group::Orphan,
+                        format!(
+                            "installed in {}, {}; {}",
+                            tool_list(&tools),
+                            kind.label(),
+                            kind.suggestion(&entry.name)
+                        ),
+                    )
+                }
+                (None, None) if !missing.is_empty() => (
+                    Group::NeedsPush,
+                    format!("source -> {} (missing)", tool_list(&missing)),
+                ),
+                (None, None) => (Group::Synced, String::new()),
+            };
+            Item {
+                name: entry.name.clone(),
+                group,
+                detail,
+                plan,
+                missing,
+                orphan,
+            }
+        })
+        .collect();
+    items.sort_by_key(|item| item.group);
+    items
+}
+
+/// Describe a sync action, and tools missing the skill, as a group and detail line.
+fn describe_action(action: &SyncAction, missing: &[Tool]) -> (Group, String) {
+    let missing_note = if missing.is_empty() {
+        String::new()
+    } else {
+        format!(" (missing in {})", tool_list(missing))
+    };
+    match action {
+        SyncAction::Push { to_tools } => (
+            Group::NeedsPush,
+            format!(
+                "source -> {}{missing_note}",
+                tool_list(&sorted_tools(to_tools.iter().copied()))
+            ),
+        ),
+        SyncAction::Pull { from_tool } => (
+            Group::NeedsPull,
+            format!("{} -> source{missing_note}", from_tool.id()),
+        ),
+        SyncAction::PullAndPush {
+            from_tool,
+            to_tools,
+        } => (
+            Group::NeedsPull,
+            format!(
+                "{} -> source -> {}{missing_note}",
+                from_tool.id(),
+                tool_list(&sorted_tools(to_tools.iter().copied()))
+            ),
+        ),
+    }
+}
+
+/// Print each non-empty group under its heading.
+fn print_groups(items: &[&Item<'_>], palette: &Palette) {
+    let width = items
+        .iter()
+        .map(|item| item.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut first = true;
+    for group in Group::ALL {
+        let members: Vec<&&Item<'_>> = items.iter().filter(|item| item.group == group).collect();
+        if members.is_empty() {
+            continue;
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("{}", palette.paint(group.role(), group.heading()));
+        for item in members {
+            let name = format!("{:<width$}", item.name);
+            println!(
+                "{INDENT}{}  {}",
+                palette.paint(Role::SkillName, &name),
+                item.detail
+            );
+        }
+    }
+}
+
+/// Offer each skill needing a push or pull for fixing, applying confirmed fixes immediately.
+///
+/// Conflicts and orphans need a decision between copies, so they are only pointed at the commands
+/// that make it, such as `skills resolve`.
+fn fix_items(
+    ctx: &Context,
+    catalog: &Catalog,
+    mut state: State,
+    items: &[Item<'_>],
+    line_endings: LineEndings,
+) -> Result<()> {
+    let mut fixed = 0;
+    for item in items {
+        match item.group {
+            Group::NeedsPush | Group::NeedsPull => {}
+            Group::Conflict => {
+                println!(
+                    "{}: choose a copy with `skills resolve {}`",
+                    item.name, item.name
+                );
+                continue;
+            }
+            Group::Orphan => {
+                if let Some(kind) = item.orphan {
+                    println!("{}: {}", item.name, kind.suggestion(&item.name));
+                }
+                continue;
+            }
+            Group::Synced => continue,
+        }
+        if !confirm(&format!("Fix {}: {}?", item.name, item.detail))? {
+            continue;
+        }
+        if let Some(plan) = item.plan {
+            apply_plan(ctx, plan, line_endings, &mut state)?;
+        }
+        if let Some(source) = catalog.sources.get(&item.name) {
+            for &tool in &item.missing {
+                push::install_missing(ctx, catalog, source, tool, line_endings, &mut state)?;
+            }
+        }
+        fixed += 1;
+    }
+    state.save(&ctx.state_path)?;
+    println!("Fixed {fixed} skill(s).");
+    Ok(())
+}
+
+/// Return tools in their canonical order.
+fn sorted_tools(tools: impl Iterator<Item = Tool>) -> Vec<Tool> {
+    let tools: Vec<Tool> = tools.collect();
+    Tool::all()
+        .into_iter()
+        .filter(|tool| tools.contains(tool))
+        .collect()
+}
+
+/// Join tool ids for display.
+fn tool_list(tools: &[Tool]) -> String {
+    tools
+        .iter()
+        .map(|tool| tool.id())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Prompt for confirmation.
+fn confirm(message: &str) -> Result<bool> {
+    match Confirm::new(message).with_default(true).prompt() {
+        Ok(value) => Ok(value),
+        Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+            Err(Error::PromptCanceled)
+        }
+        Err(error) => Err(Error::PromptFailed {
+            message: error.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Group, classify};
+    use crate::{
+        commands::sync::build_sync_plans,
+        config::SyncDirection,
+        diagnostics::Diagnostics,
+        state::{InstallOrigin, State},
+        status::{OrphanKind, build_entries},
+        testutil::{TestFixture, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    #[test]
+    fn groups_skills_by_required_action() {
+        let mut fixture = TestFixture::new()
+            .with_source_skill("synced", &simple_skill("synced"))
+            .with_source_skill("edited", &simple_skill("edited"))
+            .with_tool_skill(Tool::Codex, "edited", &skill_content("edited", "d", "a"))
+            .with_tool_skill(Tool::Claude, "edited", &skill_content("edited", "d", "b"))
+            .with_source_skill("fresh", &simple_skill("fresh"))
+            .with_tool_skill(Tool::Claude, "stray", &simple_skill("stray"))
+            .with_tool_skill(Tool::Codex, "gone", &simple_skill("gone"));
+        let mut state = State::default();
+        state
+            .record(
+                &fixture.tool_dir(Tool::Codex).join("gone"),
+                InstallOrigin::Push,
+            )
+            .expect("record");
+        for tool in Tool::all() {
+            fixture = fixture.with_tool_skill(tool, "synced", &simple_skill("synced"));
+        }
+        let catalog = fixture.catalog();
+        let mut diagnostics = Diagnostics::default();
+        let entries = build_entries(&catalog, &mut diagnostics);
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).expect("plans");
+
+        let groups = |direction| {
+            classify(&catalog, &state, &entries, &plans, direction)
+                .into_iter()
+                .map(|item| (item.name, item.group))
+                .collect::<Vec<_>>()
+        };
+        let items = groups(SyncDirection::Timestamps);
+        assert_eq!(
+            items,
+            [
+                ("edited".to_string(), Group::Conflict),
+                ("fresh".to_string(), Group::NeedsPush),
+                ("gone".to_string(), Group::Orphan),
+                ("stray".to_string(), Group::Orphan),
+                ("synced".to_string(), Group::Synced),
+            ]
+        );
+
+        let items = classify(
+            &catalog,
+            &state,
+            &entries,
+            &plans,
+            SyncDirection::Timestamps,
+        );
+        let item = |name: &str| items.iter().find(|item| item.name == name).expect("item");
+        assert_eq!(item("fresh").missing, Tool::all());
+        assert!(item("fresh").detail.ends_with("(missing)"));
+        assert_eq!(item("gone").orphan, Some(OrphanKind::Removed));
+        assert!(
+            item("gone")
+                .detail
+                .ends_with("prune with `skills unload gone`")
+        );
+        assert_eq!(item("stray").orphan, Some(OrphanKind::Created));
+        assert!(
+            item("stray")
+                .detail
+                .ends_with("adopt with `skills pull stray`")
+        );
+        assert_ne!(groups(SyncDirection::Push)[0].1, Group::Conflict);
+    }
+}
@@ -1,21 +1,29 @@
 //! Implementation of the `skills sync` command.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write,
     fs,
-    path::Path,
+    path::{Path, PathBuf},
     time::SystemTime,
 };
 
+use serde::Serialize;
+
 use crate::{
     catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
+    commands::init,
+    config::{LineEndings, SyncDirection},
+    context::Context,
     diagnostics::Diagnostics,
+    diff::unified_diff,
     error::{Error, Result},
-    palette::{fmt_label, fmt_skill_name, fmt_tool_tag},
-    skill::{SKILL_FILE_NAME, SkillTemplate, ToolSkill, render_template},
-    status::normalize_line_endings,
+    palette::{Palette, Role},
+    skill::{
+        SKILL_FILE_NAME, SkillTemplate, ToolSkill, is_template, pull_support_files,
+        push_support_files, render_template, write_skill_file,
+    },
+    state::{InstallOrigin, State, hash_bytes},
     tool::Tool,
 };
 
@@ -34,19 +42,24 @@ pub enum ConflictResolution {
 }
 
 /// Execute the sync command.
+///
+/// `direction` overrides the configured `sync.default` policy for this run. With `report`, every
+/// action taken, or planned under `dry_run`, is also written to that path as JSON or Markdown.
+/// Pinned skills are reported and left alone.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skills: Vec<String>,
     prefer_source: bool,
     prefer_tool: bool,
+    direction: Option<SyncDirection>,
+    report: Option<PathBuf>,
     dry_run: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
 
     // Determine conflict resolution strategy
     let resolution = if prefer_source {
@@ -58,6 +71,10 @@ pub async fn run(
     };
 
     // Validate specified skills exist
+    let skills: Vec<String> = skills
+        .iter()
+        .map(|name| catalog.resolve_name(name))
+        .collect();
     if !skills.is_empty() {
         for name in &skills {
             if !catalog.sources.contains_key(name) {
@@ -67,124 +84,175 @@ pub async fn run(
     }
 
     // Build sync plans for skills
-    let mut plans = build_sync_plans(&catalog, &mut diagnostics)?;
+    let direction = direction.unwrap_or(config.sync.default);
+    let mut plans = build_sync_plans(&catalog, direction, &mut diagnostics)?;
 
     // Filter to specified skills if any
     if !skills.is_empty() {
         plans.retain(|p| skills.contains(&p.name));
     }
 
+    // Set pinned skills aside
+    let mut state = ctx.load_state()?;
+    let (pinned, mut plans): (Vec<SyncPlan>, Vec<SyncPlan>) = plans
+        .into_iter()
+        .partition(|plan| state.is_pinned(&plan.name));
+    for plan in &pinned {
+        println!("{}", palette.paint(Role::SkillName, &plan.name));
+        println!("    pinned (run `skills unpin {}` to sync it)", plan.name);
+    }
+
+    let mut sync_report = SyncReport {
+        dry_run,
+        pushed: 0,
+        pulled: 0,
+        skills: Vec::new(),
+    };
     if plans.is_empty() {
-        println!("All skills are in sync.");
+        if let Some(path) = &report {
+            write_report(path, &sync_report)?;
+        }
+        if pinned.is_empty() {
+            println!("All skills are in sync.");
+        } else {
+            println!();
+            println!("All unpinned skills are in sync.");
+        }
         return Ok(());
     }
 
-    // Handle conflicts based on resolution strategy
-    handle_conflicts(&mut plans, resolution)?;
+    // Handle conflicts based on resolution strategy; pushing sources leaves nothing to resolve
+    if direction != SyncDirection::Push {
+        handle_conflicts(&mut plans, resolution)?;
+    }
 
     // Apply sync operations
     let mut push_count = 0;
     let mut pull_count = 0;
 
     for plan in &plans {
+        if report.is_some() {
+            sync_report
+                .skills
+                .push(report_entry(ctx, plan, config.line_endings)?);
+        }
         match &plan.action {
             SyncAction::Push { to_tools } => {
-                print_push(plan, to_tools, use_color);
-                if !dry_run {
-                    apply_push(plan, to_tools)?;
-                }
+                print_push(plan, to_tools, &palette);
                 push_count += 1;
             }
             SyncAction::Pull { from_tool } => {
-                print_pull(plan, *from_tool, use_color);
-                if !dry_run {
-                    apply_pull(plan, *from_tool)?;
-                }
+                print_pull(plan, *from_tool, &palette);
                 pull_count += 1;
             }
-            SyncAction::PullAndPush { from_tool, to_tools } => {
-                print_pull_and_push(plan, *from_tool, to_tools, use_color);
-                if !dry_run {
-                    apply_pull(plan, *from_tool)?;
-                    apply_push(plan, to_tools)?;
-                }
+            SyncAction::PullAndPush {
+                from_tool,
+                to_tools,
+            } => {
+                print_pull_and_push(plan, *from_tool, to_tools, &palette);
                 pull_count += 1;
                 push_count += 1;
             }
         }
+        if !dry_run {
+            apply_plan(ctx, plan, config.line_endings, &mut state)?;
+        }
+    }
+
+    if !dry_run {
+        state.save(&ctx.state_path)?;
+    }
+    if let Some(path) = &report {
+        sync_report.pushed = push_count;
+        sync_report.pulled = pull_count;
+        write_report(path, &sync_report)?;
     }
 
     println!();
     if dry_run {
         println!(
             "{} {} push, {} pull operations would be performed.",
-            fmt_label("Dry run:", use_color),
+            palette.paint(Role::Label, "Dry run:"),
             push_count,
             pull_count
         );
     } else {
         println!(
             "{} {} pushed, {} pulled.",
-            fmt_label("Synced:", use_color),
+            palette.paint(Role::Label, "Synced:"),
             push_count,
             pull_count
         );
     }
 
-    diagnostics.print_skipped_summary();
+    diagnostics.print_summary();
     Ok(())
 }
 
 /// Print a push action.
-fn print_push(plan: &SyncPlan, to_tools: &[Tool], use_color: bool) {
-    println!("{}", fmt_skill_name(&plan.name, use_color));
+fn print_push(plan: &SyncPlan, to_tools: &[Tool], palette: &Palette) {
+    println!("{}", palette.paint(Role::SkillName, &plan.name));
     println!(
         "{}{} source -> {}",
         INDENT,
-        fmt_label("sync:", use_color),
-        format_tools(to_tools, use_color)
+        palette.paint(Role::Label, "sync:"),
+        format_tools(to_tools, palette)
     );
 }
 
 /// Print a pull action.
-fn print_pull(plan: &SyncPlan, from_tool: Tool, use_color: bool) {
-    println!("{}", fmt_skill_name(&plan.name, use_color));
+fn print_pull(plan: &SyncPlan, from_tool: Tool, palette: &Palette) {
+    println!("{}", palette.paint(Role::SkillName, &plan.name));
     println!(
         "{}{} {} -> source",
         INDENT,
-        fmt_label("sync:", use_color),
-        format_tool(from_tool, use_color)
+        palette.paint(Role::Label, "sync:"),
+        format_tool(from_tool, palette)
     );
 }
 
 /// Print a pull-and-push action.
-fn print_pull_and_push(plan: &SyncPlan, from_tool: Tool, to_tools: &[Tool], use_color: bool) {
-    println!("{}", fmt_skill_name(&plan.name, use_color));
+fn print_pull_and_push(plan: &SyncPlan, from_tool: Tool, to_tools: &[Tool], palette: &Palette) {
+    println!("{}", palette.paint(Role::SkillName, &plan.name));
     println!(
         "{}{} {} -> source -> {}",
         INDENT,
-        fmt_label("sync:", use_color),
-        format_tool(from_tool, use_color),
-        format_tools(to_tools, use_color)
+        palette.paint(Role::Label, "sync:"),
+        format_tool(from_tool, palette),
+        format_tools(to_tools, palette)
     );
 }
 
 /// Sync plan for a single skill.
 #[derive(Debug)]
-struct SyncPlan {
+pub struct SyncPlan {
     /// Skill name.
-    name: String,
+    pub name: String,
     /// Source skill template.
-    source: SkillTemplate,
+    pub source: SkillTemplate,
     /// Tool skills that differ from source.
-    tool_skills: HashMap<Tool, ToolSkill>,
+    pub tool_skills: HashMap<Tool, ToolSkill>,
     /// Determined sync action.
-    action: SyncAction,
+    pub action: SyncAction,
+}
+
+impl SyncPlan {
+    /// Whether the differing tool copies also differ from each other, in any file.
+    pub fn is_conflicted(&self) -> bool {
+        let mut copies = self
+            .tool_skills
+            .values()
+            .map(|skill| (skill.hash, &skill.files));
+        let Some(first) = copies.next() else {
+            return false;
+        };
+        copies.any(|copy| copy != first)
+    }
 }
 
 /// Action to take for syncing a skill.
 #[derive(Debug)]
-enum SyncAction {
+pub enum SyncAction {
     /// Push source to tools.
     Push {
         /// Tools to push to.
@@ -205,8 +273,9 @@ enum SyncAction {
 }
 
 /// Build sync plans for all skills that need syncing.
-fn build_sync_plans(
+pub fn build_sync_plans(
     catalog: &Catalog,
+    direction: SyncDirection,
     diagnostics: &mut Diagnostics,
 ) -> Result<Vec<SyncPlan>> {
     let mut plans = Vec::new();
@@ -224,7 +293,7 @@ fn build_sync_plans(
             };
 
             // Render the template for this tool
-            let rendered = match render_template(&source.contents, tool) {
+            let rendered = match catalog.render_skill(source, tool) {
                 Ok(rendered) => rendered,
                 Err(error) => {
                     diagnostics.warn_skipped(&source.skill_path, error);
@@ -232,8 +301,8 @@ fn build_sync_plans(
                 }
             };
 
-            // Check if contents differ
-            if normalize_line_endings(&rendered) != normalize_line_endings(&tool_skill.contents) {
+            // Check if any file differs
+            if !tool_skill.matches(source, &rendered) {
                 differing_tools.insert(tool, tool_skill.clone());
             }
         }
@@ -242,8 +311,7 @@ fn build_sync_plans(
             continue;
         }
 
-        // Determine sync action based on timestamps
-        let action = determine_action(source, &differing_tools);
+        let action = determine_action(source, &differing_tools, direction);
 
         plans.push(SyncPlan {
             name: name.clone(),
@@ -254,15 +322,17 @@ fn build_sync_plans(
     }
 
     // Sort by name for consistent output
-    plans.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    plans.sort_by_key(|a| a.name.to_lowercase());
 
     Ok(plans)
 }
 
-/// Determine the sync action based on modification timestamps.
-fn determine_action(source: &SkillTemplate, tool_skills: &HashMap<Tool, ToolSkill>) -> SyncAction {
-    let source_mtime = source.modified;
-
+/// Determine the sync action from the direction policy and, for timestamps, modification times.
+fn determine_action(
+    source: &SkillTemplate,
+    tool_skills: &HashMap<Tool, ToolSkill>,
+    direction: SyncDirection,
+) -> SyncAction {
     // Find the newest tool modification
     let mut newest_tool: Option<(Tool, SystemTime)> = None;
     for (tool, skill) in tool_skills {
@@ -282,8 +352,14 @@ fn determine_action(source: &SkillTemplate, tool_skills: &HashMap<Tool, ToolSkil
         };
     };
 
-    if source_mtime >= newest_tool_mtime {
-        // Source is newer or equal, push to all differing tools
+    let push = match direction {
+        SyncDirection::Push => true,
+        SyncDirection::Pull => false,
+        SyncDirection::Timestamps => source.modified >= newest_tool_mtime,
+    };
+
+    if push {
+        // Source is canonical, or newer or equal, so push to all differing tools
         SyncAction::Push {
             to_tools: tool_skills.keys().copied().collect(),
         }
@@ -309,26 +385,10 @@ fn determine_action(source: &SkillTemplate, tool_skills: &HashMap<Tool, ToolSkil
 }
 
 /// Handle conflicts based on resolution strategy.
-fn handle_conflicts(plans: &mut Vec<SyncPlan>, resolution: ConflictResolution) -> Result<()> {
+fn handle_conflicts(plans: &mut [SyncPlan], resolution: ConflictResolution) -> Result<()> {
     for plan in plans.iter_mut() {
-        if plan.tool_skills.len() < 2 {
-            continue;
-        }
-
         // Check if the tool skills differ from each other (not just from source)
-        let skills: Vec<_> = plan.tool_skills.values().collect();
-        let first_contents = normalize_line_endings(&skills[0].contents);
-
-        let mut has_conflict = false;
-        for skill in skills.iter().skip(1) {
-            let contents = normalize_line_endings(&skill.contents);
-            if contents != first_contents {
-                has_conflict = true;
-                break;
-            }
-        }
-
-        if !has_conflict {
+        if !plan.is_conflicted() {
             continue;
         }
 
@@ -368,7 +428,9 @@ fn handle_conflicts(plans: &mut Vec<SyncPlan>, resolution: ConflictResolution) -
                     .collect();
 
                 if other_tools.is_empty() {
-                    plan.action = SyncAction::Pull { from_tool: newest_tool };
+                    plan.action = SyncAction::Pull {
+                        from_tool: newest_tool,
+                    };
                 } else {
                     plan.action = SyncAction::PullAndPush {
                         from_tool: newest_tool,
@@ -382,84 +444,363 @@ fn handle_conflicts(plans: &mut Vec<SyncPlan>, resolution: ConflictResolution) -
     Ok(())
 }
 
-/// Apply a push operation.
-fn apply_push(plan: &SyncPlan, to_tools: &[Tool]) -> Result<()> {
+/// Render a source skill for each tool and write it, with its support files, over the tool's
+/// copy.
+///
+/// Support files of the `installed` copies that the source no longer has are removed.
+pub fn apply_push(
+    ctx: &Context,
+    skill: &SkillTemplate,
+    to_tools: &[Tool],
+    installed: &HashMap<Tool, ToolSkill>,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
     for &tool in to_tools {
-        let tool_dir = tool.skills_dir()?;
-        let rendered = render_template(&plan.source.contents, tool)
+        let skill_dir = ctx.tool_dir(tool).join(&skill.name);
+        let rendered = skill
+            .render_with(|template| render_template(template, tool, &skill.bases))
             .map_err(|e| Error::TemplateRender { message: e })?;
-        write_tool_skill(&tool_dir, &plan.name, &rendered)?;
+        state.snapshot(&skill_dir, InstallOrigin::Sync)?;
+        write_skill_file(
+            &skill_dir.join(SKILL_FILE_NAME),
+            &rendered.contents,
+            line_endings,
+        )?;
+        push_support_files(
+            skill,
+            &rendered,
+            &skill_dir,
+            installed.get(&tool),
+            line_endings,
+        )?;
+        state.record(&skill_dir, InstallOrigin::Sync)?;
     }
     Ok(())
 }
 
-/// Apply a pull operation.
-fn apply_pull(plan: &SyncPlan, from_tool: Tool) -> Result<()> {
-    let tool_skill = plan.tool_skills.get(&from_tool).ok_or_else(|| {
-        Error::SkillNotFound {
+/// Apply a plan's action, recording pushed copies in the install state.
+pub fn apply_plan(
+    ctx: &Context,
+    plan: &SyncPlan,
+    line_endings: LineEndings,
+    state: &mut State,
+) -> Result<()> {
+    match &plan.action {
+        SyncAction::Push { to_tools } => apply_push(
+            ctx,
+            &plan.source,
+            to_tools,
+            &plan.tool_skills,
+            line_endings,
+            state,
+        ),
+        SyncAction::Pull { from_tool } => {
+            apply_pull(plan, *from_tool, line_endings)?;
+            Ok(())
+        }
+        SyncAction::PullAndPush {
+            from_tool,
+            to_tools,
+        } => {
+            // Push what was just pulled, not the source as it was before the pull.
+            let pulled = apply_pull(plan, *from_tool, line_endings)?;
+            apply_push(
+                ctx,
+                &pulled,
+                to_tools,
+                &plan.tool_skills,
+                line_endings,
+                state,
+            )
+        }
+    }
+}
+
+/// Apply a pull operation, copying every file of the tool's copy into the source, and return
+/// the source as it is after the pull. Source files the copy lacks are kept.
+pub fn apply_pull(
+    plan: &SyncPlan,
+    from_tool: Tool,
+    line_endings: LineEndings,
+) -> Result<SkillTemplate> {
+    let tool_skill = plan
+        .tool_skills
+        .get(&from_tool)
+        .ok_or_else(|| Error::SkillNotFound {
             name: plan.name.clone(),
+        })?;
+
+    let contents = tool_skill.read_contents()?;
+    write_skill_file(&plan.source.skill_path, &contents, line_endings)?;
+    let files = pull_support_files(
+        tool_skill,
+        &plan.source.skill_dir,
+        &plan.source.files,
+        line_endings,
+        false,
+    )?;
+    Ok(SkillTemplate {
+        contents,
+        files,
+        ..plan.source.clone()
+    })
+}
+
+/// Structured record of a sync run, written by `--report`.
+#[derive(Debug, Serialize)]
+struct SyncReport {
+    /// Whether the actions were only planned.
+    dry_run: bool,
+    /// Number of skills pushed to tools.
+    pushed: usize,
+    /// Number of skills pulled into sources.
+    pulled: usize,
+    /// Actions in the order they were taken.
+    skills: Vec<ReportEntry>,
+}
+
+/// Report of the action taken for one skill.
+#[derive(Debug, Serialize)]
+struct ReportEntry {
+    /// Skill name.
+    skill: String,
+    /// Action label: `push`, `pull`, or `pull-and-push`.
+    action: &'static str,
+    /// Files written, or that would be written.
+    changes: Vec<FileChange>,
+}
+
+/// A skill file written by sync.
+#[derive(Debug, Serialize)]
+struct FileChange {
+    /// `source` or the id of the tool whose copy is written.
+    target: &'static str,
+    /// Path of the written file.
+    path: PathBuf,
+    /// SHA-256 of the file before the write.
+    before: String,
+    /// SHA-256 of the file after the write.
+    after: String,
+    /// Unified diff from the old contents to the new.
+    diff: String,
+}
+
+impl FileChange {
+    /// Describe replacing `before` with `after` at `path`.
+    fn new(target: &'static str, path: PathBuf, before: &[u8], after: &[u8]) -> Self {
+        let label = path.display().to_string();
+        let diff = match (str::from_utf8(before), str::from_utf8(after)) {
+            (Ok(before), Ok(after)) => unified_diff(&label, &label, before, after),
+            _ => format!("Binary file {label} differs\n"),
+        };
+        Self {
+            target,
+            before: hash_bytes(before),
+            after: hash_bytes(after),
+            diff,
+            path,
         }
-    })?;
+    }
+}
 
-    let skill_path = plan.source.skill_path.clone();
-    fs::write(&skill_path, &tool_skill.contents).map_err(|e| Error::SkillWrite {
-        path: skill_path,
-        source: e,
-    })?;
+/// Add a change for each support file that differs between two sets, keyed by path, written
+/// into `dir`.
+///
+/// Markdown is written with the line ending policy; a file missing from `after` is removed.
+fn support_changes(
+    target: &'static str,
+    dir: &Path,
+    before: &BTreeMap<String, Vec<u8>>,
+    after: &BTreeMap<String, Vec<u8>>,
+    line_endings: LineEndings,
+    changes: &mut Vec<FileChange>,
+) {
+    let paths: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for path in paths {
+        let old = before.get(path).map_or(&[][..], Vec::as_slice);
+        let new = match after.get(path) {
+            Some(contents) if is_template(path) => match str::from_utf8(contents) {
+                Ok(text) => line_endings.apply(text).into_owned().into_bytes(),
+                Err(_) => contents.clone(),
+            },
+            Some(contents) => contents.clone(),
+            None => Vec::new(),
+        };
+        if old != new.as_slice() {
+            changes.push(FileChange::new(target, dir.join(path), old, &new));
+        }
+    }
+}
 
-    Ok(())
+/// Record the files a plan writes, read before any of them are changed.
+fn report_entry(ctx: &Context, plan: &SyncPlan, line_endings: LineEndings) -> Result<ReportEntry> {
+    let (action, from_tool, to_tools): (_, _, &[Tool]) = match &plan.action {
+        SyncAction::Push { to_tools } => ("push", None, to_tools),
+        SyncAction::Pull { from_tool } => ("pull", Some(*from_tool), &[]),
+        SyncAction::PullAndPush {
+            from_tool,
+            to_tools,
+        } => ("pull-and-push", Some(*from_tool), to_tools),
+    };
+
+    let mut changes = Vec::new();
+    if let Some(tool) = from_tool.and_then(|tool| plan.tool_skills.get(&tool)) {
+        let pulled = tool.read_contents()?;
+        changes.push(FileChange::new(
+            "source",
+            plan.source.skill_path.clone(),
+            plan.source.contents.as_bytes(),
+            line_endings.apply(&pulled).as_bytes(),
+        ));
+        let before = plan
+            .source
+            .files
+            .keys()
+            .map(|path| {
+                let file = plan.source.skill_dir.join(path);
+                fs::read(&file)
+                    .map(|contents| (path.clone(), contents))
+                    .map_err(|source| Error::SkillRead { path: file, source })
+            })
+            .collect::<Result<_>>()?;
+        support_changes(
+            "source",
+            &plan.source.skill_dir,
+            &before,
+            &tool.read_support_files()?,
+            line_endings,
+            &mut changes,
+        );
+    }
+    for &tool in to_tools {
+        let rendered = plan
+            .source
+            .render_with(|template| render_template(template, tool, &plan.source.bases))
+            .map_err(|e| Error::TemplateRender { message: e })?;
+        let (before, before_files) = match plan.tool_skills.get(&tool) {
+            Some(installed) => (installed.read_contents()?, installed.read_support_files()?),
+            None => (String::new(), BTreeMap::new()),
+        };
+        let skill_dir = ctx.tool_dir(tool).join(&plan.name);
+        changes.push(FileChange::new(
+            tool.id(),
+            skill_dir.join(SKILL_FILE_NAME),
+            before.as_bytes(),
+            line_endings.apply(&rendered.contents).as_bytes(),
+        ));
+        support_changes(
+            tool.id(),
+            &skill_dir,
+            &before_files,
+            &rendered.support_contents(&plan.source)?,
+            line_endings,
+            &mut changes,
+        );
+    }
+
+    Ok(ReportEntry {
+        skill: plan.name.clone(),
+        action,
+        changes,
+    })
 }
 
-/// Write a skill to the tool directory.
-fn write_tool_skill(tool_dir: &Path, name: &str, rendered: &str) -> Result<()> {
-    let skill_dir = tool_dir.join(name);
-    fs::create_dir_all(&skill_dir).map_err(|error| Error::SkillWrite {
-        path: skill_dir.clone(),
-        source: error,
-    })?;
+/// Write a report as Markdown when the path ends in `.md`, and as JSON otherwise.
+fn write_report(path: &Path, report: &SyncReport) -> Result<()> {
+    let markdown = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md"));
+    let contents = if markdown {
+        render_markdown(report)
+    } else {
+        let mut json =
+            serde_json::to_string_pretty(report).map_err(|error| Error::TemplateRender {
+                message: error.to_string(),
+            })?;
+        json.push('\n');
+        json
+    };
+    fs::write(path, contents).map_err(|source| Error::ReportWrite {
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    fs::write(&skill_path, rendered).map_err(|error| Error::SkillWrite {
-        path: skill_path,
-        source: error,
-    })?;
+/// Render a report as Markdown suitable for a CI summary or pull request comment.
+fn render_markdown(report: &SyncReport) -> String {
+    let mut output = String::from("# Sync report\n\n");
+    if report.dry_run {
+        writeln!(
+            output,
+            "Dry run: {} push, {} pull operations planned.",
+            report.pushed, report.pulled
+        )
+        .ok();
+    } else {
+        writeln!(
+            output,
+            "Synced: {} pushed, {} pulled.",
+            report.pushed, report.pulled
+        )
+        .ok();
+    }
 
-    Ok(())
+    for entry in &report.skills {
+        writeln!(output, "\n## {} ({})\n", entry.skill, entry.action).ok();
+        for change in &entry.changes {
+            writeln!(
+                output,
+                "- {}: `{}` `{}` → `{}`",
+                change.target,
+                change.path.display(),
+                &change.before[..12],
+                &change.after[..12]
+            )
+            .ok();
+        }
+        for change in &entry.changes {
+            writeln!(output, "\n```diff\n{}```", change.diff).ok();
+        }
+    }
+    output
 }
 
 /// Format a single tool for display with styling.
-fn format_tool(tool: Tool, use_color: bool) -> String {
+fn format_tool(tool: Tool, palette: &Palette) -> String {
     let tag = format!("[{}]", tool.id());
-    fmt_tool_tag(&tag, use_color)
+    palette.paint(Role::ToolTag, &tag)
 }
 
 /// Format a list of tools for display with styling.
-fn format_tools(tools: &[Tool], use_color: bool) -> String {
+fn format_tools(tools: &[Tool], palette: &Palette) -> String {
     tools
         .iter()
-        .map(|t| format_tool(*t, use_color))
+        .map(|t| format_tool(*t, palette))
         .collect::<Vec<_>>()
         .join(", ")
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use super::{SyncAction, apply_plan, build_sync_plans, run};
     use crate::{
+        config::{LineEndings, SyncDirection},
         diagnostics::Diagnostics,
         testutil::{TestFixture, simple_skill, skill_content},
         tool::Tool,
     };
 
-    use super::{build_sync_plans, SyncAction};
-
     #[test]
     fn builds_plan_for_missing_tool_skill() {
-        let fixture = TestFixture::new()
-            .with_source_skill("new-skill", &simple_skill("new-skill"));
+        let fixture = TestFixture::new().with_source_skill("new-skill", &simple_skill("new-skill"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         // No tool skills means nothing to sync (push is handled separately)
         assert!(plans.is_empty());
@@ -475,8 +816,9 @@ mod tests {
             .with_tool_skill(Tool::Claude, "modified", &tool_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
         assert_eq!(plans[0].name, "modified");
@@ -493,8 +835,9 @@ mod tests {
             .with_tool_skill(Tool::Codex, "synced", &content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert!(plans.is_empty());
     }
@@ -510,8 +853,9 @@ mod tests {
             .with_tool_skill(Tool::Codex, "partial", &tool_content); // Modified
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
         assert_eq!(plans[0].name, "partial");
@@ -538,8 +882,9 @@ mod tests {
             .with_tool_skill(Tool::Claude, "Banana", &banana_tool);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         let names: Vec<&str> = plans.iter().map(|p| p.name.as_str()).collect();
         assert_eq!(names, vec!["apple", "Banana", "Zebra"]);
@@ -558,8 +903,9 @@ mod tests {
             .with_source_skill("skill", &source_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
         match &plans[0].action {
@@ -570,15 +916,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn direction_policy_overrides_timestamps() {
+        let source_content = skill_content("skill", "desc", "older source");
+        let tool_content = skill_content("skill", "desc", "newer tool");
+
+        // Write source first, then tool (tool will be newer)
+        let fixture = TestFixture::new()
+            .with_source_skill("skill", &source_content)
+            .with_tool_skill(Tool::Claude, "skill", &tool_content);
+        let catalog = fixture.catalog();
+        let mut diagnostics = Diagnostics::default();
+
+        let plans = build_sync_plans(&catalog, SyncDirection::Push, &mut diagnostics).unwrap();
+        assert!(matches!(plans[0].action, SyncAction::Push { .. }));
+
+        let plans = build_sync_plans(&catalog, SyncDirection::Pull, &mut diagnostics).unwrap();
+        assert!(matches!(
+            plans[0].action,
+            SyncAction::Pull {
+                from_tool: Tool::Claude
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn writes_report_of_planned_actions() {
+        let fixture = TestFixture::new()
+            .with_tool_skill(
+                Tool::Claude,
+                "skill",
+                &skill_content("skill", "desc", "older tool"),
+            )
+            .with_source_skill("skill", &skill_content("skill", "desc", "newer source"));
+        let ctx = fixture.context();
+        let json_path = fixture.work_dir().join("report.json");
+        let markdown_path = fixture.work_dir().join("report.md");
+
+        for path in [&json_path, &markdown_path] {
+            run(
+                &ctx,
+                Vec::new(),
+                false,
+                false,
+                None,
+                Some(path.clone()),
+                true,
+            )
+            .await
+            .expect("sync");
+        }
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).expect("read")).expect("json");
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["pushed"], 1);
+        let change = &report["skills"][0]["changes"][0];
+        assert_eq!(report["skills"][0]["action"], "push");
+        assert_eq!(change["target"], "claude");
+        assert_ne!(change["before"], change["after"]);
+        assert!(
+            change["diff"]
+                .as_str()
+                .expect("diff")
+                .contains("+newer source")
+        );
+
+        let markdown = fs::read_to_string(&markdown_path).expect("read");
+        assert!(markdown.contains("## skill (push)"));
+        assert!(markdown.contains("```diff\n"));
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "skill")
+                .expect("tool skill")
+                .contains("older tool")
+        );
+    }
+
     #[test]
     fn ignores_orphan_tool_skills() {
         // Tool has a skill that source doesn't - sync shouldn't care
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Claude, "orphan", &simple_skill("orphan"));
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Claude, "orphan", &simple_skill("orphan"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert!(plans.is_empty());
     }
@@ -595,8 +1019,9 @@ mod tests {
             .with_tool_skill(Tool::Codex, "divergent", &codex_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
-        let plans = build_sync_plans(&catalog, &mut diagnostics).unwrap();
+        let mut diagnostics = Diagnostics::default();
+        let plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
 
         assert_eq!(plans.len(), 1);
         assert_eq!(plans[0].name, "divergent");
@@ -604,4 +1029,35 @@ mod tests {
         assert!(plans[0].tool_skills.contains_key(&Tool::Claude));
         assert!(plans[0].tool_skills.contains_key(&Tool::Codex));
     }
+
+    #[test]
+    fn pull_and_push_pushes_the_pulled_contents() {
+        let claude_content = skill_content("skill", "desc", "claude version");
+        let codex_content = skill_content("skill", "desc", "codex version");
+        let fixture = TestFixture::new()
+            .with_source_skill("skill", &skill_content("skill", "desc", "source"))
+            .with_tool_skill(Tool::Claude, "skill", &claude_content)
+            .with_tool_skill(Tool::Codex, "skill", &codex_content);
+        let ctx = fixture.context();
+        let catalog = fixture.catalog();
+        let mut diagnostics = Diagnostics::default();
+        let mut plans =
+            build_sync_plans(&catalog, SyncDirection::Timestamps, &mut diagnostics).unwrap();
+        plans[0].action = SyncAction::PullAndPush {
+            from_tool: Tool::Codex,
+            to_tools: vec![Tool::Claude],
+        };
+
+        let mut state = ctx.load_state().expect("state");
+        apply_plan(&ctx, &plans[0], LineEndings::default(), &mut state).expect("apply");
+
+        assert_eq!(
+            fixture.read_source_skill("skill"),
+            Some(codex_content.clone())
+        );
+        assert_eq!(
+            fixture.read_tool_skill(Tool::Claude, "skill"),
+            Some(codex_content)
+        );
+    }
 }
@@ -0,0 +1,314 @@
+//! Implementation of the `skills test` command.
+
+use std::{fs, io::ErrorKind, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    skill::{SkillTemplate, render_template},
+    tool::{Tool, ToolFilter},
+};
+
+/// Name of the optional assertions file inside a skill directory.
+pub const TESTS_FILE: &str = "tests.toml";
+
+/// Directory inside a skill directory holding golden renderings, one `<tool>.md` per tool.
+pub const SNAPSHOTS_DIR: &str = "__snapshots__";
+
+/// Assertions read from a skill's `tests.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SkillTests {
+    /// Assertion groups, written as `[[test]]` tables.
+    test: Vec<Assertion>,
+}
+
+/// One group of checks against rendered output.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Assertion {
+    /// Tool whose rendering is checked; every tool when omitted.
+    tool: Option<ToolFilter>,
+    /// Text the rendered output must contain.
+    contains: Vec<String>,
+    /// Text the rendered output must not contain.
+    not_contains: Vec<String>,
+    /// Largest allowed rendered length in characters.
+    max_length: Option<usize>,
+}
+
+impl Assertion {
+    /// Check rendered output for a tool, returning a message per failed check.
+    fn check(&self, rendered: &str) -> Vec<String> {
+        let mut failures = Vec::new();
+        for text in &self.contains {
+            if !rendered.contains(text.as_str()) {
+                failures.push(format!("expected to contain {text:?}"));
+            }
+        }
+        for text in &self.not_contains {
+            if rendered.contains(text.as_str()) {
+                failures.push(format!("expected not to contain {text:?}"));
+            }
+        }
+        if let Some(max) = self.max_length {
+            let length = rendered.chars().count();
+            if length > max {
+                failures.push(format!("length {length} exceeds {max}"));
+            }
+        }
+        failures
+    }
+
+    /// Return whether this assertion applies to a tool.
+    fn applies_to(&self, tool: Tool) -> bool {
+        self.tool
+            .is_none_or(|filter| filter.to_tools().contains(&tool))
+    }
+}
+
+/// Execute the test command.
+///
+/// Every selected source skill is rendered for every tool, and each rendering is checked against
+/// the assertions in the skill's `tests.toml`, if it has one, and against its snapshots, if it has
+/// a `__snapshots__` directory. With `update_snapshots`, the snapshots are rewritten instead.
+pub async fn run(ctx: &Context, skill_names: Vec<String>, update_snapshots: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let mut skills: Vec<&SkillTemplate> = if skill_names.is_empty() {
+        catalog.sources.values().collect()
+    } else {
+        skill_names
+            .iter()
+            .map(|name| {
+                catalog
+                    .sources
+                    .get(&catalog.resolve_name(name))
+                    .ok_or_else(|| Error::SkillNotFound { name: name.clone() })
+            })
+            .collect::<Result<_>>()?
+    };
+    skills.sort_by_key(|skill| skill.name.to_lowercase());
+
+    if skills.is_empty() {
+        println!("No skills to test.");
+        return Ok(());
+    }
+
+    let mut passed = 0;
+    let mut failed = 0;
+    for skill in skills {
+        let (checks, failures) = test_skill(skill, update_snapshots)?;
+        if failures.is_empty() {
+            passed += 1;
+            println!(
+                "{} {} ({} checks)",
+                palette.paint(Role::Success, "✓"),
+                palette.paint(Role::SkillName, &skill.name),
+                checks
+            );
+        } else {
+            failed += 1;
+            println!(
+                "{} {}",
+                palette.paint(Role::Failure, "✗"),
+                palette.paint(Role::SkillName, &skill.name)
+            );
+            for failure in failures {
+                println!("    - {failure}");
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} {} passed, {} failed.",
+        palette.paint(Role::Label, "Done:"),
+        passed,
+        failed
+    );
+
+    diagnostics.print_summary();
+    if failed > 0 {
+        return Err(Error::TestFailed { count: failed });
+    }
+    Ok(())
+}
+
+/// Render a skill for every tool and run its assertions and snapshot comparisons.
+///
+/// Returns the number of checks run and a message for each failure.
+fn test_skill(skill: &SkillTemplate, update_snapshots: bool) -> Result<(usize, Vec<String>)> {
+    let tests = match load_tests(&skill.skill_dir) {
+        Ok(tests) => tests,
+        Err(error) => return Ok((1, vec![error.to_string()])),
+    };
+    let snapshots = skill.skill_dir.join(SNAPSHOTS_DIR);
+    let compare_snapshots = !update_snapshots && snapshots.is_dir();
+
+    let mut checks = 0;
+    let mut failures = Vec::new();
+    for tool in Tool::all() {
+        checks += 1;
+        let rendered = match render_template(&skill.contents, tool, &skill.bases) {
+            Ok(rendered) => rendered,
+            Err(error) => {
+                failures.push(format!("{}: render failed: {error}", tool.id()));
+                continue;
+            }
+        };
+        for assertion in tests.test.iter().filter(|test| test.applies_to(tool)) {
+            checks += 1;
+            failures.extend(
+                assertion
+                    .check(&rendered)
+                    .into_iter()
+                    .map(|failure| format!("{}: {failure}", tool.id())),
+            );
+        }
+        let snapshot = snapshots.join(format!("{}.md", tool.id()));
+        if update_snapshots {
+            write_snapshot(&snapshot, &rendered)?;
+        } else if compare_snapshots {
+            checks += 1;
+            if let Some(failure) = compare_snapshot(&snapshot, &rendered)? {
+                failures.push(format!("{}: {failure}", tool.id()));
+            }
+        }
+    }
+    Ok((checks, failures))
+}
+
+/// Write a tool's rendering as its golden snapshot.
+fn write_snapshot(path: &Path, rendered: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| Error::SkillWrite {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(path, rendered).map_err(|source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Compare a rendering with its snapshot, returning a message when they differ.
+fn compare_snapshot(path: &Path, rendered: &str) -> Result<Option<String>> {
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(Some(
+                "no snapshot (run with --update-snapshots)".to_string(),
+            ));
+        }
+        Err(source) => {
+            return Err(Error::SkillRead {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+    if expected == rendered {
+        return Ok(None);
+    }
+    let line = expected
+        .lines()
+        .zip(rendered.lines())
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| expected.lines().count().min(rendered.lines().count()))
+        + 1;
+    Ok(Some(format!(
+        "rendering differs from snapshot at line {line}"
+    )))
+}
+
+/// Read a skill's assertions, treating a missing file as having none.
+fn load_tests(skill_dir: &Path) -> Result<SkillTests> {
+    let path = skill_dir.join(TESTS_FILE);
+    match fs::read_to_string(&path) {
+        Ok(contents) => toml::from_str(&contents).map_err(|error| Error::InvalidSkillFile {
+            path,
+            message: error.message().to_string(),
+        }),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(SkillTests::default()),
+        Err(source) => Err(Error::SkillRead { path, source }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{SNAPSHOTS_DIR, run};
+    use crate::{
+        error::Error,
+        testutil::{TestFixture, simple_skill, skill_content},
+    };
+
+    #[tokio::test]
+    async fn checks_rendered_output_per_tool() {
+        let fixture = TestFixture::new()
+            .with_source_skill("plain", &simple_skill("plain"))
+            .with_source_skill(
+                "tools",
+                &skill_content(
+                    "tools",
+                    "Tool specific",
+                    "{% if tool == \"codex\" %}apply_patch{% endif %}",
+                ),
+            );
+        let tests = fixture.source_dir().join("tools").join("tests.toml");
+        fs::write(
+            &tests,
+            "[[test]]\ntool = \"codex\"\ncontains = [\"apply_patch\"]\n\n[[test]]\nnot_contains = [\"{{\"]\n",
+        )
+        .expect("write");
+        let ctx = fixture.context();
+
+        run(&ctx, Vec::new(), false).await.expect("tests pass");
+
+        fs::write(
+            &tests,
+            "[[test]]\ntool = \"claude\"\ncontains = [\"apply_patch\"]\n",
+        )
+        .expect("write");
+        let error = run(&ctx, vec!["tools".to_string()], false)
+            .await
+            .expect_err("claude rendering lacks the text");
+        assert!(matches!(error, Error::TestFailed { count: 1 }));
+    }
+
+    #[tokio::test]
+    async fn fails_when_rendering_drifts_from_snapshot() {
+        let fixture = TestFixture::new().with_source_skill("pdf", &simple_skill("pdf"));
+        let ctx = fixture.context();
+        let snapshots = fixture.source_dir().join("pdf").join(SNAPSHOTS_DIR);
+
+        run(&ctx, Vec::new(), true).await.expect("update snapshots");
+        assert!(snapshots.join("claude.md").is_file());
+        assert!(snapshots.join("codex.md").is_file());
+        run(&ctx, Vec::new(), false).await.expect("snapshots match");
+
+        fs::write(
+            fixture.source_dir().join("pdf").join("SKILL.md"),
+            skill_content("pdf", "Test skill", "Changed body"),
+        )
+        .expect("write");
+        let error = run(&ctx, Vec::new(), false)
+            .await
+            .expect_err("rendering drifted");
+        assert!(matches!(error, Error::TestFailed { count: 1 }));
+
+        run(&ctx, Vec::new(), true).await.expect("update snapshots");
+        run(&ctx, Vec::new(), false).await.expect("snapshots match");
+    }
+}
@@ -1,38 +1,53 @@
 //! Implementation of the `skills unload` command.
 
-use std::fs;
+use std::{fs, path::PathBuf};
 
 use inquire::{Confirm, error::InquireError};
 
 use crate::{
-    commands::{ColorChoice, init},
+    commands::init,
+    context::Context,
     error::{Error, Result},
-    skill::SKILL_FILE_NAME,
-    tool::ToolFilter,
+    palette::Role,
+    paths::display_path,
+    tool::{Tool, ToolFilter},
 };
 
-/// Execute the unload command.
+/// Execute the unload command, removing every orphan skill when no skill is named.
 pub async fn run(
-    _color: ColorChoice,
-    _verbose: bool,
-    skill: String,
+    ctx: &Context,
+    skill: Option<String>,
     tool_filter: ToolFilter,
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    init::ensure().await?;
+    match skill {
+        Some(skill) => unload_skill(ctx, &skill, tool_filter, dry_run, force).await,
+        None => unload_orphans(ctx, tool_filter, dry_run, force).await,
+    }
+}
+
+/// Remove one skill from the selected tools.
+async fn unload_skill(
+    ctx: &Context,
+    skill: &str,
+    tool_filter: ToolFilter,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
 
     let tools = tool_filter.to_tools();
+    let mut state = ctx.load_state()?;
     let mut found_any = false;
 
     println!("Unloading {}...", skill);
 
     for tool in tools {
-        let tool_dir = tool.skills_dir()?;
-        let skill_dir = tool_dir.join(&skill);
-        let skill_path = skill_dir.join(SKILL_FILE_NAME);
-
-        if !skill_path.is_file() {
+        let tool_dir = ctx.tool_dir(tool);
+        let skill_dir = tool_dir.join(skill);
+        if !config.skill_files.path_in(&skill_dir).is_file() {
             println!("  {:<6}: - (not installed)", tool.id());
             continue;
         }
@@ -40,11 +55,7 @@ pub async fn run(
         found_any = true;
 
         if !force && !dry_run {
-            let prompt = format!(
-                "Remove skill '{}' from {}?",
-                skill,
-                tool.display_name()
-            );
+            let prompt = format!("Remove skill '{}' from {}?", skill, tool.display_name());
             let confirmed = confirm(&prompt)?;
             if !confirmed {
                 println!("  {:<6}: ! (skipped)", tool.id());
@@ -57,11 +68,16 @@ pub async fn run(
                 path: skill_dir.clone(),
                 source: e,
             })?;
+            state.forget(&skill_dir);
         }
 
         println!("  {:<6}: - (removed)", tool.id());
     }
 
+    if !dry_run {
+        state.save(&ctx.state_path)?;
+    }
+
     if !found_any {
         println!("Skill '{}' is not installed in any tool.", skill);
     }
@@ -69,6 +85,88 @@ pub async fn run(
     Ok(())
 }
 
+/// Remove every orphan tool skill.
+///
+/// Orphans are installed skills with no source of the same name. They are listed together and
+/// removed after a single confirmation.
+async fn unload_orphans(
+    ctx: &Context,
+    tool_filter: ToolFilter,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
+
+    let mut orphans: Vec<(Tool, String, PathBuf)> = Vec::new();
+    for tool in tool_filter.to_tools() {
+        let Some(skills) = catalog.tools.get(&tool) else {
+            continue;
+        };
+        for (name, skill) in skills {
+            if catalog.sources.contains_key(name) {
+                continue;
+            }
+            if let Some(dir) = skill.skill_path.parent() {
+                orphans.push((tool, name.clone(), dir.to_path_buf()));
+            }
+        }
+    }
+    orphans.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.id().cmp(b.0.id())));
+    diagnostics.print_summary();
+
+    if orphans.is_empty() {
+        println!("No orphan skills found.");
+        return Ok(());
+    }
+
+    println!("{}", palette.paint(Role::Label, "Orphan skills:"));
+    for (tool, name, dir) in &orphans {
+        println!(
+            "  {:<6}: {} {}",
+            tool.id(),
+            palette.paint(Role::SkillName, name),
+            palette.paint(Role::Path, &display_path(dir))
+        );
+    }
+
+    if dry_run {
+        println!(
+            "{} {} orphan skills would be removed.",
+            palette.paint(Role::Label, "Dry run:"),
+            orphans.len()
+        );
+        return Ok(());
+    }
+
+    if !force {
+        let prompt = format!("Remove {} orphan skills?", orphans.len());
+        if !confirm(&prompt)? {
+            println!("No skills removed.");
+            return Ok(());
+        }
+    }
+
+    let mut state = ctx.load_state()?;
+    for (_, _, dir) in &orphans {
+        fs::remove_dir_all(dir).map_err(|e| Error::SkillWrite {
+            path: dir.clone(),
+            source: e,
+        })?;
+        state.forget(dir);
+    }
+    state.save(&ctx.state_path)?;
+
+    println!(
+        "{} {} orphan skills removed.",
+        palette.paint(Role::Label, "Done:"),
+        orphans.len()
+    );
+    Ok(())
+}
 
 /// Prompt for confirmation.
 fn confirm(message: &str) -> Result<bool> {
@@ -82,3 +180,34 @@ fn confirm(message: &str) -> Result<bool> {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::{
+        testutil::{TestFixture, simple_skill},
+        tool::{Tool, ToolFilter},
+    };
+
+    #[tokio::test]
+    async fn removes_only_orphan_skills() {
+        let fixture = TestFixture::new()
+            .with_source_skill("kept", &simple_skill("kept"))
+            .with_tool_skill(Tool::Claude, "kept", &simple_skill("kept"))
+            .with_tool_skill(Tool::Claude, "stray", &simple_skill("stray"))
+            .with_tool_skill(Tool::Codex, "stray", &simple_skill("stray"));
+        let ctx = fixture.context();
+
+        run(&ctx, None, ToolFilter::All, true, false)
+            .await
+            .expect("dry run");
+        assert!(fixture.tool_dir(Tool::Codex).join("stray").is_dir());
+
+        run(&ctx, None, ToolFilter::All, false, true)
+            .await
+            .expect("unload orphans");
+        assert!(fixture.tool_dir(Tool::Claude).join("kept").is_dir());
+        assert!(!fixture.tool_dir(Tool::Claude).join("stray").exists());
+        assert!(!fixture.tool_dir(Tool::Codex).join("stray").exists());
+    }
+}
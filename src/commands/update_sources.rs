@@ -0,0 +1,271 @@
+//! Implementation of the `skills update-sources` command.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use crate::{
+    catalog::{Catalog, CatalogRoots},
+    commands::{init, sync},
+    context::Context,
+    diagnostics::Diagnostics,
+    error::Result,
+    git,
+    palette::{Palette, Role},
+    paths::display_path,
+    skill::SkillFileNames,
+    state::hash_dir,
+};
+
+/// Skills that changed in one source across an update.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SkillChanges {
+    /// Skills the update added.
+    added: Vec<String>,
+    /// Skills whose files the update changed.
+    modified: Vec<String>,
+    /// Skills the update removed.
+    removed: Vec<String>,
+}
+
+impl SkillChanges {
+    /// Compare a source's skill files before and after an update.
+    fn between(before: &SourceFiles, after: &SourceFiles) -> Self {
+        let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        let mut changes = Self::default();
+        for name in names {
+            match (before.get(name), after.get(name)) {
+                (None, Some(_)) => changes.added.push(name.clone()),
+                (Some(_), None) => changes.removed.push(name.clone()),
+                (Some(old), Some(new)) if old != new => changes.modified.push(name.clone()),
+                _ => {}
+            }
+        }
+        changes
+    }
+
+    /// Return whether no skill changed.
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// File hashes of each skill in a source, keyed by skill name.
+type SourceFiles = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Execute the update-sources command, pulling every git-backed source from its upstream and
+/// reporting the skills that changed.
+///
+/// With `run_sync`, the modified skills are then synced to the tools. Added skills are left for
+/// `skills push`, like any new skill.
+pub async fn run(ctx: &Context, run_sync: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+
+    let mut added = BTreeSet::new();
+    let mut modified = BTreeSet::new();
+    for source in config.sources() {
+        let label = palette.paint(Role::Path, &display_path(source));
+        if !git::is_repo(source) {
+            println!("{label}: not a git repository (skipped)");
+            continue;
+        }
+        let before = source_files(source, &config.skill_files, &mut diagnostics);
+        let (from, to) = match pull(source) {
+            Ok(revisions) => revisions,
+            Err(error) => {
+                println!("{label}: {}", palette.paint(Role::Failure, "update failed"));
+                diagnostics.warn(error.to_string());
+                continue;
+            }
+        };
+        if from == to {
+            println!("{label}: up to date");
+            continue;
+        }
+        println!("{label}: updated {}..{}", short(&from), short(&to));
+        let after = source_files(source, &config.skill_files, &mut diagnostics);
+        let changes = SkillChanges::between(&before, &after);
+        print_changes(&changes, &palette);
+        added.extend(changes.added);
+        modified.extend(changes.modified);
+    }
+
+    // A skill shadowed by an earlier source is unaffected by its update.
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let resolved = |names: BTreeSet<String>| -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|name| catalog.sources.contains_key(name))
+            .collect()
+    };
+    let (added, modified) = (resolved(added), resolved(modified));
+    diagnostics.print_summary();
+    if !added.is_empty() {
+        println!();
+        println!(
+            "Run `skills push {}` to install new skills.",
+            added.join(" ")
+        );
+    }
+    if modified.is_empty() {
+        return Ok(());
+    }
+    if !run_sync {
+        println!();
+        println!(
+            "Run `skills sync {}` to update your tools.",
+            modified.join(" ")
+        );
+        return Ok(());
+    }
+    println!();
+    sync::run(ctx, modified, false, false, None, None, false).await
+}
+
+/// Pull a source repository from its upstream, rebasing local commits and stashing local edits
+/// around the pull, and return the revisions before and after.
+fn pull(source: &Path) -> Result<(String, String)> {
+    let from = git::run(source, &["rev-parse", "HEAD"])?;
+    git::run(source, &["pull", "--rebase", "--autostash", "--quiet"])?;
+    let to = git::run(source, &["rev-parse", "HEAD"])?;
+    Ok((from, to))
+}
+
+/// Hash the files of every skill in one source.
+fn source_files(
+    source: &Path,
+    names: &SkillFileNames,
+    diagnostics: &mut Diagnostics,
+) -> SourceFiles {
+    let roots = CatalogRoots {
+        sources: vec![source.to_path_buf()],
+        skill_files: names.clone(),
+        ..CatalogRoots::default()
+    };
+    Catalog::load(&roots, diagnostics)
+        .sources
+        .into_values()
+        .filter_map(|skill| Some((skill.name, hash_dir(&skill.skill_dir).ok()?)))
+        .collect()
+}
+
+/// Print the skills that changed in a source, one per line.
+fn print_changes(changes: &SkillChanges, palette: &Palette) {
+    if changes.is_empty() {
+        println!("    no skill changes");
+    }
+    let groups = [
+        ("+", Role::DiffAdd, &changes.added),
+        ("~", Role::StatusModified, &changes.modified),
+        ("-", Role::DiffRemove, &changes.removed),
+    ];
+    for (marker, role, names) in groups {
+        for name in names {
+            println!(
+                "    {} {}",
+                palette.paint(role, marker),
+                palette.paint(Role::SkillName, name)
+            );
+        }
+    }
+}
+
+/// Abbreviate a commit hash for display.
+fn short(revision: &str) -> &str {
+    revision.get(..7).unwrap_or(revision)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{SkillChanges, run, source_files};
+    use crate::{
+        diagnostics::Diagnostics,
+        git,
+        skill::SkillFileNames,
+        testutil::{TestFixture, simple_skill, skill_content},
+        tool::Tool,
+    };
+
+    /// Commit everything in a repository.
+    fn commit(repo: &Path, message: &str) {
+        git::run(repo, &["add", "-A"]).expect("add");
+        git::run(
+            repo,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                message,
+            ],
+        )
+        .expect("commit");
+    }
+
+    #[tokio::test]
+    async fn pulls_sources_and_reports_changed_skills() {
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Claude, "edited", &simple_skill("edited"));
+        let upstream = fixture.work_dir().join("upstream");
+        for name in ["kept", "edited", "dropped"] {
+            fs::create_dir_all(upstream.join(name)).expect("mkdir");
+            fs::write(upstream.join(name).join("SKILL.md"), simple_skill(name)).expect("write");
+        }
+        git::run(fixture.work_dir(), &["init", "--quiet", "upstream"]).expect("init");
+        commit(&upstream, "Initial skills");
+        let source = fixture.source_dir();
+        fs::remove_dir_all(source).expect("clear source");
+        git::run(
+            fixture.work_dir(),
+            &["clone", "--quiet", "upstream", &source.to_string_lossy()],
+        )
+        .expect("clone");
+
+        let mut diagnostics = Diagnostics::default();
+        let names = SkillFileNames::default();
+        let before = source_files(source, &names, &mut diagnostics);
+
+        fs::write(
+            upstream.join("edited").join("SKILL.md"),
+            skill_content("edited", "Edited", "New body"),
+        )
+        .expect("write");
+        fs::remove_dir_all(upstream.join("dropped")).expect("remove");
+        fs::create_dir_all(upstream.join("added")).expect("mkdir");
+        fs::write(
+            upstream.join("added").join("SKILL.md"),
+            simple_skill("added"),
+        )
+        .expect("write");
+        commit(&upstream, "Update skills");
+
+        let ctx = fixture.context();
+        run(&ctx, true).await.expect("update");
+
+        let after = source_files(source, &names, &mut diagnostics);
+        assert_eq!(
+            SkillChanges::between(&before, &after),
+            SkillChanges {
+                added: vec!["added".to_string()],
+                modified: vec!["edited".to_string()],
+                removed: vec!["dropped".to_string()],
+            }
+        );
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "added"));
+        assert!(
+            fixture
+                .read_tool_skill(Tool::Claude, "edited")
+                .expect("synced")
+                .contains("New body")
+        );
+    }
+}
@@ -1,107 +1,389 @@
 //! Implementation of the `skills validate` command.
 
-use owo_colors::OwoColorize;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fs,
+    path::PathBuf,
+};
+
+use serde::Serialize;
 
 use crate::{
-    catalog::Catalog,
-    commands::{ColorChoice, init},
-    config::Config,
-    diagnostics::Diagnostics,
-    error::Result,
-    frontmatter::parse_frontmatter,
-    skill::render_template,
+    commands::{init, print_json},
+    context::Context,
+    error::{Error, Result},
+    frontmatter::{frontmatter_json, parse_frontmatter},
+    layout::{output_width, wrap_lines},
+    name::normalize_name,
+    palette::{Palette, Role},
+    prose::{self, Dictionary},
+    schema::Schema,
+    similarity::{SimilarPair, similar_descriptions},
+    skill::{BaseTemplates, SkillTemplate, render_template_located},
     tool::Tool,
+    units::format_count,
 };
 
-/// Execute the validate command.
+/// Everything `validate --json` prints.
+#[derive(Debug, Serialize)]
+struct Report {
+    /// Result of checking each skill.
+    skills: Vec<Validated>,
+    /// Pairs of skills with descriptions too alike to choose between.
+    similar: Vec<SimilarPair>,
+}
+
+/// The result of checking one skill.
+#[derive(Debug, Serialize)]
+struct Validated {
+    /// Skill name.
+    name: String,
+    /// Whether the skill passed every check.
+    valid: bool,
+    /// Problems found in the skill file.
+    problems: Vec<Problem>,
+    /// What `--fix` changed before the checks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixed: Option<String>,
+}
+
+/// Execute the validate command, also linting skill bodies when `prose` is set.
+///
+/// With `fix`, source directories whose names differ from their skills' frontmatter names are
+/// renamed to match before they are checked. Skills whose descriptions are too alike for an agent
+/// to choose between are reported after the checks. With `json`, the results are printed as JSON
+/// instead, and a named skill that is not found is an error.
 pub async fn run(
-    color: ColorChoice,
-    verbose: bool,
+    ctx: &Context,
     skill_name: Option<String>,
+    prose: bool,
+    fix: bool,
+    json: bool,
 ) -> Result<()> {
-    init::ensure().await?;
-    let mut diagnostics = Diagnostics::new(verbose);
-    let config = Config::load()?;
-    let catalog = Catalog::load(&config, &mut diagnostics);
-    let use_color = color.enabled();
+    init::ensure(ctx).await?;
+    let mut diagnostics = ctx.diagnostics();
+    let config = ctx.load_config()?;
+    let catalog = ctx.load_catalog(&config, &mut diagnostics);
+    let palette = ctx.palette(&config);
 
     // Collect skills to validate
+    let focus = skill_name.as_deref().map(|name| catalog.resolve_name(name));
     let skills_to_validate: Vec<_> = if let Some(name) = skill_name {
-        if let Some(skill) = catalog.sources.get(&name) {
+        if let Some(skill) = focus.as_ref().and_then(|focus| catalog.sources.get(focus)) {
             vec![(name, skill)]
+        } else if json {
+            return Err(Error::SkillNotFound { name });
         } else {
             println!("Skill '{}' not found in sources.", name);
             return Ok(());
         }
     } else {
-        catalog.sources.iter().map(|(n, s)| (n.clone(), s)).collect()
+        catalog
+            .sources
+            .iter()
+            .map(|(n, s)| (n.clone(), s))
+            .collect()
     };
 
-    if skills_to_validate.is_empty() {
+    if skills_to_validate.is_empty() && !json {
         println!("No skills to validate.");
         return Ok(());
     }
 
-    let mut valid_count = 0;
-    let mut invalid_count = 0;
+    let mut results = Vec::new();
+    let mut dictionaries: HashMap<PathBuf, Dictionary> = HashMap::new();
+    let mut schemas: HashMap<PathBuf, Option<Schema>> = HashMap::new();
 
     for (name, skill) in skills_to_validate {
-        let mut errors = Vec::new();
-
-        // Check 1: Frontmatter parsing
-        match parse_frontmatter(&skill.contents) {
-            Ok(fm) => {
-                // Check name matches directory
-                if fm.name != name {
-                    errors.push(format!(
-                        "frontmatter name '{}' does not match directory name '{}'",
-                        fm.name, name
+        let dictionary = if prose {
+            Some(match dictionaries.entry(skill.source_root.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(Dictionary::load(&skill.source_root)?),
+            })
+        } else {
+            None
+        };
+        let schema = match schemas.entry(skill.source_root.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Schema::load(&skill.source_root)?),
+        };
+        let mut dir_name = skill.dir_name().to_string();
+        let mut fixed = None;
+        if fix && dir_name != skill.name {
+            match rename_to_name(skill) {
+                Ok(renamed) => {
+                    fixed = Some(format!(
+                        "renamed directory '{dir_name}' to '{}'",
+                        skill.name
                     ));
+                    dir_name = renamed;
                 }
-                // Check description exists
-                if fm.description.is_empty() {
-                    errors.push("description is empty".to_string());
-                }
-            }
-            Err(e) => {
-                errors.push(format!("frontmatter: {}", e.message));
+                Err(error) => diagnostics.warn(format!("cannot fix '{name}': {error}")),
             }
         }
+        let errors = check_skill(
+            &dir_name,
+            &skill.contents,
+            &skill.bases,
+            schema.as_ref(),
+            dictionary.as_deref(),
+        );
 
-        // Check 2: Template rendering for all tools
-        for tool in Tool::all() {
-            if let Err(e) = render_template(&skill.contents, tool) {
-                errors.push(format!("template ({} render): {}", tool.id(), e));
-            }
+        let result = Validated {
+            name,
+            valid: errors.is_empty(),
+            problems: errors,
+            fixed,
+        };
+        if !json {
+            print_result(&result, &palette);
         }
+        results.push(result);
+    }
+
+    let descriptions: Vec<(&str, &str)> = catalog
+        .sources
+        .iter()
+        .map(|(name, skill)| (name.as_str(), skill.description.as_str()))
+        .collect();
+    let similar = similar_descriptions(&descriptions, focus.as_deref());
+    if json {
+        print_json(&Report {
+            skills: results,
+            similar,
+        })?;
+        diagnostics.print_summary();
+        return Ok(());
+    }
+    if !similar.is_empty() {
+        println!();
+        println!(
+            "{}",
+            palette.paint(Role::WarningHeading, "Similar descriptions:")
+        );
+        print_similar(&similar, &palette);
+    }
 
-        // Print result
-        if errors.is_empty() {
-            valid_count += 1;
-            if use_color {
-                println!("{} {}", "✓".green(), name);
-            } else {
-                println!("✓ {}", name);
+    let valid_count = results.iter().filter(|result| result.valid).count();
+    let invalid_count = results.len() - valid_count;
+    println!();
+    if similar.is_empty() {
+        println!("{} valid, {} invalid", valid_count, invalid_count);
+    } else {
+        println!(
+            "{} valid, {} invalid, {}",
+            valid_count,
+            invalid_count,
+            format_count(similar.len(), "similar description pair")
+        );
+    }
+    diagnostics.print_summary();
+
+    Ok(())
+}
+
+/// Print whether a skill passed its checks, with each problem found.
+fn print_result(result: &Validated, palette: &Palette) {
+    if result.valid {
+        println!("{} {}", palette.paint(Role::Success, "✓"), result.name);
+        if let Some(fixed) = &result.fixed {
+            println!("    fixed: {fixed}");
+        }
+    } else {
+        println!("{} {}", palette.paint(Role::Failure, "✗"), result.name);
+        for problem in &result.problems {
+            println!("    - {}", problem.message);
+        }
+    }
+}
+
+/// Print pairs of skills with similar descriptions, each followed by how to tell them apart.
+pub fn print_similar(pairs: &[SimilarPair], palette: &Palette) {
+    let width = output_width();
+    for pair in pairs {
+        println!(
+            "{} {} and {} ({:.0}% alike)",
+            palette.paint(Role::Warning, "!"),
+            pair.first,
+            pair.second,
+            pair.score * 100.0
+        );
+        for line in wrap_lines(&pair.suggestion(), "    ", width) {
+            println!("    {line}");
+        }
+    }
+}
+
+/// Rename a source skill's directory to its frontmatter name, returning the new directory name.
+fn rename_to_name(skill: &SkillTemplate) -> Result<String> {
+    let target = skill.skill_dir.with_file_name(&skill.name);
+    if normalize_name(&skill.name).ok().as_ref() != Some(&skill.name) {
+        return Err(Error::InvalidPath { path: target });
+    }
+    if target.exists() {
+        return Err(Error::PathExists { path: target });
+    }
+    fs::rename(&skill.skill_dir, &target).map_err(|source| Error::SkillMove {
+        from: skill.skill_dir.clone(),
+        to: target,
+        source,
+    })?;
+    Ok(skill.name.clone())
+}
+
+/// A problem found while validating a skill file.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    /// One-based line the problem is on, when it is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Problem {
+    /// Create a problem with no known line.
+    fn new(message: String) -> Self {
+        Self {
+            line: None,
+            message,
+        }
+    }
+}
+
+/// Check a skill file's frontmatter and templates, and its prose when a dictionary is given.
+///
+/// `name` is the skill's directory name, which the frontmatter name must match, and `bases` are
+/// the templates its source lets it extend. When the source has a `schema`, the frontmatter must
+/// also match it.
+pub fn check_skill(
+    name: &str,
+    contents: &str,
+    bases: &BaseTemplates,
+    schema: Option<&Schema>,
+    dictionary: Option<&Dictionary>,
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    // Check 1: Frontmatter parsing
+    match parse_frontmatter(contents) {
+        Ok(fm) => {
+            // Check name matches directory
+            if fm.name != name {
+                problems.push(Problem::new(format!(
+                    "frontmatter name '{}' does not match directory name '{}'",
+                    fm.name, name
+                )));
             }
-        } else {
-            invalid_count += 1;
-            if use_color {
-                println!("{} {}", "✗".red(), name);
-            } else {
-                println!("✗ {}", name);
+            // Check description exists
+            if fm.description.is_empty() {
+                problems.push(Problem::new("description is empty".to_string()));
             }
-            for error in errors {
-                println!("    - {}", error);
+        }
+        Err(e) => {
+            problems.push(Problem::new(format!("frontmatter: {}", e.message)));
+        }
+    }
+
+    // Check 2: Source frontmatter schema
+    if let Some(schema) = schema
+        && let Some((frontmatter, lines)) = frontmatter_json(contents)
+    {
+        problems.extend(schema.validate(&frontmatter).into_iter().map(|violation| {
+            Problem {
+                line: violation
+                    .field()
+                    .and_then(|field| lines.get(field).copied()),
+                message: format!("schema: {violation}"),
             }
+        }));
+    }
+
+    // Check 3: Template rendering for all tools
+    for tool in Tool::all() {
+        if let Err((line, e)) = render_template_located(contents, tool, bases) {
+            problems.push(Problem {
+                line,
+                message: format!("template ({} render): {}", tool.id(), e),
+            });
         }
     }
 
-    println!();
-    println!(
-        "{} valid, {} invalid",
-        valid_count,
-        invalid_count
-    );
+    // Check 4: Spelling and prose lints
+    if let Some(dictionary) = dictionary {
+        problems.extend(
+            prose::lint(contents, dictionary)
+                .into_iter()
+                .map(|finding| Problem {
+                    line: Some(finding.line),
+                    message: format!("prose: {finding}"),
+                }),
+        );
+    }
 
-    Ok(())
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{check_skill, run};
+    use crate::{
+        schema::{SCHEMA_FILE, Schema},
+        skill::BaseTemplates,
+        testutil::{TestFixture, simple_skill},
+    };
+
+    #[test]
+    fn checks_frontmatter_against_source_schema() {
+        let dir = tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join(SCHEMA_FILE),
+            r#"{"required": ["owner"], "properties": {"tags": {"items": {"enum": ["docs"]}}}}"#,
+        )
+        .expect("write");
+        let schema = Schema::load(dir.path()).expect("load").expect("schema");
+        let contents =
+            "---\nname: pdf\ndescription: PDF tools\ntags:\n  - docs\n  - misc\n---\nBody\n";
+
+        let problems = check_skill(
+            "pdf",
+            contents,
+            &BaseTemplates::default(),
+            Some(&schema),
+            None,
+        );
+        let found: Vec<(Option<usize>, &str)> = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (None, "schema: owner: missing required field"),
+                (Some(4), "schema: tags[1]: \"misc\" is not one of \"docs\""),
+            ]
+        );
+        assert!(check_skill("pdf", contents, &BaseTemplates::default(), None, None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn fix_renames_directories_to_skill_names() {
+        let fixture = TestFixture::new().with_source_skill("pdf-tools", &simple_skill("pdf"));
+        let ctx = fixture.context();
+
+        run(&ctx, Some("pdf-tools".to_string()), false, false, false)
+            .await
+            .expect("validate");
+        assert!(fixture.source_skill_exists("pdf-tools"));
+
+        run(&ctx, Some("pdf-tools".to_string()), false, true, false)
+            .await
+            .expect("fix");
+        assert!(!fixture.source_skill_exists("pdf-tools"));
+        assert!(fixture.source_skill_exists("pdf"));
+    }
 }
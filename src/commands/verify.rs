@@ -0,0 +1,225 @@
+//! Implementation of the `skills verify` command.
+
+use std::path::Path;
+
+use crate::{
+    commands::init,
+    context::Context,
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+    state::{InstallRecord, hash_dir},
+    tool::Tool,
+};
+
+/// Execute the verify command.
+pub async fn run(ctx: &Context, skills: Vec<String>) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let palette = ctx.palette(&config);
+    let state = ctx.load_state()?;
+
+    let mut results = Vec::new();
+    for (dir, record) in &state.installs {
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !skills.is_empty() && !skills.contains(&name) {
+            continue;
+        }
+        let label = location_label(ctx, dir);
+        let verification = verify_install(dir, record)?;
+        results.push((name, label, verification));
+    }
+
+    if results.is_empty() {
+        println!("No recorded installs to verify.");
+        return Ok(());
+    }
+
+    results.sort_by(|a, b| {
+        a.0.to_lowercase()
+            .cmp(&b.0.to_lowercase())
+            .then_with(|| a.1.cmp(&b.1))
+    });
+
+    let mut current = None;
+    for (name, label, verification) in &results {
+        if current != Some(name) {
+            println!("{}", palette.paint(Role::SkillName, name));
+            current = Some(name);
+        }
+        if verification.is_clean() {
+            println!(
+                "    {:<6}: = ({})",
+                label,
+                palette.paint(Role::StatusSynced, "verified")
+            );
+        } else {
+            println!(
+                "    {:<6}: ! ({})",
+                label,
+                palette.paint(Role::StatusError, &verification.summary())
+            );
+        }
+    }
+
+    let failed = results.iter().filter(|(_, _, v)| !v.is_clean()).count();
+    println!();
+    println!(
+        "{} {} verified, {} with problems.",
+        palette.paint(Role::Label, "Done:"),
+        results.len() - failed,
+        failed
+    );
+
+    if failed > 0 {
+        return Err(Error::VerifyFailed { count: failed });
+    }
+    Ok(())
+}
+
+/// Differences between an installed skill directory and its install record.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Verification {
+    /// Recorded files whose contents changed.
+    tampered: Vec<String>,
+    /// Files present now that were not part of the install.
+    drifted: Vec<String>,
+    /// Recorded files that no longer exist.
+    missing: Vec<String>,
+}
+
+impl Verification {
+    /// Check whether the install matches its record exactly.
+    fn is_clean(&self) -> bool {
+        self.tampered.is_empty() && self.drifted.is_empty() && self.missing.is_empty()
+    }
+
+    /// Describe every problem found, e.g. `tampered: SKILL.md; missing: run.sh`.
+    fn summary(&self) -> String {
+        [
+            ("tampered", &self.tampered),
+            ("drifted", &self.drifted),
+            ("missing", &self.missing),
+        ]
+        .iter()
+        .filter(|(_, files)| !files.is_empty())
+        .map(|(kind, files)| format!("{kind}: {}", files.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+    }
+}
+
+/// Compare a skill directory on disk against the hashes recorded when it was installed.
+fn verify_install(dir: &Path, record: &InstallRecord) -> Result<Verification> {
+    let current = if dir.is_dir() {
+        hash_dir(dir)?
+    } else {
+        Default::default()
+    };
+
+    let mut verification = Verification::default();
+    for (file, hash) in &record.files {
+        match current.get(file) {
+            None => verification.missing.push(file.clone()),
+            Some(current_hash) if current_hash != hash => verification.tampered.push(file.clone()),
+            Some(_) => {}
+        }
+    }
+    verification.drifted = current
+        .keys()
+        .filter(|file| !record.files.contains_key(*file))
+        .cloned()
+        .collect();
+    Ok(verification)
+}
+
+/// Label an install location by tool when it is a known skills directory.
+fn location_label(ctx: &Context, dir: &Path) -> String {
+    let Some(parent) = dir.parent() else {
+        return display_path(dir);
+    };
+    for tool in Tool::all() {
+        if parent == ctx.tool_dir(tool) {
+            return tool.id().to_string();
+        }
+        if parent == ctx.local_dir(tool) {
+            return format!("{} (project)", tool.id());
+        }
+    }
+    display_path(parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{run, verify_install};
+    use crate::{
+        commands::push::{self, PushOptions},
+        error::Error,
+        skill::SKILL_FILE_NAME,
+        state::{InstallOrigin, State},
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[test]
+    fn reports_tampered_drifted_and_missing_files() {
+        let fixture = TestFixture::new().with_tool_skill(Tool::Claude, "alpha", "original");
+        let dir = fixture.tool_dir(Tool::Claude).join("alpha");
+        fs::write(dir.join("run.sh"), "echo").expect("write");
+
+        let mut state = State::default();
+        state.record(&dir, InstallOrigin::Import).expect("record");
+        let record = &state.installs[&dir];
+
+        fs::write(dir.join(SKILL_FILE_NAME), "changed").expect("write");
+        fs::remove_file(dir.join("run.sh")).expect("remove");
+        fs::write(dir.join("notes.md"), "extra").expect("write");
+
+        let verification = verify_install(&dir, record).expect("verify");
+        assert_eq!(verification.tampered, vec![SKILL_FILE_NAME]);
+        assert_eq!(verification.drifted, vec!["notes.md"]);
+        assert_eq!(verification.missing, vec!["run.sh"]);
+        assert_eq!(
+            verification.summary(),
+            "tampered: SKILL.md; drifted: notes.md; missing: run.sh"
+        );
+
+        fs::remove_dir_all(&dir).expect("remove");
+        let verification = verify_install(&dir, record).expect("verify");
+        assert_eq!(verification.missing, vec![SKILL_FILE_NAME, "run.sh"]);
+    }
+
+    #[tokio::test]
+    async fn run_fails_when_pushed_copies_change() {
+        let fixture = TestFixture::new().with_source_skill("alpha", &simple_skill("alpha"));
+        let ctx = fixture.context();
+
+        push::run(
+            &ctx,
+            Vec::new(),
+            Tool::all().to_vec(),
+            PushOptions {
+                all: true,
+                yes: true,
+                ..PushOptions::default()
+            },
+        )
+        .await
+        .expect("push");
+        run(&ctx, Vec::new()).await.expect("fresh push verifies");
+
+        let skill_path = fixture
+            .tool_dir(Tool::Codex)
+            .join("alpha")
+            .join(SKILL_FILE_NAME);
+        fs::write(&skill_path, "tampered").expect("write");
+
+        let error = run(&ctx, Vec::new()).await.expect_err("tampered copy");
+        assert!(matches!(error, Error::VerifyFailed { count: 1 }));
+    }
+}
@@ -0,0 +1,249 @@
+//! Implementation of the `skills watch` command.
+
+use std::{path::PathBuf, pin::pin, time::Duration};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::{
+    select, signal,
+    sync::mpsc::{self, UnboundedReceiver},
+    time,
+};
+
+use crate::{
+    catalog::Catalog,
+    commands::{
+        init,
+        push::{self, PushOptions},
+        shell,
+    },
+    config::Config,
+    context::Context,
+    diagnostics::Diagnostics,
+    error::{Error, Result},
+    palette::Role,
+    paths::display_path,
+    skill::TEMPLATES_DIR,
+    tool::Tool,
+};
+
+/// Execute the watch command, pushing source skills to `tools` as their files change until the
+/// user presses Ctrl-C.
+///
+/// Changes are gathered until none has arrived for `debounce`, so an editor's burst of writes
+/// becomes one push. A change to a source's shared templates pushes every skill in that source.
+/// With `force`, modified tool copies are overwritten without prompting; otherwise the push asks
+/// as `skills push` does. A failed push is reported and watching continues. Ctrl-C stops watching
+/// at any point, including while a push is running.
+pub async fn run(ctx: &Context, tools: Vec<Tool>, debounce: Duration, force: bool) -> Result<()> {
+    init::ensure(ctx).await?;
+    let config = ctx.load_config()?;
+    let sources = config.sources().to_vec();
+
+    let (sender, mut events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        // Sending only fails once the loop below has stopped listening.
+        sender.send(event).ok();
+    })
+    .map_err(|error| Error::WatchFailed {
+        message: error.to_string(),
+    })?;
+    for source in &sources {
+        watcher
+            .watch(source, RecursiveMode::Recursive)
+            .map_err(|error| Error::WatchFailed {
+                message: format!("{}: {error}", display_path(source)),
+            })?;
+    }
+
+    let watched: Vec<String> = sources.iter().map(|source| display_path(source)).collect();
+    println!(
+        "Watching {} for changes. Press Ctrl-C to stop.",
+        watched.join(", ")
+    );
+    watch_loop(
+        ctx,
+        &config,
+        &tools,
+        debounce,
+        force,
+        &mut events,
+        signal::ctrl_c(),
+    )
+    .await;
+    println!("Stopped watching.");
+    Ok(())
+}
+
+/// Push the skills changed by each burst of `events` until the channel closes or `interrupt`
+/// completes. `interrupt` is polled while gathering a burst and while pushing too, so it stops
+/// the loop at any point.
+async fn watch_loop(
+    ctx: &Context,
+    config: &Config,
+    tools: &[Tool],
+    debounce: Duration,
+    force: bool,
+    events: &mut UnboundedReceiver<notify::Result<Event>>,
+    interrupt: impl Future,
+) {
+    let palette = ctx.palette(config);
+    let mut interrupt = pin!(interrupt);
+    'watch: loop {
+        let event = select! {
+            event = events.recv() => event,
+            _ = &mut interrupt => None,
+        };
+        let Some(event) = event else {
+            break;
+        };
+        let mut diagnostics = ctx.diagnostics();
+        let mut paths = Vec::new();
+        collect_paths(event, &mut paths, &mut diagnostics);
+        loop {
+            select! {
+                event = time::timeout(debounce, events.recv()) => match event {
+                    Ok(Some(event)) => collect_paths(event, &mut paths, &mut diagnostics),
+                    _ => break,
+                },
+                _ = &mut interrupt => break 'watch,
+            }
+        }
+        diagnostics.print_summary();
+        if paths.is_empty() {
+            continue;
+        }
+
+        ctx.invalidate_catalog();
+        let catalog = ctx.load_catalog(config, &mut ctx.diagnostics());
+        let names = changed_skills(&catalog, &paths);
+        if names.is_empty() {
+            continue;
+        }
+        println!();
+        println!(
+            "{} {}",
+            palette.paint(Role::Action, "Changed:"),
+            names.join(", ")
+        );
+        let options = PushOptions {
+            force,
+            yes: force,
+            ..PushOptions::default()
+        };
+        let pushed = select! {
+            pushed = push::run(ctx, names, tools.to_vec(), options) => pushed,
+            _ = &mut interrupt => Err(Error::Interrupted),
+        };
+        match pushed {
+            Ok(()) => {}
+            Err(Error::Interrupted) => break,
+            Err(error) => shell::report(&error, &ctx.error_palette()),
+        }
+    }
+}
+
+/// Add the paths a watcher event changed to `paths`, ignoring reads and warning about watcher
+/// errors.
+fn collect_paths(
+    event: notify::Result<Event>,
+    paths: &mut Vec<PathBuf>,
+    diagnostics: &mut Diagnostics,
+) {
+    match event {
+        Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+        Ok(event) => paths.extend(event.paths),
+        Err(error) => diagnostics.warn(format!("file watcher: {error}")),
+    }
+}
+
+/// Return the names of the source skills whose files are among `paths`, sorted. A change under a
+/// source's shared templates directory counts for every skill in that source.
+fn changed_skills(catalog: &Catalog, paths: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = catalog
+        .sources
+        .values()
+        .filter(|skill| {
+            let templates = skill.source_root.join(TEMPLATES_DIR);
+            paths
+                .iter()
+                .any(|path| path.starts_with(&skill.skill_dir) || path.starts_with(&templates))
+        })
+        .map(|skill| skill.name.clone())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, time::Duration};
+
+    use notify::{
+        Event, EventKind,
+        event::{AccessKind, ModifyKind},
+    };
+    use tokio::sync::mpsc;
+
+    use super::{changed_skills, watch_loop};
+    use crate::{
+        skill::{SKILL_FILE_NAME, TEMPLATES_DIR},
+        testutil::{TestFixture, simple_skill},
+        tool::Tool,
+    };
+
+    #[test]
+    fn maps_changed_paths_to_skills() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_source_skill("Beta", &simple_skill("Beta"));
+        let catalog = fixture.catalog();
+        let source = fixture.source_dir();
+
+        let script = source.join("alpha").join("scripts").join("run.sh");
+        assert_eq!(changed_skills(&catalog, &[script]), ["alpha"]);
+        let stray = source.join("notes.txt");
+        assert!(changed_skills(&catalog, &[stray]).is_empty());
+        let base = source.join(TEMPLATES_DIR).join("base.md");
+        assert_eq!(changed_skills(&catalog, &[base]), ["alpha", "Beta"]);
+    }
+
+    #[tokio::test]
+    async fn pushes_skills_changed_in_a_burst() {
+        let fixture = TestFixture::new()
+            .with_source_skill("alpha", &simple_skill("alpha"))
+            .with_source_skill("beta", &simple_skill("beta"));
+        let ctx = fixture.context();
+        let config = ctx.load_config().expect("config");
+        let source = fixture.source_dir();
+
+        let (sender, mut events) = mpsc::unbounded_channel();
+        let alpha = source.join("alpha");
+        let modify = Event::new(EventKind::Modify(ModifyKind::Any));
+        sender
+            .send(Ok(modify.add_path(alpha.join(SKILL_FILE_NAME))))
+            .unwrap();
+        sender
+            .send(Ok(
+                Event::new(EventKind::Any).add_path(alpha.join("notes.md"))
+            ))
+            .unwrap();
+        let read = Event::new(EventKind::Access(AccessKind::Any));
+        sender
+            .send(Ok(read.add_path(source.join("beta").join(SKILL_FILE_NAME))))
+            .unwrap();
+        drop(sender);
+
+        watch_loop(
+            &ctx,
+            &config,
+            &[Tool::Claude],
+            Duration::from_millis(10),
+            true,
+            &mut events,
+            future::pending::<()>(),
+        )
+        .await;
+        assert!(fixture.tool_skill_exists(Tool::Claude, "alpha"));
+        assert!(!fixture.tool_skill_exists(Tool::Claude, "beta"));
+    }
+}
@@ -1,23 +1,303 @@
 //! Configuration loading and validation.
 
 use std::{
-    fs,
+    borrow::Cow,
+    collections::HashMap,
+    fmt, fs,
     io::ErrorKind,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
+use clap::ValueEnum;
 use serde::Deserialize;
+use toml_edit::{Array, DocumentMut, value};
 
 use crate::{
+    commands::ColorChoice,
+    diagnostics::SuppressRule,
     error::{Error, Result},
+    palette::{Role, Theme, ThemeStyle},
     paths,
+    skill::SkillFileNames,
+    tool::ToolFilter,
+    units::DateFormat,
 };
 
+/// Current config format version, written as the top-level `version` key.
+pub const CONFIG_VERSION: i64 = 1;
+
+/// Upgrade steps: `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`.
+const MIGRATIONS: &[fn(&mut DocumentMut)] = &[migrate_v0_to_v1];
+
+/// Name of the per-project config file at the project root.
+pub const PROJECT_CONFIG_FILE: &str = ".skills.toml";
+
 /// Parsed configuration for the CLI.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Ordered list of configured source directories.
     sources: Vec<PathBuf>,
+    /// Line ending policy applied when writing skill files.
+    pub(crate) line_endings: LineEndings,
+    /// How timestamps are shown when `--dates` is not given.
+    pub(crate) dates: DateFormat,
+    /// Output styling overrides.
+    pub(crate) theme: Theme,
+    /// Network settings for downloads.
+    pub(crate) network: NetworkConfig,
+    /// Settings for `skills import`.
+    pub(crate) import: ImportConfig,
+    /// Project root discovery settings.
+    pub(crate) project: ProjectConfig,
+    /// Size budgets for skills.
+    pub(crate) limits: LimitsConfig,
+    /// Names a skill file may have within its directory.
+    pub(crate) skill_files: SkillFileNames,
+    /// Directories outside the sources that shared templates may link into.
+    pub(crate) include_paths: Vec<PathBuf>,
+    /// Defaults for skills created by `skills new`.
+    pub(crate) new_skill: NewSkillConfig,
+    /// Settings for `skills sync`.
+    pub(crate) sync: SyncConfig,
+    /// Warning suppression settings.
+    pub(crate) warnings: WarningsConfig,
+    /// Team policy settings.
+    pub(crate) policy: PolicyConfig,
+}
+
+/// Team policy settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyConfig {
+    /// Where the policy file is read from, if a policy is in force.
+    pub(crate) source: Option<PolicySource>,
+}
+
+/// Location of a team policy file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicySource {
+    /// A local file.
+    Path(PathBuf),
+    /// An HTTPS URL, fetched on each use.
+    Url(String),
+}
+
+impl fmt::Display for PolicySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", paths::display_path(path)),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+/// Warning suppression settings.
+#[derive(Debug, Clone, Default)]
+pub struct WarningsConfig {
+    /// Rules hiding warnings that are expected and cannot be fixed.
+    pub(crate) suppress: Vec<SuppressRule>,
+}
+
+/// Settings for `skills sync`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncConfig {
+    /// Direction used when `--direction` is not given.
+    pub(crate) default: SyncDirection,
+}
+
+/// Which way sync moves changes between sources and tool copies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncDirection {
+    /// Always push sources over modified tool copies.
+    Push,
+    /// Always pull the newest modified tool copy into the source.
+    Pull,
+    /// Push or pull depending on whether the source or a tool copy was modified last.
+    #[default]
+    Timestamps,
+}
+
+/// Size budgets for skills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitsConfig {
+    /// Largest total size in bytes a skill directory may reach before warnings are shown.
+    pub(crate) skill_size: u64,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            skill_size: 1024 * 1024,
+        }
+    }
+}
+
+/// Defaults for skills created by `skills new`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewSkillConfig {
+    /// Value of the `author` field.
+    pub(crate) author: Option<String>,
+    /// Value of the `license` field.
+    pub(crate) license: Option<String>,
+    /// Values of the `tags` field.
+    pub(crate) tags: Vec<String>,
+    /// Value of the `version` field.
+    pub(crate) version: Option<String>,
+    /// File whose contents replace the placeholder body.
+    pub(crate) template: Option<PathBuf>,
+}
+
+/// Project root discovery settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectConfig {
+    /// File or directory names that mark a project root.
+    pub(crate) markers: Vec<String>,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            markers: vec![".git".to_string()],
+        }
+    }
+}
+
+/// Default command behaviors set by a project's `.skills.toml`.
+///
+/// Flags given on the command line always take precedence over these defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectDefaults {
+    /// Output color selection.
+    pub(crate) color: Option<ColorChoice>,
+    /// Defaults for `skills push`.
+    pub(crate) push: PushDefaults,
+    /// Defaults for `skills sync`.
+    pub(crate) sync: SyncDefaults,
+}
+
+/// Project defaults for `skills push`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PushDefaults {
+    /// Tool targeted when `--tool` is not given.
+    pub(crate) default_tool: Option<ToolFilter>,
+}
+
+/// Project defaults for `skills sync`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SyncDefaults {
+    /// Side preferred on conflict when neither `--prefer-source` nor `--prefer-tool` is given.
+    pub(crate) prefer: Option<SyncPrefer>,
+}
+
+/// Side preferred when sync finds a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncPrefer {
+    /// Prefer the source version.
+    Source,
+    /// Prefer the newest tool version.
+    Tool,
+}
+
+impl SyncDefaults {
+    /// Resolve `--prefer-source`/`--prefer-tool` flags, applying the default when neither is set.
+    pub(crate) fn prefer_flags(&self, prefer_source: bool, prefer_tool: bool) -> (bool, bool) {
+        if prefer_source || prefer_tool {
+            return (prefer_source, prefer_tool);
+        }
+        match self.prefer {
+            Some(SyncPrefer::Source) => (true, false),
+            Some(SyncPrefer::Tool) => (false, true),
+            None => (false, false),
+        }
+    }
+}
+
+impl ProjectDefaults {
+    /// Load project defaults from a file, returning empty defaults when it does not exist.
+    pub(crate) fn load_from(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(Error::ConfigRead {
+                    path: path.to_path_buf(),
+                    source,
+                });
+            }
+        };
+        toml::from_str(&contents).map_err(|source| Error::ConfigParse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Settings for `skills import`.
+#[derive(Debug, Clone, Default)]
+pub struct ImportConfig {
+    /// Credentials keyed by lowercase host name.
+    pub(crate) hosts: HashMap<String, HostAuth>,
+}
+
+/// Credentials sent with every request to one import host.
+///
+/// Values may reference environment variables as `${NAME}`; they are expanded when a request is
+/// built so secrets never need to be written into the config file.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostAuth {
+    /// Extra header in `Name: value` form.
+    pub(crate) header: Option<String>,
+    /// User name for HTTP basic authentication.
+    pub(crate) username: Option<String>,
+    /// Password for HTTP basic authentication.
+    pub(crate) password: Option<String>,
+}
+
+impl fmt::Debug for HostAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = self
+            .header
+            .as_deref()
+            .map(|header| match header.split_once(':') {
+                Some((name, _)) => format!("{name}: <redacted>"),
+                None => "<redacted>".to_string(),
+            });
+        f.debug_struct("HostAuth")
+            .field("header", &header)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Network settings applied to every HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkConfig {
+    /// Proxy URL used for all requests, overriding proxy environment variables.
+    pub(crate) proxy: Option<String>,
+    /// PEM bundle of additional trusted certificate authorities.
+    pub(crate) ca_bundle: Option<PathBuf>,
+    /// Maximum time to establish a connection.
+    pub(crate) connect_timeout: Duration,
+    /// Maximum time to wait between reads of a response.
+    pub(crate) read_timeout: Duration,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            ca_bundle: None,
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+        }
+    }
 }
 
 /// Raw config file structure.
@@ -25,25 +305,192 @@ pub struct Config {
 struct RawConfig {
     /// Ordered list of configured source directories.
     sources: Option<Vec<String>>,
+    /// Line ending policy applied when writing skill files.
+    line_endings: Option<LineEndings>,
+    /// How timestamps are shown.
+    dates: Option<DateFormat>,
+    /// Output styling overrides keyed by role.
+    theme: Option<HashMap<Role, ThemeStyle>>,
+    /// Network settings for downloads.
+    network: Option<RawNetworkConfig>,
+    /// Settings for `skills import`.
+    import: Option<RawImportConfig>,
+    /// Project root discovery settings.
+    project: Option<RawProjectConfig>,
+    /// Size budgets for skills.
+    limits: Option<RawLimitsConfig>,
+    /// Skill file naming settings.
+    skill_file: Option<RawSkillFileConfig>,
+    /// Template loading settings.
+    templates: Option<RawTemplatesConfig>,
+    /// Defaults for `skills new`.
+    new: Option<RawNewConfig>,
+    /// Settings for `skills sync`.
+    sync: Option<RawSyncConfig>,
+    /// Warning suppression settings.
+    warnings: Option<RawWarningsConfig>,
+    /// Team policy settings.
+    policy: Option<RawPolicyConfig>,
+}
+
+/// Raw `[policy]` config section.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPolicyConfig {
+    /// Path or HTTPS URL of the policy file.
+    source: Option<String>,
+}
+
+/// Raw `[warnings]` config section.
+#[derive(Debug, Deserialize)]
+struct RawWarningsConfig {
+    /// Suppress rules in order.
+    suppress: Option<Vec<RawSuppressRule>>,
+}
+
+/// Raw `[[warnings.suppress]]` entry.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSuppressRule {
+    /// Path glob, resolved against the config directory when relative.
+    path: Option<String>,
+    /// Regular expression searched for in the warning message.
+    message: Option<String>,
+}
+
+/// Raw `[sync]` config section.
+#[derive(Debug, Deserialize)]
+struct RawSyncConfig {
+    /// Default sync direction.
+    default: Option<SyncDirection>,
+}
+
+/// Raw `[skill_file]` config section.
+#[derive(Debug, Deserialize)]
+struct RawSkillFileConfig {
+    /// Accepted skill file names in priority order.
+    names: Option<Vec<String>>,
+    /// Whether names match regardless of case.
+    case_insensitive: Option<bool>,
+}
+
+/// Raw `[templates]` config section.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTemplatesConfig {
+    /// Directories outside the sources that shared templates may link into.
+    include_paths: Option<Vec<String>>,
+}
+
+/// Raw `[new]` config section.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawNewConfig {
+    /// Value of the `author` field.
+    author: Option<String>,
+    /// Value of the `license` field.
+    license: Option<String>,
+    /// Values of the `tags` field.
+    tags: Option<Vec<String>>,
+    /// Value of the `version` field.
+    version: Option<String>,
+    /// Path of the body template, resolved against the config directory when relative.
+    template: Option<String>,
+}
+
+/// Raw `[limits]` config section.
+#[derive(Debug, Deserialize)]
+struct RawLimitsConfig {
+    /// Skill size budget in bytes.
+    skill_size: Option<u64>,
+}
+
+/// Raw `[project]` config section.
+#[derive(Debug, Deserialize)]
+struct RawProjectConfig {
+    /// File or directory names that mark a project root.
+    markers: Option<Vec<String>>,
+}
+
+/// Raw `[import]` config section.
+#[derive(Debug, Deserialize)]
+struct RawImportConfig {
+    /// Credentials keyed by host name.
+    hosts: Option<HashMap<String, HostAuth>>,
+}
+
+/// Raw `[network]` config section.
+#[derive(Debug, Deserialize)]
+struct RawNetworkConfig {
+    /// Proxy URL used for all requests.
+    proxy: Option<String>,
+    /// Path to a PEM bundle of additional certificate authorities.
+    ca_bundle: Option<String>,
+    /// Connect timeout in seconds.
+    connect_timeout: Option<u64>,
+    /// Read timeout in seconds.
+    read_timeout: Option<u64>,
+}
+
+/// Line ending policy applied when writing skill files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEndings {
+    /// Write LF line endings.
+    Lf,
+    /// Write CRLF line endings.
+    Crlf,
+    /// Write contents with the line endings they already have.
+    #[default]
+    Preserve,
+}
+
+impl LineEndings {
+    /// Convert contents to this policy's line endings.
+    pub(crate) fn apply(self, contents: &str) -> Cow<'_, str> {
+        match self {
+            Self::Preserve => Cow::Borrowed(contents),
+            Self::Lf if !contents.contains('\r') => Cow::Borrowed(contents),
+            Self::Lf => Cow::Owned(to_lf(contents)),
+            Self::Crlf => Cow::Owned(to_lf(contents).replace('\n', "\r\n")),
+        }
+    }
+}
+
+/// Convert CRLF and bare CR line endings to LF.
+fn to_lf(contents: &str) -> String {
+    contents.replace("\r\n", "\n").replace('\r', "\n")
 }
 
 impl Config {
     /// Create a new config with the given source directories.
     #[cfg(test)]
     pub(crate) fn new(sources: Vec<PathBuf>) -> Self {
-        Self { sources }
-    }
-
-    /// Load the default config from disk.
-    pub(crate) fn load() -> Result<Self> {
-        let path = paths::default_config_path()?;
-        Self::load_from(&path)
+        Self {
+            sources,
+            line_endings: LineEndings::default(),
+            dates: DateFormat::default(),
+            theme: Theme::default(),
+            network: NetworkConfig::default(),
+            import: ImportConfig::default(),
+            project: ProjectConfig::default(),
+            limits: LimitsConfig::default(),
+            skill_files: SkillFileNames::default(),
+            include_paths: Vec::new(),
+            new_skill: NewSkillConfig::default(),
+            sync: SyncConfig::default(),
+            warnings: WarningsConfig::default(),
+            policy: PolicyConfig::default(),
+        }
     }
 
-    /// Load a config file from an explicit path.
-    pub(crate) fn load_from(path: &Path) -> Result<Self> {
+    /// Load a config file, replacing its sources with `sources` when given.
+    ///
+    /// With replacement sources the config file may be missing or list no sources of its own.
+    pub(crate) fn load(path: &Path, sources: Option<Vec<PathBuf>>) -> Result<Self> {
         let contents = match fs::read_to_string(path) {
             Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound && sources.is_some() => String::new(),
             Err(error) if error.kind() == ErrorKind::NotFound => {
                 return Err(Error::NoSources {
                     config_path: path.to_path_buf(),
@@ -57,46 +504,281 @@ impl Config {
             }
         };
 
+        let (contents, _) = migrate_config(path, &contents)?;
         let raw: RawConfig = toml::from_str(&contents).map_err(|error| Error::ConfigParse {
             path: path.to_path_buf(),
             source: error,
         })?;
 
-        let Some(raw_sources) = raw.sources else {
-            return Err(Error::NoSources {
-                config_path: path.to_path_buf(),
-            });
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        let sources = match (sources, raw.sources) {
+            (Some(sources), _) => sources,
+            (None, Some(raw_sources)) => raw_sources
+                .iter()
+                .map(|source| paths::expand_path(source, base_dir))
+                .collect::<Result<_>>()?,
+            (None, None) => Vec::new(),
         };
-
-        if raw_sources.is_empty() {
+        if sources.is_empty() {
             return Err(Error::NoSources {
                 config_path: path.to_path_buf(),
             });
         }
 
-        let base_dir = path.parent().unwrap_or(Path::new("."));
-        let mut sources = Vec::new();
-        for source in raw_sources {
-            let expanded = paths::expand_source_path(&source, base_dir)?;
-            sources.push(expanded);
+        let mut network = NetworkConfig::default();
+        if let Some(raw_network) = raw.network {
+            network.proxy = raw_network.proxy;
+            network.ca_bundle = raw_network
+                .ca_bundle
+                .map(|path| paths::expand_path(&path, base_dir))
+                .transpose()?;
+            if let Some(seconds) = raw_network.connect_timeout {
+                network.connect_timeout = Duration::from_secs(seconds);
+            }
+            if let Some(seconds) = raw_network.read_timeout {
+                network.read_timeout = Duration::from_secs(seconds);
+            }
+        }
+
+        let import = ImportConfig {
+            hosts: raw
+                .import
+                .and_then(|raw_import| raw_import.hosts)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(host, auth)| (host.to_ascii_lowercase(), auth))
+                .collect(),
+        };
+
+        let project = raw
+            .project
+            .and_then(|raw_project| raw_project.markers)
+            .map(|markers| ProjectConfig { markers })
+            .unwrap_or_default();
+
+        let mut limits = LimitsConfig::default();
+        if let Some(skill_size) = raw.limits.and_then(|raw_limits| raw_limits.skill_size) {
+            limits.skill_size = skill_size;
         }
 
-        Ok(Self { sources })
+        let skill_files = raw
+            .skill_file
+            .map(|raw_file| {
+                SkillFileNames::new(
+                    raw_file.names.unwrap_or_default(),
+                    raw_file.case_insensitive.unwrap_or(false),
+                )
+            })
+            .unwrap_or_default();
+
+        let include_paths = raw
+            .templates
+            .and_then(|raw_templates| raw_templates.include_paths)
+            .unwrap_or_default()
+            .iter()
+            .map(|include| paths::expand_path(include, base_dir))
+            .collect::<Result<_>>()?;
+
+        let new_skill = match raw.new {
+            Some(raw_new) => NewSkillConfig {
+                author: raw_new.author,
+                license: raw_new.license,
+                tags: raw_new.tags.unwrap_or_default(),
+                version: raw_new.version,
+                template: raw_new
+                    .template
+                    .map(|template| paths::expand_path(&template, base_dir))
+                    .transpose()?,
+            },
+            None => NewSkillConfig::default(),
+        };
+
+        let sync = SyncConfig {
+            default: raw
+                .sync
+                .and_then(|raw_sync| raw_sync.default)
+                .unwrap_or_default(),
+        };
+
+        let warnings = WarningsConfig {
+            suppress: raw
+                .warnings
+                .and_then(|raw_warnings| raw_warnings.suppress)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|rule| {
+                    let glob = rule
+                        .path
+                        .map(|glob| paths::expand_path(&glob, base_dir))
+                        .transpose()?;
+                    SuppressRule::new(
+                        glob.as_deref()
+                            .map(|glob| glob.to_string_lossy())
+                            .as_deref(),
+                        rule.message.as_deref(),
+                    )
+                    .map_err(|message| Error::ConfigInvalid {
+                        path: path.to_path_buf(),
+                        message,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        let policy = PolicyConfig {
+            source: raw
+                .policy
+                .and_then(|raw_policy| raw_policy.source)
+                .map(|source| policy_source(path, base_dir, &source))
+                .transpose()?,
+        };
+
+        Ok(Self {
+            sources,
+            line_endings: raw.line_endings.unwrap_or_default(),
+            dates: raw.dates.unwrap_or_default(),
+            theme: Theme::from_overrides(raw.theme.unwrap_or_default()),
+            network,
+            import,
+            project,
+            limits,
+            skill_files,
+            include_paths,
+            new_skill,
+            sync,
+            warnings,
+            policy,
+        })
     }
 
     /// Return the configured source directories.
     pub(crate) fn sources(&self) -> &[PathBuf] {
         &self.sources
     }
+
+    /// Find a configured source by its directory name or by a path to it, resolved against
+    /// `work_dir`.
+    pub(crate) fn find_source(&self, name: &str, work_dir: &Path) -> Option<&PathBuf> {
+        if let Some(source) = self.sources.iter().find(|source| {
+            source
+                .file_name()
+                .is_some_and(|file_name| file_name == name)
+        }) {
+            return Some(source);
+        }
+        let path = paths::expand_path(name, work_dir).ok()?;
+        self.sources.iter().find(|source| **source == path)
+    }
+}
+
+/// Parse a `[policy] source` value, resolving paths against the config directory.
+///
+/// Policies are only fetched over HTTPS, since a tampered policy could lift every restriction.
+fn policy_source(config_path: &Path, base_dir: &Path, source: &str) -> Result<PolicySource> {
+    if source.starts_with("https://") {
+        Ok(PolicySource::Url(source.to_string()))
+    } else if source.starts_with("http://") {
+        Err(Error::ConfigInvalid {
+            path: config_path.to_path_buf(),
+            message: format!("policy source must use HTTPS: {source}"),
+        })
+    } else {
+        Ok(PolicySource::Path(paths::expand_path(source, base_dir)?))
+    }
 }
 
+/// Upgrade config file contents to the current format version.
+///
+/// Returns the upgraded contents, with comments and formatting preserved, and the version the
+/// file was at. Contents that are not valid TOML are returned unchanged so the parse error is
+/// reported by the caller.
+pub fn migrate_config(path: &Path, contents: &str) -> Result<(String, i64)> {
+    let Ok(mut doc) = contents.parse::<DocumentMut>() else {
+        return Ok((contents.to_string(), CONFIG_VERSION));
+    };
+    let version = match doc.get("version") {
+        None => 0,
+        Some(item) => item.as_integer().ok_or_else(|| Error::ConfigVersion {
+            path: path.to_path_buf(),
+            message: "version must be an integer".to_string(),
+        })?,
+    };
+    if !(0..=CONFIG_VERSION).contains(&version) {
+        return Err(Error::ConfigVersion {
+            path: path.to_path_buf(),
+            message: format!(
+                "version {version} is not supported (this build reads up to version \
+                 {CONFIG_VERSION})"
+            ),
+        });
+    }
+    if version == CONFIG_VERSION {
+        return Ok((contents.to_string(), version));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(&mut doc);
+        doc.insert("version", value(from as i64 + 1));
+    }
+    // Keep the version key first among the top-level keys, below any leading comment.
+    let header = doc
+        .iter_mut()
+        .find(|(key, item)| item.is_value() && key.get() != "version")
+        .and_then(|(mut key, _)| {
+            let prefix = key.leaf_decor().prefix().cloned();
+            key.leaf_decor_mut().set_prefix("");
+            prefix
+        });
+    if let (Some(header), Some(mut key)) = (header, doc.key_mut("version")) {
+        key.leaf_decor_mut().set_prefix(header);
+    }
+    doc.sort_values_by(|left, _, right, _| {
+        (left.get() != "version").cmp(&(right.get() != "version"))
+    });
+    Ok((doc.to_string(), version))
+}
+
+/// Return config file contents with a source directory appended to `sources`.
+///
+/// Comments and formatting are preserved, and a source already listed is not added again.
+pub fn add_source(path: &Path, contents: &str, source: &str) -> Result<String> {
+    let invalid = |message: String| Error::ConfigInvalid {
+        path: path.to_path_buf(),
+        message,
+    };
+    let mut doc = contents
+        .parse::<DocumentMut>()
+        .map_err(|error| invalid(error.to_string()))?;
+    let sources = doc
+        .entry("sources")
+        .or_insert_with(|| value(Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| invalid("sources must be an array".to_string()))?;
+    if !sources.iter().any(|item| item.as_str() == Some(source)) {
+        sources.push(source);
+    }
+    Ok(doc.to_string())
+}
+
+/// Version 0 configs predate versioning and need no changes beyond gaining a version key.
+fn migrate_v0_to_v1(_doc: &mut DocumentMut) {}
+
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{fs, path::Path, time::Duration};
 
     use tempfile::tempdir;
 
-    use crate::{config::Config, error::Error};
+    use crate::{
+        commands::ColorChoice,
+        config::{
+            Config, LineEndings, PolicySource, ProjectDefaults, SyncDirection, SyncPrefer,
+            add_source, migrate_config,
+        },
+        error::Error,
+        tool::ToolFilter,
+        units::DateFormat,
+    };
 
     #[test]
     fn errors_when_sources_missing() {
@@ -104,16 +786,316 @@ mod tests {
         let path = dir.path().join("skills.toml");
         fs::write(&path, "sources = []").expect("write config");
 
-        let error = Config::load_from(&path).expect_err("config should fail");
+        let error = Config::load(&path, None).expect_err("config should fail");
         assert!(matches!(error, Error::NoSources { .. }));
     }
 
+    #[test]
+    fn parses_line_endings() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]\nline_endings = \"crlf\"").expect("write config");
+
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.line_endings, LineEndings::Crlf);
+        assert_eq!(config.dates, DateFormat::Relative);
+    }
+
+    #[test]
+    fn parses_date_format() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]\ndates = \"iso\"").expect("write config");
+
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.dates, DateFormat::Iso);
+    }
+
+    #[test]
+    fn parses_network_settings() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        let contents = "sources = [\".\"]\n[network]\nproxy = \"http://proxy:8080\"\nca_bundle = \"certs/ca.pem\"\nread_timeout = 5";
+        fs::write(&path, contents).expect("write config");
+
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.network.proxy.as_deref(), Some("http://proxy:8080"));
+        assert_eq!(
+            config.network.ca_bundle,
+            Some(dir.path().join("certs").join("ca.pem"))
+        );
+        assert_eq!(config.network.read_timeout, Duration::from_secs(5));
+        assert_eq!(config.network.connect_timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn migrates_unversioned_configs() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        let contents = "# comment\nsources = [\".\"] # inline\n\n[network]\nproxy = \"http://p\"\n";
+
+        let (migrated, version) = migrate_config(&path, contents).expect("migrate");
+        assert_eq!(version, 0);
+        assert_eq!(
+            migrated,
+            "# comment\nversion = 1\nsources = [\".\"] # inline\n\n[network]\nproxy = \"http://p\"\n"
+        );
+
+        let (unchanged, version) = migrate_config(&path, &migrated).expect("current");
+        assert_eq!(version, 1);
+        assert_eq!(unchanged, migrated);
+
+        fs::write(&path, "version = 99\nsources = [\".\"]").expect("write config");
+        let error = Config::load(&path, None).expect_err("future version");
+        assert!(matches!(error, Error::ConfigVersion { .. }));
+    }
+
+    #[test]
+    fn adds_sources_keeping_comments() {
+        let path = Path::new("skills.toml");
+        let contents = "# mine\nversion = 1\nsources = [\"~/skills\"] # personal\n";
+
+        let added = add_source(path, contents, "~/.skills/sources/team").expect("add");
+        assert_eq!(
+            added,
+            "# mine\nversion = 1\nsources = [\"~/skills\", \"~/.skills/sources/team\"] # personal\n"
+        );
+        assert_eq!(
+            add_source(path, &added, "~/skills").expect("present"),
+            added
+        );
+        assert_eq!(
+            add_source(path, "version = 1\n", "team").expect("missing"),
+            "version = 1\nsources = [\"team\"]\n"
+        );
+        assert!(matches!(
+            add_source(path, "sources = \"x\"\n", "team"),
+            Err(Error::ConfigInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_project_markers() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]").expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.project.markers, vec![".git"]);
+
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[project]\nmarkers = [\".hg\", \".git\"]",
+        )
+        .expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.project.markers, vec![".hg", ".git"]);
+    }
+
+    #[test]
+    fn parses_skill_size_budget() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]").expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.limits.skill_size, 1024 * 1024);
+
+        fs::write(&path, "sources = [\".\"]\n[limits]\nskill_size = 2048").expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.limits.skill_size, 2048);
+    }
+
+    #[test]
+    fn parses_new_skill_defaults() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[new]\nauthor = \"Platform\"\ntags = [\"internal\"]\nversion = \"0.1.0\"\ntemplate = \"body.md\"",
+        )
+        .expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.new_skill.author.as_deref(), Some("Platform"));
+        assert_eq!(config.new_skill.license, None);
+        assert_eq!(config.new_skill.tags, ["internal"]);
+        assert_eq!(config.new_skill.version.as_deref(), Some("0.1.0"));
+        assert_eq!(config.new_skill.template, Some(dir.path().join("body.md")));
+
+        fs::write(&path, "sources = [\".\"]\n[new]\nowner = \"x\"").expect("write config");
+        assert!(Config::load(&path, None).is_err());
+    }
+
+    #[test]
+    fn parses_sync_direction() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]").expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.sync.default, SyncDirection::Timestamps);
+
+        fs::write(&path, "sources = [\".\"]\n[sync]\ndefault = \"push\"").expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.sync.default, SyncDirection::Push);
+
+        fs::write(&path, "sources = [\".\"]\n[sync]\ndefault = \"sideways\"").expect("write");
+        assert!(Config::load(&path, None).is_err());
+    }
+
+    #[test]
+    fn parses_warning_suppress_rules() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[[warnings.suppress]]\npath = \"drafts/**\"\n[[warnings.suppress]]\nmessage = \"frontmatter\"",
+        )
+        .expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.warnings.suppress.len(), 2);
+
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[[warnings.suppress]]\nmessage = \"(\"",
+        )
+        .expect("write config");
+        assert!(matches!(
+            Config::load(&path, None),
+            Err(Error::ConfigInvalid { .. })
+        ));
+
+        fs::write(&path, "sources = [\".\"]\n[[warnings.suppress]]\n").expect("write config");
+        assert!(Config::load(&path, None).is_err());
+    }
+
+    #[test]
+    fn parses_skill_file_names() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        let contents = "sources = [\".\"]\n[skill_file]\nnames = [\"index.md\", \"SKILL.md\"]\ncase_insensitive = true";
+        fs::write(&path, contents).expect("write config");
+
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(config.skill_files.primary(), "index.md");
+        assert!(config.skill_files.matches("skill.md"));
+    }
+
+    #[test]
+    fn parses_project_defaults() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(".skills.toml");
+        assert_eq!(
+            ProjectDefaults::load_from(&path).expect("missing file loads"),
+            ProjectDefaults::default()
+        );
+
+        let contents =
+            "color = \"never\"\n[push]\ndefault_tool = \"claude\"\n[sync]\nprefer = \"source\"";
+        fs::write(&path, contents).expect("write config");
+        let defaults = ProjectDefaults::load_from(&path).expect("defaults should load");
+        assert_eq!(defaults.color, Some(ColorChoice::Never));
+        assert_eq!(defaults.push.default_tool, Some(ToolFilter::Claude));
+        assert_eq!(defaults.sync.prefer, Some(SyncPrefer::Source));
+
+        fs::write(&path, "[push]\ntool = \"claude\"").expect("write config");
+        let error = ProjectDefaults::load_from(&path).expect_err("unknown key");
+        assert!(matches!(error, Error::ConfigParse { .. }));
+    }
+
+    #[test]
+    fn parses_import_hosts() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        let contents = "sources = [\".\"]\n[import.hosts.\"Artifacts.Example.com\"]\nheader = \"Authorization: Bearer ${TOKEN}\"";
+        fs::write(&path, contents).expect("write config");
+
+        let config = Config::load(&path, None).expect("config should load");
+        let auth = &config.import.hosts["artifacts.example.com"];
+        assert_eq!(
+            auth.header.as_deref(),
+            Some("Authorization: Bearer ${TOKEN}")
+        );
+        let debug = format!("{auth:?}");
+        assert!(debug.contains("Authorization: <redacted>"));
+        assert!(!debug.contains("TOKEN"));
+    }
+
+    #[test]
+    fn parses_policy_source() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[policy]\nsource = \"policy.toml\"",
+        )
+        .expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(
+            config.policy.source,
+            Some(PolicySource::Path(dir.path().join("policy.toml")))
+        );
+
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[policy]\nsource = \"https://example.com/policy.toml\"",
+        )
+        .expect("write config");
+        let config = Config::load(&path, None).expect("config should load");
+        assert_eq!(
+            config.policy.source,
+            Some(PolicySource::Url(
+                "https://example.com/policy.toml".to_string()
+            ))
+        );
+
+        fs::write(
+            &path,
+            "sources = [\".\"]\n[policy]\nsource = \"http://example.com/policy.toml\"",
+        )
+        .expect("write config");
+        assert!(matches!(
+            Config::load(&path, None),
+            Err(Error::ConfigInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_theme_roles() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        fs::write(&path, "sources = [\".\"]\n[theme]\nbogus = \"red\"").expect("write config");
+
+        let error = Config::load(&path, None).expect_err("config should fail");
+        assert!(matches!(error, Error::ConfigParse { .. }));
+    }
+
+    #[test]
+    fn applies_line_ending_policy() {
+        let mixed = "a\r\nb\nc";
+        assert_eq!(LineEndings::Lf.apply(mixed), "a\nb\nc");
+        assert_eq!(LineEndings::Crlf.apply(mixed), "a\r\nb\r\nc");
+        assert_eq!(LineEndings::Preserve.apply(mixed), mixed);
+    }
+
     #[test]
     fn errors_when_config_missing() {
         let dir = tempdir().expect("tempdir");
         let path = dir.path().join("missing.toml");
 
-        let error = Config::load_from(&path).expect_err("config should fail");
+        let error = Config::load(&path, None).expect_err("config should fail");
         assert!(matches!(error, Error::NoSources { .. }));
     }
+
+    #[test]
+    fn source_overrides_replace_configured_sources() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("skills.toml");
+        let overrides = vec![dir.path().join("ci-skills")];
+
+        let config = Config::load(&path, Some(overrides.clone())).expect("missing config loads");
+        assert_eq!(config.sources(), overrides);
+
+        fs::write(&path, "sources = [\"mine\"]\nline_endings = \"lf\"").expect("write config");
+        let config = Config::load(&path, Some(overrides.clone())).expect("config should load");
+        assert_eq!(config.sources(), overrides);
+        assert_eq!(config.line_endings, LineEndings::Lf);
+    }
 }
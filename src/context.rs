@@ -0,0 +1,297 @@
+//! Execution environment shared by all commands.
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{
+    catalog::{Catalog, CatalogRoots},
+    commands::ColorChoice,
+    config::{Config, PROJECT_CONFIG_FILE, ProjectConfig, ProjectDefaults},
+    diagnostics::{Diagnostics, Verbosity, WarningFormat},
+    error::{Error, Result},
+    overrides::EnvOverrides,
+    palette::Palette,
+    paths::{
+        default_cache_dir, default_clone_dir, default_config_path, default_quarantine_dir,
+        default_state_path, find_project_root,
+    },
+    render_cache::{RENDER_CACHE_FILE, RenderCache},
+    state::State,
+    tool::Tool,
+    units::DateFormat,
+};
+
+/// Environment a command runs in: output settings plus every path it reads or writes.
+///
+/// Commands never consult the home directory or the process working directory directly, so tests
+/// can point a context at temporary directories and drive full commands.
+#[derive(Debug, Clone)]
+pub struct Context {
+    /// Output color selection.
+    pub(crate) color: ColorChoice,
+    /// Diagnostic detail to print.
+    pub(crate) verbosity: Verbosity,
+    /// Format of the diagnostics summary on stderr.
+    pub(crate) warnings: WarningFormat,
+    /// File to also write the diagnostics summary to as JSON.
+    pub(crate) warnings_file: Option<PathBuf>,
+    /// How timestamps are shown, overriding the config.
+    pub(crate) dates: Option<DateFormat>,
+    /// Path to the config file.
+    pub(crate) config_path: PathBuf,
+    /// Path to the install state file.
+    pub(crate) state_path: PathBuf,
+    /// Directory holding caches, such as rendered templates.
+    pub(crate) cache_dir: PathBuf,
+    /// Directory holding imported skills awaiting review.
+    pub(crate) quarantine_dir: PathBuf,
+    /// Directory holding sources cloned by `skills clone`.
+    pub(crate) clone_dir: PathBuf,
+    /// Global skill directories keyed by tool.
+    tool_dirs: HashMap<Tool, PathBuf>,
+    /// Directory the command was run from, used to resolve relative paths.
+    pub(crate) work_dir: PathBuf,
+    /// Project root holding project-local skills.
+    pub(crate) project_dir: PathBuf,
+    /// Settings overridden by environment variables.
+    pub(crate) overrides: EnvOverrides,
+    /// Catalog kept loaded across the commands of an interactive shell.
+    session: Option<Arc<Session>>,
+}
+
+/// The most recently loaded catalog of a shell session, with the roots it was loaded from.
+#[derive(Debug, Default)]
+struct Session {
+    /// Loaded catalog, or `None` until the first load or after an invalidation.
+    catalog: Mutex<Option<(CatalogRoots, Catalog)>>,
+}
+
+impl Context {
+    /// Build a context from the user's home directory and the process working directory.
+    pub(crate) fn from_env(color: ColorChoice, verbosity: Verbosity) -> Result<Self> {
+        let work_dir = env::current_dir().map_err(|source| Error::CurrentDirMissing { source })?;
+        let overrides = EnvOverrides::from_env(&work_dir)?;
+        let mut tool_dirs = HashMap::new();
+        for tool in Tool::all() {
+            let dir = match overrides.tool_dirs.get(&tool) {
+                Some(dir) => dir.clone(),
+                None => tool.skills_dir()?,
+            };
+            tool_dirs.insert(tool, dir);
+        }
+        let config_path = match &overrides.config_path {
+            Some(path) => path.clone(),
+            None => default_config_path()?,
+        };
+        // The config may not exist yet or may be invalid; commands report that when they load it.
+        let markers = Config::load(&config_path, overrides.sources.clone())
+            .map(|config| config.project.markers)
+            .unwrap_or_else(|_| ProjectConfig::default().markers);
+        let project_dir = find_project_root(&work_dir, &markers);
+        Ok(Self {
+            color,
+            verbosity,
+            warnings: WarningFormat::Text,
+            warnings_file: None,
+            dates: None,
+            config_path,
+            state_path: default_state_path()?,
+            cache_dir: default_cache_dir()?,
+            quarantine_dir: default_quarantine_dir()?,
+            clone_dir: default_clone_dir()?,
+            tool_dirs,
+            work_dir,
+            project_dir,
+            overrides,
+            session: None,
+        })
+    }
+
+    /// Build a context from explicit paths.
+    #[cfg(test)]
+    pub(crate) fn new(
+        config_path: PathBuf,
+        tool_dirs: HashMap<Tool, PathBuf>,
+        work_dir: PathBuf,
+    ) -> Self {
+        let state_path = config_path.with_file_name("state.json");
+        let cache_dir = config_path.with_file_name("cache");
+        let quarantine_dir = config_path.with_file_name("quarantine");
+        let clone_dir = config_path.with_file_name("clones");
+        Self {
+            color: ColorChoice::Never,
+            verbosity: Verbosity::Normal,
+            warnings: WarningFormat::Text,
+            warnings_file: None,
+            dates: None,
+            config_path,
+            state_path,
+            cache_dir,
+            quarantine_dir,
+            clone_dir,
+            tool_dirs,
+            project_dir: work_dir.clone(),
+            work_dir,
+            overrides: EnvOverrides::default(),
+            session: None,
+        }
+    }
+
+    /// Return the global skills directory for a tool.
+    pub(crate) fn tool_dir(&self, tool: Tool) -> &Path {
+        &self.tool_dirs[&tool]
+    }
+
+    /// Return the project-local skills directory for a tool.
+    pub(crate) fn local_dir(&self, tool: Tool) -> PathBuf {
+        self.project_dir.join(tool.local_skills_dir())
+    }
+
+    /// Create a diagnostics collector for this invocation.
+    pub(crate) fn diagnostics(&self) -> Diagnostics {
+        Diagnostics::new(self.verbosity).with_output(self.warnings, self.warnings_file.clone())
+    }
+
+    /// Load the config file.
+    pub(crate) fn load_config(&self) -> Result<Config> {
+        Config::load(&self.config_path, self.overrides.sources.clone())
+    }
+
+    /// Load the project's default command behaviors.
+    ///
+    /// A project root that is also the home directory has no project config: its `.skills.toml`
+    /// is the global config.
+    pub(crate) fn load_project_defaults(&self) -> Result<ProjectDefaults> {
+        let path = self.project_dir.join(PROJECT_CONFIG_FILE);
+        if path == self.config_path {
+            return Ok(ProjectDefaults::default());
+        }
+        ProjectDefaults::load_from(&path)
+    }
+
+    /// Load the install state.
+    pub(crate) fn load_state(&self) -> Result<State> {
+        State::load(&self.state_path)
+    }
+
+    /// Create an output palette for a loaded config.
+    pub(crate) fn palette(&self, config: &Config) -> Palette {
+        Palette::new(&config.theme, self.color.enabled())
+    }
+
+    /// Create a palette for errors on stderr, with the config's theme when the config loads.
+    pub(crate) fn error_palette(&self) -> Palette {
+        let theme = self
+            .load_config()
+            .map(|config| config.theme)
+            .unwrap_or_default();
+        Palette::new(&theme, self.color.enabled_for_stderr())
+    }
+
+    /// Return how timestamps are shown: the `--dates` choice, or else the config's.
+    pub(crate) fn dates(&self, config: &Config) -> DateFormat {
+        self.dates.unwrap_or(config.dates)
+    }
+
+    /// Load the skill catalog for a config, applying its warning suppress rules to `diagnostics`.
+    ///
+    /// Within a shell session the catalog is read from disk once and reused until
+    /// [`Context::invalidate_catalog`] is called or the catalog roots change. Load warnings are
+    /// only reported by the command that read it.
+    pub(crate) fn load_catalog(&self, config: &Config, diagnostics: &mut Diagnostics) -> Catalog {
+        diagnostics.suppress(&config.warnings.suppress);
+        let roots = self.catalog_roots(config);
+        let catalog = match &self.session {
+            Some(session) => {
+                let mut loaded = session
+                    .catalog
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                match &*loaded {
+                    Some((cached, catalog)) if *cached == roots => {
+                        diagnostics.debug("reusing the shell session's catalog");
+                        catalog.clone_skills(diagnostics.verbosity())
+                    }
+                    _ => {
+                        let catalog = Catalog::load(&roots, diagnostics);
+                        *loaded = Some((roots, catalog.clone_skills(diagnostics.verbosity())));
+                        catalog
+                    }
+                }
+            }
+            None => Catalog::load(&roots, diagnostics),
+        };
+        catalog.with_render_cache(RenderCache::load(&self.cache_dir.join(RENDER_CACHE_FILE)))
+    }
+
+    /// Return a copy of this context that keeps its catalog loaded between commands.
+    pub(crate) fn with_session(&self) -> Self {
+        Self {
+            session: Some(Arc::default()),
+            ..self.clone()
+        }
+    }
+
+    /// Drop the session's loaded catalog so the next command reads skills from disk again.
+    pub(crate) fn invalidate_catalog(&self) {
+        if let Some(session) = &self.session {
+            *session
+                .catalog
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+
+    /// Return the directories the catalog is loaded from.
+    pub(crate) fn catalog_roots(&self, config: &Config) -> CatalogRoots {
+        CatalogRoots {
+            sources: config.sources().to_vec(),
+            tools: Tool::all()
+                .into_iter()
+                .map(|tool| (tool, self.tool_dir(tool).to_path_buf()))
+                .collect(),
+            local: Tool::all()
+                .into_iter()
+                .map(|tool| (tool, self.local_dir(tool)))
+                .collect(),
+            skill_files: config.skill_files.clone(),
+            include_paths: config.include_paths.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::testutil::{TestFixture, simple_skill};
+
+    #[test]
+    fn session_reuses_catalog_until_invalidated() {
+        let fixture = TestFixture::new().with_source_skill("alpha", &simple_skill("alpha"));
+        let ctx = fixture.context().with_session();
+        let config = ctx.load_config().expect("config");
+        let load = || ctx.load_catalog(&config, &mut ctx.diagnostics());
+        assert_eq!(load().sources.len(), 1);
+
+        let beta = config.sources()[0].join("beta");
+        fs::create_dir_all(&beta).expect("mkdir");
+        fs::write(beta.join("SKILL.md"), simple_skill("beta")).expect("write");
+        assert_eq!(load().sources.len(), 1);
+
+        ctx.invalidate_catalog();
+        assert_eq!(load().sources.len(), 2);
+        assert_eq!(
+            fixture
+                .context()
+                .load_catalog(&config, &mut ctx.diagnostics())
+                .sources
+                .len(),
+            2
+        );
+    }
+}
@@ -1,9 +1,127 @@
 //! Warning aggregation and diagnostic summaries.
 
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-/// Details about a skipped skill file.
+use clap::ValueEnum;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::paths::display_path;
+
+/// How much diagnostic detail a command prints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only the end-of-command summary of skipped skills and warnings.
+    #[default]
+    Normal,
+    /// Each warning as it is recorded.
+    Verbose,
+    /// Warnings plus debug details, such as the paths probed and render timings.
+    Debug,
+}
+
+impl Verbosity {
+    /// Map the number of `-v` flags to a verbosity.
+    pub(crate) fn from_count(count: u8) -> Self {
+        match count {
+            0 => Self::Normal,
+            1 => Self::Verbose,
+            _ => Self::Debug,
+        }
+    }
+
+    /// Print a debug detail when debug output is enabled.
+    pub(crate) fn debug(self, message: impl Into<String>) {
+        if self == Self::Debug {
+            eprintln!("debug: {}", message.into());
+        }
+    }
+}
+
+/// How the end-of-command summary is written to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum WarningFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object with every warning, skipped skill, and conflict.
+    Json,
+}
+
+/// A config rule that hides the warnings matching it.
 #[derive(Debug, Clone)]
+pub struct SuppressRule {
+    /// Pattern matched against the warning's path or one of its ancestors.
+    path: Option<Regex>,
+    /// Pattern searched for in the warning message.
+    message: Option<Regex>,
+}
+
+impl SuppressRule {
+    /// Build a rule from a path glob and a message regex, at least one of which must be given.
+    pub(crate) fn new(path: Option<&str>, message: Option<&str>) -> Result<Self, String> {
+        if path.is_none() && message.is_none() {
+            return Err("a suppress rule needs a path, a message, or both".to_string());
+        }
+        Ok(Self {
+            path: path
+                .map(|glob| {
+                    Regex::new(&glob_pattern(glob))
+                        .map_err(|error| format!("invalid path glob '{glob}': {error}"))
+                })
+                .transpose()?,
+            message: message
+                .map(|pattern| {
+                    Regex::new(pattern)
+                        .map_err(|error| format!("invalid message pattern '{pattern}': {error}"))
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Whether a warning about `path`, if it has one, with `message` matches every part of the
+    /// rule.
+    fn matches(&self, path: Option<&Path>, message: &str) -> bool {
+        let path_matches = match (&self.path, path) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(pattern), Some(path)) => path
+                .ancestors()
+                .any(|ancestor| pattern.is_match(&ancestor.to_string_lossy())),
+        };
+        path_matches
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(message))
+    }
+}
+
+/// Translate a path glob into an anchored regex: `**` matches across directories, while `*` and
+/// `?` stay within one path component.
+fn glob_pattern(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                pattern.push_str(".*");
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// Details about a skipped skill file.
+#[derive(Debug, Clone, Serialize)]
 pub struct SkippedSkill {
     /// Path to the skipped skill file.
     pub(crate) path: PathBuf,
@@ -11,64 +129,289 @@ pub struct SkippedSkill {
     pub(crate) reason: String,
 }
 
+/// A skill name defined in more than one source directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    /// Skill name.
+    pub(crate) skill: String,
+    /// Source directory whose copy is used.
+    pub(crate) used: PathBuf,
+    /// Every skill directory defining the name.
+    pub(crate) paths: Vec<PathBuf>,
+}
+
+/// Structured form of a command's diagnostics.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    /// Every warning message, including those for skipped skills and conflicts.
+    warnings: &'a [String],
+    /// Skills skipped because they could not be loaded.
+    skipped: &'a [SkippedSkill],
+    /// Skill names defined in more than one source.
+    conflicts: &'a [Conflict],
+    /// Number of warnings hidden by suppress rules.
+    suppressed: usize,
+}
+
 /// Aggregates warnings and skipped skills for a command run.
 #[derive(Debug, Default)]
 pub struct Diagnostics {
+    /// Detail printed while the command runs.
+    verbosity: Verbosity,
+    /// Format of the summary printed on stderr.
+    format: WarningFormat,
+    /// File the summary is also written to as JSON.
+    report_path: Option<PathBuf>,
     /// Collected warning messages.
     warnings: Vec<String>,
     /// Collected skipped skill records.
     skipped: Vec<SkippedSkill>,
+    /// Collected source conflicts.
+    conflicts: Vec<Conflict>,
+    /// Rules hiding matching warnings.
+    suppress: Vec<SuppressRule>,
+    /// Number of warnings hidden by the rules.
+    suppressed: usize,
 }
 
 impl Diagnostics {
     /// Create a new diagnostics collector.
-    pub(crate) fn new(_verbose: bool) -> Self {
+    pub(crate) fn new(verbosity: Verbosity) -> Self {
         Self {
-            warnings: Vec::new(),
-            skipped: Vec::new(),
+            verbosity,
+            ..Self::default()
+        }
+    }
+
+    /// Choose the summary format and an optional file to write the summary to as JSON.
+    pub(crate) fn with_output(
+        mut self,
+        format: WarningFormat,
+        report_path: Option<PathBuf>,
+    ) -> Self {
+        self.format = format;
+        self.report_path = report_path;
+        self
+    }
+
+    /// Return the verbosity this collector prints at.
+    pub(crate) fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Hide warnings matching any of the rules from here on.
+    pub(crate) fn suppress(&mut self, rules: &[SuppressRule]) {
+        self.suppress = rules.to_vec();
+    }
+
+    /// Whether a warning about any of `paths`, or about no path when empty, matches a suppress
+    /// rule, counting it as suppressed if so.
+    fn is_suppressed(&mut self, paths: &[&Path], message: &str) -> bool {
+        let suppressed = self.suppress.iter().any(|rule| {
+            rule.matches(None, message)
+                || paths.iter().any(|path| rule.matches(Some(path), message))
+        });
+        if suppressed {
+            self.suppressed += 1;
         }
+        suppressed
+    }
+
+    /// Whether warnings are printed as they are recorded.
+    ///
+    /// JSON summaries keep stderr machine-readable, so they print nothing else.
+    fn prints_warnings(&self) -> bool {
+        self.verbosity >= Verbosity::Verbose && self.format == WarningFormat::Text
     }
 
-    /// Record a warning and print it immediately.
+    /// Record a warning, printing it immediately when verbose.
     pub(crate) fn warn(&mut self, message: impl Into<String>) {
         let message = message.into();
-        eprintln!("Warning: {message}");
+        if self.is_suppressed(&[], &message) {
+            return;
+        }
+        self.record(message);
+    }
+
+    /// Record a warning that passed the suppress rules.
+    fn record(&mut self, message: String) {
+        if self.prints_warnings() {
+            eprintln!("Warning: {message}");
+        }
         self.warnings.push(message);
     }
 
-    /// Print a non-warning continuation line.
+    /// Print a continuation line for the preceding warning when verbose.
     pub(crate) fn note(&self, message: impl Into<String>) {
-        eprintln!("{}", message.into());
+        if self.prints_warnings() {
+            eprintln!("{}", message.into());
+        }
+    }
+
+    /// Print a debug detail when debug output is enabled.
+    pub(crate) fn debug(&self, message: impl Into<String>) {
+        self.verbosity.debug(message);
     }
 
     /// Record a skipped skill and emit the warning.
     pub(crate) fn warn_skipped(&mut self, path: &Path, reason: impl Into<String>) {
         let reason = reason.into();
-        self.warn(format!("{} - {reason}", path.display()));
+        let message = format!("{} - {reason}", path.display());
+        if self.is_suppressed(&[path], &message) {
+            return;
+        }
+        self.record(message);
         self.skipped.push(SkippedSkill {
             path: path.to_path_buf(),
             reason,
         });
     }
 
-    /// Print a summary for skipped skills if any were recorded.
-    pub(crate) fn print_skipped_summary(&self) {
-        if self.skipped.is_empty() {
+    /// Record a skill defined in several sources and emit the warning.
+    pub(crate) fn warn_conflict(&mut self, skill: &str, used: &Path, paths: &[PathBuf]) {
+        let message = format!(
+            "skill '{skill}' exists in multiple sources, using {}",
+            display_path(used)
+        );
+        let path_refs: Vec<&Path> = paths.iter().map(PathBuf::as_path).collect();
+        if self.is_suppressed(&path_refs, &message) {
             return;
         }
-
-        eprintln!("Skipped {} skills due to errors:", self.skipped.len());
-        for skipped in &self.skipped {
-            eprintln!("  - {}: {}", skipped.path.display(), skipped.reason);
+        self.record(message);
+        for path in paths {
+            self.note(format!("  - {}", display_path(path)));
         }
+        self.conflicts.push(Conflict {
+            skill: skill.to_string(),
+            used: used.to_path_buf(),
+            paths: paths.to_vec(),
+        });
     }
 
-    /// Print a warning summary when warnings were emitted.
-    pub(crate) fn print_warning_summary(&self) {
+    /// Print the end-of-command summary: each skipped skill, then the warning count.
+    ///
+    /// Every command prints this once when it finishes, whatever the verbosity, so a run that
+    /// hid its warnings still says how many there were. The JSON form is printed even for a
+    /// clean run, so wrappers can tell the two apart.
+    pub(crate) fn print_summary(&self) {
+        let report = Report {
+            warnings: &self.warnings,
+            skipped: &self.skipped,
+            conflicts: &self.conflicts,
+            suppressed: self.suppressed,
+        };
+        if let Some(path) = &self.report_path {
+            let written = serde_json::to_string_pretty(&report)
+                .map_err(|error| error.to_string())
+                .and_then(|json| fs::write(path, json + "\n").map_err(|error| error.to_string()));
+            if let Err(error) = written {
+                eprintln!("failed to write warnings to {}: {error}", path.display());
+            }
+        }
+        if self.format == WarningFormat::Json {
+            if let Ok(json) = serde_json::to_string(&report) {
+                eprintln!("{json}");
+            }
+            return;
+        }
+
+        if !self.skipped.is_empty() {
+            eprintln!("Skipped {} skills due to errors:", self.skipped.len());
+            for skipped in &self.skipped {
+                eprintln!("  - {}: {}", skipped.path.display(), skipped.reason);
+            }
+        }
+
+        if self.suppressed > 0 && self.verbosity >= Verbosity::Verbose {
+            eprintln!(
+                "Suppressed {} warning(s) matching config rules.",
+                self.suppressed
+            );
+        }
         if self.warnings.is_empty() {
             return;
         }
+        if self.verbosity == Verbosity::Normal {
+            eprintln!(
+                "Completed with {} warning(s). Use --verbose for details.",
+                self.warnings.len()
+            );
+        } else {
+            eprintln!("Completed with {} warning(s).", self.warnings.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use serde_json::Value;
+    use tempfile::tempdir;
+
+    use super::{Diagnostics, SuppressRule, Verbosity, WarningFormat};
+
+    #[test]
+    fn counts_map_to_increasing_verbosity() {
+        assert_eq!(Verbosity::from_count(0), Verbosity::Normal);
+        assert_eq!(Verbosity::from_count(1), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_count(2), Verbosity::Debug);
+        assert_eq!(Verbosity::from_count(5), Verbosity::Debug);
+        assert!(Verbosity::Debug > Verbosity::Verbose);
+    }
+
+    #[test]
+    fn writes_structured_summary_to_file() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("warnings.json");
+        let report = || -> Value {
+            serde_json::from_str(&fs::read_to_string(&path).expect("read")).expect("json")
+        };
+
+        let clean = Diagnostics::default().with_output(WarningFormat::Json, Some(path.clone()));
+        clean.print_summary();
+        assert_eq!(report()["warnings"].as_array().expect("warnings").len(), 0);
+
+        let mut diagnostics =
+            Diagnostics::default().with_output(WarningFormat::Text, Some(path.clone()));
+        diagnostics.warn_skipped(&PathBuf::from("/src/broken/SKILL.md"), "missing name");
+        diagnostics.warn_conflict(
+            "pdf",
+            &PathBuf::from("/src"),
+            &[PathBuf::from("/src/pdf"), PathBuf::from("/team/pdf")],
+        );
+        diagnostics.print_summary();
+        let report = report();
+        assert_eq!(report["warnings"].as_array().expect("warnings").len(), 2);
+        assert_eq!(report["skipped"][0]["reason"], "missing name");
+        assert_eq!(report["conflicts"][0]["skill"], "pdf");
+        assert_eq!(report["conflicts"][0]["paths"][1], "/team/pdf");
+    }
+
+    #[test]
+    fn suppress_rules_hide_matching_warnings() {
+        let rules = [
+            SuppressRule::new(Some("/src/drafts/**"), None).expect("rule"),
+            SuppressRule::new(None, Some("^deprecated")).expect("rule"),
+            SuppressRule::new(Some("/src/*/notes"), Some("frontmatter")).expect("rule"),
+        ];
+        assert!(SuppressRule::new(None, None).is_err());
+        assert!(SuppressRule::new(None, Some("(")).is_err());
+
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.suppress(&rules);
+        diagnostics.warn_skipped(&PathBuf::from("/src/drafts/a/SKILL.md"), "missing name");
+        diagnostics.warn("deprecated option");
+        diagnostics.warn_skipped(
+            &PathBuf::from("/src/x/notes/SKILL.md"),
+            "missing frontmatter",
+        );
+        assert_eq!(diagnostics.suppressed, 3);
 
-        eprintln!("Completed with {} warning(s).", self.warnings.len());
+        diagnostics.warn_skipped(&PathBuf::from("/src/x/notes/SKILL.md"), "missing name");
+        diagnostics.warn_skipped(&PathBuf::from("/src/drafted/SKILL.md"), "missing name");
+        diagnostics.warn("a deprecated option");
+        assert_eq!(diagnostics.warnings.len(), 3);
+        assert_eq!(diagnostics.skipped.len(), 2);
     }
 }
@@ -2,14 +2,21 @@
 
 use std::{
     env,
-    io::Write,
+    io::{self, IsTerminal, Write},
+    path::Path,
     process::{Command, Stdio},
 };
 
-use owo_colors::OwoColorize;
 use similar::TextDiff;
 
-use crate::error::{Error, Result};
+use crate::{
+    error::{Error, Result},
+    palette::{Palette, Role},
+};
+
+/// Options given to `less` when `LESS` is unset, as git does: quit if the output fits on one
+/// screen, pass color escapes through, and leave the output on the screen.
+const LESS_OPTIONS: &str = "FRX";
 
 /// Render a unified diff between two texts.
 pub fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
@@ -20,22 +27,22 @@ pub fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> S
         .to_string()
 }
 
-/// Colorize a unified diff string when enabled.
-pub fn colorize_diff(diff: &str, color: bool) -> String {
-    if !color {
+/// Colorize a unified diff string when the palette is enabled.
+pub fn colorize_diff(diff: &str, palette: &Palette) -> String {
+    if !palette.enabled() {
         return diff.to_string();
     }
 
     let mut output = String::new();
     for line in diff.lines() {
         let colored = if line.starts_with("+++") || line.starts_with("---") {
-            line.bold().to_string()
+            palette.paint(Role::DiffHeader, line)
         } else if line.starts_with("@@") {
-            line.cyan().to_string()
+            palette.paint(Role::DiffHunk, line)
         } else if line.starts_with('+') {
-            line.green().to_string()
+            palette.paint(Role::DiffAdd, line)
         } else if line.starts_with('-') {
-            line.red().to_string()
+            palette.paint(Role::DiffRemove, line)
         } else {
             line.to_string()
         };
@@ -69,7 +76,11 @@ pub fn write_output(output: &str, pager: Option<&str>) -> Result<()> {
     })?;
     let args = parts.split_off(1);
 
-    let mut child = Command::new(&program)
+    let mut command = Command::new(&program);
+    if is_less(&program) && env::var_os("LESS").is_none() {
+        command.env("LESS", LESS_OPTIONS);
+    }
+    let mut child = command
         .args(&args)
         .stdin(Stdio::piped())
         .stdout(Stdio::inherit())
@@ -104,11 +115,29 @@ pub fn write_output(output: &str, pager: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Return whether a pager program is `less`, which needs `-R` to show colors.
+fn is_less(program: &str) -> bool {
+    Path::new(program)
+        .file_stem()
+        .is_some_and(|stem| stem == "less")
+}
+
 /// Resolve the pager command to use for diff output.
-pub fn resolve_pager(override_pager: Option<&str>) -> Option<String> {
+///
+/// A pager given with `--pager` is always used. The fallbacks, starting with `configured` from
+/// `SKILLS_PAGER`, only apply when stdout is a terminal, as in git, so redirected output is never
+/// paged. Color under `--color auto` follows stdout too, so it is on exactly when a fallback
+/// pager runs.
+pub fn resolve_pager(override_pager: Option<&str>, configured: Option<&str>) -> Option<String> {
     if let Some(pager) = override_pager {
         return Some(pager.to_string());
     }
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    if let Some(pager) = configured {
+        return Some(pager.to_string());
+    }
 
     env_pager("GIT_PAGER")
         .or_else(|| git_config_pager("pager.diff"))
@@ -144,3 +173,30 @@ fn git_config_pager(key: &str) -> Option<String> {
         Some(trimmed.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, IsTerminal};
+
+    use super::{is_less, resolve_pager};
+
+    #[test]
+    fn recognizes_less_by_program_name() {
+        assert!(is_less("less"));
+        assert!(is_less("/usr/bin/less"));
+        assert!(is_less("less.exe"));
+        assert!(!is_less("lesspipe"));
+        assert!(!is_less("more"));
+    }
+
+    #[test]
+    fn pages_redirected_output_only_with_explicit_pager() {
+        assert_eq!(
+            resolve_pager(Some("more"), Some("cat")).as_deref(),
+            Some("more")
+        );
+        if !io::stdout().is_terminal() {
+            assert_eq!(resolve_pager(None, Some("cat")), None);
+        }
+    }
+}
@@ -10,6 +10,9 @@ use std::{
 
 use thiserror::Error;
 use toml::{de::Error as TomlError, ser::Error as TomlSerError};
+use url::Url;
+
+use crate::name::NameError;
 
 /// Result type for skills operations.
 pub type Result<T> = StdResult<T, Error>;
@@ -39,6 +42,46 @@ pub enum Error {
         /// Underlying parse error.
         source: TomlError,
     },
+    /// The configuration file has a version this build cannot read.
+    #[error("Unsupported config version in {path}: {message}")]
+    ConfigVersion {
+        /// Path to the config file.
+        path: PathBuf,
+        /// Description of the problem.
+        message: String,
+    },
+    /// The configuration file has a setting that parses but cannot be used.
+    #[error("Invalid config at {path}: {message}")]
+    ConfigInvalid {
+        /// Path to the config file.
+        path: PathBuf,
+        /// Description of the problem.
+        message: String,
+    },
+    /// A project's template variables file could not be read or parsed.
+    #[error("Invalid project variables at {path}: {message}")]
+    ProjectVars {
+        /// Path to the variables file.
+        path: PathBuf,
+        /// Description of the problem.
+        message: String,
+    },
+    /// A source's frontmatter schema could not be read or is not a usable schema.
+    #[error("Invalid skill schema at {path}: {message}")]
+    SchemaInvalid {
+        /// Path to the schema file.
+        path: PathBuf,
+        /// Description of the problem.
+        message: String,
+    },
+    /// The team policy could not be read or is not a valid policy.
+    #[error("Invalid policy at {origin}: {message}")]
+    PolicyInvalid {
+        /// Path or URL the policy was read from.
+        origin: String,
+        /// Description of the problem.
+        message: String,
+    },
     /// The configuration file could not be serialized.
     #[error("Failed to serialize config: {source}")]
     ConfigSerialize {
@@ -56,6 +99,20 @@ pub enum Error {
     /// Home directory resolution failed.
     #[error("Failed to resolve the home directory.")]
     HomeDirMissing,
+    /// A `SKILLS_*` environment variable has an invalid value.
+    #[error("Invalid value for {name}: {message}")]
+    InvalidEnvVar {
+        /// Variable name.
+        name: String,
+        /// Description of the problem.
+        message: String,
+    },
+    /// The current working directory could not be determined.
+    #[error("Failed to resolve the current directory: {source}")]
+    CurrentDirMissing {
+        /// Underlying IO error.
+        source: io::Error,
+    },
     /// A configured path could not be expanded.
     #[error("Invalid path in config: {path}: {source}")]
     PathExpansion {
@@ -103,6 +160,9 @@ pub enum Error {
     /// An interactive prompt was interrupted or canceled.
     #[error("Prompt canceled.")]
     PromptCanceled,
+    /// The user interrupted a running operation.
+    #[error("Interrupted.")]
+    Interrupted,
     /// An interactive prompt failed.
     #[error("Prompt failed: {message}")]
     PromptFailed {
@@ -115,6 +175,22 @@ pub enum Error {
         /// Missing skill name.
         name: String,
     },
+    /// A skill name is not usable as a directory name.
+    #[error("Invalid skill name '{name}': {source}")]
+    InvalidSkillName {
+        /// The rejected name.
+        name: String,
+        /// Reason the name was rejected.
+        source: NameError,
+    },
+    /// A skill's frontmatter version cannot be bumped.
+    #[error("Cannot bump version '{version}' of skill '{name}': expected MAJOR.MINOR.PATCH")]
+    InvalidVersion {
+        /// Skill name.
+        name: String,
+        /// The version in the skill's frontmatter.
+        version: String,
+    },
     /// A required path already exists.
     #[error("Path already exists: {path}")]
     PathExists {
@@ -133,6 +209,22 @@ pub enum Error {
         /// Path that does not exist.
         path: PathBuf,
     },
+    /// Batch input for `skills exec` could not be read.
+    #[error("Failed to read batch input from {path}: {source}")]
+    BatchRead {
+        /// Batch file, or `<stdin>`.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// A sync report could not be written.
+    #[error("Failed to write report at {path}: {source}")]
+    ReportWrite {
+        /// Path to the report file.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
     /// A skill file could not be written.
     #[error("Failed to write skill file at {path}: {source}")]
     SkillWrite {
@@ -149,6 +241,30 @@ pub enum Error {
         /// Underlying IO error.
         source: io::Error,
     },
+    /// A skill file has invalid frontmatter.
+    #[error("Invalid skill file at {path}: {message}")]
+    InvalidSkillFile {
+        /// Path to the skill file.
+        path: PathBuf,
+        /// Error message.
+        message: String,
+    },
+    /// A composed skill could not be generated from its members.
+    #[error("Cannot compose skill '{name}': {message}")]
+    ComposeFailed {
+        /// Composed skill name.
+        name: String,
+        /// Reason the composition failed.
+        message: String,
+    },
+    /// A skill could not be split into several.
+    #[error("Cannot split skill '{name}': {message}")]
+    SplitFailed {
+        /// Name of the skill being split.
+        name: String,
+        /// Reason the split failed.
+        message: String,
+    },
     /// A template could not be rendered.
     #[error("Failed to render template: {message}")]
     TemplateRender {
@@ -161,6 +277,40 @@ pub enum Error {
         /// Missing skill name.
         name: String,
     },
+    /// No configured source matches a name or path.
+    #[error("No configured source named '{name}'")]
+    SourceNotFound {
+        /// Source name or path given.
+        name: String,
+    },
+    /// No quarantined skill was found with the given name.
+    #[error("No quarantined skill named '{name}'")]
+    QuarantinedSkillNotFound {
+        /// Missing skill name.
+        name: String,
+    },
+    /// No snapshot of a skill matches the requested operation or time.
+    #[error("No snapshot of skill '{skill}' at {at}")]
+    SnapshotNotFound {
+        /// Skill name.
+        skill: String,
+        /// The requested operation id or time.
+        at: String,
+    },
+    /// A snapshot selector is neither an operation id nor a date.
+    #[error("Invalid snapshot '{value}': expected an operation id or a date like 2026-10-15T14:02")]
+    InvalidSnapshotQuery {
+        /// The rejected value.
+        value: String,
+    },
+    /// A quarantined skill's record could not be parsed or serialized.
+    #[error("Invalid quarantine record at {path}: {message}")]
+    QuarantineRecord {
+        /// Path to the record file.
+        path: PathBuf,
+        /// Error message.
+        message: String,
+    },
     /// A skill already exists at the target location.
     #[error("Skill '{name}' already exists at {path}. Use --force to overwrite.")]
     SkillExists {
@@ -193,6 +343,14 @@ pub enum Error {
         /// Error message.
         message: String,
     },
+    /// A symlink in a skill directory cannot be packed.
+    #[error("Cannot pack symlink {path}: {message}")]
+    UnsupportedSymlink {
+        /// Path to the symlink.
+        path: PathBuf,
+        /// Why the symlink was refused.
+        message: String,
+    },
     /// Failed to read a ZIP archive.
     #[error("Failed to read ZIP archive at {path}: {message}")]
     ZipRead {
@@ -207,6 +365,12 @@ pub enum Error {
         /// Error message describing the invalid structure.
         message: String,
     },
+    /// Invalid tar.zst archive.
+    #[error("Invalid tar.zst archive: {message}")]
+    InvalidTar {
+        /// Error message describing the invalid structure.
+        message: String,
+    },
     /// Failed to download from URL.
     #[error("Failed to download from {url}: {message}")]
     Download {
@@ -215,6 +379,197 @@ pub enum Error {
         /// Error message.
         message: String,
     },
+    /// The configured proxy URL is invalid.
+    #[error("Invalid proxy '{proxy}': {message}")]
+    InvalidProxy {
+        /// Proxy URL from config.
+        proxy: String,
+        /// Error message.
+        message: String,
+    },
+    /// The configured CA bundle could not be read.
+    #[error("Failed to read CA bundle at {path}: {source}")]
+    CaBundleRead {
+        /// Path to the CA bundle.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// The configured CA bundle is not valid PEM.
+    #[error("Invalid CA bundle at {path}: {message}")]
+    CaBundleParse {
+        /// Path to the CA bundle.
+        path: PathBuf,
+        /// Error message.
+        message: String,
+    },
+    /// The install state file could not be read.
+    #[error("Failed to read install state at {path}: {source}")]
+    StateRead {
+        /// Path to the state file.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// The install state file could not be parsed or serialized.
+    #[error("Invalid install state at {path}: {message}")]
+    StateParse {
+        /// Path to the state file.
+        path: PathBuf,
+        /// Error message.
+        message: String,
+    },
+    /// The install state file could not be written.
+    #[error("Failed to write install state at {path}: {source}")]
+    StateWrite {
+        /// Path to the state file.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// Installed skill copies no longer match what was recorded.
+    #[error("{count} installed skill(s) failed verification")]
+    VerifyFailed {
+        /// Number of skill copies with problems.
+        count: usize,
+    },
+    /// Skill template tests failed.
+    #[error("{count} skill(s) failed their tests")]
+    TestFailed {
+        /// Number of skills with failing tests.
+        count: usize,
+    },
+    /// Skills have high-severity scan findings.
+    #[error("{count} skill(s) have high-severity scan findings")]
+    ScanFailed {
+        /// Number of skills with high-severity findings.
+        count: usize,
+    },
+    /// An imported skill has high-severity scan findings.
+    #[error("Skill '{name}' has {count} high-severity scan finding(s)")]
+    RiskyImport {
+        /// Skill name.
+        name: String,
+        /// Number of high-severity findings.
+        count: usize,
+    },
+    /// A skill or import source breaks the team policy.
+    #[error("Policy forbids '{name}': {message}")]
+    PolicyViolation {
+        /// Skill name or import source.
+        name: String,
+        /// The rule that was broken.
+        message: String,
+    },
+    /// `diff --exit-code` found skills that differ.
+    #[error("{count} skill(s) differ")]
+    Differences {
+        /// Number of skills with differences.
+        count: usize,
+    },
+    /// `fmt --check` found skill files that are not formatted.
+    #[error("{count} skill file(s) need formatting")]
+    FormatCheckFailed {
+        /// Number of unformatted skill files.
+        count: usize,
+    },
+    /// Some doctor checks failed.
+    #[error("{count} check(s) failed")]
+    DoctorFailed {
+        /// Number of failed checks.
+        count: usize,
+    },
+    /// `import --push` was asked to import somewhere other than a source.
+    #[error("--push pushes skills from a source, but {target} is not a configured source")]
+    PushWithoutSource {
+        /// Where the skills would have been imported.
+        target: String,
+    },
+    /// Some archives in a multi-archive import failed.
+    #[error("{count} archive(s) failed to import")]
+    ImportFailed {
+        /// Number of archives that failed.
+        count: usize,
+    },
+    /// The language server could not read or write a protocol message.
+    #[error("Language server error: {message}")]
+    Lsp {
+        /// Error message.
+        message: String,
+    },
+    /// A search pattern is not a valid regular expression.
+    #[error("Invalid search pattern '{pattern}': {message}")]
+    InvalidPattern {
+        /// Pattern as given.
+        pattern: String,
+        /// Error message.
+        message: String,
+    },
+    /// An OCI artifact reference could not be parsed.
+    #[error("Invalid OCI reference '{reference}': {message}")]
+    InvalidOciReference {
+        /// Reference as given.
+        reference: String,
+        /// Error message.
+        message: String,
+    },
+    /// A registry push or pull failed.
+    #[error("OCI registry request for {reference} failed: {message}")]
+    Oci {
+        /// Artifact reference.
+        reference: String,
+        /// Error message.
+        message: String,
+    },
+    /// Credentials configured for an import host could not be applied.
+    #[error("Invalid credentials for import host {host}: {message}")]
+    InvalidHostAuth {
+        /// Host the credentials are configured for.
+        host: String,
+        /// Error message, never including credential values.
+        message: String,
+    },
+    /// The HTTP client could not be constructed.
+    #[error("Failed to initialize HTTP client: {message}")]
+    HttpClient {
+        /// Error message.
+        message: String,
+    },
+    /// A server responded with a non-success HTTP status.
+    #[error("Failed to download from {url}: HTTP {status}: {reason}")]
+    HttpStatus {
+        /// URL that failed.
+        url: String,
+        /// HTTP status code.
+        status: u16,
+        /// Explanation of the status.
+        reason: &'static str,
+    },
+    /// An argument is not a GitHub repository location.
+    #[error(
+        "Not a GitHub repository: '{repo}' (expected owner/repo[/path][@ref] or a github.com tree \
+         URL)"
+    )]
+    InvalidGithubRepo {
+        /// The argument as given.
+        repo: String,
+    },
+    /// A server refused a request because its rate limit was exceeded.
+    #[error("Rate limited by {url}; the limit resets in {wait}s")]
+    RateLimited {
+        /// URL that was refused.
+        url: String,
+        /// Seconds until the limit resets.
+        wait: u64,
+    },
+    /// A download did not contain a ZIP archive.
+    #[error("Expected a ZIP archive from {url} but received {content_type}")]
+    NotZip {
+        /// URL that was downloaded.
+        url: String,
+        /// Content type reported by the server.
+        content_type: String,
+    },
     /// Invalid URL.
     #[error("Invalid URL: {url}")]
     InvalidUrl {
@@ -243,6 +598,54 @@ pub enum Error {
         /// Tools with conflicting changes.
         tools: String,
     },
+    /// Two writes in one run would replace the same skill directory with different content.
+    #[error("Conflicting writes: {first} and {second} would both write {path}")]
+    WriteCollision {
+        /// Skill directory both writes target.
+        path: PathBuf,
+        /// The first write.
+        first: String,
+        /// The second write.
+        second: String,
+    },
+    /// A skill directory cannot be written.
+    #[error("Cannot write to {path}: {message}")]
+    NotWritable {
+        /// Directory or file that cannot be written.
+        path: PathBuf,
+        /// Why it cannot be written.
+        message: String,
+        /// Shell command that fixes the problem, when there is one.
+        fix: Option<String>,
+    },
+    /// A filesystem lacks room for a set of writes.
+    #[error("Not enough free space at {path}: {needed} needed, {available} available")]
+    InsufficientSpace {
+        /// Path on the filesystem that is short of space.
+        path: PathBuf,
+        /// Space the writes need, formatted.
+        needed: String,
+        /// Space available, formatted.
+        available: String,
+    },
+    /// A failed set of writes could not be undone.
+    #[error("Failed to restore {path}: {message}; its previous contents are kept in {backup}")]
+    RollbackFailed {
+        /// Skill directory that could not be restored.
+        path: PathBuf,
+        /// Backup holding the directory's previous contents.
+        backup: PathBuf,
+        /// Error message.
+        message: String,
+    },
+    /// A `git` command could not be run or failed.
+    #[error("`git {args}` failed: {message}")]
+    GitFailed {
+        /// Arguments passed to git.
+        args: String,
+        /// Error output from git, or why it could not be run.
+        message: String,
+    },
     /// Failed to launch the editor.
     #[error("Failed to run editor `{editor}`: {message}")]
     EditorFailed {
@@ -251,17 +654,266 @@ pub enum Error {
         /// Error message.
         message: String,
     },
+    /// A description command could not be run or failed.
+    #[error("Failed to run description command `{command}`: {message}")]
+    DescribeCommandFailed {
+        /// Command that was run.
+        command: String,
+        /// Why it failed.
+        message: String,
+    },
     /// Validation failed.
     #[error("Validation failed: {message}")]
     ValidationFailed {
         /// Error message describing the validation failure.
         message: String,
     },
+    /// Source directories could not be watched for changes.
+    #[error("Failed to watch for changes: {message}")]
+    WatchFailed {
+        /// Error from the filesystem watcher.
+        message: String,
+    },
 }
 
 impl Error {
     /// Map errors to exit codes for CLI termination.
+    ///
+    /// Codes are grouped by category so scripts can branch on the kind of failure: 3 for
+    /// configuration, 4 for missing skills or paths, 5 for conflicts, 6 for network and registry
+    /// failures, 7 for validation failures, 8 for differences found by `diff --exit-code`, 130 for
+    /// cancellation, and 1 for I/O and other failures.
     pub fn exit_code(&self) -> ExitCode {
-        ExitCode::from(1)
+        ExitCode::from(self.exit_status())
+    }
+
+    /// Suggest how to recover from this error, when there is a common fix.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::NoSources { .. } => {
+                Some("run `skills init` to create a config with a source directory".to_string())
+            }
+            Self::SkillExists { .. } => {
+                Some("use --force to overwrite the existing skill".to_string())
+            }
+            Self::SyncConflict { name, .. } => Some(format!(
+                "run `skills resolve {name}` to choose a copy, or resolve with `skills sync \
+                 --prefer-source` or `--prefer-tool`"
+            )),
+            Self::SkillNotFound { .. } | Self::LocalSkillNotFound { .. } => {
+                Some("run `skills list` to see available skills".to_string())
+            }
+            Self::RiskyImport { .. } => Some(
+                "stage it for review with `skills import --quarantine`, or import anyway with \
+                 --allow-risky"
+                    .to_string(),
+            ),
+            Self::PolicyViolation { .. } => {
+                Some("run `skills doctor` to see the active policy".to_string())
+            }
+            Self::QuarantinedSkillNotFound { .. } => {
+                Some("run `skills review` to see quarantined skills".to_string())
+            }
+            Self::FormatCheckFailed { .. } => Some("run `skills fmt` to reformat them".to_string()),
+            Self::PushWithoutSource { .. } => {
+                Some("import into a source with --to source or --to source:<name>".to_string())
+            }
+            Self::SourceNotFound { .. } => {
+                Some("run `skills env` to see the configured sources".to_string())
+            }
+            Self::SnapshotNotFound { skill, .. } => Some(format!(
+                "run `skills show {skill} --history` to see the snapshots taken"
+            )),
+            Self::HttpStatus {
+                url,
+                status: 401 | 403,
+                ..
+            } => {
+                let host = Url::parse(url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .unwrap_or_else(|| "the host".to_string());
+                Some(format!(
+                    "add credentials for {host} to [import.hosts] in your config, e.g. \
+                     header = \"Authorization: Bearer ${{GITHUB_TOKEN}}\""
+                ))
+            }
+            Self::RateLimited { url, .. } => {
+                let host = Url::parse(url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .unwrap_or_else(|| "the host".to_string());
+                Some(format!(
+                    "authenticated requests have higher limits; add credentials for {host} to \
+                     [import.hosts] in your config"
+                ))
+            }
+            Self::NotWritable { fix: Some(fix), .. } => Some(format!("run `{fix}`")),
+            Self::ConfigVersion { .. } => Some("upgrade skills to read this config".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Return the numeric exit status for this error.
+    fn exit_status(&self) -> u8 {
+        match self {
+            Self::NoSources { .. }
+            | Self::ConfigRead { .. }
+            | Self::ConfigParse { .. }
+            | Self::ConfigVersion { .. }
+            | Self::ConfigInvalid { .. }
+            | Self::ProjectVars { .. }
+            | Self::SchemaInvalid { .. }
+            | Self::PolicyInvalid { .. }
+            | Self::ConfigSerialize { .. }
+            | Self::ConfigWrite { .. }
+            | Self::HomeDirMissing
+            | Self::InvalidEnvVar { .. }
+            | Self::PathExpansion { .. }
+            | Self::InvalidProxy { .. }
+            | Self::CaBundleRead { .. }
+            | Self::CaBundleParse { .. }
+            | Self::InvalidHostAuth { .. } => 3,
+            Self::SkillNotFound { .. }
+            | Self::LocalSkillNotFound { .. }
+            | Self::QuarantinedSkillNotFound { .. }
+            | Self::SourceNotFound { .. }
+            | Self::SnapshotNotFound { .. }
+            | Self::PathMissing { .. } => 4,
+            Self::SkillExists { .. }
+            | Self::PathExists { .. }
+            | Self::SyncConflict { .. }
+            | Self::WriteCollision { .. }
+            | Self::AmbiguousLocalSkill { .. } => 5,
+            Self::Download { .. }
+            | Self::HttpClient { .. }
+            | Self::HttpStatus { .. }
+            | Self::RateLimited { .. }
+            | Self::NotZip { .. }
+            | Self::HttpNotAllowed { .. }
+            | Self::FileTooLarge { .. }
+            | Self::Oci { .. } => 6,
+            Self::ValidationFailed { .. }
+            | Self::VerifyFailed { .. }
+            | Self::TestFailed { .. }
+            | Self::ScanFailed { .. }
+            | Self::RiskyImport { .. }
+            | Self::PolicyViolation { .. }
+            | Self::DoctorFailed { .. }
+            | Self::FormatCheckFailed { .. }
+            | Self::InvalidSkillName { .. }
+            | Self::InvalidVersion { .. }
+            | Self::InvalidSkillFile { .. }
+            | Self::TemplateRender { .. }
+            | Self::ComposeFailed { .. }
+            | Self::SplitFailed { .. }
+            | Self::InvalidZip { .. }
+            | Self::InvalidTar { .. }
+            | Self::UnsupportedSymlink { .. } => 7,
+            Self::Differences { .. } => 8,
+            Self::PromptCanceled | Self::Interrupted => 130,
+            Self::CurrentDirMissing { .. }
+            | Self::PathNotUnicode { .. }
+            | Self::PagerParse { .. }
+            | Self::PagerSpawn { .. }
+            | Self::PagerStatus { .. }
+            | Self::PagerWrite { .. }
+            | Self::PromptFailed { .. }
+            | Self::InvalidPath { .. }
+            | Self::BatchRead { .. }
+            | Self::ReportWrite { .. }
+            | Self::SkillWrite { .. }
+            | Self::SkillRead { .. }
+            | Self::InvalidSnapshotQuery { .. }
+            | Self::QuarantineRecord { .. }
+            | Self::SkillMove { .. }
+            | Self::ZipCreate { .. }
+            | Self::ZipRead { .. }
+            | Self::StateRead { .. }
+            | Self::StateParse { .. }
+            | Self::StateWrite { .. }
+            | Self::PushWithoutSource { .. }
+            | Self::ImportFailed { .. }
+            | Self::Lsp { .. }
+            | Self::InvalidPattern { .. }
+            | Self::InvalidOciReference { .. }
+            | Self::InvalidGithubRepo { .. }
+            | Self::InvalidUrl { .. }
+            | Self::RollbackFailed { .. }
+            | Self::GitFailed { .. }
+            | Self::EditorFailed { .. }
+            | Self::DescribeCommandFailed { .. } => 1,
+            // The write preflight and the watcher fail on the filesystem itself, like other I/O.
+            Self::NotWritable { .. }
+            | Self::InsufficientSpace { .. }
+            | Self::WatchFailed { .. } => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::ErrorKind, path::PathBuf};
+
+    use super::Error;
+
+    #[test]
+    fn hints_at_credentials_for_forbidden_downloads() {
+        let error = Error::HttpStatus {
+            url: "https://api.github.com/repos/acme/skills".to_string(),
+            status: 403,
+            reason: "access denied",
+        };
+        let hint = error.hint().expect("hint");
+        assert!(hint.contains("api.github.com"));
+        assert!(hint.contains("${GITHUB_TOKEN}"));
+
+        let error = Error::HttpStatus {
+            url: "https://example.com/skill.zip".to_string(),
+            status: 500,
+            reason: "server error",
+        };
+        assert!(error.hint().is_none());
+    }
+
+    #[test]
+    fn groups_exit_statuses_by_category() {
+        let path = PathBuf::from("skills.toml");
+        assert_eq!(
+            Error::NoSources {
+                config_path: path.clone()
+            }
+            .exit_status(),
+            3
+        );
+        assert_eq!(
+            Error::SkillNotFound {
+                name: "pdf".to_string()
+            }
+            .exit_status(),
+            4
+        );
+        assert_eq!(Error::PathExists { path: path.clone() }.exit_status(), 5);
+        assert_eq!(Error::FileTooLarge { size: 2, max: 1 }.exit_status(), 6);
+        assert_eq!(Error::VerifyFailed { count: 1 }.exit_status(), 7);
+        assert_eq!(Error::Differences { count: 1 }.exit_status(), 8);
+        assert_eq!(Error::PromptCanceled.exit_status(), 130);
+        assert_eq!(
+            Error::InsufficientSpace {
+                path: path.clone(),
+                needed: "2 MB".to_string(),
+                available: "1 MB".to_string(),
+            }
+            .exit_status(),
+            1
+        );
+        assert_eq!(
+            Error::SkillWrite {
+                path,
+                source: ErrorKind::Other.into()
+            }
+            .exit_status(),
+            1
+        );
     }
 }
@@ -0,0 +1,165 @@
+//! Text filters available to skill templates.
+//!
+//! The filters cover formatting that skill authors otherwise write by hand in every tool branch:
+//! wrapping prose, fencing code, turning lists into bullets, and quoting shell arguments. Like the
+//! rest of rendering they never touch the filesystem; `relative_path` works on path text alone.
+
+use std::path::{Component, Path};
+
+use minijinja::{Environment, Error, ErrorKind, Value};
+use path_clean::PathClean;
+use textwrap::fill;
+
+/// Width `wrap` fills lines to when none is given.
+pub const DEFAULT_WRAP_WIDTH: usize = 80;
+
+/// Signatures and descriptions of the filters [`register`] adds, for listing to template authors.
+pub const FILTERS: &[(&str, &str)] = &[
+    (
+        "bullet_list",
+        "format a list, or the lines of a string, as Markdown bullets",
+    ),
+    (
+        "code(lang)",
+        "fence text as a Markdown code block, tagged with an optional language",
+    ),
+    (
+        "relative_path(base)",
+        "rewrite a path relative to base, using the path text only",
+    ),
+    ("shell_quote", "quote text as a single POSIX shell argument"),
+    (
+        "wrap(width)",
+        "fill text to lines of at most width (default 80) columns",
+    ),
+];
+
+/// Add the text filters to a template environment.
+pub fn register(env: &mut Environment<'_>) {
+    env.add_filter("bullet_list", bullet_list);
+    env.add_filter("code", code);
+    env.add_filter("relative_path", relative_path);
+    env.add_filter("shell_quote", |value: String| {
+        shell_words::quote(&value).into_owned()
+    });
+    env.add_filter("wrap", |value: String, width: Option<usize>| {
+        fill(&value, width.unwrap_or(DEFAULT_WRAP_WIDTH))
+    });
+}
+
+/// Format the items of a sequence, or the non-blank lines of a string, as Markdown bullets.
+fn bullet_list(value: &Value) -> Result<String, Error> {
+    let items: Vec<String> = match value.as_str() {
+        Some(text) => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => value.try_iter()?.map(|item| item.to_string()).collect(),
+    };
+    Ok(items
+        .iter()
+        .map(|item| format!("- {item}"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Fence text as a Markdown code block, using a fence longer than any backtick run inside it.
+fn code(value: &str, lang: Option<&str>) -> String {
+    let longest = value
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{fence}{}\n{}\n{fence}",
+        lang.unwrap_or_default(),
+        value.trim_end_matches('\n')
+    )
+}
+
+/// Rewrite `path` relative to the directory `base`, comparing the cleaned path text.
+fn relative_path(path: &str, base: &str) -> Result<String, Error> {
+    let (path, base) = (Path::new(path).clean(), Path::new(base).clean());
+    if path.has_root() != base.has_root() {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!(
+                "relative_path needs two absolute or two relative paths, got {} and {}",
+                path.display(),
+                base.display()
+            ),
+        ));
+    }
+    let parts = |path: &Path| -> Vec<String> {
+        path.components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect()
+    };
+    let (path, base) = (parts(&path), parts(&base));
+    let shared = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let relative: Vec<&str> = base[shared..]
+        .iter()
+        .map(|_| "..")
+        .chain(path[shared..].iter().map(String::as_str))
+        .collect();
+    Ok(if relative.is_empty() {
+        ".".to_string()
+    } else {
+        relative.join("/")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use minijinja::Environment;
+
+    use super::register;
+
+    #[test]
+    fn formats_with_filters() {
+        let mut env = Environment::new();
+        register(&mut env);
+        let render = |template: &str| {
+            env.render_str(template, ())
+                .map_err(|error| error.to_string())
+        };
+
+        assert_eq!(
+            render("{{ 'one two three four' | wrap(9) }}").expect("wrap"),
+            "one two\nthree\nfour"
+        );
+        assert_eq!(
+            render("{{ 'ls -la\\n' | code('sh') }}").expect("code"),
+            "```sh\nls -la\n```"
+        );
+        assert_eq!(
+            render("{{ 'a ```b```' | code }}").expect("code"),
+            "````\na ```b```\n````"
+        );
+        assert_eq!(
+            render("{{ ['rg', 'fd'] | bullet_list }}").expect("bullets"),
+            "- rg\n- fd"
+        );
+        assert_eq!(
+            render("{{ 'first\\n\\n  second' | bullet_list }}").expect("bullets"),
+            "- first\n- second"
+        );
+        assert_eq!(
+            render("{{ \"it's here\" | shell_quote }}").expect("quote"),
+            "'it'\\''s here'"
+        );
+        assert_eq!(
+            render("{{ '/repo/docs/a.md' | relative_path('/repo/src/bin') }}").expect("relative"),
+            "../../docs/a.md"
+        );
+        assert_eq!(
+            render("{{ 'src/./lib' | relative_path('src/lib') }}").expect("relative"),
+            "."
+        );
+        assert!(render("{{ '/abs' | relative_path('rel') }}").is_err());
+    }
+}
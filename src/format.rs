@@ -0,0 +1,287 @@
+//! Canonical formatting of skill files.
+//!
+//! [`format_skill`] rewrites a skill file so that equivalent files are byte-identical: frontmatter
+//! keys in a fixed order with minimal quoting, heading levels that never skip, `-` bullets, no
+//! trailing whitespace, and a single final newline. Fenced code blocks and template tags are left
+//! as written, and a frontmatter that cannot be reordered without changing its meaning is kept
+//! unchanged.
+
+use std::mem;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::frontmatter::split_frontmatter;
+
+/// Frontmatter keys in the order they are written; other keys follow in their original order.
+const KEY_ORDER: &[&str] = &[
+    "name",
+    "description",
+    "version",
+    "author",
+    "license",
+    "tags",
+    "targets",
+];
+
+/// Format a skill file, returning it with LF line endings.
+pub fn format_skill(contents: &str) -> String {
+    let contents = contents.replace("\r\n", "\n");
+    let mut output = String::new();
+    let body = match split_frontmatter(&contents) {
+        Some((yaml, body)) => {
+            output.push_str("---\n");
+            output.push_str(&format_frontmatter(yaml));
+            output.push_str("---\n");
+            body
+        }
+        None => contents.as_str(),
+    };
+    output.push_str(&format_body(body, !body.contains("{% extends")));
+    let trimmed = output.trim_end().len();
+    output.truncate(trimmed);
+    output.push('\n');
+    output
+}
+
+/// Return the index, level, and text of each heading line in a Markdown body, skipping fenced
+/// code.
+pub fn headings(body: &str) -> Vec<(usize, usize, &str)> {
+    let mut found = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (index, line) in body.lines().enumerate() {
+        let indented = line.trim_start();
+        if let Some(marker) = fence {
+            if indented.starts_with(marker) {
+                fence = None;
+            }
+        } else if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| indented.starts_with(marker))
+        {
+            fence = Some(marker);
+        } else if let Some((level, text)) = heading(line.trim_end()) {
+            found.push((index, level, text));
+        }
+    }
+    found
+}
+
+/// Move every heading of a Markdown body `by` levels, keeping each between one and six.
+pub fn shift_headings(body: &str, by: isize) -> String {
+    let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+    for (index, level, _) in headings(body) {
+        let shifted = level.saturating_add_signed(by).clamp(1, 6);
+        lines[index] = format!("{}{}", "#".repeat(shifted), &lines[index][level..]);
+    }
+    lines.join("\n")
+}
+
+/// A top-level frontmatter field with its continuation lines and any comments above it.
+#[derive(Debug)]
+struct Field<'a> {
+    /// Key the field sets.
+    key: &'a str,
+    /// Lines of the field, each without its line ending.
+    lines: Vec<String>,
+}
+
+/// Reorder frontmatter fields and normalize the quoting of plain string values.
+///
+/// The YAML is returned unchanged when it does not parse, holds lines that are not simple
+/// top-level fields (such as template tags), or would parse to something different afterwards.
+fn format_frontmatter(yaml: &str) -> String {
+    let Ok(original) = serde_yaml::from_str::<Mapping>(yaml) else {
+        return yaml.to_string();
+    };
+    let Some(mut fields) = split_fields(yaml) else {
+        return yaml.to_string();
+    };
+    fields.sort_by_key(|field| {
+        KEY_ORDER
+            .iter()
+            .position(|key| *key == field.key)
+            .unwrap_or(KEY_ORDER.len())
+    });
+    let mut formatted = String::new();
+    for field in &fields {
+        for line in &field.lines {
+            formatted.push_str(line);
+            formatted.push('\n');
+        }
+    }
+    match serde_yaml::from_str::<Mapping>(&formatted) {
+        Ok(reparsed) if reparsed == original => formatted,
+        _ => yaml.to_string(),
+    }
+}
+
+/// Split frontmatter into its top-level fields, or `None` when a line cannot be attributed to one.
+fn split_fields(yaml: &str) -> Option<Vec<Field<'_>>> {
+    let mut fields: Vec<Field<'_>> = Vec::new();
+    let mut comments = Vec::new();
+    for line in yaml.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('#') {
+            comments.push(line.to_string());
+            continue;
+        }
+        if line.starts_with([' ', '\t', '-']) {
+            fields.last_mut()?.lines.push(line.to_string());
+            continue;
+        }
+        if line.contains("{%") || line.contains("{#") {
+            return None;
+        }
+        let (key, value) = line.split_once(':')?;
+        let mut lines = mem::take(&mut comments);
+        lines.push(match requote(value) {
+            Some(value) => format!("{key}: {value}"),
+            None => line.to_string(),
+        });
+        fields.push(Field { key, lines });
+    }
+    if !comments.is_empty() {
+        fields.last_mut()?.lines.extend(comments);
+    }
+    Some(fields)
+}
+
+/// Return a single-line string value with minimal quoting, or `None` to keep it as written.
+///
+/// Values holding template expressions, comments, or anything but a one-line string are kept.
+fn requote(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.contains(" #") || value.contains("{{") {
+        return None;
+    }
+    match serde_yaml::from_str::<Value>(value) {
+        Ok(Value::String(text)) if !text.contains('\n') => serde_yaml::to_string(&text)
+            .ok()
+            .map(|scalar| scalar.trim_end().to_string()),
+        _ => None,
+    }
+}
+
+/// Normalize the Markdown of a skill body, renumbering headings when `headings` is set.
+fn format_body(body: &str, headings: bool) -> String {
+    let mut output = String::with_capacity(body.len());
+    let mut fence: Option<&str> = None;
+    // Original and normalized level of each enclosing heading.
+    let mut levels: Vec<(usize, usize)> = Vec::new();
+    for line in body.lines() {
+        let line = line.trim_end();
+        let indented = line.trim_start();
+        if let Some(marker) = fence {
+            if indented.starts_with(marker) {
+                fence = None;
+            }
+            output.push_str(line);
+        } else if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| indented.starts_with(marker))
+        {
+            fence = Some(marker);
+            output.push_str(line);
+        } else if let Some((level, text)) = heading(line) {
+            let level = if headings {
+                while levels
+                    .last()
+                    .is_some_and(|(original, _)| *original >= level)
+                {
+                    levels.pop();
+                }
+                let normalized = levels.last().map_or(level, |(_, parent)| parent + 1);
+                levels.push((level, normalized));
+                normalized
+            } else {
+                level
+            };
+            output.push_str(&"#".repeat(level));
+            if !text.is_empty() {
+                output.push(' ');
+                output.push_str(text);
+            }
+        } else {
+            output.push_str(&normalize_bullet(line));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Parse an ATX heading into its level and text, without any closing `#` sequence.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|char| *char == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let text = rest.trim();
+    let closed = text.trim_end_matches('#');
+    let text = if closed.is_empty() || closed.ends_with([' ', '\t']) {
+        closed.trim_end()
+    } else {
+        text
+    };
+    Some((level, text))
+}
+
+/// Replace a `*` or `+` list marker with `-`, leaving thematic breaks alone.
+fn normalize_bullet(line: &str) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let is_break = rest.chars().filter(|char| *char == '*').count() >= 3
+        && rest.chars().all(|char| char == '*' || char == ' ');
+    if !is_break && (rest.starts_with("* ") || rest.starts_with("+ ") || rest == "*" || rest == "+")
+    {
+        format!("{}-{}", &line[..indent], &rest[1..])
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_skill, shift_headings};
+
+    #[test]
+    fn shifts_headings_outside_code() {
+        let body = "# Title\n```sh\n# comment\n```\n##### Deep\n#tag";
+        assert_eq!(
+            shift_headings(body, 2),
+            "### Title\n```sh\n# comment\n```\n###### Deep\n#tag"
+        );
+        assert_eq!(
+            shift_headings("### Steps\n#### Detail", -2),
+            "# Steps\n## Detail"
+        );
+    }
+
+    #[test]
+    fn formats_frontmatter_and_body() {
+        let contents = "---\r\ntags: [a]\r\n# Who owns it\r\nauthor: \"Platform\"\r\ndescription: 'Read PDFs: fast'\r\nname: pdf\r\nlicense: MIT   \r\n---\r\n\r\n# Pdf  \r\n\r\n### Steps ###\r\n* one\r\n  + nested\r\n* * *\r\n\r\n```\r\n# not a heading\r\n* kept\r\n```\r\n#### Detail\r\n## Next\r\n#tag\r\n\r\n\r\n";
+        assert_eq!(
+            format_skill(contents),
+            "---\nname: pdf\ndescription: 'Read PDFs: fast'\n# Who owns it\nauthor: Platform\nlicense: MIT\ntags: [a]\n---\n\n# Pdf\n\n## Steps\n- one\n  - nested\n* * *\n\n```\n# not a heading\n* kept\n```\n### Detail\n## Next\n#tag\n"
+        );
+        assert_eq!(
+            format_skill(&format_skill(contents)),
+            format_skill(contents)
+        );
+    }
+
+    #[test]
+    fn keeps_templates_and_inherited_headings() {
+        let templated = "---\nname: pdf\n{% if tool == \"codex\" %}\ndescription: Codex\n{% endif %}\n---\nBody\n";
+        assert_eq!(format_skill(templated), templated);
+
+        let extending = "---\nname: pdf\ndescription: d\n---\n{% extends \"base.md\" %}\n{% block steps %}\n### Step\n{% endblock %}\n";
+        assert_eq!(format_skill(extending), extending);
+
+        let expression = "---\nname: pdf\ndescription: \"{{ tool }} helper\"\n---\n";
+        assert_eq!(format_skill(expression), expression);
+    }
+}
@@ -1,6 +1,15 @@
 //! YAML frontmatter parsing for skill files.
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use serde_yaml::{Mapping, Value};
+
+use crate::{name::normalize_name, tool::Tool};
+
+/// Key in a per-tool description map naming the description for tools without their own entry.
+pub const DEFAULT_DESCRIPTION: &str = "default";
 
 /// Parsed frontmatter fields from a skill file.
 #[derive(Debug, Clone)]
@@ -8,16 +17,29 @@ pub struct Frontmatter {
     /// The declared skill name.
     pub name: String,
     /// The declared skill description.
+    ///
+    /// For a per-tool description map this is the default entry, or else the first tool's.
     pub description: String,
 }
 
+/// Optional descriptive frontmatter fields used for catalogs and inventories.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// Free-form tags.
+    pub tags: Vec<String>,
+    /// Declared skill version.
+    pub version: Option<String>,
+    /// Tools the skill is intended for.
+    pub targets: Vec<String>,
+}
+
 /// Raw frontmatter fields for validation.
 #[derive(Debug, Deserialize)]
 struct RawFrontmatter {
     /// The declared skill name.
     name: Option<String>,
-    /// The declared skill description.
-    description: Option<String>,
+    /// The declared skill description, a string or a map of tool ids to strings.
+    description: Option<Value>,
 }
 
 /// Errors that can occur when parsing frontmatter.
@@ -44,12 +66,34 @@ pub fn parse_frontmatter(contents: &str) -> Result<Frontmatter, FrontmatterError
     let raw: RawFrontmatter = serde_yaml::from_str(frontmatter)
         .map_err(|error| FrontmatterError::new(error.to_string()))?;
 
-    let name = raw.name.unwrap_or_default().trim().to_string();
-    if name.is_empty() {
+    let raw_name = raw.name.unwrap_or_default();
+    if raw_name.trim().is_empty() {
         return Err(FrontmatterError::new("missing required field 'name'"));
     }
+    let name = normalize_name(&raw_name)
+        .map_err(|error| FrontmatterError::new(format!("invalid field 'name': {error}")))?;
 
-    let description = raw.description.unwrap_or_default().trim().to_string();
+    let description = match raw.description {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::Mapping(map)) => {
+            let descriptions = tool_descriptions(&map)?;
+            descriptions
+                .get(DEFAULT_DESCRIPTION)
+                .or_else(|| {
+                    Tool::all()
+                        .into_iter()
+                        .find_map(|tool| descriptions.get(tool.id()))
+                })
+                .cloned()
+                .unwrap_or_default()
+        }
+        Some(value) => value_string(&value)
+            .ok_or_else(|| {
+                FrontmatterError::new("invalid field 'description': expected a string or a map")
+            })?
+            .trim()
+            .to_string(),
+    };
     if description.is_empty() {
         return Err(FrontmatterError::new(
             "missing required field 'description'",
@@ -59,6 +103,198 @@ pub fn parse_frontmatter(contents: &str) -> Result<Frontmatter, FrontmatterError
     Ok(Frontmatter { name, description })
 }
 
+/// Read a per-tool description map, checking that its keys are tool ids or
+/// [`DEFAULT_DESCRIPTION`] and its values are non-empty strings.
+fn tool_descriptions(map: &Mapping) -> Result<BTreeMap<String, String>, FrontmatterError> {
+    let mut descriptions = BTreeMap::new();
+    for (key, value) in map {
+        let key = key.as_str().unwrap_or_default();
+        if key != DEFAULT_DESCRIPTION && !Tool::all().iter().any(|tool| tool.id() == key) {
+            return Err(FrontmatterError::new(format!(
+                "invalid field 'description': unknown tool '{key}'"
+            )));
+        }
+        let text = value_string(value).unwrap_or_default().trim().to_string();
+        if text.is_empty() {
+            return Err(FrontmatterError::new(format!(
+                "invalid field 'description': entry '{key}' must be a non-empty string"
+            )));
+        }
+        descriptions.insert(key.to_string(), text);
+    }
+    Ok(descriptions)
+}
+
+/// Replace a per-tool `description` map in a rendered skill file with the entry for `tool`.
+///
+/// Tools without an entry get the [`DEFAULT_DESCRIPTION`] one. Files whose description is already
+/// a string are returned unchanged.
+pub fn select_description(contents: &str, tool: Tool) -> Result<String, FrontmatterError> {
+    let Some(bounds) = frontmatter_bounds(contents) else {
+        return Ok(contents.to_string());
+    };
+    let yaml = &contents[bounds.start..bounds.end];
+    let Ok(fields) = serde_yaml::from_str::<Mapping>(yaml) else {
+        return Ok(contents.to_string());
+    };
+    let Some(Value::Mapping(map)) = fields.get("description") else {
+        return Ok(contents.to_string());
+    };
+    let descriptions = tool_descriptions(map)?;
+    let description = descriptions
+        .get(tool.id())
+        .or_else(|| descriptions.get(DEFAULT_DESCRIPTION))
+        .ok_or_else(|| {
+            FrontmatterError::new(format!(
+                "description has no entry for {} and no '{DEFAULT_DESCRIPTION}' entry",
+                tool.id()
+            ))
+        })?;
+    let scalar = serde_yaml::to_string(description)
+        .map_err(|error| FrontmatterError::new(error.to_string()))?;
+
+    // Replace the `description:` line and the indented lines of its map.
+    let mut output = String::from(&contents[..bounds.start]);
+    let mut in_description = false;
+    for line in yaml.split_inclusive('\n') {
+        if in_description && line.starts_with([' ', '\t']) {
+            continue;
+        }
+        in_description = false;
+        if line.starts_with("description:") {
+            in_description = true;
+            let ending = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+            output.push_str("description: ");
+            output.push_str(&scalar.trim_end().replace('\n', ending));
+            output.push_str(ending);
+        } else {
+            output.push_str(line);
+        }
+    }
+    output.push_str(&contents[bounds.end..]);
+    Ok(output)
+}
+
+/// Set a top-level field in a skill file's frontmatter to a plain scalar, replacing the field and
+/// any indented lines of its value, or adding it at the end of the frontmatter.
+///
+/// Returns `None` when the document has no frontmatter.
+pub fn set_field(contents: &str, key: &str, value: &str) -> Option<String> {
+    let bounds = frontmatter_bounds(contents)?;
+    let ending = if contents[..bounds.start].ends_with("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let field = format!("{key}: {value}{ending}");
+    let prefix = format!("{key}:");
+
+    let mut output = String::from(&contents[..bounds.start]);
+    let mut in_field = false;
+    let mut replaced = false;
+    for line in contents[bounds.start..bounds.end].split_inclusive('\n') {
+        if in_field && line.starts_with([' ', '\t']) {
+            continue;
+        }
+        in_field = false;
+        if !replaced && line.starts_with(&prefix) {
+            output.push_str(&field);
+            in_field = true;
+            replaced = true;
+        } else {
+            output.push_str(line);
+        }
+    }
+    if !replaced {
+        output.push_str(&field);
+    }
+    output.push_str(&contents[bounds.end..]);
+    Some(output)
+}
+
+/// Format a string as a YAML scalar, quoting it when it would otherwise parse as something else.
+pub fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|scalar| scalar.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// Read the optional metadata fields from a skill file's frontmatter.
+///
+/// Unlike [`parse_frontmatter`] this never fails: missing, malformed, or oddly typed fields are
+/// left empty, and a single value is accepted where a list is expected.
+pub fn parse_metadata(contents: &str) -> Metadata {
+    let Some(bounds) = frontmatter_bounds(contents) else {
+        return Metadata::default();
+    };
+    let Ok(fields) = serde_yaml::from_str::<Mapping>(&contents[bounds.start..bounds.end]) else {
+        return Metadata::default();
+    };
+    Metadata {
+        tags: fields.get("tags").map(value_list).unwrap_or_default(),
+        version: fields.get("version").and_then(value_string),
+        targets: fields.get("targets").map(value_list).unwrap_or_default(),
+    }
+}
+
+/// Return a skill file's frontmatter as a JSON value, with the one-based line of each top-level
+/// field.
+///
+/// Returns `None` when the document has no frontmatter or it is not valid YAML that JSON can
+/// represent; [`parse_frontmatter`] reports those problems.
+pub fn frontmatter_json(contents: &str) -> Option<(JsonValue, HashMap<String, usize>)> {
+    let bounds = frontmatter_bounds(contents)?;
+    let yaml = &contents[bounds.start..bounds.end];
+    let value = serde_yaml::from_str(yaml).ok()?;
+    let first_line = contents[..bounds.start].matches('\n').count() + 1;
+    let mut lines = HashMap::new();
+    for (index, line) in yaml.lines().enumerate() {
+        if line.starts_with([' ', '\t', '#', '-']) {
+            continue;
+        }
+        if let Some((key, _)) = line.split_once(':') {
+            let key = key.trim().trim_matches(['"', '\'']);
+            lines.entry(key.to_string()).or_insert(first_line + index);
+        }
+    }
+    Some((value, lines))
+}
+
+/// Convert a scalar YAML value to a string.
+fn value_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+/// Convert a YAML list, or a single scalar, to strings.
+fn value_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Sequence(items) => items.iter().filter_map(value_string).collect(),
+        value => value_string(value).into_iter().collect(),
+    }
+}
+
+/// Split a document into its frontmatter YAML and the body after the closing delimiter.
+///
+/// Returns `None` when the document has no frontmatter.
+pub fn split_frontmatter(contents: &str) -> Option<(&str, &str)> {
+    frontmatter_bounds(contents).map(|bounds| {
+        (
+            &contents[bounds.start..bounds.end],
+            &contents[bounds.body..],
+        )
+    })
+}
+
+/// Return the document body following the frontmatter, or the whole document if there is none.
+pub fn strip_frontmatter(contents: &str) -> &str {
+    frontmatter_bounds(contents).map_or(contents, |bounds| &contents[bounds.body..])
+}
+
 /// Byte range bounds for frontmatter in a document.
 #[derive(Debug, Clone, Copy)]
 struct FrontmatterBounds {
@@ -66,6 +302,8 @@ struct FrontmatterBounds {
     start: usize,
     /// End byte index of the YAML payload.
     end: usize,
+    /// Start byte index of the body after the closing delimiter.
+    body: usize,
 }
 
 /// Locate the byte range containing frontmatter in a document.
@@ -81,7 +319,11 @@ fn frontmatter_bounds(contents: &str) -> Option<FrontmatterBounds> {
 
     for line in lines {
         if trim_line_endings(line) == "---" {
-            return Some(FrontmatterBounds { start, end: offset });
+            return Some(FrontmatterBounds {
+                start,
+                end: offset,
+                body: offset + line.len(),
+            });
         }
         offset += line.len();
     }
@@ -96,7 +338,11 @@ fn trim_line_endings(line: &str) -> &str {
 
 #[cfg(test)]
 mod tests {
-    use super::{FrontmatterError, parse_frontmatter};
+    use super::{
+        FrontmatterError, Metadata, parse_frontmatter, parse_metadata, select_description,
+        set_field, strip_frontmatter,
+    };
+    use crate::tool::Tool;
 
     fn parse_error(contents: &str) -> FrontmatterError {
         parse_frontmatter(contents).expect_err("frontmatter should fail")
@@ -115,10 +361,88 @@ mod tests {
         assert_eq!(error.message, "missing required field 'description'");
     }
 
+    #[test]
+    fn rejects_unsafe_names() {
+        let contents = "---\nname: .hidden\ndescription: test\n---\n";
+        let error = parse_error(contents);
+        assert_eq!(
+            error.message,
+            "invalid field 'name': name must not start with '.'"
+        );
+    }
+
     #[test]
     fn parses_required_fields() {
         let contents = "---\nname: example\ndescription: test\n---\nBody";
         let parsed = parse_frontmatter(contents).expect("frontmatter should parse");
         assert_eq!(parsed.name, "example");
     }
+
+    #[test]
+    fn sets_fields() {
+        let contents = "---\nname: a\nversion: 1.0\ntags:\n  - x\n---\nversion: body\n";
+        assert_eq!(
+            set_field(contents, "version", "1.0.1").expect("set"),
+            "---\nname: a\nversion: 1.0.1\ntags:\n  - x\n---\nversion: body\n"
+        );
+        assert_eq!(
+            set_field(contents, "tags", "[y]").expect("set"),
+            "---\nname: a\nversion: 1.0\ntags: [y]\n---\nversion: body\n"
+        );
+        assert_eq!(
+            set_field("---\nname: a\n---\n", "version", "0.0.1").expect("set"),
+            "---\nname: a\nversion: 0.0.1\n---\n"
+        );
+        assert!(set_field("# No frontmatter\n", "version", "0.0.1").is_none());
+    }
+
+    #[test]
+    fn strips_frontmatter_from_body() {
+        let contents = "---\r\nname: example\r\n---\r\n# Body\r\n";
+        assert_eq!(strip_frontmatter(contents), "# Body\r\n");
+        assert_eq!(strip_frontmatter("# No frontmatter"), "# No frontmatter");
+    }
+
+    #[test]
+    fn parses_optional_metadata() {
+        let contents = "---\nname: example\ndescription: test\ntags: [pdf, docs]\nversion: 1.2\ntargets: claude\n---\n";
+        assert_eq!(
+            parse_metadata(contents),
+            Metadata {
+                tags: vec!["pdf".to_string(), "docs".to_string()],
+                version: Some("1.2".to_string()),
+                targets: vec!["claude".to_string()],
+            }
+        );
+        assert_eq!(parse_metadata("# No frontmatter"), Metadata::default());
+    }
+
+    #[test]
+    fn selects_per_tool_descriptions() {
+        let contents = "---\nname: example\ndescription:\n  default: Shared\n  codex: \"Codex: only\"\ntags: [a]\n---\nBody\n";
+        assert_eq!(
+            parse_frontmatter(contents).expect("parse").description,
+            "Shared"
+        );
+        assert_eq!(
+            select_description(contents, Tool::Codex).expect("codex"),
+            "---\nname: example\ndescription: 'Codex: only'\ntags: [a]\n---\nBody\n"
+        );
+        assert_eq!(
+            select_description(contents, Tool::Claude).expect("claude"),
+            "---\nname: example\ndescription: Shared\ntags: [a]\n---\nBody\n"
+        );
+
+        let partial = "---\nname: example\ndescription: {claude: Claude}\n---\n";
+        assert!(select_description(partial, Tool::Codex).is_err());
+        assert_eq!(
+            parse_error("---\nname: example\ndescription: {cursor: Cursor}\n---\n").message,
+            "invalid field 'description': unknown tool 'cursor'"
+        );
+        let plain = "---\nname: example\ndescription: Plain\n---\n";
+        assert_eq!(
+            select_description(plain, Tool::Codex).expect("plain"),
+            plain
+        );
+    }
 }
@@ -0,0 +1,33 @@
+//! Running the `git` command for source repositories.
+
+use std::{path::Path, process::Command};
+
+use crate::error::{Error, Result};
+
+/// Run `git` with arguments in a directory, returning its trimmed standard output.
+pub fn run(dir: &Path, args: &[&str]) -> Result<String> {
+    let failed = |message: String| Error::GitFailed {
+        args: args.join(" "),
+        message,
+    };
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|error| failed(error.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = match stderr.trim() {
+            "" => output.status.to_string(),
+            stderr => stderr.to_string(),
+        };
+        return Err(failed(message));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Return whether a directory is inside a git work tree.
+pub fn is_repo(dir: &Path) -> bool {
+    run(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|output| output == "true")
+}
@@ -0,0 +1,385 @@
+//! GitHub skill locations and repository metadata.
+
+use std::{collections::BTreeSet, fmt, time::Duration};
+
+use reqwest::Client;
+use serde::{Deserialize, de::DeserializeOwned};
+use tokio::time::sleep;
+use url::Url;
+
+use crate::{
+    config::ImportConfig,
+    error::{Error, Result},
+    http::{authorized_get, fetch_json},
+    skill::SkillFileNames,
+};
+
+/// Maximum size of a repository metadata response in bytes (1 MB).
+const MAX_METADATA_SIZE: u64 = 1024 * 1024;
+
+/// Maximum size of a git tree response in bytes (50 MB).
+const MAX_TREE_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Longest rate limit wait, in seconds, sat out before retrying rather than failing.
+const MAX_RATE_LIMIT_WAIT: u64 = 60;
+
+/// Times a rate-limited API request is retried.
+const RATE_LIMIT_RETRIES: usize = 3;
+
+/// Subset of the GitHub git tree response.
+#[derive(Debug, Deserialize)]
+struct Tree {
+    /// Entries of the tree, or of every subtree for a recursive listing.
+    tree: Vec<TreeEntry>,
+    /// Whether a recursive listing was cut short by GitHub's size limits.
+    #[serde(default)]
+    truncated: bool,
+}
+
+/// One entry of a git tree.
+#[derive(Debug, Clone, Deserialize)]
+struct TreeEntry {
+    /// Path relative to the listed tree.
+    path: String,
+    /// Entry type: `blob`, `tree`, or `commit` for submodules.
+    #[serde(rename = "type")]
+    kind: String,
+    /// Object id, used to list a subtree on its own.
+    sha: String,
+}
+
+/// A skill found in a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSkill {
+    /// Skill directory name.
+    pub(crate) name: String,
+    /// Path to the skill directory within the repository.
+    pub(crate) path: String,
+}
+
+/// GitHub REST API client that waits out short rate limits.
+///
+/// Requests carry any `[import.hosts."api.github.com"]` credentials, which also raise the limit.
+pub struct GithubApi<'a> {
+    /// HTTP client shared with downloads.
+    client: &'a Client,
+    /// Credentials by host.
+    import: &'a ImportConfig,
+}
+
+impl<'a> GithubApi<'a> {
+    /// Create an API client.
+    pub(crate) fn new(client: &'a Client, import: &'a ImportConfig) -> Self {
+        Self { client, import }
+    }
+
+    /// Fetch a JSON API resource, retrying after rate limits that reset within a minute.
+    async fn get<T: DeserializeOwned>(&self, url: &str, max_size: u64) -> Result<T> {
+        let mut retries = 0;
+        loop {
+            let request = authorized_get(self.client, self.import, url)?
+                .header("Accept", "application/vnd.github+json");
+            match fetch_json(request, url, max_size).await {
+                Err(Error::RateLimited { wait, .. })
+                    if wait <= MAX_RATE_LIMIT_WAIT && retries < RATE_LIMIT_RETRIES =>
+                {
+                    eprintln!("Rate limited by GitHub, retrying in {wait}s...");
+                    sleep(Duration::from_secs(wait.max(1))).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// List every skill in a repository at a resolved ref, under the source's path.
+    ///
+    /// A skill is a directory holding a skill file. The whole tree is listed in one request when
+    /// GitHub can return it; a repository too large for that is walked one subtree at a time.
+    pub(crate) async fn list_skills(
+        &self,
+        source: &GithubSource,
+        skill_files: &SkillFileNames,
+    ) -> Result<Vec<RemoteSkill>> {
+        let git_ref = source.git_ref.as_deref().unwrap_or("HEAD");
+        let base = format!(
+            "https://api.github.com/repos/{}/{}/git/trees",
+            source.owner, source.repo
+        );
+        let tree: Tree = self
+            .get(&format!("{base}/{git_ref}?recursive=1"), MAX_TREE_SIZE)
+            .await?;
+        let entries = if tree.truncated {
+            self.walk_tree(&base, git_ref).await?
+        } else {
+            tree.tree
+        };
+        Ok(skill_dirs(&entries, &source.path, skill_files))
+    }
+
+    /// List a tree by fetching each subtree separately, for trees too large to list at once.
+    async fn walk_tree(&self, base: &str, root: &str) -> Result<Vec<TreeEntry>> {
+        let mut entries = Vec::new();
+        let mut pending = vec![(String::new(), root.to_string())];
+        while let Some((prefix, sha)) = pending.pop() {
+            let tree: Tree = self.get(&format!("{base}/{sha}"), MAX_TREE_SIZE).await?;
+            for mut entry in tree.tree {
+                entry.path = format!("{prefix}{}", entry.path);
+                if entry.kind == "tree" {
+                    pending.push((format!("{}/", entry.path), entry.sha.clone()));
+                }
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Subset of the GitHub repository metadata response.
+#[derive(Debug, Deserialize)]
+struct RepoMetadata {
+    /// Name of the repository's default branch.
+    default_branch: String,
+}
+
+/// A skill directory inside a GitHub repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubSource {
+    /// Repository owner.
+    pub(crate) owner: String,
+    /// Repository name.
+    pub(crate) repo: String,
+    /// Branch, tag, or commit; `None` selects the repository's default branch.
+    pub(crate) git_ref: Option<String>,
+    /// Path to the skill within the repository, empty for the repository root.
+    pub(crate) path: String,
+}
+
+impl GithubSource {
+    /// Parse a `https://github.com/owner/repo/tree/ref/path` URL.
+    pub(crate) fn from_tree_url(raw: &str) -> Option<Self> {
+        let url = Url::parse(raw).ok()?;
+        if url.host_str() != Some("github.com") {
+            return None;
+        }
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [owner, repo, "tree", git_ref, path @ ..] => Some(Self {
+                owner: (*owner).to_string(),
+                repo: (*repo).to_string(),
+                git_ref: Some((*git_ref).to_string()),
+                path: path.join("/"),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parse `owner/repo[/path/to/skill][@ref]` shorthand.
+    ///
+    /// Anything that reads as a local path, such as `./dist/skill`, `~/skill`, or a name ending in
+    /// an archive extension, is rejected so that a mistyped path is reported as missing rather than
+    /// fetched from GitHub.
+    pub(crate) fn from_shorthand(raw: &str) -> Option<Self> {
+        let (location, git_ref) = match raw.rsplit_once('@') {
+            Some((location, git_ref)) if !git_ref.is_empty() => {
+                (location, Some(git_ref.to_string()))
+            }
+            Some(_) => return None,
+            None => (raw, None),
+        };
+        if looks_like_path(location) {
+            return None;
+        }
+
+        let mut segments = location.split('/');
+        let owner = segments.next().filter(|s| is_name(s))?;
+        let repo = segments.next().filter(|s| is_name(s))?;
+        let path: Vec<&str> = segments.collect();
+        if path.iter().any(|s| s.is_empty() || *s == "." || *s == "..") {
+            return None;
+        }
+
+        Some(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            git_ref,
+            path: path.join("/"),
+        })
+    }
+
+    /// Fill in the repository's default branch when no ref was given.
+    pub(crate) async fn resolve_ref(
+        &mut self,
+        client: &Client,
+        import: &ImportConfig,
+    ) -> Result<()> {
+        if self.git_ref.is_some() {
+            return Ok(());
+        }
+        let url = format!("https://api.github.com/repos/{}/{}", self.owner, self.repo);
+        let metadata: RepoMetadata = GithubApi::new(client, import)
+            .get(&url, MAX_METADATA_SIZE)
+            .await?;
+        self.git_ref = Some(metadata.default_branch);
+        Ok(())
+    }
+
+    /// Return the API URL for a ZIP archive of the repository at the requested ref.
+    pub(crate) fn archive_url(&self) -> String {
+        let base = format!(
+            "https://api.github.com/repos/{}/{}/zipball",
+            self.owner, self.repo
+        );
+        match &self.git_ref {
+            Some(git_ref) => format!("{base}/{git_ref}"),
+            None => base,
+        }
+    }
+}
+
+impl fmt::Display for GithubSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)?;
+        if !self.path.is_empty() {
+            write!(f, "/{}", self.path)?;
+        }
+        if let Some(git_ref) = &self.git_ref {
+            write!(f, "@{git_ref}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the skill directories among tree entries, limited to those under `root`.
+///
+/// The repository root itself is skipped, since import needs a skill path.
+fn skill_dirs(entries: &[TreeEntry], root: &str, skill_files: &SkillFileNames) -> Vec<RemoteSkill> {
+    let dirs: BTreeSet<&str> = entries
+        .iter()
+        .filter(|entry| entry.kind == "blob")
+        .filter_map(|entry| entry.path.rsplit_once('/'))
+        .filter(|(_, file_name)| skill_files.matches(file_name))
+        .map(|(dir, _)| dir)
+        .filter(|dir| {
+            root.is_empty()
+                || *dir == root
+                || dir
+                    .strip_prefix(root)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .collect();
+    dirs.into_iter()
+        .map(|dir| RemoteSkill {
+            name: dir.rsplit('/').next().unwrap_or(dir).to_string(),
+            path: dir.to_string(),
+        })
+        .collect()
+}
+
+/// Return whether `location` reads as a local path rather than GitHub shorthand: it starts with
+/// `./`, `../`, or `~`, or its last segment has an archive extension.
+fn looks_like_path(location: &str) -> bool {
+    let name = location
+        .rsplit('/')
+        .next()
+        .unwrap_or(location)
+        .to_ascii_lowercase();
+    location.starts_with("./")
+        || location.starts_with("../")
+        || location.starts_with('~')
+        || name.ends_with(".zip")
+        || name.ends_with(".tgz")
+        || name.contains(".tar")
+}
+
+/// Check whether a segment is a valid GitHub owner or repository name.
+fn is_name(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment != "."
+        && segment != ".."
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GithubSource, TreeEntry, skill_dirs};
+    use crate::skill::SkillFileNames;
+
+    #[test]
+    fn finds_skill_directories_in_trees() {
+        let entry = |path: &str, kind: &str| TreeEntry {
+            path: path.to_string(),
+            kind: kind.to_string(),
+            sha: String::new(),
+        };
+        let entries = [
+            entry("SKILL.md", "blob"),
+            entry("skills", "tree"),
+            entry("skills/pdf/SKILL.md", "blob"),
+            entry("skills/pdf/scripts/fill.sh", "blob"),
+            entry("skills/xlsx/SKILL.md", "blob"),
+            entry("docs/SKILL.md", "tree"),
+            entry("other/deep/SKILL.md", "blob"),
+        ];
+        let names = SkillFileNames::default();
+
+        let found: Vec<String> = skill_dirs(&entries, "", &names)
+            .into_iter()
+            .map(|skill| skill.path)
+            .collect();
+        assert_eq!(found, ["other/deep", "skills/pdf", "skills/xlsx"]);
+
+        let found = skill_dirs(&entries, "skills", &names);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "pdf");
+        assert!(skill_dirs(&entries, "skill", &names).is_empty());
+    }
+
+    #[test]
+    fn parses_tree_urls() {
+        let source =
+            GithubSource::from_tree_url("https://github.com/acme/skills/tree/main/skills/pdf")
+                .expect("tree url");
+        assert_eq!(source.owner, "acme");
+        assert_eq!(source.repo, "skills");
+        assert_eq!(source.git_ref.as_deref(), Some("main"));
+        assert_eq!(source.path, "skills/pdf");
+        assert!(GithubSource::from_tree_url("https://example.com/a/b/tree/main/c").is_none());
+    }
+
+    #[test]
+    fn parses_shorthand_with_and_without_ref() {
+        let source = GithubSource::from_shorthand("acme/skills/skills/pdf@v1.2").expect("ref");
+        assert_eq!(source.git_ref.as_deref(), Some("v1.2"));
+        assert_eq!(source.path, "skills/pdf");
+        assert_eq!(
+            source.archive_url(),
+            "https://api.github.com/repos/acme/skills/zipball/v1.2"
+        );
+
+        let source = GithubSource::from_shorthand("acme/skills").expect("repo only");
+        assert_eq!(source.git_ref, None);
+        assert_eq!(source.path, "");
+        assert_eq!(
+            source.archive_url(),
+            "https://api.github.com/repos/acme/skills/zipball"
+        );
+        assert_eq!(source.to_string(), "acme/skills");
+    }
+
+    #[test]
+    fn rejects_non_shorthand() {
+        assert!(GithubSource::from_shorthand("skill.zip").is_none());
+        assert!(GithubSource::from_shorthand("acme/skills/../etc").is_none());
+        assert!(GithubSource::from_shorthand("acme//pdf").is_none());
+        assert!(GithubSource::from_shorthand("acme/skills@").is_none());
+        assert!(GithubSource::from_shorthand("./local/skill").is_none());
+        assert!(GithubSource::from_shorthand("../acme/skills").is_none());
+        assert!(GithubSource::from_shorthand("~/acme/skills").is_none());
+        assert!(GithubSource::from_shorthand("dist/skills.zip").is_none());
+        assert!(GithubSource::from_shorthand("dist/skills.tar.gz").is_none());
+        assert!(GithubSource::from_shorthand("acme/skills/pdf.ZIP@v1").is_none());
+    }
+}
@@ -0,0 +1,310 @@
+//! Snapshots of installed skill copies taken just before the CLI overwrites them.
+//!
+//! Each snapshot lists the hashes of the files a copy held before a push, sync, import, or
+//! pruning pull replaced it. File contents are stored once per hash, compressed with zstd, in a `snapshots`
+//! directory beside the state file, so unchanged files are shared between snapshots. Contents
+//! written uncompressed by older versions are still read.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use zstd::{decode_all, encode_all};
+
+use crate::{
+    error::{Error, Result},
+    skill::SkillFileNames,
+    state::{InstallOrigin, State},
+    units::{format_time, parse_time},
+};
+
+/// Most snapshots kept; the oldest are dropped first.
+pub const MAX_SNAPSHOTS: usize = 500;
+
+/// Extension of compressed snapshot contents.
+const BLOB_EXTENSION: &str = "zst";
+
+/// A file in the snapshot store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredBlob {
+    /// Path of the file.
+    pub(crate) path: PathBuf,
+    /// Size on disk in bytes.
+    pub(crate) size: u64,
+}
+
+/// Contents of one skill directory just before an operation wrote to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Operation id, increasing with every snapshot taken.
+    pub(crate) id: u64,
+    /// Command that was about to write the copy.
+    pub(crate) origin: InstallOrigin,
+    /// Skill directory the snapshot was taken of.
+    pub(crate) dir: PathBuf,
+    /// When the snapshot was taken.
+    pub(crate) taken_at: SystemTime,
+    /// SHA-256 hex digests keyed by `/`-separated relative path, empty when there was no copy.
+    pub(crate) files: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    /// Return the name of the skill the snapshot belongs to.
+    pub(crate) fn skill(&self) -> Option<&str> {
+        self.dir.file_name()?.to_str()
+    }
+
+    /// Describe the operation the snapshot was taken for, e.g. `#3 (push, 2026-10-15 14:02 UTC)`.
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "#{} ({}, {})",
+            self.id,
+            self.origin.label(),
+            format_time(self.taken_at)
+        )
+    }
+
+    /// Return the relative path of the snapshot's skill file, preferring names listed earlier.
+    pub(crate) fn skill_file(&self, names: &SkillFileNames) -> Option<&str> {
+        self.files
+            .keys()
+            .filter(|path| !path.contains('/'))
+            .filter_map(|path| Some((names.rank(path)?, path.as_str())))
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, path)| path)
+    }
+}
+
+/// Which snapshot of a skill to look at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotQuery {
+    /// The snapshot taken by one operation.
+    Id(u64),
+    /// The first snapshot taken at or after a time, i.e. the content as it was then.
+    Time(SystemTime),
+}
+
+impl SnapshotQuery {
+    /// Parse an operation id, or a UTC date with an optional time.
+    pub(crate) fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let invalid = || Error::InvalidSnapshotQuery {
+            value: value.to_string(),
+        };
+        let id = value.strip_prefix('#').unwrap_or(value);
+        if !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()) {
+            return id.parse().map(Self::Id).map_err(|_| invalid());
+        }
+        parse_time(value).map(Self::Time).ok_or_else(invalid)
+    }
+
+    /// Return whether a snapshot matches, ignoring which skill it belongs to.
+    pub(crate) fn admits(&self, snapshot: &Snapshot) -> bool {
+        match self {
+            Self::Id(id) => snapshot.id == *id,
+            Self::Time(time) => snapshot.taken_at >= *time,
+        }
+    }
+}
+
+/// A skill file as it was before an operation.
+#[derive(Debug, Clone)]
+pub struct SnapshotFile {
+    /// The snapshot the file was read from.
+    pub(crate) snapshot: Snapshot,
+    /// Path the file had in the skill directory, absent when there was no copy.
+    pub(crate) path: Option<PathBuf>,
+    /// File contents, absent when there was no copy.
+    pub(crate) contents: Option<String>,
+}
+
+/// Find the snapshot of a skill selected by `at` and read its skill file.
+pub fn load_skill_file(
+    state: &State,
+    state_path: &Path,
+    skill: &str,
+    at: &str,
+    names: &SkillFileNames,
+) -> Result<SnapshotFile> {
+    let query = SnapshotQuery::parse(at)?;
+    let snapshot = state
+        .find_snapshot(skill, query)
+        .ok_or_else(|| Error::SnapshotNotFound {
+            skill: skill.to_string(),
+            at: at.to_string(),
+        })?
+        .clone();
+    let Some(relative) = snapshot.skill_file(names) else {
+        return Ok(SnapshotFile {
+            snapshot,
+            path: None,
+            contents: None,
+        });
+    };
+    let path = snapshot.dir.join(relative);
+    let contents = state
+        .snapshot_file(state_path, &snapshot, relative)?
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+    Ok(SnapshotFile {
+        snapshot,
+        path: Some(path),
+        contents,
+    })
+}
+
+/// Return the directory holding snapshot file contents for a state file.
+pub fn blob_dir(state_path: &Path) -> PathBuf {
+    state_path.with_file_name("snapshots")
+}
+
+/// Read the stored contents of a snapshot file by hash.
+pub fn read_blob(state_path: &Path, hash: &str) -> Result<Vec<u8>> {
+    let dir = blob_dir(state_path);
+    let path = dir.join(format!("{hash}.{BLOB_EXTENSION}"));
+    match fs::read(&path) {
+        Ok(compressed) => {
+            decode_all(compressed.as_slice()).map_err(|source| Error::StateRead { path, source })
+        }
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            let path = dir.join(hash);
+            fs::read(&path).map_err(|source| Error::StateRead { path, source })
+        }
+        Err(source) => Err(Error::StateRead { path, source }),
+    }
+}
+
+/// Store file contents by hash, skipping any already stored.
+pub fn write_blobs(state_path: &Path, blobs: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+    if blobs.is_empty() {
+        return Ok(());
+    }
+    let dir = blob_dir(state_path);
+    let write_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| Error::StateWrite { path, source }
+    };
+    fs::create_dir_all(&dir).map_err(write_error(&dir))?;
+    for (hash, contents) in blobs {
+        let path = dir.join(format!("{hash}.{BLOB_EXTENSION}"));
+        if path.exists() || dir.join(hash).exists() {
+            continue;
+        }
+        let compressed = encode_all(contents.as_slice(), 0).map_err(write_error(&path))?;
+        let staging = dir.join(format!(".{hash}.partial"));
+        fs::write(&staging, compressed).map_err(write_error(&staging))?;
+        fs::rename(&staging, &path).map_err(write_error(&path))?;
+    }
+    Ok(())
+}
+
+/// List the snapshot store, split into files some snapshot refers to and files none does.
+///
+/// Staging files left by an interrupted write are never referenced.
+pub fn stored_blobs(
+    state_path: &Path,
+    snapshots: &[Snapshot],
+) -> Result<(Vec<StoredBlob>, Vec<StoredBlob>)> {
+    let dir = blob_dir(state_path);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Default::default()),
+        Err(source) => return Err(Error::StateRead { path: dir, source }),
+    };
+    let referenced: BTreeSet<&str> = snapshots
+        .iter()
+        .flat_map(|snapshot| snapshot.files.values().map(String::as_str))
+        .collect();
+    let read_error = |source| Error::StateRead {
+        path: dir.clone(),
+        source,
+    };
+    let (mut kept, mut unreferenced) = (Vec::new(), Vec::new());
+    for entry in entries {
+        let entry = entry.map_err(read_error)?;
+        let blob = StoredBlob {
+            path: entry.path(),
+            size: entry.metadata().map_err(read_error)?.len(),
+        };
+        let name = entry.file_name();
+        let hash = name.to_str().map(|name| {
+            name.strip_suffix(&format!(".{BLOB_EXTENSION}"))
+                .unwrap_or(name)
+        });
+        if hash.is_some_and(|hash| referenced.contains(hash)) {
+            kept.push(blob);
+        } else {
+            unreferenced.push(blob);
+        }
+    }
+    Ok((kept, unreferenced))
+}
+
+/// Remove stored contents no snapshot refers to any more, returning what was removed.
+pub fn prune_blobs(state_path: &Path, snapshots: &[Snapshot]) -> Result<Vec<StoredBlob>> {
+    let (_, unreferenced) = stored_blobs(state_path, snapshots)?;
+    for blob in &unreferenced {
+        fs::remove_file(&blob.path).map_err(|source| Error::StateWrite {
+            path: blob.path.clone(),
+            source,
+        })?;
+    }
+    Ok(unreferenced)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        fs,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use tempfile::tempdir;
+
+    use super::{SnapshotQuery, blob_dir, read_blob, write_blobs};
+    use crate::error::Error;
+
+    #[test]
+    fn stores_contents_compressed_and_reads_older_stores() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("state.json");
+        let contents = "repeated line\n".repeat(200).into_bytes();
+        write_blobs(
+            &path,
+            &BTreeMap::from([("abc".to_string(), contents.clone())]),
+        )
+        .expect("write");
+
+        let stored = blob_dir(&path).join("abc.zst");
+        assert!(fs::metadata(&stored).expect("stored").len() < contents.len() as u64 / 10);
+        assert_eq!(read_blob(&path, "abc").expect("read"), contents);
+
+        fs::write(blob_dir(&path).join("def"), "legacy").expect("write");
+        assert_eq!(read_blob(&path, "def").expect("read"), b"legacy");
+    }
+
+    #[test]
+    fn parses_queries() {
+        assert_eq!(
+            SnapshotQuery::parse("12").expect("id"),
+            SnapshotQuery::Id(12)
+        );
+        assert_eq!(
+            SnapshotQuery::parse("#3").expect("id"),
+            SnapshotQuery::Id(3)
+        );
+        assert_eq!(
+            SnapshotQuery::parse("2000-02-29").expect("date"),
+            SnapshotQuery::Time(UNIX_EPOCH + Duration::from_secs(951_782_400))
+        );
+        assert!(matches!(
+            SnapshotQuery::parse("last week"),
+            Err(Error::InvalidSnapshotQuery { .. })
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Terminal-aware text layout helpers.
+
+use std::{
+    borrow::Cow,
+    io::{self, IsTerminal},
+};
+
+use terminal_size::{Width, terminal_size};
+use textwrap::{Options, wrap};
+use unicode_width::UnicodeWidthStr;
+
+/// Width used when stdout is a terminal but its size cannot be determined.
+const FALLBACK_WIDTH: usize = 80;
+
+/// Narrowest column budget used for wrapped text, regardless of indent.
+const MIN_WRAP_WIDTH: usize = 20;
+
+/// Return the column width to wrap output at, or `None` when stdout is not a terminal.
+pub fn output_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    Some(
+        terminal_size()
+            .map(|(Width(width), _)| usize::from(width))
+            .unwrap_or(FALLBACK_WIDTH),
+    )
+}
+
+/// Wrap plain text into lines that fit within `width` display columns once `indent` is prepended.
+///
+/// Widths are measured in terminal columns, so wide characters and emoji count correctly. The
+/// returned lines do not include the indent, so callers can style the text before prefixing it.
+/// With no width the text is returned as a single line.
+pub fn wrap_lines<'a>(text: &'a str, indent: &str, width: Option<usize>) -> Vec<Cow<'a, str>> {
+    let Some(width) = width else {
+        return vec![Cow::Borrowed(text)];
+    };
+    let available = width.saturating_sub(indent.width()).max(MIN_WRAP_WIDTH);
+    wrap(text, Options::new(available))
+}
+
+/// Pad text with spaces to `width` display columns, leaving longer text as it is.
+///
+/// Unlike `format!("{:<width$}")`, which counts characters, this keeps columns aligned when the
+/// text holds wide characters.
+pub fn pad(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(text.width())))
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::{pad, wrap_lines};
+
+    #[test]
+    fn does_not_wrap_without_width() {
+        let text = "word ".repeat(40);
+        let lines = wrap_lines(text.trim(), "  ", None);
+        assert_eq!(lines, vec![text.trim()]);
+    }
+
+    #[test]
+    fn wraps_by_display_width() {
+        let text = "日本語のテキスト ".repeat(6);
+        let lines = wrap_lines(text.trim(), "    ", Some(40));
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(line.width() + 4 <= 40, "line too wide: {line:?}");
+        }
+    }
+
+    #[test]
+    fn pads_by_display_width() {
+        assert_eq!(pad("pull", 6), "pull  ");
+        assert_eq!(pad("日本", 6).width(), 6);
+        assert_eq!(pad("restore", 6), "restore");
+    }
+
+    #[test]
+    fn keeps_a_minimum_width_for_deep_indents() {
+        let lines = wrap_lines("alpha beta gamma", "        ", Some(4));
+        assert_eq!(lines, vec!["alpha beta gamma"]);
+    }
+}
@@ -1,39 +1,95 @@
 #![warn(missing_docs)]
 //! Library support for the skills CLI.
 
+/// Skill archive formats, packing, and safe extraction.
+mod archive;
 /// Catalog loading for source and tool skills.
 mod catalog;
+/// Per-skill changelogs.
+mod changelog;
 /// Command-line interface wiring and dispatch.
 mod cli;
 /// Command implementations.
 mod commands;
 /// Configuration loading and validation.
 mod config;
+/// Execution environment shared by all commands.
+mod context;
 /// Common diagnostics and warning aggregation.
 mod diagnostics;
 /// Unified diff rendering helpers.
 mod diff;
 /// Error handling for the crate.
 mod error;
+/// Text filters available to skill templates.
+mod filters;
+/// Canonical formatting of skill files.
+mod format;
 /// YAML frontmatter parsing for skills.
 mod frontmatter;
+/// Running `git` for source repositories.
+mod git;
+/// GitHub skill locations and repository metadata.
+mod github;
+/// Snapshots of installed skill copies taken before writes.
+mod history;
+/// Shared HTTP client construction.
+mod http;
+/// Terminal-aware text layout helpers.
+mod layout;
+/// Machine environment functions available to skill templates.
+mod machine;
+/// Skill name validation and normalization.
+mod name;
+/// OCI artifact packaging and registry transfer.
+mod oci;
+/// Environment variable overrides for config settings.
+mod overrides;
 /// Color palette and styling for CLI output.
 mod palette;
 /// Path expansion and normalization utilities.
 mod paths;
+/// Executable-bit handling and write access checks for skill files.
+mod permissions;
+/// Team policy restricting what may be imported and pushed.
+mod policy;
+/// Opt-in timing of the phases of a command.
+mod profile;
+/// Project context exposed to templates.
+mod project;
+/// Built-in spellchecking and prose lints.
+mod prose;
+/// Staging area for imported skills awaiting review.
+mod quarantine;
+/// On-disk cache of rendered skill templates.
+mod render_cache;
+/// Heuristic scanning of skill content for risky instructions.
+mod scan;
+/// Frontmatter validation against source-provided JSON Schemas.
+mod schema;
+/// Detection of skills with near-identical descriptions.
+mod similarity;
 /// Skill loading and templating helpers.
 mod skill;
+/// Persistent record of installed skill copies.
+mod state;
 /// Status computation for list/diff operations.
 mod status;
+/// Storage backends that skill catalogs are read from.
+mod store;
 /// Test utilities for mock skill environments.
 #[cfg(test)]
 mod testutil;
 /// Tool directory discovery and metadata.
 mod tool;
+/// All-or-nothing writes of skill directories.
+mod transaction;
+/// Human-readable formatting of sizes, counts, and ages.
+mod units;
 
 pub use crate::error::{Error, Result};
 
-/// Run the CLI, returning a structured error on failure.
+/// Run the CLI, reporting any error on stderr and returning it for its exit code.
 pub async fn run() -> Result<()> {
     cli::run().await
 }
@@ -0,0 +1,197 @@
+//! Machine environment functions available to skill templates.
+//!
+//! Templates can ask which platform they are rendered on, whether a command is installed, and
+//! the value of a small set of environment variables. The functions only inspect the machine:
+//! commands are looked up on `PATH` but never run, and variables outside [`ENV_ALLOWED`] and the
+//! [`ENV_PREFIX`] namespace cannot be read, so a template cannot copy secrets into a skill.
+
+use std::{
+    env, fs,
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use minijinja::{Environment, ErrorKind};
+
+/// Environment variables any template may read.
+pub const ENV_ALLOWED: &[&str] = &["EDITOR", "LANG", "SHELL", "TERM", "VISUAL"];
+
+/// Prefix of environment variables set aside for templates.
+pub const ENV_PREFIX: &str = "SKILLS_";
+
+/// File extensions tried when looking up a command on Windows.
+#[cfg(windows)]
+const COMMAND_EXTENSIONS: &[&str] = &["exe", "cmd", "bat", "com"];
+
+/// A machine lookup made while rendering, such as `has_command("rg") = true`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    /// The call as written in a template.
+    pub call: String,
+    /// The value it returned.
+    pub value: String,
+}
+
+/// Records the machine lookups made by a rendering.
+///
+/// Renderings that made lookups depend on the machine they ran on, so they are not cached.
+#[derive(Debug, Clone, Default)]
+pub struct Probe {
+    /// Lookups in the order they were made.
+    lookups: Arc<Mutex<Vec<Lookup>>>,
+}
+
+impl Probe {
+    /// Record a lookup.
+    fn record(&self, call: String, value: String) {
+        self.lookups
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Lookup { call, value });
+    }
+
+    /// Return the lookups made so far, without repeats.
+    pub fn lookups(&self) -> Vec<Lookup> {
+        let mut lookups: Vec<Lookup> = Vec::new();
+        for lookup in self
+            .lookups
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+        {
+            if !lookups.contains(lookup) {
+                lookups.push(lookup.clone());
+            }
+        }
+        lookups
+    }
+}
+
+/// Signatures of the functions [`register`] adds, for listing to template authors.
+pub const FUNCTIONS: &[&str] = &["env(name, default)", "has_command(name)", "platform()"];
+
+/// Add the machine functions to a template environment, recording each call in `probe`.
+pub fn register(env: &mut Environment<'_>, probe: &Probe) {
+    let recorder = probe.clone();
+    env.add_function("platform", move || {
+        let value = platform();
+        recorder.record("platform()".to_string(), value.to_string());
+        value
+    });
+
+    let recorder = probe.clone();
+    env.add_function(
+        "has_command",
+        move |name: String| -> Result<bool, minijinja::Error> {
+            let found = has_command(&name).map_err(invalid)?;
+            recorder.record(format!("has_command({name:?})"), found.to_string());
+            Ok(found)
+        },
+    );
+
+    let recorder = probe.clone();
+    env.add_function(
+        "env",
+        move |name: String, default: Option<String>| -> Result<String, minijinja::Error> {
+            let value = read_env(&name, default.as_deref()).map_err(invalid)?;
+            recorder.record(format!("env({name:?})"), value.clone());
+            Ok(value)
+        },
+    );
+}
+
+/// Wrap a lookup error as a template error.
+fn invalid(message: String) -> minijinja::Error {
+    minijinja::Error::new(ErrorKind::InvalidOperation, message)
+}
+
+/// Return the platform name: `macos`, `linux`, `windows`, or the OS name on other platforms.
+pub fn platform() -> &'static str {
+    env::consts::OS
+}
+
+/// Return whether a command is installed on `PATH`.
+fn has_command(name: &str) -> Result<bool, String> {
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(format!(
+            "has_command expects a command name, not a path: {name:?}"
+        ));
+    }
+    let Some(path) = env::var_os("PATH") else {
+        return Ok(false);
+    };
+    Ok(env::split_paths(&path).any(|dir| is_command(&dir, name)))
+}
+
+/// Return whether a directory holds an executable with a command's name.
+#[cfg(not(windows))]
+fn is_command(dir: &Path, name: &str) -> bool {
+    use crate::permissions::is_executable;
+
+    fs::metadata(dir.join(name))
+        .is_ok_and(|metadata| metadata.is_file() && is_executable(&metadata))
+}
+
+/// Return whether a directory holds an executable with a command's name.
+#[cfg(windows)]
+fn is_command(dir: &Path, name: &str) -> bool {
+    COMMAND_EXTENSIONS.iter().any(|extension| {
+        fs::metadata(dir.join(name).with_extension(extension))
+            .is_ok_and(|metadata| metadata.is_file())
+    })
+}
+
+/// Read an environment variable a template is allowed to see, or `default` when it is unset.
+fn read_env(name: &str, default: Option<&str>) -> Result<String, String> {
+    if !ENV_ALLOWED.contains(&name) && !name.starts_with(ENV_PREFIX) {
+        return Err(format!(
+            "env({name:?}) is not readable from templates; only {} and {ENV_PREFIX}* variables are",
+            ENV_ALLOWED.join(", ")
+        ));
+    }
+    Ok(env::var(name).unwrap_or_else(|_| default.unwrap_or_default().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use minijinja::Environment;
+
+    use super::{Probe, platform, register};
+
+    #[test]
+    fn records_machine_lookups() {
+        let probe = Probe::default();
+        let mut env = Environment::new();
+        register(&mut env, &probe);
+        let render = |template: &str| {
+            env.render_str(template, ())
+                .map_err(|error| error.to_string())
+        };
+
+        assert_eq!(render("{{ platform() }}").expect("platform"), platform());
+        assert_eq!(
+            render("{{ has_command(\"skills-no-such-command\") }}").expect("has_command"),
+            "false"
+        );
+        assert_eq!(
+            render("{{ env(\"SKILLS_TEST_UNSET\", \"fallback\") }}{{ platform() }}").expect("env"),
+            format!("fallback{}", platform())
+        );
+        assert!(render("{{ env(\"AWS_SECRET_ACCESS_KEY\") }}").is_err());
+        assert!(render("{{ has_command(\"/bin/sh\") }}").is_err());
+
+        let calls: Vec<String> = probe
+            .lookups()
+            .into_iter()
+            .map(|lookup| lookup.call)
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                "platform()",
+                "has_command(\"skills-no-such-command\")",
+                "env(\"SKILLS_TEST_UNSET\")",
+            ]
+        );
+    }
+}
@@ -8,9 +8,6 @@ use skills::run;
 async fn main() -> ExitCode {
     match run().await {
         Ok(()) => ExitCode::SUCCESS,
-        Err(error) => {
-            eprintln!("{error}");
-            error.exit_code()
-        }
+        Err(error) => error.exit_code(),
     }
 }
@@ -0,0 +1,68 @@
+//! Skill name validation and normalization.
+
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+/// Reasons a skill name cannot be used as a directory name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum NameError {
+    /// The name is empty after trimming.
+    #[error("name is empty")]
+    Empty,
+    /// The name contains a control character.
+    #[error("name contains a control character")]
+    ControlCharacter,
+    /// The name contains a path separator.
+    #[error("name contains a path separator")]
+    PathSeparator,
+    /// The name starts with a dot.
+    #[error("name must not start with '.'")]
+    LeadingDot,
+}
+
+/// Normalize a skill name to NFC and validate that it is safe to use as a directory name.
+pub fn normalize_name(raw: &str) -> Result<String, NameError> {
+    let name = raw.trim().nfc().collect::<String>();
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.chars().any(char::is_control) {
+        return Err(NameError::ControlCharacter);
+    }
+    if name.contains(['/', '\\']) {
+        return Err(NameError::PathSeparator);
+    }
+    if name.starts_with('.') {
+        return Err(NameError::LeadingDot);
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NameError, normalize_name};
+
+    #[test]
+    fn normalizes_to_nfc() {
+        let decomposed = "cafe\u{301}";
+        assert_eq!(normalize_name(decomposed), Ok("caf\u{e9}".to_string()));
+    }
+
+    #[test]
+    fn trims_surrounding_whitespace() {
+        assert_eq!(normalize_name("  my-skill "), Ok("my-skill".to_string()));
+    }
+
+    #[test]
+    fn rejects_unsafe_names() {
+        assert_eq!(normalize_name("   "), Err(NameError::Empty));
+        assert_eq!(
+            normalize_name("bad\u{7}name"),
+            Err(NameError::ControlCharacter)
+        );
+        assert_eq!(normalize_name("a/b"), Err(NameError::PathSeparator));
+        assert_eq!(normalize_name("a\\b"), Err(NameError::PathSeparator));
+        assert_eq!(normalize_name(".hidden"), Err(NameError::LeadingDot));
+        assert_eq!(normalize_name(".."), Err(NameError::LeadingDot));
+    }
+}
@@ -0,0 +1,281 @@
+//! OCI artifact packaging and registry transfer for skills.
+
+use std::{collections::BTreeMap, fs, path::Path, slice};
+
+use docker_credential::DockerCredential;
+use oci_client::{
+    Client, Reference,
+    client::{Certificate, CertificateEncoding, ClientConfig, Config, ImageLayer},
+    errors::OciDistributionError,
+    manifest::{OCI_IMAGE_MEDIA_TYPE, OciImageManifest},
+    secrets::RegistryAuth,
+};
+use serde_json::json;
+
+use crate::{
+    config::NetworkConfig,
+    error::{Error, Result},
+    http::USER_AGENT,
+    profile::{Phase, span},
+    state::hash_bytes,
+};
+
+/// Artifact type identifying packaged skills.
+pub const ARTIFACT_TYPE: &str = "application/vnd.skills.skill.v1";
+
+/// Media type of the layer holding the skill ZIP archive.
+pub const LAYER_MEDIA_TYPE: &str = "application/vnd.skills.skill.layer.v1+zip";
+
+/// Media type of the config blob holding skill metadata.
+const CONFIG_MEDIA_TYPE: &str = "application/vnd.skills.skill.config.v1+json";
+
+/// Annotation holding a human-readable title.
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+/// Annotation holding a description.
+const DESCRIPTION_ANNOTATION: &str = "org.opencontainers.image.description";
+
+/// Annotation naming a manifest within an image layout.
+const REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+
+/// Registry key Docker uses for Docker Hub credentials.
+const DOCKER_HUB_SERVER: &str = "https://index.docker.io/v1/";
+
+/// A skill packaged as an OCI artifact: a metadata config blob plus one ZIP layer.
+#[derive(Clone)]
+pub struct Artifact {
+    /// Config blob with the skill name and description.
+    config: Config,
+    /// Layer holding the skill ZIP archive.
+    layer: ImageLayer,
+    /// Manifest describing the config and layer.
+    manifest: OciImageManifest,
+}
+
+impl Artifact {
+    /// Package a skill ZIP archive as an artifact.
+    pub(crate) fn new(name: &str, description: &str, archive: Vec<u8>) -> Self {
+        let config_data = json!({ "name": name, "description": description }).to_string();
+        let config = Config::new(
+            config_data.into_bytes(),
+            CONFIG_MEDIA_TYPE.to_string(),
+            None,
+        );
+        let layer = ImageLayer::new(
+            archive,
+            LAYER_MEDIA_TYPE.to_string(),
+            Some(BTreeMap::from([(
+                TITLE_ANNOTATION.to_string(),
+                format!("{name}.zip"),
+            )])),
+        );
+        let annotations = BTreeMap::from([
+            (TITLE_ANNOTATION.to_string(), name.to_string()),
+            (DESCRIPTION_ANNOTATION.to_string(), description.to_string()),
+        ]);
+        let mut manifest =
+            OciImageManifest::build(slice::from_ref(&layer), &config, Some(annotations));
+        manifest.artifact_type = Some(ARTIFACT_TYPE.to_string());
+        Self {
+            config,
+            layer,
+            manifest,
+        }
+    }
+
+    /// Write the artifact as an OCI image layout directory tagged with `tag`.
+    pub(crate) fn write_layout(&self, dir: &Path, tag: &str) -> Result<()> {
+        let blobs = dir.join("blobs").join("sha256");
+        fs::create_dir_all(&blobs).map_err(|source| Error::SkillWrite {
+            path: blobs.clone(),
+            source,
+        })?;
+
+        let manifest = serde_json::to_vec(&self.manifest).map_err(|error| Error::ZipCreate {
+            path: dir.to_path_buf(),
+            message: error.to_string(),
+        })?;
+        for blob in [&self.config.data[..], &self.layer.data[..], &manifest] {
+            write_file(&blobs.join(hash_bytes(blob)), blob)?;
+        }
+
+        let index = json!({
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.oci.image.index.v1+json",
+            "manifests": [{
+                "mediaType": OCI_IMAGE_MEDIA_TYPE,
+                "artifactType": ARTIFACT_TYPE,
+                "digest": format!("sha256:{}", hash_bytes(&manifest)),
+                "size": manifest.len(),
+                "annotations": { REF_NAME_ANNOTATION: tag },
+            }],
+        });
+        write_file(&dir.join("index.json"), index.to_string().as_bytes())?;
+        write_file(
+            &dir.join("oci-layout"),
+            json!({ "imageLayoutVersion": "1.0.0" })
+                .to_string()
+                .as_bytes(),
+        )
+    }
+
+    /// Push the artifact to a registry, returning the pullable manifest URL.
+    pub(crate) async fn push(&self, network: &NetworkConfig, reference: &str) -> Result<String> {
+        let parsed = parse_reference(reference)?;
+        let client = registry_client(network)?;
+        let _span = span(Phase::Network);
+        let response = client
+            .push(
+                &parsed,
+                slice::from_ref(&self.layer),
+                self.config.clone(),
+                &registry_auth(&parsed),
+                Some(self.manifest.clone()),
+            )
+            .await
+            .map_err(|error| registry_error(reference, &error))?;
+        Ok(response.manifest_url)
+    }
+}
+
+/// Pull a skill artifact from a registry and return its ZIP archive.
+pub async fn pull(network: &NetworkConfig, reference: &str) -> Result<Vec<u8>> {
+    let parsed = parse_reference(reference)?;
+    let client = registry_client(network)?;
+    let _span = span(Phase::Network);
+    let image = client
+        .pull(&parsed, &registry_auth(&parsed), vec![LAYER_MEDIA_TYPE])
+        .await
+        .map_err(|error| registry_error(reference, &error))?;
+    image
+        .layers
+        .into_iter()
+        .find(|layer| layer.media_type == LAYER_MEDIA_TYPE)
+        .map(|layer| layer.data.to_vec())
+        .ok_or_else(|| Error::Oci {
+            reference: reference.to_string(),
+            message: "artifact does not contain a skill archive".to_string(),
+        })
+}
+
+/// Parse a `registry/repository[:tag|@digest]` reference.
+fn parse_reference(reference: &str) -> Result<Reference> {
+    reference
+        .parse()
+        .map_err(|error: oci_client::ParseError| Error::InvalidOciReference {
+            reference: reference.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// Build a registry client that honors the `[network]` settings.
+fn registry_client(network: &NetworkConfig) -> Result<Client> {
+    let mut extra_root_certificates = Vec::new();
+    if let Some(path) = &network.ca_bundle {
+        let data = fs::read(path).map_err(|source| Error::CaBundleRead {
+            path: path.clone(),
+            source,
+        })?;
+        extra_root_certificates.push(Certificate {
+            encoding: CertificateEncoding::Pem,
+            data,
+        });
+    }
+    let config = ClientConfig {
+        https_proxy: network.proxy.clone(),
+        http_proxy: network.proxy.clone(),
+        extra_root_certificates,
+        connect_timeout: Some(network.connect_timeout),
+        read_timeout: Some(network.read_timeout),
+        user_agent: USER_AGENT,
+        ..ClientConfig::default()
+    };
+    Client::try_from(config).map_err(|error| Error::HttpClient {
+        message: error.to_string(),
+    })
+}
+
+/// Look up registry credentials from the Docker credential store, falling back to anonymous.
+fn registry_auth(reference: &Reference) -> RegistryAuth {
+    let server = match reference.resolve_registry() {
+        "index.docker.io" | "docker.io" | "registry-1.docker.io" => DOCKER_HUB_SERVER,
+        server => server,
+    };
+    match docker_credential::get_credential(server) {
+        Ok(DockerCredential::UsernamePassword(username, password)) => {
+            RegistryAuth::Basic(username, password)
+        }
+        Ok(DockerCredential::IdentityToken(_)) | Err(_) => RegistryAuth::Anonymous,
+    }
+}
+
+/// Convert a registry client failure into an error.
+fn registry_error(reference: &str, error: &OciDistributionError) -> Error {
+    Error::Oci {
+        reference: reference.to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// Write a file in an image layout.
+fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+    fs::write(path, contents).map_err(|source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::Value;
+    use tempfile::tempdir;
+
+    use super::{ARTIFACT_TYPE, Artifact, LAYER_MEDIA_TYPE, parse_reference};
+    use crate::{error::Error, state::hash_bytes};
+
+    #[test]
+    fn writes_image_layout_with_skill_layer() {
+        let dir = tempdir().expect("tempdir");
+        let layout = dir.path().join("alpha.oci");
+        let archive = b"PK\x05\x06archive".to_vec();
+
+        Artifact::new("alpha", "Alpha skill", archive.clone())
+            .write_layout(&layout, "alpha")
+            .expect("write layout");
+
+        let index: Value =
+            serde_json::from_str(&fs::read_to_string(layout.join("index.json")).expect("index"))
+                .expect("json");
+        let entry = &index["manifests"][0];
+        assert_eq!(entry["artifactType"], ARTIFACT_TYPE);
+        assert_eq!(
+            entry["annotations"]["org.opencontainers.image.ref.name"],
+            "alpha"
+        );
+
+        let blobs = layout.join("blobs").join("sha256");
+        let digest = entry["digest"].as_str().expect("digest");
+        let manifest: Value = serde_json::from_slice(
+            &fs::read(blobs.join(digest.trim_start_matches("sha256:"))).expect("manifest"),
+        )
+        .expect("json");
+        assert_eq!(manifest["layers"][0]["mediaType"], LAYER_MEDIA_TYPE);
+        assert_eq!(
+            manifest["layers"][0]["digest"],
+            format!("sha256:{}", hash_bytes(&archive))
+        );
+        assert_eq!(
+            fs::read(blobs.join(hash_bytes(&archive))).expect("layer"),
+            archive
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_references() {
+        let error = parse_reference("Not A Reference").expect_err("invalid");
+        assert!(matches!(error, Error::InvalidOciReference { .. }));
+        assert!(parse_reference("ghcr.io/acme/skills/pdf:1.0").is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+//! Environment variable overrides for config settings.
+//!
+//! Settings resolve in the order: command-line flag, environment variable, project config, user
+//! config. Overrides are read once at startup and carried on the command context.
+
+use std::{
+    collections::HashMap,
+    env,
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::ColorChoice,
+    error::{Error, Result},
+    paths::expand_path,
+    tool::Tool,
+};
+
+/// Variable naming the config file to load instead of `~/.skills.toml`.
+const CONFIG_VAR: &str = "SKILLS_CONFIG";
+
+/// Variable listing source directories, separated like `PATH`.
+const SOURCES_VAR: &str = "SKILLS_SOURCES";
+
+/// Variable naming the pager used when `--pager` is not given.
+const PAGER_VAR: &str = "SKILLS_PAGER";
+
+/// Variable selecting color output when `--color` is not given.
+const COLOR_VAR: &str = "SKILLS_COLOR";
+
+/// Settings taken from `SKILLS_*` environment variables.
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    /// Config file path from `SKILLS_CONFIG`.
+    pub(crate) config_path: Option<PathBuf>,
+    /// Source directories from `SKILLS_SOURCES`.
+    pub(crate) sources: Option<Vec<PathBuf>>,
+    /// Global skill directories from `SKILLS_<TOOL>_DIR`.
+    pub(crate) tool_dirs: HashMap<Tool, PathBuf>,
+    /// Pager command from `SKILLS_PAGER`.
+    pub(crate) pager: Option<String>,
+    /// Color selection from `SKILLS_COLOR`.
+    pub(crate) color: Option<ColorChoice>,
+    /// Names and raw values of the variables that were applied, in the order they are checked.
+    pub(crate) applied: Vec<(String, String)>,
+}
+
+impl EnvOverrides {
+    /// Read overrides from the process environment, resolving relative paths against `work_dir`.
+    pub(crate) fn from_env(work_dir: &Path) -> Result<Self> {
+        Self::from_vars(|name| env::var_os(name), work_dir)
+    }
+
+    /// Read overrides through a variable lookup, resolving relative paths against `work_dir`.
+    ///
+    /// Unset and empty variables are ignored.
+    pub(crate) fn from_vars(
+        lookup: impl Fn(&str) -> Option<OsString>,
+        work_dir: &Path,
+    ) -> Result<Self> {
+        let mut overrides = Self::default();
+        let mut get = |name: &str| {
+            let value = lookup(name).filter(|value| !value.is_empty())?;
+            overrides
+                .applied
+                .push((name.to_string(), value.to_string_lossy().into_owned()));
+            Some(value)
+        };
+
+        let config_path = get(CONFIG_VAR);
+        let sources = get(SOURCES_VAR);
+        let tool_dirs: Vec<_> = Tool::all()
+            .into_iter()
+            .filter_map(|tool| get(&tool_dir_var(tool)).map(|value| (tool, value)))
+            .collect();
+        let pager = get(PAGER_VAR);
+        let color = get(COLOR_VAR);
+
+        overrides.config_path = config_path
+            .map(|value| resolve_path(CONFIG_VAR, &value, work_dir))
+            .transpose()?;
+        overrides.sources = sources
+            .map(|value| {
+                env::split_paths(&value)
+                    .filter(|path| !path.as_os_str().is_empty())
+                    .map(|path| resolve_path(SOURCES_VAR, path.as_os_str(), work_dir))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+        for (tool, value) in tool_dirs {
+            let dir = resolve_path(&tool_dir_var(tool), &value, work_dir)?;
+            overrides.tool_dirs.insert(tool, dir);
+        }
+        overrides.pager = pager.map(|value| value.to_string_lossy().into_owned());
+        overrides.color = color.map(|value| parse_color(&value)).transpose()?;
+        Ok(overrides)
+    }
+}
+
+/// Return the variable overriding a tool's global skills directory, e.g. `SKILLS_CLAUDE_DIR`.
+pub fn tool_dir_var(tool: Tool) -> String {
+    format!("SKILLS_{}_DIR", tool.id().to_uppercase())
+}
+
+/// Expand a path from an environment variable.
+fn resolve_path(name: &str, value: &OsStr, work_dir: &Path) -> Result<PathBuf> {
+    let raw = value.to_str().ok_or_else(|| Error::InvalidEnvVar {
+        name: name.to_string(),
+        message: "value is not valid unicode".to_string(),
+    })?;
+    expand_path(raw, work_dir)
+}
+
+/// Parse a `SKILLS_COLOR` value.
+fn parse_color(value: &OsStr) -> Result<ColorChoice> {
+    match value.to_str() {
+        Some("auto") => Ok(ColorChoice::Auto),
+        Some("always") => Ok(ColorChoice::Always),
+        Some("never") => Ok(ColorChoice::Never),
+        _ => Err(Error::InvalidEnvVar {
+            name: COLOR_VAR.to_string(),
+            message: format!(
+                "expected auto, always, or never, got '{}'",
+                value.to_string_lossy()
+            ),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, ffi::OsString, path::Path};
+
+    use super::EnvOverrides;
+    use crate::{
+        commands::ColorChoice,
+        error::{Error, Result},
+        tool::Tool,
+    };
+
+    fn overrides(vars: &[(&str, &str)]) -> Result<EnvOverrides> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        EnvOverrides::from_vars(
+            |name| vars.get(name).map(OsString::from),
+            Path::new("/work"),
+        )
+    }
+
+    #[test]
+    fn reads_paths_relative_to_work_dir() {
+        let overrides = overrides(&[
+            ("SKILLS_SOURCES", "team:/abs/skills"),
+            ("SKILLS_CODEX_DIR", "codex"),
+            ("SKILLS_PAGER", "less -R"),
+            ("SKILLS_COLOR", "never"),
+            ("SKILLS_CLAUDE_DIR", ""),
+        ])
+        .expect("overrides");
+
+        assert_eq!(
+            overrides.sources,
+            Some(vec!["/work/team".into(), "/abs/skills".into()])
+        );
+        assert_eq!(overrides.tool_dirs[&Tool::Codex], Path::new("/work/codex"));
+        assert!(!overrides.tool_dirs.contains_key(&Tool::Claude));
+        assert_eq!(overrides.pager.as_deref(), Some("less -R"));
+        assert_eq!(overrides.color, Some(ColorChoice::Never));
+        assert_eq!(
+            overrides
+                .applied
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "SKILLS_SOURCES",
+                "SKILLS_CODEX_DIR",
+                "SKILLS_PAGER",
+                "SKILLS_COLOR"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_colors() {
+        let error = overrides(&[("SKILLS_COLOR", "sometimes")]).expect_err("invalid color");
+        assert!(matches!(error, Error::InvalidEnvVar { .. }));
+    }
+}
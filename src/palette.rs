@@ -1,133 +1,220 @@
 //! Color palette and styling for CLI output.
 //!
-//! This module defines a consistent visual style for all CLI output.
-//! Colors are designed for modern terminals with full color support.
-
-use owo_colors::{OwoColorize, Style};
-
-/// Style for skill names - the primary identifier, visually prominent.
-pub fn skill_name() -> Style {
-    Style::new().cyan().bold()
-}
-
-/// Style for section headings like "Local Skills:" or "Conflicts:".
-pub fn heading() -> Style {
-    Style::new().white().bold()
-}
-
-/// Style for labels like "source:", "path:", "claude:", "codex:".
-pub fn label() -> Style {
-    Style::new().blue()
-}
-
-/// Style for description text - readable but subdued.
-pub fn description() -> Style {
-    Style::new().dimmed()
-}
-
-/// Style for path values.
-pub fn path() -> Style {
-    Style::new().white()
-}
-
-/// Style for tool tags like "[claude]" or "[codex]".
-pub fn tool_tag() -> Style {
-    Style::new().dimmed()
+//! This module defines a consistent visual style for all CLI output. Every styled element is
+//! assigned a [`Role`], and the user's `[theme]` config section can override the style of any
+//! role. Commands render through a [`Palette`], which applies the theme only when color output
+//! is enabled.
+
+use std::{collections::HashMap, str::FromStr};
+
+use owo_colors::{DynColors, OwoColorize, Style};
+use serde::Deserialize;
+
+/// Styled output roles that can be configured in the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Skill names - the primary identifier, visually prominent.
+    SkillName,
+    /// Section headings like "Local Skills:" or "Conflicts:".
+    Heading,
+    /// Labels like "source:", "path:", "claude:", "codex:".
+    Label,
+    /// Description text - readable but subdued.
+    Description,
+    /// Path values.
+    Path,
+    /// Tool tags like "[claude]" or "[codex]".
+    ToolTag,
+    /// Action verbs like "Importing" or "Would rename".
+    Action,
+    /// Synced status.
+    StatusSynced,
+    /// Modified status.
+    StatusModified,
+    /// Missing and orphan statuses.
+    StatusError,
+    /// Success markers.
+    Success,
+    /// Failure markers.
+    Failure,
+    /// Warning headings.
+    WarningHeading,
+    /// Warning text.
+    Warning,
+    /// Diff file headers.
+    DiffHeader,
+    /// Diff hunk headers.
+    DiffHunk,
+    /// Added diff lines.
+    DiffAdd,
+    /// Removed diff lines.
+    DiffRemove,
+    /// Markdown YAML frontmatter.
+    Frontmatter,
+    /// Markdown headings.
+    MarkdownHeading,
+    /// Markdown code blocks and inline code.
+    Code,
+    /// Markdown list markers.
+    Bullet,
+    /// Markdown blockquotes.
+    Quote,
+    /// Markdown strong emphasis.
+    Strong,
+    /// Markdown emphasis.
+    Emphasis,
+    /// Markdown link text.
+    Link,
+    /// Markdown link targets.
+    LinkUrl,
+    /// Template tags and annotations of template-conditional output.
+    Template,
+}
+
+impl Role {
+    /// Return the built-in style for this role.
+    fn default_style(self) -> Style {
+        match self {
+            Self::SkillName => Style::new().cyan().bold(),
+            Self::Heading | Self::MarkdownHeading => Style::new().white().bold(),
+            Self::Label | Self::Bullet => Style::new().blue(),
+            Self::Description | Self::ToolTag | Self::Frontmatter | Self::LinkUrl => {
+                Style::new().dimmed()
+            }
+            Self::Path => Style::new().white(),
+            Self::Action | Self::DiffHeader | Self::Strong => Style::new().bold(),
+            Self::StatusSynced | Self::Success | Self::DiffAdd => Style::new().green(),
+            Self::StatusModified | Self::Warning | Self::Quote => Style::new().yellow(),
+            Self::StatusError | Self::Failure | Self::DiffRemove => Style::new().red(),
+            Self::WarningHeading => Style::new().yellow().bold(),
+            Self::DiffHunk | Self::Code => Style::new().cyan(),
+            Self::Emphasis => Style::new().italic(),
+            Self::Link => Style::new().blue().underline(),
+            Self::Template => Style::new().magenta(),
+        }
+    }
 }
 
-/// Style for synced status.
-pub fn status_synced() -> Style {
-    Style::new().green()
+/// A style parsed from a theme specification such as `"bold bright-red"`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeStyle(Style);
+
+impl TryFrom<String> for ThemeStyle {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        let mut style = Style::new();
+        for token in spec.split_whitespace() {
+            style = match token {
+                "bold" => style.bold(),
+                "dimmed" => style.dimmed(),
+                "italic" => style.italic(),
+                "underline" => style.underline(),
+                color => {
+                    let color = DynColors::from_str(&color.replace('-', " "))
+                        .map_err(|_| format!("unknown color or effect '{token}'"))?;
+                    style.color(color)
+                }
+            };
+        }
+        Ok(Self(style))
+    }
 }
 
-/// Style for modified status.
-pub fn status_modified() -> Style {
-    Style::new().yellow()
+/// User style overrides layered over the built-in styles.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    /// Overridden styles keyed by role.
+    overrides: HashMap<Role, Style>,
 }
 
-/// Style for missing/orphan status.
-pub fn status_error() -> Style {
-    Style::new().red()
-}
+impl Theme {
+    /// Build a theme from configured role styles.
+    pub(crate) fn from_overrides(overrides: HashMap<Role, ThemeStyle>) -> Self {
+        Self {
+            overrides: overrides
+                .into_iter()
+                .map(|(role, style)| (role, style.0))
+                .collect(),
+        }
+    }
 
-/// Style for warning headings.
-pub fn warning_heading() -> Style {
-    Style::new().yellow().bold()
+    /// Return the effective style for a role.
+    fn style(&self, role: Role) -> Style {
+        self.overrides
+            .get(&role)
+            .copied()
+            .unwrap_or_else(|| role.default_style())
+    }
 }
 
-/// Style for warning text.
-pub fn warning() -> Style {
-    Style::new().yellow()
+/// Applies a theme to output text when color is enabled.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    /// Theme used to style roles.
+    theme: Theme,
+    /// Whether styling is applied at all.
+    enabled: bool,
 }
 
-/// Format a skill name with styling.
-pub fn fmt_skill_name(name: &str, use_color: bool) -> String {
-    if use_color {
-        name.style(skill_name()).to_string()
-    } else {
-        name.to_string()
+impl Palette {
+    /// Create a palette for a theme.
+    pub(crate) fn new(theme: &Theme, enabled: bool) -> Self {
+        Self {
+            theme: theme.clone(),
+            enabled,
+        }
     }
-}
 
-/// Format a section heading with styling.
-pub fn fmt_heading(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(heading()).to_string()
-    } else {
-        text.to_string()
+    /// Create a palette that never applies styling.
+    #[cfg(test)]
+    pub(crate) fn plain() -> Self {
+        Self::new(&Theme::default(), false)
     }
-}
 
-/// Format a label with styling.
-pub fn fmt_label(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(label()).to_string()
-    } else {
-        text.to_string()
+    /// Return whether styling is applied.
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
     }
-}
 
-/// Format description text with styling.
-pub fn fmt_description(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(description()).to_string()
-    } else {
-        text.to_string()
+    /// Style text for a role.
+    pub(crate) fn paint(&self, role: Role, text: &str) -> String {
+        if self.enabled {
+            text.style(self.theme.style(role)).to_string()
+        } else {
+            text.to_string()
+        }
     }
 }
 
-/// Format a path with styling.
-pub fn fmt_path(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(path()).to_string()
-    } else {
-        text.to_string()
-    }
-}
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use owo_colors::{OwoColorize, Style};
+
+    use super::{Palette, Role, Theme, ThemeStyle};
 
-/// Format a tool tag with styling.
-pub fn fmt_tool_tag(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(tool_tag()).to_string()
-    } else {
-        text.to_string()
+    #[test]
+    fn plain_palette_does_not_style() {
+        assert_eq!(Palette::plain().paint(Role::SkillName, "name"), "name");
     }
-}
 
-/// Format a warning heading with styling.
-pub fn fmt_warning_heading(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(warning_heading()).to_string()
-    } else {
-        text.to_string()
+    #[test]
+    fn theme_overrides_default_style() {
+        let style = ThemeStyle::try_from("bold bright-red".to_string()).expect("style");
+        let theme = Theme::from_overrides(HashMap::from([(Role::SkillName, style)]));
+        let palette = Palette::new(&theme, true);
+
+        let expected = "name".style(Style::new().bright_red().bold()).to_string();
+        assert_eq!(palette.paint(Role::SkillName, "name"), expected);
     }
-}
 
-/// Format warning text with styling.
-pub fn fmt_warning(text: &str, use_color: bool) -> String {
-    if use_color {
-        text.style(warning()).to_string()
-    } else {
-        text.to_string()
+    #[test]
+    fn rejects_unknown_colors() {
+        let error = ThemeStyle::try_from("bold chartreuse".to_string()).expect_err("invalid");
+        assert_eq!(error, "unknown color or effect 'chartreuse'");
     }
 }
@@ -1,6 +1,9 @@
 //! Path expansion and normalization utilities.
 
-use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+use std::{
+    fs, io,
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+};
 
 use path_clean::PathClean;
 
@@ -12,8 +15,32 @@ pub fn default_config_path() -> Result<PathBuf> {
     Ok(home.join(".skills.toml"))
 }
 
-/// Expand a config-provided path and resolve it relative to a base directory.
-pub fn expand_source_path(raw: &str, base_dir: &Path) -> Result<PathBuf> {
+/// Return the default install state path for the current platform.
+pub fn default_state_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_local_dir().ok_or(Error::HomeDirMissing)?;
+    Ok(data_dir.join("skills").join("state.json"))
+}
+
+/// Return the default directory for caches that can be rebuilt at any time.
+pub fn default_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir().ok_or(Error::HomeDirMissing)?;
+    Ok(cache_dir.join("skills"))
+}
+
+/// Return the default directory holding imported skills awaiting review.
+pub fn default_quarantine_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or(Error::HomeDirMissing)?;
+    Ok(home.join(".skills").join("quarantine"))
+}
+
+/// Return the default directory holding sources cloned by `skills clone`.
+pub fn default_clone_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or(Error::HomeDirMissing)?;
+    Ok(home.join(".skills").join("sources"))
+}
+
+/// Expand a user-provided path and resolve it relative to a base directory.
+pub fn expand_path(raw: &str, base_dir: &Path) -> Result<PathBuf> {
     let expanded = shellexpand::full(raw).map_err(|error| Error::PathExpansion {
         path: raw.to_string(),
         source: error,
@@ -35,6 +62,45 @@ pub fn normalize_path(path: &Path) -> PathBuf {
     }
 }
 
+/// Find the project root containing a directory.
+///
+/// Walks up from `start` to the nearest ancestor (including `start` itself) containing any of the
+/// marker names, falling back to `start` when none is found.
+pub fn find_project_root(start: &Path, markers: &[String]) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| markers.iter().any(|marker| dir.join(marker).exists()))
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Return whether renaming `from` to `to` only changes the case of the final component.
+///
+/// On a case-insensitive filesystem `to` then already names `from`, so it must not be treated as
+/// an existing destination.
+pub fn is_case_only_rename(from: &Path, to: &Path) -> bool {
+    let (Some(from_name), Some(to_name)) = (from.file_name(), to.file_name()) else {
+        return false;
+    };
+    from.parent() == to.parent()
+        && from_name != to_name
+        && from_name.to_string_lossy().to_lowercase() == to_name.to_string_lossy().to_lowercase()
+}
+
+/// Rename a file or directory, going through a temporary sibling when only the case of its name
+/// changes, which a case-insensitive filesystem would otherwise refuse or ignore.
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    if !is_case_only_rename(from, to) {
+        return fs::rename(from, to);
+    }
+    let name = to.file_name().unwrap_or_default().to_string_lossy();
+    let temporary = to.with_file_name(format!(".{name}.rename"));
+    fs::rename(from, &temporary)?;
+    fs::rename(&temporary, to).inspect_err(|_| {
+        fs::rename(&temporary, from).ok();
+    })
+}
+
 /// Render a path for display, using a tilde prefix for the home directory.
 pub fn display_path(path: &Path) -> String {
     if let Some(home) = dirs::home_dir()
@@ -47,3 +113,70 @@ pub fn display_path(path: &Path) -> String {
     }
     path.display().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{find_project_root, is_case_only_rename, rename};
+
+    #[test]
+    fn finds_nearest_marked_ancestor() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path().join("repo");
+        let nested = root.join("src").join("deep");
+        fs::create_dir_all(&nested).expect("mkdir");
+        fs::create_dir_all(root.join(".git")).expect("mkdir");
+        let markers = vec![".git".to_string()];
+
+        assert_eq!(find_project_root(&nested, &markers), root);
+        assert_eq!(find_project_root(&root, &markers), root);
+
+        fs::write(nested.join(".skills-root"), "").expect("write");
+        let markers = vec![".skills-root".to_string(), ".git".to_string()];
+        assert_eq!(find_project_root(&nested, &markers), nested);
+    }
+
+    #[test]
+    fn falls_back_to_start_without_markers() {
+        let dir = tempdir().expect("tempdir");
+        let markers = vec!["no-such-marker".to_string()];
+        assert_eq!(find_project_root(dir.path(), &markers), dir.path());
+    }
+
+    #[test]
+    fn renames_through_a_temporary_name_when_only_case_changes() {
+        let dir = tempdir().expect("tempdir");
+        let from = dir.path().join("Foo");
+        let to = dir.path().join("foo");
+        assert!(is_case_only_rename(&from, &to));
+        assert!(!is_case_only_rename(&from, &from));
+        assert!(!is_case_only_rename(&from, &dir.path().join("bar")));
+        assert!(!is_case_only_rename(
+            &from,
+            &dir.path().join("sub").join("foo")
+        ));
+
+        fs::create_dir_all(&from).expect("mkdir");
+        fs::write(from.join("SKILL.md"), "skill").expect("write");
+        rename(&from, &to).expect("rename");
+
+        let names: Vec<String> = fs::read_dir(dir.path())
+            .expect("read dir")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(names, ["foo"]);
+        assert_eq!(
+            fs::read_to_string(to.join("SKILL.md")).expect("read"),
+            "skill"
+        );
+    }
+}
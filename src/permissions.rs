@@ -0,0 +1,182 @@
+//! Executable-bit handling for files copied into and out of skill directories, and checks that
+//! the CLI can write the directories it is about to change.
+//!
+//! Archives record Unix permissions for each file; skills carry only the distinction between
+//! plain files and executable helper scripts. Platforms without Unix permissions treat every
+//! file as plain.
+
+use std::{
+    fs::Metadata,
+    io,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+use crate::paths::display_path;
+
+/// Unix mode for plain files.
+pub const FILE_MODE: u32 = 0o644;
+
+/// Unix mode for executable files.
+pub const EXECUTABLE_MODE: u32 = 0o755;
+
+/// Return whether a Unix mode has any execute bit set.
+pub fn mode_is_executable(mode: u32) -> bool {
+    mode & 0o111 != 0
+}
+
+/// Return whether a file has any execute bit set.
+#[cfg(unix)]
+pub fn is_executable(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    mode_is_executable(metadata.permissions().mode())
+}
+
+/// Return whether a file has any execute bit set.
+#[cfg(not(unix))]
+pub fn is_executable(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// Mark a file as executable.
+#[cfg(unix)]
+pub fn set_executable(path: &Path) -> io::Result<()> {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    fs::set_permissions(path, fs::Permissions::from_mode(EXECUTABLE_MODE))
+}
+
+/// Mark a file as executable.
+#[cfg(not(unix))]
+pub fn set_executable(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Something that would stop the CLI from writing a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessProblem {
+    /// The path belongs to another user, as happens after running an agent under sudo or in a
+    /// container.
+    Owner {
+        /// Path with the wrong owner.
+        path: PathBuf,
+        /// Numeric id of the user that owns it.
+        owner: u32,
+    },
+    /// The path's owner has no write permission.
+    ReadOnly {
+        /// Read-only path.
+        path: PathBuf,
+    },
+}
+
+impl AccessProblem {
+    /// Describe the problem.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Owner { path, owner } => {
+                format!(
+                    "{} is owned by another user (uid {owner})",
+                    display_path(path)
+                )
+            }
+            Self::ReadOnly { path } => format!("{} is read-only", display_path(path)),
+        }
+    }
+
+    /// Return a shell command that fixes the problem for everything under `root`.
+    pub fn fix(&self, root: &Path) -> String {
+        match self {
+            Self::Owner { .. } => format!("sudo chown -R \"$USER\" {}", display_path(root)),
+            Self::ReadOnly { .. } => format!("chmod -R u+w {}", display_path(root)),
+        }
+    }
+}
+
+/// Find the first path the current user could not write: `path` itself, and with `recursive`
+/// everything under it.
+///
+/// Symlinks are skipped, since replacing one only needs access to its directory. Nothing is
+/// reported when running as root, which can write anything.
+#[cfg(unix)]
+pub fn find_access_problem(path: &Path, recursive: bool) -> Option<AccessProblem> {
+    use std::os::unix::fs::MetadataExt;
+
+    use rustix::process::getuid;
+
+    let user = getuid().as_raw();
+    if user == 0 {
+        return None;
+    }
+    walk(path, recursive)
+        .find_map(|(path, metadata)| access_problem(path, metadata.uid(), metadata.mode(), user))
+}
+
+/// Find the first path the current user could not write: `path` itself, and with `recursive`
+/// everything under it.
+///
+/// Symlinks are skipped, since replacing one only needs access to its directory.
+#[cfg(not(unix))]
+pub fn find_access_problem(path: &Path, recursive: bool) -> Option<AccessProblem> {
+    walk(path, recursive).find_map(|(path, metadata)| {
+        metadata
+            .permissions()
+            .readonly()
+            .then_some(AccessProblem::ReadOnly { path })
+    })
+}
+
+/// Walk a path, and with `recursive` everything under it, yielding everything but symlinks.
+fn walk(path: &Path, recursive: bool) -> impl Iterator<Item = (PathBuf, Metadata)> {
+    let depth = if recursive { usize::MAX } else { 0 };
+    WalkDir::new(path)
+        .max_depth(depth)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.path_is_symlink())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((entry.into_path(), metadata))
+        })
+}
+
+/// Classify a path `user` wants to write from its owner and Unix mode.
+#[cfg(unix)]
+fn access_problem(path: PathBuf, owner: u32, mode: u32, user: u32) -> Option<AccessProblem> {
+    if owner != user {
+        Some(AccessProblem::Owner { path, owner })
+    } else if mode & 0o200 == 0 {
+        Some(AccessProblem::ReadOnly { path })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    #[test]
+    fn classifies_access_problems() {
+        use std::path::PathBuf;
+
+        use super::{AccessProblem, access_problem};
+
+        let path = PathBuf::from("/skills/pdf/SKILL.md");
+        assert_eq!(access_problem(path.clone(), 501, 0o644, 501), None);
+        assert_eq!(
+            access_problem(path.clone(), 0, 0o644, 501),
+            Some(AccessProblem::Owner {
+                path: path.clone(),
+                owner: 0
+            })
+        );
+        let problem = access_problem(path.clone(), 501, 0o444, 501).expect("read-only");
+        assert_eq!(problem, AccessProblem::ReadOnly { path });
+        assert_eq!(
+            problem.fix(&PathBuf::from("/skills")),
+            "chmod -R u+w /skills"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Team policy restricting what may be imported and pushed.
+//!
+//! An organization distributes a policy file and points `[policy] source` at it, by path or
+//! HTTPS URL. The policy can limit the hosts imports download from, require import archives to
+//! carry an Ed25519 signature from a listed key, ban frontmatter fields, and cap skill sizes.
+//! `skills import` and `skills push` refuse anything that breaks it, and `skills doctor` reports
+//! it.
+
+use std::{fs, io::ErrorKind};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, PolicySource},
+    context::Context,
+    diagnostics::Diagnostics,
+    error::{Error, Result},
+    frontmatter::frontmatter_json,
+    http::{authorized_get, build_client, cancellable, fetch_text},
+    state::hash_bytes,
+    units::format_size,
+};
+
+/// Maximum size of a downloaded policy file in bytes.
+const MAX_POLICY_SIZE: u64 = 1024 * 1024;
+
+/// Length in bytes of an Ed25519 public key.
+const KEY_LEN: usize = 32;
+
+/// Raw policy file structure.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawPolicy {
+    /// Hosts imports may download from.
+    allowed_hosts: Vec<String>,
+    /// Base64 Ed25519 public keys, one of which must have signed each import archive.
+    signing_keys: Vec<String>,
+    /// Frontmatter fields no skill may set.
+    banned_fields: Vec<String>,
+    /// Largest total size in bytes a skill directory may have.
+    max_skill_size: Option<u64>,
+}
+
+/// A team policy in force.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// Path or URL the policy was read from.
+    pub(crate) origin: String,
+    /// Hosts imports may download from, with `*.` prefixes matching subdomains; empty allows any.
+    pub(crate) allowed_hosts: Vec<String>,
+    /// Base64 Ed25519 public keys; when any are listed every import archive must be signed.
+    pub(crate) signing_keys: Vec<String>,
+    /// Frontmatter fields no skill may set.
+    pub(crate) banned_fields: Vec<String>,
+    /// Largest total size in bytes a skill directory may have.
+    pub(crate) max_skill_size: Option<u64>,
+}
+
+impl Policy {
+    /// Load the policy named by the config, or `None` when no policy is configured.
+    ///
+    /// A URL policy is fetched on every load. When the fetch fails the copy cached by the last
+    /// successful fetch is used instead, with a warning, so an outage does not lift the policy.
+    pub(crate) async fn load(
+        ctx: &Context,
+        config: &Config,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Option<Self>> {
+        let Some(source) = &config.policy.source else {
+            return Ok(None);
+        };
+        let origin = source.to_string();
+        let contents = match source {
+            PolicySource::Path(path) => {
+                fs::read_to_string(path).map_err(|error| Error::PolicyInvalid {
+                    origin: origin.clone(),
+                    message: error.to_string(),
+                })?
+            }
+            PolicySource::Url(url) => {
+                let cache = ctx
+                    .cache_dir
+                    .join(format!("policy-{}.toml", &hash_bytes(url.as_bytes())[..16]));
+                let client = build_client(&config.network, &config.import)?;
+                let request = authorized_get(&client, &config.import, url)?;
+                match cancellable(fetch_text(request, url, MAX_POLICY_SIZE)).await {
+                    Ok(contents) => {
+                        Self::parse(&origin, &contents)?;
+                        fs::create_dir_all(&ctx.cache_dir).ok();
+                        fs::write(&cache, &contents).ok();
+                        contents
+                    }
+                    Err(error @ Error::Interrupted) => return Err(error),
+                    Err(error) => match fs::read_to_string(&cache) {
+                        Ok(contents) => {
+                            diagnostics.warn(format!(
+                                "Could not fetch the policy, using the cached copy: {error}"
+                            ));
+                            contents
+                        }
+                        Err(cache_error) if cache_error.kind() == ErrorKind::NotFound => {
+                            return Err(error);
+                        }
+                        Err(cache_error) => {
+                            return Err(Error::PolicyInvalid {
+                                origin,
+                                message: cache_error.to_string(),
+                            });
+                        }
+                    },
+                }
+            }
+        };
+        Self::parse(&origin, &contents).map(Some)
+    }
+
+    /// Parse a policy file, checking that every signing key is a usable Ed25519 key.
+    pub(crate) fn parse(origin: &str, contents: &str) -> Result<Self> {
+        let invalid = |message: String| Error::PolicyInvalid {
+            origin: origin.to_string(),
+            message,
+        };
+        let raw: RawPolicy =
+            toml::from_str(contents).map_err(|error| invalid(error.to_string()))?;
+        for key in &raw.signing_keys {
+            if decode_base64(key).map(|bytes| bytes.len()) != Some(KEY_LEN) {
+                return Err(invalid(format!(
+                    "signing key '{key}' is not a base64 Ed25519 public key"
+                )));
+            }
+        }
+        Ok(Self {
+            origin: origin.to_string(),
+            allowed_hosts: raw
+                .allowed_hosts
+                .iter()
+                .map(|host| host.to_ascii_lowercase())
+                .collect(),
+            signing_keys: raw.signing_keys,
+            banned_fields: raw.banned_fields,
+            max_skill_size: raw.max_skill_size,
+        })
+    }
+
+    /// Refuse an import source whose host is not allowed.
+    pub(crate) fn check_host(&self, source: &str, host: &str) -> Result<()> {
+        let host = host.to_ascii_lowercase();
+        let allowed = self.allowed_hosts.is_empty()
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|allowed| match allowed.strip_prefix("*.") {
+                    Some(domain) => host
+                        .strip_suffix(domain)
+                        .is_some_and(|prefix| prefix.ends_with('.')),
+                    None => *allowed == host,
+                });
+        if allowed {
+            return Ok(());
+        }
+        Err(Error::PolicyViolation {
+            name: source.to_string(),
+            message: format!("imports from {host} are not allowed"),
+        })
+    }
+
+    /// Return whether import archives must be signed.
+    pub(crate) fn requires_signature(&self) -> bool {
+        !self.signing_keys.is_empty()
+    }
+
+    /// Refuse an import archive that is not signed by one of the policy's keys.
+    ///
+    /// The signature is the base64 Ed25519 signature of the archive bytes, or `None` when the
+    /// source has none.
+    pub(crate) fn check_signature(
+        &self,
+        source: &str,
+        data: &[u8],
+        signature: Option<&str>,
+    ) -> Result<()> {
+        if !self.requires_signature() {
+            return Ok(());
+        }
+        let violation = |message: &str| Error::PolicyViolation {
+            name: source.to_string(),
+            message: message.to_string(),
+        };
+        let signature = signature.ok_or_else(|| violation("archive has no signature"))?;
+        let signature = decode_base64(signature)
+            .ok_or_else(|| violation("archive signature is not valid base64"))?;
+        let signed = self.signing_keys.iter().any(|key| {
+            decode_base64(key).is_some_and(|key| {
+                UnparsedPublicKey::new(&ED25519, key)
+                    .verify(data, &signature)
+                    .is_ok()
+            })
+        });
+        if signed {
+            Ok(())
+        } else {
+            Err(violation("archive is not signed by a policy key"))
+        }
+    }
+
+    /// Refuse a skill that sets a banned frontmatter field or exceeds the size limit.
+    ///
+    /// `contents` is the skill file as it will be installed, and `size` the total size of the
+    /// skill directory in bytes.
+    pub(crate) fn check_skill(&self, name: &str, contents: &str, size: u64) -> Result<()> {
+        let mut problems = Vec::new();
+        if let Some((_, fields)) = frontmatter_json(contents) {
+            let mut banned: Vec<&str> = self
+                .banned_fields
+                .iter()
+                .filter(|field| fields.contains_key(field.as_str()))
+                .map(String::as_str)
+                .collect();
+            banned.sort_unstable();
+            problems.extend(
+                banned
+                    .iter()
+                    .map(|field| format!("frontmatter field '{field}' is banned")),
+            );
+        }
+        if let Some(max) = self.max_skill_size.filter(|max| size > *max) {
+            problems.push(format!(
+                "skill is {}, over the {} limit",
+                format_size(size),
+                format_size(max)
+            ));
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(Error::PolicyViolation {
+            name: name.to_string(),
+            message: problems.join("; "),
+        })
+    }
+}
+
+/// Decode base64 text, ignoring whitespace so wrapped output from `base64` is accepted.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD.decode(compact).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use ring::{
+        rand::SystemRandom,
+        signature::{Ed25519KeyPair, KeyPair},
+    };
+
+    use super::Policy;
+    use crate::error::Error;
+
+    #[test]
+    fn checks_hosts() {
+        let policy = Policy::parse(
+            "policy.toml",
+            "allowed_hosts = [\"github.com\", \"*.Example.com\"]",
+        )
+        .expect("parse");
+        assert!(policy.check_host("src", "github.com").is_ok());
+        assert!(policy.check_host("src", "skills.example.com").is_ok());
+        assert!(policy.check_host("src", "example.com").is_err());
+        assert!(matches!(
+            policy.check_host("src", "evil-example.com"),
+            Err(Error::PolicyViolation { .. })
+        ));
+
+        let open = Policy::parse("policy.toml", "").expect("parse");
+        assert!(open.check_host("src", "anywhere.net").is_ok());
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let rng = SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).expect("generate");
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("key pair");
+        let key = STANDARD.encode(pair.public_key().as_ref());
+        let policy =
+            Policy::parse("policy.toml", &format!("signing_keys = [\"{key}\"]")).expect("parse");
+
+        let data = b"archive bytes";
+        let signature = STANDARD.encode(pair.sign(data).as_ref());
+        assert!(
+            policy
+                .check_signature("a.zip", data, Some(&signature))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .check_signature("a.zip", b"tampered", Some(&signature))
+                .is_err()
+        );
+        assert!(policy.check_signature("a.zip", data, None).is_err());
+
+        let error = Policy::parse("policy.toml", "signing_keys = [\"c2hvcnQ=\"]");
+        assert!(matches!(error, Err(Error::PolicyInvalid { .. })));
+    }
+
+    #[test]
+    fn checks_fields_and_size() {
+        let policy = Policy::parse(
+            "policy.toml",
+            "banned_fields = [\"allowed-tools\"]\nmax_skill_size = 100",
+        )
+        .expect("parse");
+        let allowed = "---\nname: a\ndescription: d\n---\nBody\n";
+        assert!(policy.check_skill("a", allowed, 50).is_ok());
+
+        let banned = "---\nname: a\ndescription: d\nallowed-tools: Bash\n---\nBody\n";
+        let Err(Error::PolicyViolation { message, .. }) = policy.check_skill("a", banned, 500)
+        else {
+            panic!("expected a violation");
+        };
+        assert_eq!(
+            message,
+            "frontmatter field 'allowed-tools' is banned; skill is 500 B, over the 100 B limit"
+        );
+    }
+}
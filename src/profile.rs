@@ -0,0 +1,255 @@
+//! Opt-in timing of the phases of a command.
+//!
+//! `--profile-perf` turns recording on for one command. Catalog loading, template rendering, skill
+//! file writes, and network requests each open a [`Span`] around their work; the spans add their
+//! elapsed time to a process-wide [`Recording`] that is reported once the command finishes. When
+//! profiling is off a span costs a single atomic load.
+
+use std::{
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Whether a recording is in progress, checked before touching [`RECORDING`].
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The recording of the command being profiled.
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// A kind of work timed separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Reading sources, tool directories, and local skills into a catalog.
+    Catalog,
+    /// Rendering skill templates.
+    Render,
+    /// Writing and copying skill files.
+    Io,
+    /// HTTP and registry requests.
+    Network,
+}
+
+impl Phase {
+    /// Every phase, in report order.
+    const ALL: [Self; 4] = [Self::Catalog, Self::Render, Self::Io, Self::Network];
+
+    /// Name used in reports.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Catalog => "catalog",
+            Self::Render => "render",
+            Self::Io => "io",
+            Self::Network => "network",
+        }
+    }
+
+    /// Position of the phase's totals in a recording.
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// How a timing report is written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProfileFormat {
+    /// An aligned table.
+    Text,
+    /// One JSON object.
+    Json,
+}
+
+/// Time spent in one phase.
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    /// Summed duration of every span.
+    elapsed: Duration,
+    /// Number of spans.
+    calls: u64,
+}
+
+/// Phase timings collected while a command runs.
+#[derive(Debug)]
+pub struct Recording {
+    /// When the command started.
+    started: Instant,
+    /// Totals per phase, indexed by [`Phase::index`].
+    phases: [Totals; 4],
+}
+
+impl Recording {
+    /// Start an empty recording.
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: [Totals::default(); 4],
+        }
+    }
+
+    /// Add one span's duration to a phase.
+    fn add(&mut self, phase: Phase, elapsed: Duration) {
+        let totals = &mut self.phases[phase.index()];
+        totals.elapsed += elapsed;
+        totals.calls += 1;
+    }
+
+    /// Summarize the recording for a command.
+    fn report(&self, command: &str) -> Report {
+        Report {
+            command: command.to_string(),
+            total_ms: millis(self.started.elapsed()),
+            phases: Phase::ALL
+                .into_iter()
+                .map(|phase| {
+                    let totals = self.phases[phase.index()];
+                    PhaseReport {
+                        phase: phase.label(),
+                        ms: millis(totals.elapsed),
+                        calls: totals.calls,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Timing of a whole command, as printed at its end.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Subcommand that ran.
+    command: String,
+    /// Wall-clock time of the command in milliseconds.
+    total_ms: f64,
+    /// Time spent in each phase.
+    phases: Vec<PhaseReport>,
+}
+
+/// Time spent in one phase of a command.
+#[derive(Debug, Serialize)]
+struct PhaseReport {
+    /// Phase name.
+    phase: &'static str,
+    /// Summed time in milliseconds.
+    ms: f64,
+    /// Number of times the phase was entered.
+    calls: u64,
+}
+
+impl Report {
+    /// Render the report in a format.
+    fn format(&self, format: ProfileFormat) -> String {
+        match format {
+            ProfileFormat::Json => serde_json::to_string(self).unwrap_or_default(),
+            ProfileFormat::Text => {
+                let mut lines = vec![format!("Timing for {}:", self.command)];
+                for phase in &self.phases {
+                    lines.push(format!(
+                        "  {:<8} {:>10.2} ms  {:>6} {}",
+                        phase.phase,
+                        phase.ms,
+                        phase.calls,
+                        if phase.calls == 1 { "call" } else { "calls" }
+                    ));
+                }
+                lines.push(format!("  {:<8} {:>10.2} ms", "total", self.total_ms));
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+/// Convert a duration to fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Start recording, discarding any earlier recording.
+pub fn start() {
+    *RECORDING.lock().unwrap_or_else(PoisonError::into_inner) = Some(Recording::new());
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop recording and print the report for a command to stderr.
+pub fn finish(command: &str, format: ProfileFormat) {
+    ENABLED.store(false, Ordering::Relaxed);
+    let recording = RECORDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(recording) = recording {
+        eprintln!("{}", recording.report(command).format(format));
+    }
+}
+
+/// Time a phase until the returned span is dropped.
+pub fn span(phase: Phase) -> Span {
+    Span {
+        phase,
+        started: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+    }
+}
+
+/// A timed stretch of work, recorded when dropped.
+#[derive(Debug)]
+#[must_use = "a span records its phase when dropped"]
+pub struct Span {
+    /// Phase the work belongs to.
+    phase: Phase,
+    /// When the work started, or `None` when profiling is off.
+    started: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(started) = self.started else {
+            return;
+        };
+        if let Some(recording) = RECORDING
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            recording.add(self.phase, started.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Phase, ProfileFormat, Recording};
+
+    #[test]
+    fn reports_phase_totals() {
+        let mut recording = Recording::new();
+        recording.add(Phase::Render, Duration::from_millis(3));
+        recording.add(Phase::Render, Duration::from_millis(2));
+        recording.add(Phase::Network, Duration::from_micros(1500));
+        let report = recording.report("push");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.format(ProfileFormat::Json)).expect("json");
+        assert_eq!(json["command"], "push");
+        let phases = json["phases"].as_array().expect("phases");
+        let labels: Vec<&str> = phases
+            .iter()
+            .map(|phase| phase["phase"].as_str().expect("label"))
+            .collect();
+        assert_eq!(labels, ["catalog", "render", "io", "network"]);
+        assert_eq!(phases[1]["calls"], 2);
+        assert_eq!(phases[1]["ms"], 5.0);
+        assert_eq!(phases[3]["ms"], 1.5);
+        assert_eq!(phases[0]["calls"], 0);
+
+        let text = report.format(ProfileFormat::Text);
+        assert!(text.starts_with("Timing for push:"));
+        assert!(text.contains("render         5.00 ms       2 calls"));
+        assert!(text.contains("network        1.50 ms       1 call\n"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Project context exposed to templates rendered for project-local skills.
+
+use std::{fs, io::ErrorKind, path::Path};
+
+use serde::Serialize;
+use toml::{Table, de::Error as TomlError};
+
+use crate::error::{Error, Result};
+
+/// File in the project root whose values are exposed to templates as `project.vars`.
+pub const VARS_FILE: &str = ".skills-vars.toml";
+
+/// Package manifests recognized in the project root, with the language each indicates.
+const MANIFESTS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("CMakeLists.txt", "c++"),
+    ("Gemfile", "ruby"),
+    ("Package.swift", "swift"),
+    ("build.gradle", "java"),
+    ("build.gradle.kts", "kotlin"),
+    ("composer.json", "php"),
+    ("go.mod", "go"),
+    ("mix.exs", "elixir"),
+    ("package.json", "javascript"),
+    ("pom.xml", "java"),
+    ("pyproject.toml", "python"),
+    ("requirements.txt", "python"),
+    ("setup.py", "python"),
+    ("tsconfig.json", "typescript"),
+];
+
+/// What templates see as `project` when rendering for a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectContext {
+    /// Name of the project root directory.
+    pub name: String,
+    /// Languages indicated by the manifests present, sorted and without repeats.
+    pub languages: Vec<&'static str>,
+    /// Package manifests present in the project root, sorted.
+    pub manifests: Vec<&'static str>,
+    /// Values from the project's [`VARS_FILE`], empty when it has none.
+    pub vars: Table,
+}
+
+impl ProjectContext {
+    /// Detect the context of the project rooted at a directory.
+    pub fn detect(root: &Path) -> Result<Self> {
+        let mut manifests: Vec<&'static str> = MANIFESTS
+            .iter()
+            .map(|(manifest, _)| *manifest)
+            .filter(|manifest| root.join(manifest).is_file())
+            .collect();
+        manifests.sort_unstable();
+        let mut languages: Vec<&'static str> = MANIFESTS
+            .iter()
+            .filter(|(manifest, _)| manifests.contains(manifest))
+            .map(|(_, language)| *language)
+            .collect();
+        languages.sort_unstable();
+        languages.dedup();
+
+        Ok(Self {
+            name: root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            languages,
+            manifests,
+            vars: load_vars(&root.join(VARS_FILE))?,
+        })
+    }
+}
+
+/// Read a project variables file, returning an empty table when it does not exist.
+fn load_vars(path: &Path) -> Result<Table> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Table::new()),
+        Err(error) => {
+            return Err(Error::ProjectVars {
+                path: path.to_path_buf(),
+                message: error.to_string(),
+            });
+        }
+    };
+    contents
+        .parse()
+        .map_err(|error: TomlError| Error::ProjectVars {
+            path: path.to_path_buf(),
+            message: error.message().to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{ProjectContext, VARS_FILE};
+
+    #[test]
+    fn detects_manifests_languages_and_vars() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path().join("webapp");
+        fs::create_dir(&root).expect("mkdir");
+        for manifest in ["package.json", "tsconfig.json", "Cargo.toml"] {
+            fs::write(root.join(manifest), "").expect("write");
+        }
+        fs::write(
+            root.join(VARS_FILE),
+            "team = \"web\"\n[deploy]\ntarget = \"fly\"\n",
+        )
+        .expect("write");
+
+        let project = ProjectContext::detect(&root).expect("detect");
+        assert_eq!(project.name, "webapp");
+        assert_eq!(
+            project.manifests,
+            ["Cargo.toml", "package.json", "tsconfig.json"]
+        );
+        assert_eq!(project.languages, ["javascript", "rust", "typescript"]);
+        assert_eq!(project.vars["team"].as_str(), Some("web"));
+        assert_eq!(project.vars["deploy"]["target"].as_str(), Some("fly"));
+
+        fs::write(root.join(VARS_FILE), "team = ").expect("write");
+        assert!(ProjectContext::detect(&root).is_err());
+    }
+}
@@ -0,0 +1,397 @@
+//! Built-in spellchecking and prose lints for skill bodies.
+
+use std::{collections::HashSet, fmt, fs, io::ErrorKind, path::Path};
+
+use crate::{
+    error::{Error, Result},
+    frontmatter::strip_frontmatter,
+};
+
+/// Name of the optional custom dictionary file at the root of a source directory.
+pub const DICTIONARY_FILE: &str = ".skills-dictionary";
+
+/// Sentences with more words than this are reported as too long.
+const MAX_SENTENCE_WORDS: usize = 40;
+
+/// Common misspellings and their corrections, sorted by misspelling.
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accomodate", "accommodate"),
+    ("accross", "across"),
+    ("acheive", "achieve"),
+    ("adn", "and"),
+    ("adress", "address"),
+    ("agian", "again"),
+    ("alot", "a lot"),
+    ("aquire", "acquire"),
+    ("arguement", "argument"),
+    ("arguements", "arguments"),
+    ("assoicated", "associated"),
+    ("asynchonous", "asynchronous"),
+    ("availble", "available"),
+    ("basicly", "basically"),
+    ("becasue", "because"),
+    ("becuase", "because"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("calender", "calendar"),
+    ("catagory", "category"),
+    ("collegue", "colleague"),
+    ("comming", "coming"),
+    ("commited", "committed"),
+    ("compatability", "compatibility"),
+    ("completly", "completely"),
+    ("concious", "conscious"),
+    ("conditon", "condition"),
+    ("configuraiton", "configuration"),
+    ("consistant", "consistent"),
+    ("definately", "definitely"),
+    ("defualt", "default"),
+    ("dependancy", "dependency"),
+    ("dependecy", "dependency"),
+    ("desciption", "description"),
+    ("diffrent", "different"),
+    ("directiory", "directory"),
+    ("dissapear", "disappear"),
+    ("embarass", "embarrass"),
+    ("enviroment", "environment"),
+    ("excecute", "execute"),
+    ("existance", "existence"),
+    ("explaination", "explanation"),
+    ("familar", "familiar"),
+    ("finaly", "finally"),
+    ("foward", "forward"),
+    ("funciton", "function"),
+    ("goverment", "government"),
+    ("grammer", "grammar"),
+    ("guarentee", "guarantee"),
+    ("happend", "happened"),
+    ("hte", "the"),
+    ("immediatly", "immediately"),
+    ("implmentation", "implementation"),
+    ("independant", "independent"),
+    ("initalize", "initialize"),
+    ("instanciate", "instantiate"),
+    ("interupt", "interrupt"),
+    ("knowlege", "knowledge"),
+    ("lenght", "length"),
+    ("libary", "library"),
+    ("maintainance", "maintenance"),
+    ("mesage", "message"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("noone", "no one"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("occuring", "occurring"),
+    ("optionaly", "optionally"),
+    ("overriden", "overridden"),
+    ("paramter", "parameter"),
+    ("perfomance", "performance"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("prefered", "preferred"),
+    ("priviledge", "privilege"),
+    ("proccess", "process"),
+    ("publically", "publicly"),
+    ("recieve", "receive"),
+    ("recomend", "recommend"),
+    ("refered", "referred"),
+    ("relevent", "relevant"),
+    ("repositry", "repository"),
+    ("responsability", "responsibility"),
+    ("retreive", "retrieve"),
+    ("retun", "return"),
+    ("seperate", "separate"),
+    ("seperately", "separately"),
+    ("similiar", "similar"),
+    ("specifc", "specific"),
+    ("succeded", "succeeded"),
+    ("succesful", "successful"),
+    ("sucess", "success"),
+    ("supress", "suppress"),
+    ("sytem", "system"),
+    ("teh", "the"),
+    ("temporarly", "temporarily"),
+    ("thier", "their"),
+    ("threshhold", "threshold"),
+    ("tommorow", "tomorrow"),
+    ("transfered", "transferred"),
+    ("truely", "truly"),
+    ("untill", "until"),
+    ("usefull", "useful"),
+    ("varaible", "variable"),
+    ("verison", "version"),
+    ("visable", "visible"),
+    ("wether", "whether"),
+    ("wich", "which"),
+    ("wierd", "weird"),
+    ("writting", "writing"),
+];
+
+/// A prose problem found on a line of a skill file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// One-based line number in the skill file.
+    pub line: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Words a source accepts even when the spellchecker would flag them.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    /// Accepted words, lowercased.
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Load the dictionary for a source root, treating a missing file as empty.
+    ///
+    /// The file lists one word per line; blank lines and lines starting with `#` are ignored.
+    pub fn load(source_root: &Path) -> Result<Self> {
+        let path = source_root.join(DICTIONARY_FILE);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(Error::SkillRead { path, source }),
+        }
+    }
+
+    /// Parse dictionary file contents.
+    fn parse(contents: &str) -> Self {
+        let words = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+        Self { words }
+    }
+
+    /// Return whether a lowercased word is accepted.
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+/// Check the prose of a skill file, returning each finding in line order.
+///
+/// Fenced code blocks, inline code, and template tags are skipped. Line numbers count from the
+/// start of the file, including the frontmatter.
+pub fn lint(contents: &str, dictionary: &Dictionary) -> Vec<Finding> {
+    let body = strip_frontmatter(contents);
+    let first_line = contents[..contents.len() - body.len()].lines().count() + 1;
+
+    let mut findings = Vec::new();
+    let mut in_fence = false;
+    let mut sentence_words = 0;
+    let mut sentence_line = first_line;
+    for (index, line) in body.lines().enumerate() {
+        let number = first_line + index;
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            sentence_words = 0;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if starts_block(trimmed) {
+            sentence_words = 0;
+        }
+
+        let text = strip_code(line);
+        let mut previous: Option<&str> = None;
+        let mut gap_is_space = false;
+        for token in Token::split(&text) {
+            let word = match token {
+                Token::Word(word) => word,
+                Token::Gap(gap) => {
+                    gap_is_space = gap.chars().all(char::is_whitespace);
+                    if ends_sentence(gap) {
+                        sentence_words = 0;
+                    }
+                    continue;
+                }
+            };
+            let lower = word.to_lowercase();
+            if let Some(correction) = misspelling(&lower)
+                && !dictionary.contains(&lower)
+            {
+                findings.push(Finding {
+                    line: number,
+                    message: format!(
+                        "possible misspelling '{word}' (did you mean '{correction}'?)"
+                    ),
+                });
+            }
+            if gap_is_space
+                && previous.is_some_and(|prev| prev.to_lowercase() == lower)
+                && word.chars().any(char::is_alphabetic)
+            {
+                findings.push(Finding {
+                    line: number,
+                    message: format!("repeated word '{word}'"),
+                });
+            }
+            if sentence_words == 0 {
+                sentence_line = number;
+            }
+            sentence_words += 1;
+            if sentence_words == MAX_SENTENCE_WORDS + 1 {
+                findings.push(Finding {
+                    line: sentence_line,
+                    message: format!("sentence is longer than {MAX_SENTENCE_WORDS} words"),
+                });
+            }
+            previous = Some(word);
+        }
+    }
+    findings
+}
+
+/// Return the correction for a lowercased word if it is a known misspelling.
+fn misspelling(word: &str) -> Option<&'static str> {
+    MISSPELLINGS
+        .binary_search_by(|(wrong, _)| (*wrong).cmp(word))
+        .ok()
+        .map(|index| MISSPELLINGS[index].1)
+}
+
+/// Return whether a line starts a new block, which also ends any running sentence.
+fn starts_block(line: &str) -> bool {
+    line.is_empty()
+        || line.starts_with('#')
+        || line.starts_with('|')
+        || line.starts_with('>')
+        || line.starts_with("- ")
+        || line.starts_with("* ")
+        || line.split_once(". ").is_some_and(|(prefix, _)| {
+            !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// Return whether the text between two words ends a sentence.
+fn ends_sentence(gap: &str) -> bool {
+    gap.contains(['.', '!', '?', ':'])
+}
+
+/// Replace inline code spans and template tags with spaces.
+fn strip_code(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    loop {
+        let span = [("`", "`"), ("{{", "}}"), ("{%", "%}"), ("{#", "#}")]
+            .into_iter()
+            .filter_map(|(open, close)| rest.find(open).map(|start| (start, open, close)))
+            .min_by_key(|(start, _, _)| *start);
+        let Some((start, open, close)) = span else {
+            out.push_str(rest);
+            return out;
+        };
+        out.push_str(&rest[..start]);
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        out.push(' ');
+        rest = &after[end + close.len()..];
+    }
+}
+
+/// A run of word or non-word characters in a line.
+enum Token<'a> {
+    /// Letters, digits, and inner apostrophes.
+    Word(&'a str),
+    /// Anything between words.
+    Gap(&'a str),
+}
+
+impl<'a> Token<'a> {
+    /// Split a line into alternating words and gaps.
+    fn split(text: &'a str) -> Vec<Self> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        let mut in_word = None;
+        for (index, c) in text.char_indices() {
+            let is_word = c.is_alphanumeric() || (c == '\'' && in_word == Some(true));
+            if in_word != Some(is_word) {
+                if let Some(was_word) = in_word {
+                    tokens.push(Self::new(&text[start..index], was_word));
+                }
+                start = index;
+                in_word = Some(is_word);
+            }
+        }
+        if let Some(was_word) = in_word {
+            tokens.push(Self::new(&text[start..], was_word));
+        }
+        tokens
+    }
+
+    /// Build a token, trimming a trailing apostrophe from words.
+    fn new(text: &'a str, word: bool) -> Self {
+        if word {
+            Self::Word(text.trim_end_matches('\''))
+        } else {
+            Self::Gap(text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dictionary, MISSPELLINGS, lint};
+
+    #[test]
+    fn misspellings_are_sorted() {
+        assert!(MISSPELLINGS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn reports_misspellings_and_repeated_words() {
+        let contents = "---\nname: pdf\ndescription: PDFs\n---\nRecieve the the file.\n\n```\nteh code\n```\nUse `teh` here.\n";
+        let findings: Vec<String> = lint(contents, &Dictionary::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                "line 5: possible misspelling 'Recieve' (did you mean 'receive'?)".to_string(),
+                "line 5: repeated word 'the'".to_string(),
+            ]
+        );
+
+        let dictionary = Dictionary::parse("# accepted\nrecieve\n");
+        assert_eq!(lint(contents, &dictionary).len(), 1);
+    }
+
+    #[test]
+    fn reports_long_sentences() {
+        let long = (0..45)
+            .map(|i| format!("w{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let contents = format!("Short one. {long}.\n\n- {long}\n");
+        let findings: Vec<String> = lint(&contents, &Dictionary::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                "line 1: sentence is longer than 40 words".to_string(),
+                "line 3: sentence is longer than 40 words".to_string(),
+            ]
+        );
+    }
+}
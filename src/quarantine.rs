@@ -0,0 +1,166 @@
+//! Staging area for imported skills awaiting review.
+//!
+//! `skills import --quarantine` extracts each skill into its own directory under the quarantine
+//! root instead of its targets, beside a record of where it came from and where it will go.
+//! `skills review` inspects quarantined skills and `skills approve` installs them.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// File in a quarantine entry holding its [`QuarantineRecord`].
+const RECORD_FILE: &str = "record.json";
+
+/// Directory in a quarantine entry holding the extracted skill.
+const SKILL_DIR: &str = "skill";
+
+/// Provenance and destination of a quarantined skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineRecord {
+    /// Import source as given on the command line, such as a URL or archive path.
+    pub(crate) source: String,
+    /// Skill directories the skill is installed to when approved.
+    pub(crate) targets: Vec<PathBuf>,
+    /// When the skill was quarantined.
+    pub(crate) quarantined_at: SystemTime,
+}
+
+/// A skill held in quarantine.
+#[derive(Debug, Clone)]
+pub struct QuarantinedSkill {
+    /// Skill name from frontmatter.
+    pub(crate) name: String,
+    /// Directory holding the extracted skill.
+    pub(crate) skill_dir: PathBuf,
+    /// Where the skill came from and where it will go.
+    pub(crate) record: QuarantineRecord,
+}
+
+/// The quarantine directory.
+#[derive(Debug, Clone)]
+pub struct Quarantine {
+    /// Root directory holding one entry per quarantined skill.
+    root: PathBuf,
+}
+
+impl Quarantine {
+    /// Open the quarantine rooted at a directory, which need not exist yet.
+    pub(crate) fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Return the directory a quarantined skill is extracted into.
+    pub(crate) fn skill_dir(&self, name: &str) -> PathBuf {
+        self.root.join(name).join(SKILL_DIR)
+    }
+
+    /// Record the provenance of a skill just extracted into [`Self::skill_dir`].
+    pub(crate) fn write_record(&self, name: &str, record: &QuarantineRecord) -> Result<()> {
+        let path = self.root.join(name).join(RECORD_FILE);
+        let contents =
+            serde_json::to_string_pretty(record).map_err(|error| Error::QuarantineRecord {
+                path: path.clone(),
+                message: error.to_string(),
+            })?;
+        fs::write(&path, contents).map_err(|source| Error::SkillWrite { path, source })
+    }
+
+    /// Return a quarantined skill by name.
+    pub(crate) fn get(&self, name: &str) -> Result<QuarantinedSkill> {
+        let path = self.root.join(name).join(RECORD_FILE);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Err(Error::QuarantinedSkillNotFound {
+                    name: name.to_string(),
+                });
+            }
+            Err(source) => return Err(Error::SkillRead { path, source }),
+        };
+        let record = serde_json::from_str(&contents).map_err(|error| Error::QuarantineRecord {
+            path,
+            message: error.to_string(),
+        })?;
+        Ok(QuarantinedSkill {
+            name: name.to_string(),
+            skill_dir: self.skill_dir(name),
+            record,
+        })
+    }
+
+    /// Return every quarantined skill, sorted by name.
+    pub(crate) fn list(&self) -> Result<Vec<QuarantinedSkill>> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(Error::SkillRead {
+                    path: self.root.clone(),
+                    source,
+                });
+            }
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(RECORD_FILE).is_file())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names.iter().map(|name| self.get(name)).collect()
+    }
+
+    /// Remove a skill's quarantine entry.
+    pub(crate) fn remove(&self, name: &str) -> Result<()> {
+        let dir = self.root.join(name);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(source) => Err(Error::SkillWrite { path: dir, source }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, time::SystemTime};
+
+    use tempfile::tempdir;
+
+    use super::{Quarantine, QuarantineRecord};
+    use crate::error::Error;
+
+    #[test]
+    fn records_lists_and_removes_skills() {
+        let dir = tempdir().expect("tempdir");
+        let quarantine = Quarantine::new(&dir.path().join("quarantine"));
+        assert!(quarantine.list().expect("list").is_empty());
+
+        let record = QuarantineRecord {
+            source: "https://example.com/pdf.zip".to_string(),
+            targets: vec![PathBuf::from("/tools/claude/pdf")],
+            quarantined_at: SystemTime::UNIX_EPOCH,
+        };
+        fs::create_dir_all(quarantine.skill_dir("pdf")).expect("mkdir");
+        quarantine.write_record("pdf", &record).expect("write");
+
+        let listed = quarantine.list().expect("list");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "pdf");
+        assert_eq!(listed[0].record, record);
+
+        quarantine.remove("pdf").expect("remove");
+        assert!(matches!(
+            quarantine.get("pdf"),
+            Err(Error::QuarantinedSkillNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+//! On-disk cache of rendered skill templates.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    machine::Probe,
+    skill::{BaseTemplates, render_template_probed},
+    state::hash_bytes,
+    tool::Tool,
+};
+
+/// File name of the render cache within the cache directory.
+pub const RENDER_CACHE_FILE: &str = "render-cache.json";
+
+/// Most renderings kept on disk; the least recently used are dropped first.
+const MAX_ENTRIES: usize = 1000;
+
+/// Seconds before a cache hit refreshes its entry's last-used time, so reads rarely rewrite the
+/// cache file.
+const TOUCH_INTERVAL: u64 = 24 * 60 * 60;
+
+/// A cached rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Rendered output.
+    rendered: String,
+    /// Seconds since the Unix epoch when the entry was last used.
+    used: u64,
+}
+
+/// Rendered templates keyed by a digest of everything that affects the output.
+///
+/// The cache is an optimization only: unreadable cache files are ignored and failed writes are
+/// dropped, so a command never fails because of it.
+#[derive(Debug, Default)]
+pub struct RenderCache {
+    /// Cache file, or `None` for a cache that lives only in memory.
+    path: Option<PathBuf>,
+    /// Cached renderings keyed by [`cache_key`].
+    entries: RefCell<BTreeMap<String, Entry>>,
+    /// Whether entries changed since the cache was loaded.
+    dirty: Cell<bool>,
+}
+
+impl RenderCache {
+    /// Load the cache file at a path, starting empty when it is missing or unreadable.
+    pub(crate) fn load(path: &Path) -> Self {
+        let entries = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path.to_path_buf()),
+            entries: RefCell::new(entries),
+            dirty: Cell::new(false),
+        }
+    }
+
+    /// Render a template for a tool, reusing an earlier rendering of identical input.
+    ///
+    /// Failed renders are not cached, so their errors are reported every time, and neither are
+    /// renders that looked up the machine, since they are evaluated afresh on every push.
+    pub(crate) fn render(
+        &self,
+        template: &str,
+        tool: Tool,
+        bases: &BaseTemplates,
+    ) -> Result<String, String> {
+        let key = cache_key(template, tool, bases);
+        let now = now_secs();
+        if let Some(entry) = self.entries.borrow_mut().get_mut(&key) {
+            if now.saturating_sub(entry.used) > TOUCH_INTERVAL {
+                entry.used = now;
+                self.dirty.set(true);
+            }
+            return Ok(entry.rendered.clone());
+        }
+
+        let probe = Probe::default();
+        let rendered = render_template_probed(template, tool, bases, None, &probe)
+            .map_err(|(_, message)| message)?;
+        if !probe.lookups().is_empty() {
+            return Ok(rendered);
+        }
+        self.entries.borrow_mut().insert(
+            key,
+            Entry {
+                rendered: rendered.clone(),
+                used: now,
+            },
+        );
+        self.dirty.set(true);
+        Ok(rendered)
+    }
+
+    /// Write changed entries back to the cache file, keeping the most recently used.
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.dirty.get() {
+            return;
+        }
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() > MAX_ENTRIES {
+            let mut used: Vec<u64> = entries.values().map(|entry| entry.used).collect();
+            used.sort_unstable_by(|a, b| b.cmp(a));
+            let cutoff = used[MAX_ENTRIES - 1];
+            entries.retain(|_, entry| entry.used >= cutoff);
+        }
+        let Ok(contents) = serde_json::to_string(&*entries) else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).ok();
+        }
+        // Write then rename so concurrent runs never read a partial file.
+        let staging = path.with_extension("json.partial");
+        if fs::write(&staging, contents).is_ok() {
+            fs::rename(&staging, path).ok();
+        }
+    }
+}
+
+impl Drop for RenderCache {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+/// Digest the inputs of a rendering: the template, the base templates it may extend, the tool, and
+/// the CLI version, whose template engine and functions determine the output.
+fn cache_key(template: &str, tool: Tool, bases: &BaseTemplates) -> String {
+    let input = format!(
+        "{}\0{}\0{}\0{template}",
+        env!("CARGO_PKG_VERSION"),
+        tool.id(),
+        bases.digest()
+    );
+    hash_bytes(input.as_bytes())
+}
+
+/// Return the current time in seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{RENDER_CACHE_FILE, RenderCache, cache_key};
+    use crate::{skill::BaseTemplates, tool::Tool};
+
+    #[test]
+    fn reuses_renderings_across_loads() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("cache").join(RENDER_CACHE_FILE);
+        let bases = BaseTemplates::default();
+        let template = "{% if tool == \"codex\" %}Codex{% else %}Other{% endif %}";
+
+        {
+            let cache = RenderCache::load(&path);
+            assert_eq!(
+                cache.render(template, Tool::Codex, &bases).expect("render"),
+                "Codex"
+            );
+            assert_eq!(
+                cache
+                    .render(template, Tool::Claude, &bases)
+                    .expect("render"),
+                "Other"
+            );
+            assert!(cache.render("{{ missing }}", Tool::Codex, &bases).is_err());
+        }
+        assert!(path.is_file());
+
+        let cache = RenderCache::load(&path);
+        assert_eq!(cache.entries.borrow().len(), 2);
+        cache
+            .entries
+            .borrow_mut()
+            .get_mut(&cache_key(template, Tool::Codex, &bases))
+            .expect("entry")
+            .rendered = "cached".to_string();
+        assert_eq!(
+            cache.render(template, Tool::Codex, &bases).expect("render"),
+            "cached"
+        );
+        assert_eq!(
+            cache
+                .render("changed", Tool::Codex, &bases)
+                .expect("render"),
+            "changed"
+        );
+    }
+
+    #[test]
+    fn ignores_unreadable_cache_files() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(RENDER_CACHE_FILE);
+        fs::write(&path, "not json").expect("write");
+        let bases = BaseTemplates::default();
+
+        let cache = RenderCache::load(&path);
+        assert_eq!(
+            cache.render("plain", Tool::Claude, &bases).expect("render"),
+            "plain"
+        );
+    }
+
+    #[test]
+    fn skips_renderings_that_look_up_the_machine() {
+        let cache = RenderCache::default();
+        let bases = BaseTemplates::default();
+
+        assert!(
+            cache
+                .render("{{ platform() }}", Tool::Codex, &bases)
+                .is_ok()
+        );
+        assert!(cache.entries.borrow().is_empty());
+    }
+}
@@ -0,0 +1,429 @@
+//! Frontmatter validation against a source's JSON Schema.
+//!
+//! Sources can ship a [`SCHEMA_FILE`] describing the frontmatter their skills must have beyond
+//! `name` and `description`. The supported keywords are the subset of JSON Schema that metadata
+//! standards need: `type`, `enum`, `const`, `required`, `properties`, `additionalProperties`,
+//! `items`, `minLength`, `maxLength`, `pattern`, `minimum`, `maximum`, `minItems`, `maxItems`,
+//! and `uniqueItems`. Other keywords, such as `title` and `description`, are ignored.
+
+use std::{fmt, fs, io::ErrorKind, path::Path, result::Result as StdResult};
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+/// File in a source directory holding the schema its skills' frontmatter must match.
+pub const SCHEMA_FILE: &str = "skill.schema.json";
+
+/// A frontmatter schema loaded from a source.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    /// Root schema object.
+    root: Value,
+}
+
+impl Schema {
+    /// Load the schema in a source directory, returning `None` when the source has none.
+    pub fn load(source_root: &Path) -> Result<Option<Self>> {
+        let path = source_root.join(SCHEMA_FILE);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(Error::SchemaInvalid {
+                    path,
+                    message: error.to_string(),
+                });
+            }
+        };
+        Self::parse(&contents)
+            .map(Some)
+            .map_err(|message| Error::SchemaInvalid { path, message })
+    }
+
+    /// Parse a schema, checking that it is an object and its patterns compile.
+    fn parse(contents: &str) -> StdResult<Self, String> {
+        let root: Value = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+        check_schema(&root, "")?;
+        Ok(Self { root })
+    }
+
+    /// Return each way a frontmatter document fails the schema.
+    pub fn validate(&self, frontmatter: &Value) -> Vec<Violation> {
+        let mut problems = Vec::new();
+        validate(&self.root, frontmatter, "", &mut problems);
+        problems
+    }
+}
+
+/// A way a frontmatter value fails a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Path of the offending value, such as `tags[1]`, or empty for the whole frontmatter.
+    path: String,
+    /// Description of the failure.
+    message: String,
+}
+
+impl Violation {
+    /// Create a violation at a value path.
+    fn new(path: &str, message: String) -> Self {
+        Self {
+            path: path.to_string(),
+            message,
+        }
+    }
+
+    /// Return the top-level frontmatter field the violation is in, if any.
+    pub fn field(&self) -> Option<&str> {
+        let end = self.path.find(['.', '[']).unwrap_or(self.path.len());
+        Some(&self.path[..end]).filter(|field| !field.is_empty())
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", display(&self.path), self.message)
+    }
+}
+
+/// Check that a schema and its subschemas are objects with compilable patterns.
+fn check_schema(schema: &Value, path: &str) -> StdResult<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Err(format!("{}: schema must be an object", display(path)));
+    };
+    if let Some(pattern) = schema.get("pattern") {
+        let pattern = pattern
+            .as_str()
+            .ok_or_else(|| format!("{}: pattern must be a string", display(path)))?;
+        Regex::new(pattern).map_err(|error| format!("{}: {error}", display(path)))?;
+    }
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            check_schema(property, &join_key(path, key))?;
+        }
+    }
+    if let Some(items) = schema.get("items") {
+        check_schema(items, &format!("{path}[]"))?;
+    }
+    if let Some(additional) = schema.get("additionalProperties")
+        && !additional.is_boolean()
+    {
+        check_schema(additional, &join_key(path, "*"))?;
+    }
+    Ok(())
+}
+
+/// Validate a value against a schema, adding a problem for each failure.
+fn validate(schema: &Value, value: &Value, path: &str, problems: &mut Vec<Violation>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    if let Some(expected) = schema.get("type")
+        && !type_matches(expected, value)
+    {
+        problems.push(Violation::new(
+            path,
+            format!(
+                "expected {}, found {}",
+                type_names(expected),
+                type_name(value)
+            ),
+        ));
+        return;
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        problems.push(Violation::new(
+            path,
+            format!("{value} is not one of {}", allowed.join(", ")),
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        problems.push(Violation::new(
+            path,
+            format!("expected {expected}, found {value}"),
+        ));
+    }
+
+    match value {
+        Value::Object(fields) => validate_object(schema, fields, path, problems),
+        Value::Array(items) => validate_array(schema, items, path, problems),
+        Value::String(text) => validate_string(schema, text, path, problems),
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+                && number < minimum
+            {
+                problems.push(Violation::new(
+                    path,
+                    format!("{number} is less than {minimum}"),
+                ));
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+                && number > maximum
+            {
+                problems.push(Violation::new(
+                    path,
+                    format!("{number} is greater than {maximum}"),
+                ));
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+}
+
+/// Validate an object's required, declared, and additional properties.
+fn validate_object(
+    schema: &Map<String, Value>,
+    fields: &Map<String, Value>,
+    path: &str,
+    problems: &mut Vec<Violation>,
+) {
+    for key in schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if !fields.contains_key(key) {
+            problems.push(Violation::new(
+                &join_key(path, key),
+                "missing required field".to_string(),
+            ));
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, field) in fields {
+        let field_path = join_key(path, key);
+        match properties.and_then(|properties| properties.get(key)) {
+            Some(property) => validate(property, field, &field_path, problems),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    problems.push(Violation::new(
+                        &field_path,
+                        "field is not allowed".to_string(),
+                    ));
+                }
+                Some(additional) if additional.is_object() => {
+                    validate(additional, field, &field_path, problems);
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Validate an array's length, uniqueness, and items.
+fn validate_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    problems: &mut Vec<Violation>,
+) {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+        && (items.len() as u64) < min
+    {
+        problems.push(Violation::new(
+            path,
+            format!("expected at least {min} item(s)"),
+        ));
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+        && (items.len() as u64) > max
+    {
+        problems.push(Violation::new(
+            path,
+            format!("expected at most {max} item(s)"),
+        ));
+    }
+    if schema.get("uniqueItems") == Some(&Value::Bool(true))
+        && let Some(repeat) = items
+            .iter()
+            .enumerate()
+            .find(|(index, item)| items[..*index].contains(item))
+    {
+        problems.push(Violation::new(
+            path,
+            format!("{} appears more than once", repeat.1),
+        ));
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            validate(item_schema, item, &format!("{path}[{index}]"), problems);
+        }
+    }
+}
+
+/// Validate a string's length and pattern.
+fn validate_string(
+    schema: &Map<String, Value>,
+    text: &str,
+    path: &str,
+    problems: &mut Vec<Violation>,
+) {
+    let length = text.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+        && length < min
+    {
+        problems.push(Violation::new(
+            path,
+            format!("expected at least {min} character(s)"),
+        ));
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+        && length > max
+    {
+        problems.push(Violation::new(
+            path,
+            format!("expected at most {max} character(s)"),
+        ));
+    }
+    // Patterns were checked when the schema was loaded.
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+        && let Ok(regex) = Regex::new(pattern)
+        && !regex.is_match(text)
+    {
+        problems.push(Violation::new(
+            path,
+            format!("{text:?} does not match /{pattern}/"),
+        ));
+    }
+}
+
+/// Whether a value has one of the types a schema's `type` keyword names.
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+/// Whether a value is of a named JSON Schema type.
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        name => type_name(value) == name,
+    }
+}
+
+/// Return the JSON Schema type name of a value.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Describe the types a schema's `type` keyword names, such as `string or array`.
+fn type_names(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or_default().to_string(),
+    }
+}
+
+/// Append an object key to a value path.
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Return a value path for display, naming the root `frontmatter`.
+fn display(path: &str) -> &str {
+    if path.is_empty() { "frontmatter" } else { path }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Schema;
+
+    #[test]
+    fn reports_each_schema_violation() {
+        let schema = Schema::parse(
+            r#"{
+                "type": "object",
+                "required": ["name", "description", "owner"],
+                "properties": {
+                    "tags": {
+                        "type": "array",
+                        "uniqueItems": true,
+                        "items": { "enum": ["docs", "data", "ops"] }
+                    },
+                    "version": { "type": "string", "pattern": "^\\d+\\.\\d+$" },
+                    "priority": { "type": "integer", "minimum": 1, "maximum": 5 }
+                },
+                "additionalProperties": true
+            }"#,
+        )
+        .expect("schema");
+
+        let valid = json!({
+            "name": "pdf",
+            "description": "PDF tools",
+            "owner": "docs-team",
+            "tags": ["docs", "data"],
+            "version": "1.2",
+            "priority": 3,
+        });
+        assert!(schema.validate(&valid).is_empty());
+
+        let invalid = json!({
+            "name": "pdf",
+            "description": "PDF tools",
+            "tags": ["docs", "misc", "docs"],
+            "version": "v1",
+            "priority": "high",
+        });
+        let violations = schema.validate(&invalid);
+        assert_eq!(violations[2].field(), Some("tags"));
+        assert_eq!(
+            violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "owner: missing required field",
+                "priority: expected integer, found string",
+                "tags: \"docs\" appears more than once",
+                "tags[1]: \"misc\" is not one of \"docs\", \"data\", \"ops\"",
+                "version: \"v1\" does not match /^\\d+\\.\\d+$/",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_schemas() {
+        assert!(Schema::parse("[]").is_err());
+        assert!(Schema::parse(r#"{"properties": {"a": {"pattern": "("}}}"#).is_err());
+        let closed = Schema::parse(r#"{"additionalProperties": false}"#).expect("schema");
+        assert_eq!(
+            closed.validate(&json!({"extra": 1}))[0].to_string(),
+            "extra: field is not allowed"
+        );
+    }
+}
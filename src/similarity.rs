@@ -0,0 +1,191 @@
+//! Detection of skills whose descriptions are too alike for an agent to tell apart.
+//!
+//! Agents pick a skill by its description, so two descriptions that say nearly the same thing make
+//! the choice a coin toss. Descriptions are compared as bags of content words: lowercased, with
+//! common words dropped and plural endings stripped, scored by cosine similarity.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+
+/// Pairs scoring at or above this similarity are reported.
+pub const THRESHOLD: f64 = 0.8;
+
+/// Words too common in descriptions to tell skills apart.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "an", "and", "any", "are", "as", "at", "be", "by", "can", "for", "from", "how",
+    "in", "into", "is", "it", "its", "of", "on", "or", "skill", "that", "the", "their", "this",
+    "to", "use", "used", "using", "when", "with", "you", "your",
+];
+
+/// Two skills whose descriptions are alike.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarPair {
+    /// Name of the first skill, in name order.
+    pub first: String,
+    /// Name of the second skill.
+    pub second: String,
+    /// Cosine similarity of the two descriptions, from 0 to 1.
+    pub score: f64,
+    /// Content words only the first description uses.
+    pub only_first: Vec<String>,
+    /// Content words only the second description uses.
+    pub only_second: Vec<String>,
+}
+
+impl SimilarPair {
+    /// Advice on how to tell the two skills apart.
+    pub fn suggestion(&self) -> String {
+        if self.only_first.is_empty() && self.only_second.is_empty() {
+            return format!(
+                "the descriptions say the same thing; state what '{}' does that '{}' does not",
+                self.first, self.second
+            );
+        }
+        let words = |words: &[String]| {
+            if words.is_empty() {
+                "nothing".to_string()
+            } else {
+                words.join(", ")
+            }
+        };
+        format!(
+            "only '{}' mentions {}; only '{}' mentions {}; lead with what sets each apart",
+            self.first,
+            words(&self.only_first),
+            self.second,
+            words(&self.only_second)
+        )
+    }
+}
+
+/// Find pairs of skills whose descriptions score at or above [`THRESHOLD`].
+///
+/// `descriptions` holds skill names and descriptions. When `focus` names a skill, only pairs
+/// including it are returned. Pairs are ordered by descending score, then by name.
+pub fn similar_descriptions(
+    descriptions: &[(&str, &str)],
+    focus: Option<&str>,
+) -> Vec<SimilarPair> {
+    let mut skills: Vec<(&str, HashMap<String, usize>)> = descriptions
+        .iter()
+        .map(|(name, description)| (*name, term_counts(description)))
+        .filter(|(_, terms)| !terms.is_empty())
+        .collect();
+    skills.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut pairs = Vec::new();
+    for (index, (first, first_terms)) in skills.iter().enumerate() {
+        for (second, second_terms) in &skills[index + 1..] {
+            if focus.is_some_and(|focus| focus != *first && focus != *second) {
+                continue;
+            }
+            let score = cosine(first_terms, second_terms);
+            if score >= THRESHOLD {
+                pairs.push(SimilarPair {
+                    first: first.to_string(),
+                    second: second.to_string(),
+                    score,
+                    only_first: difference(first_terms, second_terms),
+                    only_second: difference(second_terms, first_terms),
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.score.total_cmp(&a.score));
+    pairs
+}
+
+/// Count the content words of a description.
+fn term_counts(description: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let word = word.to_lowercase();
+        if STOP_WORDS.contains(&word.as_str()) {
+            continue;
+        }
+        *counts.entry(stem(&word)).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Strip a plural ending so that `file` and `files` count as one word.
+fn stem(word: &str) -> String {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+/// Cosine similarity of two word counts.
+fn cosine(a: &HashMap<String, usize>, b: &HashMap<String, usize>) -> f64 {
+    let dot: usize = a
+        .iter()
+        .filter_map(|(word, count)| b.get(word).map(|other| count * other))
+        .sum();
+    let squares =
+        |counts: &HashMap<String, usize>| counts.values().map(|count| count * count).sum::<usize>();
+    dot as f64 / ((squares(a) * squares(b)) as f64).sqrt()
+}
+
+/// Words in `a` but not in `b`, sorted.
+fn difference(a: &HashMap<String, usize>, b: &HashMap<String, usize>) -> Vec<String> {
+    a.keys()
+        .filter(|word| !b.contains_key(*word))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::similar_descriptions;
+
+    #[test]
+    fn flags_near_identical_descriptions() {
+        let descriptions = [
+            ("pdf-tools", "Extract text and tables from PDF files."),
+            ("pdf-reader", "Extract the text and tables from a PDF file."),
+            (
+                "pdf-forms",
+                "Fill PDF forms and extract their field values.",
+            ),
+            ("git-commit", "Write commit messages for staged changes."),
+        ];
+        let pairs = similar_descriptions(&descriptions, None);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(
+            (pairs[0].first.as_str(), pairs[0].second.as_str()),
+            ("pdf-reader", "pdf-tools")
+        );
+        assert!((pairs[0].score - 1.0).abs() < 1e-9);
+        assert!(pairs[0].suggestion().contains("say the same thing"));
+
+        let descriptions = [
+            (
+                "review",
+                "Review pull requests for bugs, style, and missing tests.",
+            ),
+            (
+                "audit",
+                "Review pull requests for bugs, style, tests, and security.",
+            ),
+        ];
+        let pairs = similar_descriptions(&descriptions, None);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].only_first, ["security"]);
+        assert_eq!(pairs[0].only_second, ["missing"]);
+        assert!(
+            pairs[0]
+                .suggestion()
+                .contains("only 'audit' mentions security; only 'review' mentions missing")
+        );
+
+        assert!(similar_descriptions(&descriptions, Some("other")).is_empty());
+        assert_eq!(similar_descriptions(&descriptions, Some("audit")).len(), 1);
+    }
+}
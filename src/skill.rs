@@ -1,18 +1,237 @@
 //! Skill loading and templating helpers.
 
 use std::{
+    collections::BTreeMap,
     fs,
+    io::{self, Write},
     path::{Path, PathBuf},
+    sync::Arc,
     time::SystemTime,
 };
 
-use minijinja::{Environment, UndefinedBehavior, context};
+use minijinja::{Environment, ErrorKind, UndefinedBehavior, Value, context};
 
-use crate::{diagnostics::Diagnostics, frontmatter::parse_frontmatter, tool::Tool};
+use crate::{
+    archive::{SymlinkPolicy, create_symlink, preserved_link_target},
+    changelog::CHANGELOG_FILE,
+    commands::test::{SNAPSHOTS_DIR, TESTS_FILE},
+    config::LineEndings,
+    diagnostics::Diagnostics,
+    error::{self, Error},
+    filters,
+    frontmatter::{parse_frontmatter, select_description},
+    machine::{self, Probe},
+    profile::{Phase, span},
+    project::ProjectContext,
+    state::hash_bytes,
+    status::ContentHash,
+    store::{DirStats, FsStore, SkillStore},
+    tool::Tool,
+};
 
 /// The expected skill file name within a skill directory.
 pub const SKILL_FILE_NAME: &str = "SKILL.md";
 
+/// Directory within a skill conventionally holding executable helper scripts.
+pub const SCRIPTS_DIR: &str = "scripts";
+
+/// Directory within a source holding shared base templates that skills can `{% extends %}`.
+pub const TEMPLATES_DIR: &str = "_templates";
+
+/// Directory within a source holding archived skills, which are left out of the catalog.
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Entries at the top of a skill directory that describe the skill rather than ship with it, so
+/// they are neither rendered nor pushed to tools.
+const SKILL_METADATA: [&str; 3] = [CHANGELOG_FILE, TESTS_FILE, SNAPSHOTS_DIR];
+
+/// Template instructions one render may execute, so a runaway loop fails instead of hanging.
+const RENDER_FUEL: u64 = 1_000_000;
+
+/// Nesting a render may reach; blocks and loops count one level, includes and macros more.
+const RENDER_RECURSION_LIMIT: usize = 200;
+
+/// Largest rendered skill, in bytes.
+const MAX_RENDER_OUTPUT: usize = 1024 * 1024;
+
+/// Shared templates from a source's [`TEMPLATES_DIR`], keyed by their path within it.
+#[derive(Debug, Clone, Default)]
+pub struct BaseTemplates {
+    /// Template contents keyed by `/`-separated relative path, such as `base-skill.md`.
+    templates: BTreeMap<String, String>,
+    /// Digest of every template, empty when there are none.
+    digest: String,
+    /// Template files left out because they link outside the source and the include paths.
+    refused: Vec<PathBuf>,
+}
+
+impl BaseTemplates {
+    /// Load every template under a source's templates directory, which may be missing.
+    ///
+    /// Templates are only read from inside the source or one of `include_paths`; a file that
+    /// links anywhere else is refused, so a template cannot pull arbitrary files into a skill.
+    pub(crate) fn load(
+        store: &dyn SkillStore,
+        source_root: &Path,
+        include_paths: &[PathBuf],
+    ) -> Self {
+        let allowed: Vec<PathBuf> = [source_root]
+            .into_iter()
+            .chain(include_paths.iter().map(PathBuf::as_path))
+            .filter_map(|root| store.real_path(root).ok())
+            .collect();
+        let mut templates = BTreeMap::new();
+        let mut refused = Vec::new();
+        let dir = source_root.join(TEMPLATES_DIR);
+        let mut found = Vec::new();
+        collect_templates(store, &dir, "", &mut found);
+        for (name, path, contents) in found {
+            let inside = store
+                .real_path(&path)
+                .is_ok_and(|real| allowed.iter().any(|root| real.starts_with(root)));
+            if inside {
+                templates.insert(name, contents);
+            } else {
+                refused.push(path);
+            }
+        }
+        let digest = if templates.is_empty() {
+            String::new()
+        } else {
+            let mut input = String::new();
+            for (name, contents) in &templates {
+                input.push_str(name);
+                input.push('\0');
+                input.push_str(contents);
+                input.push('\0');
+            }
+            hash_bytes(input.as_bytes())
+        };
+        Self {
+            templates,
+            digest,
+            refused,
+        }
+    }
+
+    /// Return a digest of the templates, so cached renderings are invalidated when a base changes.
+    pub(crate) fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    /// Return the template files refused for linking outside the allowed directories.
+    pub(crate) fn refused(&self) -> &[PathBuf] {
+        &self.refused
+    }
+
+    /// Return the names templates can extend or include, in order.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+}
+
+/// Add the name, path, and contents of each file under a templates directory to `templates`,
+/// recursing into subdirectories.
+fn collect_templates(
+    store: &dyn SkillStore,
+    dir: &Path,
+    prefix: &str,
+    templates: &mut Vec<(String, PathBuf, String)>,
+) {
+    let Ok(entries) = store.list(dir) else {
+        return;
+    };
+    for entry in entries {
+        let Some(name) = entry.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let name = format!("{prefix}{name}");
+        match store.read(&entry) {
+            Ok(Some(file)) => templates.push((name, entry, file.contents)),
+            Ok(None) => collect_templates(store, &entry, &format!("{name}/"), templates),
+            Err(_) => {}
+        }
+    }
+}
+
+/// File names that identify a skill directory's main file.
+///
+/// Tool directories are always written with [`SKILL_FILE_NAME`], since that is what agents load,
+/// but any accepted name is recognized when reading sources, tool installs, and archives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillFileNames {
+    /// Accepted names in priority order; the first is used for new source skills.
+    names: Vec<String>,
+    /// Whether names match regardless of ASCII case.
+    case_insensitive: bool,
+}
+
+impl Default for SkillFileNames {
+    fn default() -> Self {
+        Self {
+            names: vec![SKILL_FILE_NAME.to_string()],
+            case_insensitive: false,
+        }
+    }
+}
+
+impl SkillFileNames {
+    /// Create a set of accepted names, falling back to the default name when `names` is empty.
+    pub(crate) fn new(names: Vec<String>, case_insensitive: bool) -> Self {
+        if names.is_empty() {
+            return Self {
+                case_insensitive,
+                ..Self::default()
+            };
+        }
+        Self {
+            names,
+            case_insensitive,
+        }
+    }
+
+    /// Return the name used when creating a new source skill.
+    pub(crate) fn primary(&self) -> &str {
+        &self.names[0]
+    }
+
+    /// Return whether a file name is an accepted skill file name.
+    pub(crate) fn matches(&self, file_name: &str) -> bool {
+        self.rank(file_name).is_some()
+    }
+
+    /// Return the priority of a file name, lower is preferred.
+    pub(crate) fn rank(&self, file_name: &str) -> Option<usize> {
+        self.names.iter().position(|name| {
+            if self.case_insensitive {
+                name.eq_ignore_ascii_case(file_name)
+            } else {
+                name == file_name
+            }
+        })
+    }
+
+    /// Find the skill file in a directory, preferring names listed earlier.
+    pub(crate) fn find(&self, store: &dyn SkillStore, dir: &Path) -> Option<PathBuf> {
+        store
+            .list(dir)
+            .ok()?
+            .into_iter()
+            .filter_map(|path| {
+                let rank = self.rank(path.file_name()?.to_str()?)?;
+                Some((rank, path))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, path)| path)
+    }
+
+    /// Return the skill file in a directory on disk, or where a new one should be created.
+    pub(crate) fn path_in(&self, dir: &Path) -> PathBuf {
+        self.find(&FsStore, dir)
+            .unwrap_or_else(|| dir.join(self.primary()))
+    }
+}
+
 /// Source skill metadata and template contents.
 #[derive(Debug, Clone)]
 pub struct SkillTemplate {
@@ -28,8 +247,115 @@ pub struct SkillTemplate {
     pub(crate) skill_path: PathBuf,
     /// Raw template contents of the skill file.
     pub(crate) contents: String,
+    /// Other files in the skill directory, keyed by `/`-separated path within it.
+    pub(crate) files: BTreeMap<String, SupportFile>,
+    /// Base templates the skill can extend, shared by every skill in its source.
+    pub(crate) bases: Arc<BaseTemplates>,
     /// Modified time for the skill file.
     pub(crate) modified: SystemTime,
+    /// Size and file count of the skill directory.
+    pub(crate) stats: DirStats,
+}
+
+impl SkillTemplate {
+    /// Return the name of the directory holding the skill, which should match its name.
+    pub(crate) fn dir_name(&self) -> &str {
+        self.skill_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    /// Render the skill file and markdown support files with `render`.
+    pub(crate) fn render_with(
+        &self,
+        mut render: impl FnMut(&str) -> Result<String, String>,
+    ) -> Result<RenderedSkill, String> {
+        let contents = render(&self.contents)?;
+        let mut templates = BTreeMap::new();
+        for (path, file) in &self.files {
+            if let SupportFile::Template(template) = file {
+                let rendered = render(template).map_err(|error| format!("{path}: {error}"))?;
+                templates.insert(path.clone(), rendered);
+            }
+        }
+        Ok(RenderedSkill {
+            contents,
+            templates,
+        })
+    }
+}
+
+/// A file in a skill directory besides the skill file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupportFile {
+    /// Markdown, rendered for each tool like the skill file.
+    Template(String),
+    /// Any other file, copied as is, with the digest of its contents.
+    Asset(ContentHash),
+}
+
+impl SupportFile {
+    /// Classify a file by its path: UTF-8 markdown is a template, anything else an asset.
+    fn new(path: &str, contents: Vec<u8>) -> Self {
+        if !is_template(path) {
+            return Self::Asset(ContentHash::of_bytes(&contents));
+        }
+        match String::from_utf8(contents) {
+            Ok(template) => Self::Template(template),
+            Err(error) => Self::Asset(ContentHash::of_bytes(error.as_bytes())),
+        }
+    }
+}
+
+/// Return whether a support file is rendered as a template: markdown is, scripts and data are
+/// copied as they are.
+pub fn is_template(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md"))
+}
+
+/// A source skill rendered for one tool: what a push writes into the tool's skill directory.
+#[derive(Debug, Clone)]
+pub struct RenderedSkill {
+    /// Rendered skill file.
+    pub(crate) contents: String,
+    /// Rendered markdown support files keyed by path; other support files are copied from the
+    /// source.
+    pub(crate) templates: BTreeMap<String, String>,
+}
+
+impl RenderedSkill {
+    /// Return a digest of the rendered files, so identical writes can be told apart from
+    /// conflicting ones.
+    pub(crate) fn digest(&self) -> String {
+        let mut input = self.contents.clone();
+        for (path, contents) in &self.templates {
+            input.push('\0');
+            input.push_str(path);
+            input.push('\0');
+            input.push_str(contents);
+        }
+        hash_bytes(input.as_bytes())
+    }
+
+    /// Read every support file a push of `skill` writes, keyed by path: rendered markdown, and
+    /// other files as they are in the source.
+    pub(crate) fn support_contents(
+        &self,
+        skill: &SkillTemplate,
+    ) -> error::Result<BTreeMap<String, Vec<u8>>> {
+        let mut files = BTreeMap::new();
+        for path in skill.files.keys() {
+            let contents = match self.templates.get(path) {
+                Some(rendered) => rendered.clone().into_bytes(),
+                None => read_file(&skill.skill_dir.join(path))?,
+            };
+            files.insert(path.clone(), contents);
+        }
+        Ok(files)
+    }
 }
 
 /// Installed tool skill metadata and contents.
@@ -37,15 +363,55 @@ pub struct SkillTemplate {
 pub struct ToolSkill {
     /// Skill name from frontmatter.
     pub(crate) name: String,
+    /// Directory containing the skill file.
+    pub(crate) skill_dir: PathBuf,
     /// Path to the skill file.
     pub(crate) skill_path: PathBuf,
-    /// Raw contents of the skill file.
-    pub(crate) contents: String,
+    /// Digest of the skill file contents.
+    pub(crate) hash: ContentHash,
+    /// Digests of the other files in the skill directory, keyed by `/`-separated path within it.
+    pub(crate) files: BTreeMap<String, ContentHash>,
     /// Modified time for the skill file.
     pub(crate) modified: SystemTime,
 }
 
-/// Local skill in a project directory (.claude/skills or .codex/skills).
+impl ToolSkill {
+    /// Read the installed skill file, for when its contents are displayed or copied.
+    pub(crate) fn read_contents(&self) -> error::Result<String> {
+        fs::read_to_string(&self.skill_path).map_err(|error| Error::SkillRead {
+            path: self.skill_path.clone(),
+            source: error,
+        })
+    }
+
+    /// Read every support file of the installed copy, keyed by path.
+    pub(crate) fn read_support_files(&self) -> error::Result<BTreeMap<String, Vec<u8>>> {
+        self.files
+            .keys()
+            .map(|path| Ok((path.clone(), read_file(&self.skill_dir.join(path))?)))
+            .collect()
+    }
+
+    /// Return whether the copy matches a rendering of its source, every support file included.
+    pub(crate) fn matches(&self, skill: &SkillTemplate, rendered: &RenderedSkill) -> bool {
+        if ContentHash::of(&rendered.contents) != self.hash
+            || !self.files.keys().eq(skill.files.keys())
+        {
+            return false;
+        }
+        skill.files.iter().all(|(path, file)| {
+            let expected = match (file, rendered.templates.get(path)) {
+                (SupportFile::Asset(hash), _) => *hash,
+                (SupportFile::Template(template), rendered) => {
+                    ContentHash::of(rendered.unwrap_or(template))
+                }
+            };
+            self.files.get(path) == Some(&expected)
+        })
+    }
+}
+
+/// Local skill in a project directory (.claude/skills, .codex/skills, or .gemini/skills).
 #[derive(Debug, Clone)]
 pub struct LocalSkill {
     /// Skill name from frontmatter.
@@ -60,30 +426,68 @@ pub struct LocalSkill {
     pub(crate) skill_path: PathBuf,
     /// Raw contents of the skill file.
     pub(crate) contents: String,
+    /// Digests of the other files in the skill directory, keyed by `/`-separated path within it.
+    pub(crate) files: BTreeMap<String, ContentHash>,
     /// Modified time for the skill file.
     pub(crate) modified: SystemTime,
+    /// Size and file count of the skill directory.
+    pub(crate) stats: DirStats,
 }
 
-/// Load a source skill from a directory if present.
-pub fn load_source_skill(
-    source_root: &Path,
-    skill_dir: &Path,
-    diagnostics: &mut Diagnostics,
-) -> Option<SkillTemplate> {
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    if !skill_path.is_file() {
-        return None;
+impl LocalSkill {
+    /// Describe the local copy as an installed copy, for comparing it against a rendering.
+    pub(crate) fn to_tool_skill(&self) -> ToolSkill {
+        ToolSkill {
+            name: self.name.clone(),
+            skill_dir: self.skill_dir.clone(),
+            skill_path: self.skill_path.clone(),
+            hash: ContentHash::of(&self.contents),
+            files: self.files.clone(),
+            modified: self.modified,
+        }
     }
+}
 
-    let contents = match fs::read_to_string(&skill_path) {
-        Ok(contents) => contents,
+/// Reads one support file from a store given its path within the skill directory and its path in
+/// the store, returning `None` when the entry is not a regular file.
+type SupportLoader<T> = fn(&dyn SkillStore, &str, &Path) -> io::Result<Option<T>>;
+
+/// A parsed skill file read from a store.
+struct LoadedSkill<T> {
+    /// Path to the skill file.
+    skill_path: PathBuf,
+    /// Skill name from frontmatter.
+    name: String,
+    /// Skill description from frontmatter.
+    description: String,
+    /// Raw contents of the skill file.
+    contents: String,
+    /// The other files in the skill directory as `load` read them, keyed by path within it.
+    files: BTreeMap<String, T>,
+    /// Modified time for the skill file.
+    modified: SystemTime,
+}
+
+/// Read and parse the skill file in a directory, reading its support files with `load`, and
+/// warning and skipping on errors.
+fn load_skill_file<T>(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
+    skill_dir: &Path,
+    load: SupportLoader<T>,
+    diagnostics: &mut Diagnostics,
+) -> Option<LoadedSkill<T>> {
+    let skill_path = names.find(store, skill_dir)?;
+    let file = match store.read(&skill_path) {
+        Ok(Some(file)) => file,
+        Ok(None) => return None,
         Err(error) => {
             diagnostics.warn_skipped(&skill_path, error.to_string());
             return None;
         }
     };
 
-    let frontmatter = match parse_frontmatter(&contents) {
+    let frontmatter = match parse_frontmatter(&file.contents) {
         Ok(frontmatter) => frontmatter,
         Err(error) => {
             diagnostics.warn_skipped(&skill_path, error.message);
@@ -91,118 +495,621 @@ pub fn load_source_skill(
         }
     };
 
-    let modified = fs::metadata(&skill_path)
-        .and_then(|metadata| metadata.modified())
-        .unwrap_or(SystemTime::UNIX_EPOCH);
-
-    Some(SkillTemplate {
+    let mut files = BTreeMap::new();
+    let walk = SupportWalk {
+        store,
+        skill_path: &skill_path,
+        load,
+    };
+    walk.collect(skill_dir, "", &mut files, diagnostics);
+    Some(LoadedSkill {
+        skill_path,
         name: frontmatter.name,
         description: frontmatter.description,
-        source_root: source_root.to_path_buf(),
-        skill_dir: skill_dir.to_path_buf(),
-        skill_path,
-        contents,
-        modified,
+        contents: file.contents,
+        files,
+        modified: file.modified,
     })
 }
 
-/// Load a tool-installed skill from a directory if present.
-pub fn load_tool_skill(skill_dir: &Path, diagnostics: &mut Diagnostics) -> Option<ToolSkill> {
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    if !skill_path.is_file() {
-        return None;
-    }
+/// A walk over the support files of one skill directory.
+struct SupportWalk<'a, T> {
+    /// Store the skill is read from.
+    store: &'a dyn SkillStore,
+    /// Path of the skill file, which is not a support file.
+    skill_path: &'a Path,
+    /// How each support file is read.
+    load: SupportLoader<T>,
+}
 
-    let contents = match fs::read_to_string(&skill_path) {
-        Ok(contents) => contents,
-        Err(error) => {
-            diagnostics.warn_skipped(&skill_path, error.to_string());
-            return None;
+impl<T> SupportWalk<'_, T> {
+    /// Add each file under `dir` other than the skill file and [`SKILL_METADATA`] to `files` as
+    /// `load` reads it, recursing into subdirectories and warning about files that cannot be read.
+    fn collect(
+        &self,
+        dir: &Path,
+        prefix: &str,
+        files: &mut BTreeMap<String, T>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let entries = match self.store.list(dir) {
+            Ok(entries) => entries,
+            Err(error) => {
+                diagnostics.warn(format!("cannot read {}: {error}", dir.display()));
+                return;
+            }
+        };
+        for entry in entries {
+            if entry == self.skill_path {
+                continue;
+            }
+            let Some(name) = entry.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if prefix.is_empty() && SKILL_METADATA.contains(&name) {
+                continue;
+            }
+            let name = format!("{prefix}{name}");
+            match (self.load)(self.store, &name, &entry) {
+                Ok(Some(file)) => {
+                    files.insert(name, file);
+                }
+                Ok(None) => self.collect(&entry, &format!("{name}/"), files, diagnostics),
+                Err(error) => {
+                    diagnostics.warn(format!("cannot read {}: {error}", entry.display()));
+                }
+            }
         }
-    };
+    }
+}
 
-    let frontmatter = match parse_frontmatter(&contents) {
-        Ok(frontmatter) => frontmatter,
-        Err(error) => {
-            diagnostics.warn_skipped(&skill_path, error.message);
-            return None;
-        }
-    };
+/// Read a source support file: markdown whole, as it is rendered, anything else only hashed.
+fn load_source_file(
+    store: &dyn SkillStore,
+    path: &str,
+    entry: &Path,
+) -> io::Result<Option<SupportFile>> {
+    if is_template(path) {
+        Ok(store
+            .read_bytes(entry)?
+            .map(|contents| SupportFile::new(path, contents)))
+    } else {
+        Ok(store.hash(entry)?.map(SupportFile::Asset))
+    }
+}
+
+/// Hash a support file of an installed copy, which is only compared, never rendered.
+fn load_installed_file(
+    store: &dyn SkillStore,
+    _path: &str,
+    entry: &Path,
+) -> io::Result<Option<ContentHash>> {
+    store.hash(entry)
+}
 
-    let modified = fs::metadata(&skill_path)
-        .and_then(|metadata| metadata.modified())
-        .unwrap_or(SystemTime::UNIX_EPOCH);
+/// Load a source skill from a directory if present.
+pub fn load_source_skill(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
+    source_root: &Path,
+    bases: &Arc<BaseTemplates>,
+    skill_dir: &Path,
+    diagnostics: &mut Diagnostics,
+) -> Option<SkillTemplate> {
+    let loaded = load_skill_file(store, names, skill_dir, load_source_file, diagnostics)?;
+    Some(SkillTemplate {
+        name: loaded.name,
+        description: loaded.description,
+        source_root: source_root.to_path_buf(),
+        skill_dir: skill_dir.to_path_buf(),
+        skill_path: loaded.skill_path,
+        contents: loaded.contents,
+        files: loaded.files,
+        bases: Arc::clone(bases),
+        modified: loaded.modified,
+        stats: store.dir_stats(skill_dir).unwrap_or_default(),
+    })
+}
 
+/// Load a tool-installed skill from a directory if present.
+pub fn load_tool_skill(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
+    skill_dir: &Path,
+    diagnostics: &mut Diagnostics,
+) -> Option<ToolSkill> {
+    let loaded = load_skill_file(store, names, skill_dir, load_installed_file, diagnostics)?;
     Some(ToolSkill {
-        name: frontmatter.name,
-        skill_path,
-        contents,
-        modified,
+        name: loaded.name,
+        skill_dir: skill_dir.to_path_buf(),
+        skill_path: loaded.skill_path,
+        hash: ContentHash::of(&loaded.contents),
+        files: loaded.files,
+        modified: loaded.modified,
     })
 }
 
 /// Load a local skill from a project directory if present.
 pub fn load_local_skill(
+    store: &dyn SkillStore,
+    names: &SkillFileNames,
     skill_dir: &Path,
     tool: Tool,
     diagnostics: &mut Diagnostics,
 ) -> Option<LocalSkill> {
-    let skill_path = skill_dir.join(SKILL_FILE_NAME);
-    if !skill_path.is_file() {
-        return None;
+    let loaded = load_skill_file(store, names, skill_dir, load_installed_file, diagnostics)?;
+    Some(LocalSkill {
+        name: loaded.name,
+        description: loaded.description,
+        tool,
+        skill_dir: skill_dir.to_path_buf(),
+        skill_path: loaded.skill_path,
+        contents: loaded.contents,
+        files: loaded.files,
+        modified: loaded.modified,
+        stats: store.dir_stats(skill_dir).unwrap_or_default(),
+    })
+}
+
+/// Write a skill file, creating its directory and applying the line ending policy.
+pub fn write_skill_file(
+    skill_path: &Path,
+    contents: &str,
+    line_endings: LineEndings,
+) -> error::Result<()> {
+    let _span = span(Phase::Io);
+    if let Some(skill_dir) = skill_path.parent() {
+        fs::create_dir_all(skill_dir).map_err(|error| Error::SkillWrite {
+            path: skill_dir.to_path_buf(),
+            source: error,
+        })?;
     }
 
-    let contents = match fs::read_to_string(&skill_path) {
-        Ok(contents) => contents,
-        Err(error) => {
-            diagnostics.warn_skipped(&skill_path, error.to_string());
-            return None;
+    fs::write(skill_path, line_endings.apply(contents).as_bytes()).map_err(|error| {
+        Error::SkillWrite {
+            path: skill_path.to_path_buf(),
+            source: error,
         }
-    };
+    })
+}
 
-    let frontmatter = match parse_frontmatter(&contents) {
-        Ok(frontmatter) => frontmatter,
-        Err(error) => {
-            diagnostics.warn_skipped(&skill_path, error.message);
-            return None;
+/// Write a source skill's support files into a tool's skill directory, then remove the copy's
+/// files the source no longer has.
+///
+/// Rendered markdown is written with the line ending policy; other files are copied from the
+/// source as they are, keeping their permissions.
+pub fn push_support_files(
+    skill: &SkillTemplate,
+    rendered: &RenderedSkill,
+    skill_dir: &Path,
+    existing: Option<&ToolSkill>,
+    line_endings: LineEndings,
+) -> error::Result<()> {
+    for path in skill.files.keys() {
+        let target = skill_dir.join(path);
+        match rendered.templates.get(path) {
+            Some(contents) if !is_symlink(&skill.skill_dir.join(path)) => {
+                write_skill_file(&target, contents, line_endings)?;
+            }
+            _ => copy_file(&skill.skill_dir, path, &target)?,
         }
-    };
+    }
+    let stale = existing
+        .into_iter()
+        .flat_map(|existing| existing.files.keys())
+        .filter(|path| !skill.files.contains_key(*path));
+    remove_files(skill_dir, stale)
+}
 
-    let modified = fs::metadata(&skill_path)
-        .and_then(|metadata| metadata.modified())
-        .unwrap_or(SystemTime::UNIX_EPOCH);
+/// Copy an installed skill's support files into a source skill directory, returning the
+/// source's new support files.
+///
+/// Markdown is written with the line ending policy, so it is pulled like the skill file; other
+/// files are copied as they are, keeping their permissions. Source files the copy does not have
+/// are kept unless `prune` is set, in which case they are removed.
+pub fn pull_support_files(
+    tool_skill: &ToolSkill,
+    source_dir: &Path,
+    existing: &BTreeMap<String, SupportFile>,
+    line_endings: LineEndings,
+    prune: bool,
+) -> error::Result<BTreeMap<String, SupportFile>> {
+    let mut files = BTreeMap::new();
+    for (path, contents) in tool_skill.read_support_files()? {
+        let target = source_dir.join(&path);
+        let file = SupportFile::new(&path, contents);
+        match &file {
+            SupportFile::Template(template) if !is_symlink(&tool_skill.skill_dir.join(&path)) => {
+                write_skill_file(&target, template, line_endings)?;
+            }
+            _ => copy_file(&tool_skill.skill_dir, &path, &target)?,
+        }
+        files.insert(path, file);
+    }
+    let stale: Vec<&String> = existing
+        .keys()
+        .filter(|path| !files.contains_key(*path))
+        .collect();
+    if prune {
+        remove_files(source_dir, stale.into_iter())?;
+    } else {
+        for path in stale {
+            files.insert(path.clone(), existing[path].clone());
+        }
+    }
+    Ok(files)
+}
 
-    Some(LocalSkill {
-        name: frontmatter.name,
-        description: frontmatter.description,
-        tool,
-        skill_dir: skill_dir.to_path_buf(),
-        skill_path,
-        contents,
-        modified,
+/// Read a file in a skill directory.
+fn read_file(path: &Path) -> error::Result<Vec<u8>> {
+    fs::read(path).map_err(|source| Error::SkillRead {
+        path: path.to_path_buf(),
+        source,
     })
 }
 
-/// Render a skill template for a specific tool.
-pub fn render_template(template: &str, tool: Tool) -> Result<String, String> {
+/// Whether a path is a symlink, without following it.
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_symlink())
+}
+
+/// Copy a file from a skill directory, creating its directory and keeping its permissions.
+///
+/// Symlinks are not followed: a link is recreated as a link when its target stays inside the
+/// skill directory, and refused otherwise, as archives preserve them. An existing link at the
+/// destination is replaced rather than written through.
+fn copy_file(from_dir: &Path, path: &str, to: &Path) -> error::Result<()> {
+    let _span = span(Phase::Io);
+    let write_error = |path: &Path, source| Error::SkillWrite {
+        path: path.to_path_buf(),
+        source,
+    };
+    let from = from_dir.join(path);
+    let metadata = fs::symlink_metadata(&from).map_err(|source| Error::SkillRead {
+        path: from.clone(),
+        source,
+    })?;
+    let link_target = if metadata.is_symlink() {
+        Some(preserved_link_target(
+            from_dir,
+            &from,
+            SymlinkPolicy::Preserve,
+        )?)
+    } else {
+        None
+    };
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir).map_err(|source| write_error(dir, source))?;
+    }
+    if link_target.is_some() || is_symlink(to) {
+        match fs::remove_file(to) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => return Err(write_error(to, source)),
+        }
+    }
+    match link_target {
+        Some(target) => {
+            create_symlink(Path::new(&target), to).map_err(|source| write_error(to, source))
+        }
+        None => fs::copy(&from, to)
+            .map(|_| ())
+            .map_err(|source| write_error(to, source)),
+    }
+}
+
+/// Remove files from a skill directory by path, along with the directories they leave empty.
+fn remove_files<'a>(
+    skill_dir: &Path,
+    paths: impl Iterator<Item = &'a String>,
+) -> error::Result<()> {
+    for path in paths {
+        let target = skill_dir.join(path);
+        match fs::remove_file(&target) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => {
+                return Err(Error::SkillWrite {
+                    path: target,
+                    source,
+                });
+            }
+        }
+        let mut dir = target.parent();
+        while let Some(parent) = dir.filter(|parent| *parent != skill_dir) {
+            if fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+    }
+    Ok(())
+}
+
+/// Render a skill template for a specific tool, resolving `extends` against `bases`.
+pub fn render_template(
+    template: &str,
+    tool: Tool,
+    bases: &BaseTemplates,
+) -> Result<String, String> {
+    render_template_located(template, tool, bases).map_err(|(_, message)| message)
+}
+
+/// Render a skill template, reporting the one-based line of a failure when it is known.
+pub fn render_template_located(
+    template: &str,
+    tool: Tool,
+    bases: &BaseTemplates,
+) -> Result<String, (Option<usize>, String)> {
+    render_template_probed(template, tool, bases, None, &Probe::default())
+}
+
+/// Return the variables a template sees when rendered for a tool and, optionally, a project.
+pub fn template_context(tool: Tool, project: Option<&ProjectContext>) -> Value {
+    match project {
+        Some(project) => context! { tool => tool.id(), project },
+        None => context! { tool => tool.id() },
+    }
+}
+
+/// Render a skill template, recording the machine lookups it makes in `probe`.
+///
+/// With a project, the template also sees its context as `project`; otherwise `project` is
+/// undefined. A per-tool `description` map in the rendered frontmatter is replaced with the
+/// tool's entry.
+pub fn render_template_probed(
+    template: &str,
+    tool: Tool,
+    bases: &BaseTemplates,
+    project: Option<&ProjectContext>,
+    probe: &Probe,
+) -> Result<String, (Option<usize>, String)> {
+    let _span = span(Phase::Render);
+    let env = template_environment(bases, probe).map_err(|message| (None, message))?;
+    let mut output = BoundedOutput::default();
+    env.template_from_str(template)
+        .and_then(|template| {
+            template
+                .render_to_write(template_context(tool, project), &mut output)
+                .map(|_| ())
+        })
+        .map_err(|error| {
+            let message = if output.overflowed {
+                format!("rendered output exceeds {MAX_RENDER_OUTPUT} bytes")
+            } else if error.kind() == ErrorKind::OutOfFuel {
+                format!("template ran for more than {RENDER_FUEL} steps")
+            } else {
+                error.to_string()
+            };
+            (error.line(), message)
+        })?;
+    let rendered = String::from_utf8(output.bytes)
+        .map_err(|_| (None, "rendered output is not UTF-8".to_string()))?;
+    select_description(&rendered, tool).map_err(|error| (None, error.message))
+}
+
+/// Build the environment skill templates are rendered in: sandbox limits, the machine functions
+/// recording into `probe`, the text filters, and the source's shared templates.
+pub fn template_environment<'a>(
+    bases: &'a BaseTemplates,
+    probe: &Probe,
+) -> Result<Environment<'a>, String> {
     let mut env = Environment::new();
     env.set_undefined_behavior(UndefinedBehavior::Strict);
-    let template = env
-        .template_from_str(template)
-        .map_err(|error| error.to_string())?;
-    template
-        .render(context! { tool => tool.id() })
-        .map_err(|error| error.to_string())
+    env.set_fuel(Some(RENDER_FUEL));
+    env.set_recursion_limit(RENDER_RECURSION_LIMIT);
+    machine::register(&mut env, probe);
+    filters::register(&mut env);
+    for (name, contents) in &bases.templates {
+        env.add_template(name, contents)
+            .map_err(|error| format!("{TEMPLATES_DIR}/{name}: {error}"))?;
+    }
+    Ok(env)
+}
+
+/// Rendered output that refuses to grow past [`MAX_RENDER_OUTPUT`].
+#[derive(Debug, Default)]
+struct BoundedOutput {
+    /// Output written so far.
+    bytes: Vec<u8>,
+    /// Whether a write was refused for exceeding the limit.
+    overflowed: bool,
+}
+
+impl Write for BoundedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.bytes.len() + buf.len() > MAX_RENDER_OUTPUT {
+            self.overflowed = true;
+            return Err(io::Error::other("rendered output too large"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{skill::render_template, tool::Tool};
+    use std::{path::Path, sync::Arc};
+
+    use crate::{
+        diagnostics::Diagnostics,
+        skill::{
+            BaseTemplates, SkillFileNames, SupportFile, TEMPLATES_DIR, copy_file,
+            load_source_skill, load_tool_skill, render_template,
+        },
+        status::ContentHash,
+        testutil::{MemoryStore, simple_skill},
+        tool::Tool,
+    };
 
     #[test]
     fn renders_tool_specific_templates() {
         let template = "{% if tool == \"codex\" %}Codex{% endif %}";
-        let rendered = render_template(template, Tool::Codex).expect("rendered");
+        let rendered =
+            render_template(template, Tool::Codex, &BaseTemplates::default()).expect("rendered");
         assert_eq!(rendered, "Codex");
     }
+
+    #[test]
+    fn finds_skill_files_by_configured_names() {
+        let store = MemoryStore::default()
+            .with_file("/src/alpha/skill.md", "lower")
+            .with_file("/src/alpha/index.md", "index")
+            .with_file("/src/beta/SKILL.md", "upper");
+        let dir = Path::new("/src/alpha");
+
+        let exact = SkillFileNames::default();
+        assert_eq!(exact.find(&store, dir), None);
+        assert!(exact.find(&store, Path::new("/src/beta")).is_some());
+
+        let names = vec!["SKILL.md".to_string(), "index.md".to_string()];
+        let any_case = SkillFileNames::new(names.clone(), true);
+        assert_eq!(
+            any_case.find(&store, dir).as_deref(),
+            Some(Path::new("/src/alpha/skill.md"))
+        );
+        assert!(any_case.matches("Index.MD"));
+
+        let exact = SkillFileNames::new(names, false);
+        assert_eq!(
+            exact.find(&store, dir).as_deref(),
+            Some(Path::new("/src/alpha/index.md"))
+        );
+        assert_eq!(SkillFileNames::new(Vec::new(), false).primary(), "SKILL.md");
+    }
+
+    #[test]
+    fn loads_and_compares_support_files() {
+        let skill = simple_skill("alpha");
+        let store = MemoryStore::default()
+            .with_file("/src/alpha/SKILL.md", &skill)
+            .with_file("/src/alpha/guide.md", "{{ tool }}")
+            .with_file("/src/alpha/scripts/run.sh", "echo {{ tool }}")
+            .with_file("/src/alpha/CHANGELOG.md", "# Changelog")
+            .with_file("/src/alpha/tests.toml", "")
+            .with_file("/src/alpha/__snapshots__/claude.md", "{{ tool }}")
+            .with_file("/tool/alpha/SKILL.md", &skill)
+            .with_file("/tool/alpha/guide.md", "claude")
+            .with_file("/tool/alpha/scripts/run.sh", "echo {{ tool }}");
+        let names = SkillFileNames::default();
+        let bases = Arc::new(BaseTemplates::default());
+        let mut diagnostics = Diagnostics::default();
+
+        let source = load_source_skill(
+            &store,
+            &names,
+            Path::new("/src"),
+            &bases,
+            Path::new("/src/alpha"),
+            &mut diagnostics,
+        )
+        .expect("source skill");
+        assert_eq!(
+            source.files.get("guide.md"),
+            Some(&SupportFile::Template("{{ tool }}".to_string()))
+        );
+        assert_eq!(
+            source.files.get("scripts/run.sh"),
+            Some(&SupportFile::Asset(ContentHash::of("echo {{ tool }}")))
+        );
+        assert_eq!(
+            source.files.len(),
+            2,
+            "skill metadata is not a support file"
+        );
+
+        let installed = load_tool_skill(&store, &names, Path::new("/tool/alpha"), &mut diagnostics)
+            .expect("tool skill");
+        let render = |tool| source.render_with(|template| render_template(template, tool, &bases));
+        assert!(installed.matches(&source, &render(Tool::Claude).expect("rendered")));
+        assert!(!installed.matches(&source, &render(Tool::Codex).expect("rendered")));
+    }
+
+    #[test]
+    fn stops_runaway_templates() {
+        let bases = BaseTemplates::default();
+        let endless =
+            "{% for i in range(100000) %}{% for j in range(100000) %}{% endfor %}{% endfor %}";
+        let error = render_template(endless, Tool::Claude, &bases).expect_err("out of fuel");
+        assert!(error.contains("steps"), "{error}");
+
+        let huge = "{% for i in range(2000) %}{{ \"x\" * 1000 }}{% endfor %}";
+        let error = render_template(huge, Tool::Claude, &bases).expect_err("too large");
+        assert!(error.contains("exceeds"), "{error}");
+
+        let deep = "{% macro f(n) %}{{ f(n + 1) }}{% endmacro %}{{ f(0) }}";
+        assert!(render_template(deep, Tool::Claude, &bases).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_templates_linking_outside_the_source() {
+        use std::{fs, os::unix::fs::symlink};
+
+        use crate::store::FsStore;
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(source.join(TEMPLATES_DIR)).expect("templates dir");
+        fs::create_dir_all(&shared).expect("shared dir");
+        fs::write(source.join(TEMPLATES_DIR).join("local.md"), "local").expect("local");
+        fs::write(shared.join("base.md"), "shared").expect("shared");
+        symlink(
+            shared.join("base.md"),
+            source.join(TEMPLATES_DIR).join("base.md"),
+        )
+        .expect("symlink");
+
+        let bases = BaseTemplates::load(&FsStore, &source, &[]);
+        assert_eq!(bases.names().collect::<Vec<_>>(), ["local.md"]);
+        assert_eq!(
+            bases.refused(),
+            [source.join(TEMPLATES_DIR).join("base.md")]
+        );
+
+        let bases = BaseTemplates::load(&FsStore, &source, &[shared]);
+        assert_eq!(bases.names().collect::<Vec<_>>(), ["base.md", "local.md"]);
+        assert!(bases.refused().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copies_support_symlinks_as_links() {
+        use std::{fs, os::unix::fs::symlink};
+
+        use crate::error::Error;
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let tool = temp.path().join("tool");
+        fs::create_dir_all(source.join("scripts")).expect("scripts dir");
+        fs::write(source.join("scripts/run.sh"), "echo").expect("script");
+        fs::write(temp.path().join("secret"), "secret").expect("secret");
+        symlink("run.sh", source.join("scripts/alias.sh")).expect("inner link");
+        symlink(temp.path().join("secret"), source.join("secret.txt")).expect("outer link");
+
+        let alias = tool.join("scripts/alias.sh");
+        copy_file(&source, "scripts/alias.sh", &alias).expect("inner link copied");
+        assert_eq!(fs::read_link(&alias).expect("link"), Path::new("run.sh"));
+
+        let error = copy_file(&source, "secret.txt", &tool.join("secret.txt"))
+            .expect_err("outer link refused");
+        assert!(matches!(error, Error::UnsupportedSymlink { .. }), "{error}");
+        assert!(!tool.join("secret.txt").exists());
+
+        let elsewhere = temp.path().join("elsewhere");
+        fs::write(&elsewhere, "untouched").expect("elsewhere");
+        symlink(&elsewhere, tool.join("run.sh")).expect("tool link");
+        copy_file(&source, "scripts/run.sh", &tool.join("run.sh")).expect("copied");
+        assert_eq!(
+            fs::read_to_string(&elsewhere).expect("elsewhere"),
+            "untouched"
+        );
+        assert!(!tool.join("run.sh").is_symlink());
+    }
 }
@@ -0,0 +1,485 @@
+//! Persistent record of the skill copies the CLI has installed.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::{
+    error::{Error, Result},
+    history::{self, MAX_SNAPSHOTS, Snapshot, SnapshotQuery},
+};
+
+/// How an installed skill copy was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallOrigin {
+    /// Rendered from a source by `skills push`.
+    Push,
+    /// Rendered from a source by `skills sync`.
+    Sync,
+    /// Extracted from an archive by `skills import`.
+    Import,
+    /// Copied from a tool into a source by `skills pull --prune`.
+    Pull,
+}
+
+impl InstallOrigin {
+    /// Return the command name that writes copies with this origin.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Push => "push",
+            Self::Sync => "sync",
+            Self::Import => "import",
+            Self::Pull => "pull",
+        }
+    }
+}
+
+/// A remembered answer to the prompt to overwrite a modified tool copy of a skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwriteChoice {
+    /// Overwrite modified copies without asking.
+    Always,
+    /// Leave modified copies alone without asking.
+    Never,
+}
+
+/// A skill held at its tool copies, which push and sync leave alone until it is unpinned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pin {
+    /// SHA-256 hex digests of each tool copy's skill file when pinned, keyed by tool id.
+    pub(crate) hashes: BTreeMap<String, String>,
+    /// When the skill was pinned.
+    pub(crate) pinned_at: SystemTime,
+}
+
+/// Content hashes recorded for one installed skill directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallRecord {
+    /// Command that wrote the copy.
+    pub(crate) origin: InstallOrigin,
+    /// SHA-256 hex digests keyed by `/`-separated path relative to the skill directory.
+    pub(crate) files: BTreeMap<String, String>,
+    /// When the copy was written, absent for records from older versions.
+    #[serde(default)]
+    pub(crate) recorded_at: Option<SystemTime>,
+}
+
+/// Install records keyed by skill directory, with snapshots of copies before they were written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Records for every skill directory the CLI has written.
+    pub(crate) installs: BTreeMap<PathBuf, InstallRecord>,
+    /// Snapshots taken before writes, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) snapshots: Vec<Snapshot>,
+    /// Remembered answers to overwrite prompts, keyed by skill name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    overwrite: BTreeMap<String, OverwriteChoice>,
+    /// Pinned skills, keyed by skill name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) pins: BTreeMap<String, Pin>,
+    /// Contents of snapshot files not yet stored, keyed by hash.
+    #[serde(skip)]
+    pending: BTreeMap<String, Vec<u8>>,
+    /// Whether old snapshots were dropped since loading.
+    #[serde(skip)]
+    trimmed: bool,
+}
+
+impl State {
+    /// Load state from a file, returning empty state when the file does not exist.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(Error::StateRead {
+                    path: path.to_path_buf(),
+                    source,
+                });
+            }
+        };
+        serde_json::from_str(&contents).map_err(|error| Error::StateParse {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    /// Write state to a file, replacing it atomically.
+    ///
+    /// Snapshot contents are stored before the state that refers to them, and contents no
+    /// snapshot refers to any more are removed after.
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        let write_error = |source| Error::StateWrite {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(|error| Error::StateParse {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })?;
+        history::write_blobs(path, &self.pending)?;
+        let staging = path.with_extension("json.partial");
+        fs::write(&staging, contents).map_err(write_error)?;
+        fs::rename(&staging, path).map_err(write_error)?;
+        if self.trimmed {
+            history::prune_blobs(path, &self.snapshots)?;
+        }
+        Ok(())
+    }
+
+    /// Snapshot the current contents of a skill directory that is about to be written.
+    ///
+    /// A directory that does not exist yet is recorded with no files.
+    pub(crate) fn snapshot(&mut self, dir: &Path, origin: InstallOrigin) -> Result<()> {
+        let mut files = BTreeMap::new();
+        if dir.is_dir() {
+            for (relative, contents) in read_files(dir)? {
+                let hash = hash_bytes(&contents);
+                self.pending.entry(hash.clone()).or_insert(contents);
+                files.insert(relative, hash);
+            }
+        }
+        let id = self.snapshots.last().map_or(1, |last| last.id + 1);
+        self.snapshots.push(Snapshot {
+            id,
+            origin,
+            dir: dir.to_path_buf(),
+            taken_at: SystemTime::now(),
+            files,
+        });
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            let excess = self.snapshots.len() - MAX_SNAPSHOTS;
+            self.snapshots.drain(..excess);
+            self.trimmed = true;
+        }
+        Ok(())
+    }
+
+    /// Return the snapshot of a skill that matches a query, the earliest first.
+    pub(crate) fn find_snapshot(&self, skill: &str, query: SnapshotQuery) -> Option<&Snapshot> {
+        self.snapshots
+            .iter()
+            .find(|snapshot| snapshot.skill() == Some(skill) && query.admits(snapshot))
+    }
+
+    /// Return the snapshots of a skill, oldest first.
+    pub(crate) fn snapshots_of<'a>(&'a self, skill: &'a str) -> impl Iterator<Item = &'a Snapshot> {
+        self.snapshots
+            .iter()
+            .filter(move |snapshot| snapshot.skill() == Some(skill))
+    }
+
+    /// Drop snapshots taken before a time, returning how many were dropped.
+    ///
+    /// Contents only they referred to are removed when the state is next saved.
+    pub(crate) fn drop_snapshots_before(&mut self, cutoff: SystemTime) -> usize {
+        let before = self.snapshots.len();
+        self.snapshots
+            .retain(|snapshot| snapshot.taken_at >= cutoff);
+        let dropped = before - self.snapshots.len();
+        self.trimmed |= dropped > 0;
+        dropped
+    }
+
+    /// Read one file of a snapshot, returning `None` when the snapshot has no such file.
+    pub(crate) fn snapshot_file(
+        &self,
+        state_path: &Path,
+        snapshot: &Snapshot,
+        relative: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(hash) = snapshot.files.get(relative) else {
+            return Ok(None);
+        };
+        match self.pending.get(hash) {
+            Some(contents) => Ok(Some(contents.clone())),
+            None => history::read_blob(state_path, hash).map(Some),
+        }
+    }
+
+    /// Record the current contents of a skill directory that was just written.
+    pub(crate) fn record(&mut self, dir: &Path, origin: InstallOrigin) -> Result<()> {
+        let files = hash_dir(dir)?;
+        self.installs.insert(
+            dir.to_path_buf(),
+            InstallRecord {
+                origin,
+                files,
+                recorded_at: Some(SystemTime::now()),
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the most recent time any of the given skill directories was written.
+    pub(crate) fn last_recorded<'a>(
+        &self,
+        dirs: impl IntoIterator<Item = &'a Path>,
+    ) -> Option<SystemTime> {
+        dirs.into_iter()
+            .filter_map(|dir| self.installs.get(dir)?.recorded_at)
+            .max()
+    }
+
+    /// Move the record for a renamed skill directory.
+    pub(crate) fn rename(&mut self, from: &Path, to: &Path) {
+        self.installs.remove(to);
+        if let Some(record) = self.installs.remove(from) {
+            self.installs.insert(to.to_path_buf(), record);
+        }
+    }
+
+    /// Return the remembered answer to overwrite prompts for a skill.
+    pub(crate) fn overwrite_choice(&self, skill: &str) -> Option<OverwriteChoice> {
+        self.overwrite.get(skill).copied()
+    }
+
+    /// Remember an answer to overwrite prompts for a skill, or forget it with `None`.
+    pub(crate) fn set_overwrite_choice(&mut self, skill: &str, choice: Option<OverwriteChoice>) {
+        match choice {
+            Some(choice) => {
+                self.overwrite.insert(skill.to_string(), choice);
+            }
+            None => {
+                self.overwrite.remove(skill);
+            }
+        }
+    }
+
+    /// Return whether a skill is pinned.
+    pub(crate) fn is_pinned(&self, skill: &str) -> bool {
+        self.pins.contains_key(skill)
+    }
+
+    /// Move the remembered overwrite answer and pin of a renamed skill.
+    pub(crate) fn rename_skill(&mut self, from: &str, to: &str) {
+        if let Some(choice) = self.overwrite.remove(from) {
+            self.overwrite.insert(to.to_string(), choice);
+        }
+        if let Some(pin) = self.pins.remove(from) {
+            self.pins.insert(to.to_string(), pin);
+        }
+    }
+
+    /// Drop the record for a removed skill directory.
+    pub(crate) fn forget(&mut self, dir: &Path) {
+        self.installs.remove(dir);
+    }
+}
+
+/// Hash every file under a skill directory.
+pub fn hash_dir(dir: &Path) -> Result<BTreeMap<String, String>> {
+    Ok(read_files(dir)?
+        .into_iter()
+        .map(|(relative, contents)| (relative, hash_bytes(&contents)))
+        .collect())
+}
+
+/// Read every file under a skill directory, keyed by `/`-separated relative path.
+fn read_files(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.map_err(|error| Error::SkillRead {
+            path: error.path().unwrap_or(dir).to_path_buf(),
+            source: error.into(),
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let bytes = fs::read(entry.path()).map_err(|source| Error::SkillRead {
+            path: entry.path().to_path_buf(),
+            source,
+        })?;
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.insert(relative, bytes);
+    }
+    Ok(files)
+}
+
+/// Return the SHA-256 hex digest of a byte string.
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            write!(hex, "{byte:02x}").ok();
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use tempfile::tempdir;
+
+    use super::{InstallOrigin, State, hash_dir};
+    use crate::history::{MAX_SNAPSHOTS, SnapshotQuery, blob_dir};
+
+    #[test]
+    fn hashes_nested_files_with_relative_paths() {
+        let dir = tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("scripts")).expect("mkdir");
+        fs::write(dir.path().join("SKILL.md"), "abc").expect("write");
+        fs::write(dir.path().join("scripts").join("run.sh"), "").expect("write");
+
+        let files = hash_dir(dir.path()).expect("hash");
+        assert_eq!(
+            files["SKILL.md"],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(files.contains_key("scripts/run.sh"));
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let dir = tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("pdf");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+        fs::write(skill_dir.join("SKILL.md"), "contents").expect("write");
+        let path = dir.path().join("state").join("state.json");
+
+        let mut state = State::load(&path).expect("missing state loads");
+        state
+            .record(&skill_dir, InstallOrigin::Push)
+            .expect("record");
+        state.save(&path).expect("save");
+
+        let loaded = State::load(&path).expect("load");
+        assert_eq!(loaded.installs, state.installs);
+        assert_eq!(loaded.installs[&skill_dir].origin, InstallOrigin::Push);
+    }
+
+    #[test]
+    fn reports_last_recorded_time() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("state.json");
+        fs::write(
+            &path,
+            r#"{"installs": {"/claude/pdf": {"origin": "push", "files": {}}}}"#,
+        )
+        .expect("write");
+        let mut state = State::load(&path).expect("records without timestamps load");
+        let legacy = Path::new("/claude/pdf");
+        assert_eq!(state.last_recorded([legacy]), None);
+
+        let skill_dir = dir.path().join("pdf");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+        state
+            .record(&skill_dir, InstallOrigin::Sync)
+            .expect("record");
+        assert!(state.last_recorded([legacy, skill_dir.as_path()]).is_some());
+    }
+
+    #[test]
+    fn snapshots_copies_before_writes() {
+        let dir = tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("claude").join("pdf");
+        let path = dir.path().join("state").join("state.json");
+
+        let mut state = State::default();
+        state
+            .snapshot(&skill_dir, InstallOrigin::Push)
+            .expect("snapshot missing copy");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+        fs::write(skill_dir.join("SKILL.md"), "first").expect("write");
+        state
+            .snapshot(&skill_dir, InstallOrigin::Sync)
+            .expect("snapshot");
+        fs::write(skill_dir.join("SKILL.md"), "second").expect("write");
+        state.save(&path).expect("save");
+
+        let loaded = State::load(&path).expect("load");
+        assert_eq!(loaded.snapshots, state.snapshots);
+        let ids: Vec<u64> = loaded.snapshots_of("pdf").map(|s| s.id).collect();
+        assert_eq!(ids, [1, 2]);
+        assert!(
+            loaded
+                .find_snapshot("other", SnapshotQuery::Id(1))
+                .is_none()
+        );
+
+        let first = loaded
+            .find_snapshot("pdf", SnapshotQuery::Id(1))
+            .expect("first");
+        assert!(first.files.is_empty());
+        let second = loaded
+            .find_snapshot("pdf", SnapshotQuery::Time(first.taken_at))
+            .expect("earliest at or after");
+        assert_eq!(second.id, 1);
+        let second = loaded
+            .find_snapshot("pdf", SnapshotQuery::Id(2))
+            .expect("second");
+        let contents = loaded
+            .snapshot_file(&path, second, "SKILL.md")
+            .expect("read");
+        assert_eq!(contents.as_deref(), Some(&b"first"[..]));
+    }
+
+    #[test]
+    fn prunes_contents_of_dropped_snapshots() {
+        let dir = tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("pdf");
+        let path = dir.path().join("state.json");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+
+        let mut state = State::default();
+        fs::write(skill_dir.join("SKILL.md"), "oldest").expect("write");
+        state
+            .snapshot(&skill_dir, InstallOrigin::Push)
+            .expect("snapshot");
+        state.save(&path).expect("save");
+        let stored = || fs::read_dir(blob_dir(&path)).expect("blobs").count();
+        assert_eq!(stored(), 1);
+
+        let mut state = State::load(&path).expect("load");
+        fs::write(skill_dir.join("SKILL.md"), "newer").expect("write");
+        for _ in 0..MAX_SNAPSHOTS {
+            state
+                .snapshot(&skill_dir, InstallOrigin::Push)
+                .expect("snapshot");
+        }
+        state.save(&path).expect("save");
+        assert_eq!(state.snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(state.snapshots[0].id, 2);
+        assert_eq!(stored(), 1);
+    }
+
+    #[test]
+    fn renames_and_forgets_records() {
+        let dir = tempdir().expect("tempdir");
+        let old = dir.path().join("old");
+        let new = dir.path().join("new");
+        fs::create_dir_all(&old).expect("mkdir");
+
+        let mut state = State::default();
+        state.record(&old, InstallOrigin::Import).expect("record");
+        state.rename(&old, &new);
+        assert!(state.installs.contains_key(&new));
+        assert!(!state.installs.contains_key(&old));
+
+        state.forget(&new);
+        assert!(state.installs.is_empty());
+    }
+}
@@ -1,11 +1,24 @@
 //! Status computation for skills across tools.
 
-use std::collections::BTreeSet;
-
-use crate::{catalog::Catalog, diagnostics::Diagnostics, skill::render_template, tool::Tool};
+use std::{
+    collections::BTreeSet,
+    io::{self, Read},
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    catalog::Catalog,
+    diagnostics::Diagnostics,
+    palette::Role,
+    state::{InstallOrigin, State},
+    tool::Tool,
+};
 
 /// Sync status for a skill in a tool directory.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SyncStatus {
     /// Tool copy matches the rendered source template.
     Synced,
@@ -17,6 +30,40 @@ pub enum SyncStatus {
     Orphan,
 }
 
+impl SyncStatus {
+    /// Every status, in the order legends list them.
+    pub(crate) const ALL: [Self; 4] = [Self::Synced, Self::Modified, Self::Missing, Self::Orphan];
+
+    /// Return the user-facing label for this status.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Synced => "synced",
+            Self::Modified => "modified",
+            Self::Missing => "missing",
+            Self::Orphan => "orphan",
+        }
+    }
+
+    /// Return a one-line explanation of this status.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::Synced => "tool copy matches the source rendered for that tool",
+            Self::Modified => "tool copy differs from the rendered source",
+            Self::Missing => "source exists but the tool has no copy",
+            Self::Orphan => "tool copy exists without a source",
+        }
+    }
+
+    /// Return the palette role used to display this status.
+    pub(crate) fn role(self) -> Role {
+        match self {
+            Self::Synced => Role::StatusSynced,
+            Self::Modified => Role::StatusModified,
+            Self::Missing | Self::Orphan => Role::StatusError,
+        }
+    }
+}
+
 /// Status for a specific tool and skill.
 #[derive(Debug, Clone, Copy)]
 pub struct ToolStatus {
@@ -35,6 +82,116 @@ pub struct SkillEntry {
     pub(crate) tool_statuses: Vec<ToolStatus>,
 }
 
+impl SkillEntry {
+    /// Return the status that best describes the skill as a whole.
+    ///
+    /// A skill without a source is an orphan; otherwise any modified tool copy makes it modified,
+    /// then any missing one makes it missing.
+    pub(crate) fn overall(&self) -> SyncStatus {
+        let has = |status| self.tool_statuses.iter().any(|tool| tool.status == status);
+        if has(SyncStatus::Orphan) {
+            SyncStatus::Orphan
+        } else if has(SyncStatus::Modified) {
+            SyncStatus::Modified
+        } else if has(SyncStatus::Missing) {
+            SyncStatus::Missing
+        } else {
+            SyncStatus::Synced
+        }
+    }
+}
+
+/// Why an installed skill has no source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanKind {
+    /// The skill was made in the tool directory, so it can be adopted with `skills pull`.
+    Created,
+    /// A source skill was pushed here and has since been removed, so the copy can be pruned with
+    /// `skills unload`.
+    Removed,
+}
+
+impl OrphanKind {
+    /// Classify an orphan by the install records of its tool copies.
+    ///
+    /// A copy the CLI rendered from a source with `push` or `sync` outlived that source; any other
+    /// copy, including one imported straight into a tool, was created there.
+    pub(crate) fn of(catalog: &Catalog, state: &State, name: &str) -> Self {
+        let rendered = Tool::all().into_iter().any(|tool| {
+            catalog
+                .tools
+                .get(&tool)
+                .and_then(|skills| skills.get(name))
+                .and_then(|skill| skill.skill_path.parent())
+                .and_then(|dir| state.installs.get(dir))
+                .is_some_and(|record| {
+                    matches!(record.origin, InstallOrigin::Push | InstallOrigin::Sync)
+                })
+        });
+        if rendered {
+            Self::Removed
+        } else {
+            Self::Created
+        }
+    }
+
+    /// Return a short description of how the orphan came about.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Created => "created in the tool",
+            Self::Removed => "source removed",
+        }
+    }
+
+    /// Return the command that deals with an orphan of this kind.
+    pub(crate) fn suggestion(self, name: &str) -> String {
+        match self {
+            Self::Created => format!("adopt with `skills pull {name}`"),
+            Self::Removed => format!("prune with `skills unload {name}`"),
+        }
+    }
+}
+
+/// Number of skills in each overall status, plus project-local skills.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StatusCounts {
+    /// Skills whose every tool copy is synced.
+    pub(crate) synced: usize,
+    /// Skills with at least one modified tool copy.
+    pub(crate) modified: usize,
+    /// Skills missing from at least one tool, with none modified.
+    pub(crate) missing: usize,
+    /// Installed skills without a source.
+    pub(crate) orphans: usize,
+    /// Project-local skills, counted once per tool directory holding them.
+    pub(crate) local: usize,
+}
+
+impl StatusCounts {
+    /// Tally entries by [`SkillEntry::overall`] and count the catalog's local skills.
+    pub(crate) fn tally(entries: &[SkillEntry], catalog: &Catalog) -> Self {
+        let mut counts = Self {
+            local: catalog.local.values().map(|skills| skills.len()).sum(),
+            ..Self::default()
+        };
+        for entry in entries {
+            match entry.overall() {
+                SyncStatus::Synced => counts.synced += 1,
+                SyncStatus::Modified => counts.modified += 1,
+                SyncStatus::Missing => counts.missing += 1,
+                SyncStatus::Orphan => counts.orphans += 1,
+            }
+        }
+        counts
+    }
+
+    /// Return the number of source and orphan skills.
+    pub(crate) fn skills(&self) -> usize {
+        self.synced + self.modified + self.missing + self.orphans
+    }
+}
+
 /// Compute list entries with sync status across tools.
 pub fn build_entries(catalog: &Catalog, diagnostics: &mut Diagnostics) -> Vec<SkillEntry> {
     let mut names = collect_names(catalog);
@@ -49,7 +206,7 @@ pub fn build_entries(catalog: &Catalog, diagnostics: &mut Diagnostics) -> Vec<Sk
             let tool_skill = tool_map.and_then(|skills| skills.get(&name));
             let status = match (source, tool_skill) {
                 (Some(source), Some(tool_skill)) => {
-                    let rendered = match render_template(&source.contents, tool) {
+                    let rendered = match catalog.render_skill(source, tool) {
                         Ok(rendered) => rendered,
                         Err(error) => {
                             diagnostics.warn_skipped(&source.skill_path, error);
@@ -57,9 +214,7 @@ pub fn build_entries(catalog: &Catalog, diagnostics: &mut Diagnostics) -> Vec<Sk
                             break;
                         }
                     };
-                    if normalize_line_endings(&rendered)
-                        == normalize_line_endings(&tool_skill.contents)
-                    {
+                    if tool_skill.matches(source, &rendered) {
                         SyncStatus::Synced
                     } else {
                         SyncStatus::Modified
@@ -90,7 +245,8 @@ pub fn build_entries(catalog: &Catalog, diagnostics: &mut Diagnostics) -> Vec<Sk
     entries.into_iter().map(|(_, entry)| entry).collect()
 }
 
-/// Normalize line endings and trailing newline for content comparisons.
+/// Normalize line endings and trailing newline the way [`ContentHash`] does before hashing.
+#[cfg(test)]
 pub fn normalize_line_endings(contents: &str) -> String {
     let mut normalized = contents.replace("\r\n", "\n").replace('\r', "\n");
     if normalized.ends_with('\n') {
@@ -99,6 +255,101 @@ pub fn normalize_line_endings(contents: &str) -> String {
     normalized
 }
 
+/// Bytes of normalized content buffered before they are fed to the digest.
+const HASH_CHUNK: usize = 8 * 1024;
+
+/// SHA-256 digest of content after normalizing line endings, used to detect modified copies
+/// without holding their contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentHash([u8; 32]);
+
+impl ContentHash {
+    /// Hash content, normalizing line endings as it streams through the digest.
+    pub fn of(contents: &str) -> Self {
+        Self::of_bytes(contents.as_bytes())
+    }
+
+    /// Hash raw file contents, which need not be text, normalizing line endings the same way.
+    pub fn of_bytes(contents: &[u8]) -> Self {
+        let mut hasher = NormalizedHasher::default();
+        hasher.update(contents);
+        hasher.finish()
+    }
+
+    /// Hash contents read from `reader` a chunk at a time, so large files are never held whole.
+    pub fn of_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut hasher = NormalizedHasher::default();
+        let mut chunk = [0; HASH_CHUNK];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => return Ok(hasher.finish()),
+                Ok(read) => hasher.update(&chunk[..read]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// Streaming digest that normalizes line endings without building a normalized copy.
+#[derive(Default)]
+struct NormalizedHasher {
+    /// Digest of the normalized bytes flushed so far.
+    digest: Sha256,
+    /// Normalized bytes not yet fed to the digest.
+    buffer: Vec<u8>,
+    /// Whether the last byte seen was a carriage return.
+    pending_cr: bool,
+    /// Whether a newline is held back in case it ends the content.
+    held_newline: bool,
+}
+
+impl NormalizedHasher {
+    /// Feed raw bytes, translating `\r\n` and lone `\r` to `\n`.
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.pending_cr {
+                self.pending_cr = false;
+                self.push(b'\n');
+                if byte == b'\n' {
+                    continue;
+                }
+            }
+            if byte == b'\r' {
+                self.pending_cr = true;
+            } else {
+                self.push(byte);
+            }
+        }
+    }
+
+    /// Append a normalized byte, holding back a newline until more content follows it.
+    fn push(&mut self, byte: u8) {
+        if self.held_newline {
+            self.held_newline = false;
+            self.buffer.push(b'\n');
+        }
+        if byte == b'\n' {
+            self.held_newline = true;
+        } else {
+            self.buffer.push(byte);
+        }
+        if self.buffer.len() >= HASH_CHUNK {
+            self.digest.update(&self.buffer);
+            self.buffer.clear();
+        }
+    }
+
+    /// Finish the digest, dropping a single trailing newline.
+    fn finish(mut self) -> ContentHash {
+        if self.pending_cr {
+            self.push(b'\n');
+        }
+        self.digest.update(&self.buffer);
+        ContentHash(self.digest.finalize().into())
+    }
+}
+
 /// Sort skill entries case-insensitively, stable within ties.
 fn sort_entries(entries: &mut [(usize, SkillEntry)]) {
     entries.sort_by(|(left_index, left), (right_index, right)| {
@@ -143,7 +394,7 @@ fn collect_names(catalog: &Catalog) -> Vec<String> {
 mod tests {
     use crate::{
         diagnostics::Diagnostics,
-        status::{SyncStatus, build_entries, normalize_line_endings},
+        status::{ContentHash, StatusCounts, SyncStatus, build_entries, normalize_line_endings},
         testutil::{TestFixture, simple_skill, skill_content},
         tool::Tool,
     };
@@ -160,6 +411,52 @@ mod tests {
         assert_eq!(normalized, "a\n");
     }
 
+    #[test]
+    fn hashes_content_with_normalized_line_endings() {
+        assert_eq!(ContentHash::of("a\r\nb\r"), ContentHash::of("a\nb"));
+        assert_eq!(ContentHash::of("a\n"), ContentHash::of("a"));
+        assert_ne!(ContentHash::of("a\n\n"), ContentHash::of("a"));
+        assert_eq!(ContentHash::of("a\r\n\r\n"), ContentHash::of("a\n\n"));
+
+        let large = "line\r\n".repeat(5000);
+        assert_eq!(
+            ContentHash::of(&large),
+            ContentHash::of(&normalize_line_endings(&large))
+        );
+        let streamed = ContentHash::of_reader(large.as_bytes()).expect("hash");
+        assert_eq!(streamed, ContentHash::of(&large));
+    }
+
+    #[test]
+    fn tallies_skills_by_overall_status() {
+        let mut fixture = TestFixture::new()
+            .with_source_skill("synced", &simple_skill("synced"))
+            .with_source_skill("changed", &simple_skill("changed"))
+            .with_tool_skill(Tool::Codex, "changed", &skill_content("changed", "d", "x"))
+            .with_source_skill("absent", &simple_skill("absent"))
+            .with_tool_skill(Tool::Claude, "stray", &simple_skill("stray"))
+            .with_local_skill(Tool::Codex, "here", &simple_skill("here"));
+        for tool in Tool::all() {
+            fixture = fixture.with_tool_skill(tool, "synced", &simple_skill("synced"));
+        }
+
+        let catalog = fixture.catalog();
+        let entries = build_entries(&catalog, &mut Diagnostics::default());
+        let counts = StatusCounts::tally(&entries, &catalog);
+
+        assert_eq!(
+            counts,
+            StatusCounts {
+                synced: 1,
+                modified: 1,
+                missing: 1,
+                orphans: 1,
+                local: 1,
+            }
+        );
+        assert_eq!(counts.skills(), 4);
+    }
+
     #[test]
     fn reports_modified_status() {
         let source_content = skill_content("sample", "desc", "");
@@ -170,7 +467,7 @@ mod tests {
             .with_tool_skill(Tool::Codex, "sample", &tool_content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let entries = build_entries(&catalog, &mut diagnostics);
 
         let status = entries
@@ -187,6 +484,36 @@ mod tests {
         assert_eq!(status, Some(SyncStatus::Modified));
     }
 
+    #[test]
+    fn compares_gemini_copies_with_gemini_renderings() {
+        let template = "---\nname: sample\ndescription: desc\n---\n{{ tool }}\n";
+        let fixture = TestFixture::new()
+            .with_source_skill("sample", template)
+            .with_tool_skill(
+                Tool::Gemini,
+                "sample",
+                &skill_content("sample", "desc", "gemini"),
+            )
+            .with_tool_skill(
+                Tool::Claude,
+                "sample",
+                &skill_content("sample", "desc", "gemini"),
+            );
+
+        let catalog = fixture.catalog();
+        let entries = build_entries(&catalog, &mut Diagnostics::default());
+        let status = |tool| {
+            entries[0]
+                .tool_statuses
+                .iter()
+                .find(|status| status.tool == tool)
+                .map(|status| status.status)
+        };
+
+        assert_eq!(status(Tool::Gemini), Some(SyncStatus::Synced));
+        assert_eq!(status(Tool::Claude), Some(SyncStatus::Modified));
+    }
+
     #[test]
     fn reports_synced_status() {
         let content = simple_skill("synced");
@@ -196,7 +523,7 @@ mod tests {
             .with_tool_skill(Tool::Claude, "synced", &content);
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let entries = build_entries(&catalog, &mut diagnostics);
 
         let status = entries
@@ -215,11 +542,10 @@ mod tests {
 
     #[test]
     fn reports_missing_status() {
-        let fixture = TestFixture::new()
-            .with_source_skill("missing", &simple_skill("missing"));
+        let fixture = TestFixture::new().with_source_skill("missing", &simple_skill("missing"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let entries = build_entries(&catalog, &mut diagnostics);
 
         let status = entries
@@ -238,11 +564,11 @@ mod tests {
 
     #[test]
     fn reports_orphan_status() {
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Codex, "orphan", &simple_skill("orphan"));
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Codex, "orphan", &simple_skill("orphan"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let entries = build_entries(&catalog, &mut diagnostics);
 
         let status = entries
@@ -266,7 +592,7 @@ mod tests {
             .with_source_skill("Alpha", &simple_skill("Alpha"));
 
         let catalog = fixture.catalog();
-        let mut diagnostics = Diagnostics::new(false);
+        let mut diagnostics = Diagnostics::default();
         let entries = build_entries(&catalog, &mut diagnostics);
 
         let names = entries
@@ -0,0 +1,165 @@
+//! Storage backends that skill catalogs are read from.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use walkdir::WalkDir;
+
+use crate::{
+    status::ContentHash,
+    units::{format_count, format_size},
+};
+
+/// A file read from a skill store.
+#[derive(Debug, Clone)]
+pub struct StoredFile {
+    /// File contents.
+    pub(crate) contents: String,
+    /// Modified time for the file.
+    pub(crate) modified: SystemTime,
+}
+
+/// Total size and file count of a skill directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirStats {
+    /// Combined size of all files in bytes.
+    pub(crate) size: u64,
+    /// Number of files, including those in subdirectories.
+    pub(crate) files: usize,
+}
+
+impl DirStats {
+    /// Describe the size and file count, e.g. `12.5 KB in 3 files`.
+    pub(crate) fn summary(self) -> String {
+        format!(
+            "{} in {}",
+            format_size(self.size),
+            format_count(self.files, "file")
+        )
+    }
+
+    /// Whether the total size is larger than a budget in bytes.
+    pub(crate) fn exceeds(self, budget: u64) -> bool {
+        self.size > budget
+    }
+}
+
+/// Read access to the directories and files that make up a skill catalog.
+pub trait SkillStore {
+    /// List the entries directly under a directory, sorted by file name.
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Read a file, returning `None` when no regular file exists at the path.
+    fn read(&self, path: &Path) -> io::Result<Option<StoredFile>>;
+
+    /// Read a file's raw bytes, returning `None` when no regular file exists at the path.
+    fn read_bytes(&self, path: &Path) -> io::Result<Option<Vec<u8>>>;
+
+    /// Hash a file's contents as they stream in, returning `None` when no regular file exists at
+    /// the path.
+    fn hash(&self, path: &Path) -> io::Result<Option<ContentHash>>;
+
+    /// Measure the files under a directory, recursively.
+    fn dir_stats(&self, dir: &Path) -> io::Result<DirStats>;
+
+    /// Resolve the links in a path, returning where its contents are actually read from.
+    fn real_path(&self, path: &Path) -> io::Result<PathBuf> {
+        Ok(path.to_path_buf())
+    }
+}
+
+/// Skill store backed by the local filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsStore;
+
+impl SkillStore for FsStore {
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        Ok(entries)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Option<StoredFile>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        Ok(Some(StoredFile { contents, modified }))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Option<Vec<u8>>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        match fs::read(path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn hash(&self, path: &Path) -> io::Result<Option<ContentHash>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        match fs::File::open(path) {
+            Ok(file) => ContentHash::of_reader(file).map(Some),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn dir_stats(&self, dir: &Path) -> io::Result<DirStats> {
+        let mut stats = DirStats::default();
+        for entry in WalkDir::new(dir).min_depth(1) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                stats.size += entry.metadata()?.len();
+                stats.files += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    fn real_path(&self, path: &Path) -> io::Result<PathBuf> {
+        dunce::canonicalize(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{DirStats, FsStore, SkillStore};
+
+    #[test]
+    fn measures_nested_files() {
+        let dir = tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("alpha");
+        fs::create_dir_all(skill_dir.join("scripts")).expect("mkdir");
+        fs::write(skill_dir.join("SKILL.md"), "12345").expect("write");
+        fs::write(skill_dir.join("scripts").join("run.sh"), "123").expect("write");
+
+        let stats = FsStore.dir_stats(&skill_dir).expect("stats");
+        assert_eq!(stats, DirStats { size: 8, files: 2 });
+        assert_eq!(stats.summary(), "8 B in 2 files");
+        assert!(stats.exceeds(7));
+        assert!(!stats.exceeds(8));
+    }
+}
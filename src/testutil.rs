@@ -6,18 +6,23 @@
 #![allow(dead_code)]
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs,
+    io::{self, ErrorKind},
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use tempfile::TempDir;
 
 use crate::{
-    catalog::Catalog,
+    catalog::{Catalog, CatalogRoots},
     config::Config,
+    context::Context,
     diagnostics::Diagnostics,
-    skill::{LocalSkill, SkillTemplate, ToolSkill, SKILL_FILE_NAME},
+    skill::SKILL_FILE_NAME,
+    status::ContentHash,
+    store::{DirStats, FsStore, SkillStore, StoredFile},
     tool::Tool,
 };
 
@@ -49,6 +54,8 @@ pub struct TestFixture {
     local_dirs: HashMap<Tool, PathBuf>,
     /// Working directory (simulates project root).
     work_dir: PathBuf,
+    /// Config file listing the source directory.
+    config_path: PathBuf,
 }
 
 impl TestFixture {
@@ -78,12 +85,17 @@ impl TestFixture {
             local_dirs.insert(tool, local_dir);
         }
 
+        let config_path = root_path.join("skills.toml");
+        let config = format!("sources = ['{}']\n", source_dir.display());
+        fs::write(&config_path, config).expect("write config");
+
         Self {
             _root: root,
             source_dir,
             tool_dirs,
             local_dirs,
             work_dir,
+            config_path,
         }
     }
 
@@ -141,91 +153,42 @@ impl TestFixture {
         Config::new(vec![self.source_dir.clone()])
     }
 
+    /// Build a command context rooted in the test directories.
+    pub fn context(&self) -> Context {
+        Context::new(
+            self.config_path.clone(),
+            self.tool_dirs.clone(),
+            self.work_dir.clone(),
+        )
+    }
+
     /// Load a Catalog from the test directories.
     ///
-    /// This directly constructs the catalog from the test directories,
-    /// bypassing the normal loading which looks at real home directories.
+    /// This loads the catalog from the test directories, bypassing root
+    /// discovery which looks at real home directories.
     pub fn catalog(&self) -> Catalog {
-        self.catalog_with_diagnostics(&mut Diagnostics::new(false))
+        self.catalog_with_diagnostics(&mut Diagnostics::default())
     }
 
     /// Load a Catalog with custom diagnostics.
     pub fn catalog_with_diagnostics(&self, diagnostics: &mut Diagnostics) -> Catalog {
-        let sources = self.load_sources(diagnostics);
-        let tools = self.load_tools(diagnostics);
-        let local = self.load_local(diagnostics);
-        Catalog::new(sources, tools, local)
-    }
-
-    /// Load source skills from the test source directory.
-    fn load_sources(&self, diagnostics: &mut Diagnostics) -> HashMap<String, SkillTemplate> {
-        let mut skills = HashMap::new();
-        let entries = match fs::read_dir(&self.source_dir) {
-            Ok(entries) => entries,
-            Err(_) => return skills,
-        };
-
-        for entry in entries.filter_map(|e| e.ok()) {
-            let skill_dir = entry.path();
-            if let Some(skill) =
-                crate::skill::load_source_skill(&self.source_dir, &skill_dir, diagnostics)
-            {
-                skills.insert(skill.name.clone(), skill);
-            }
-        }
-        skills
-    }
-
-    /// Load tool skills from the test tool directories.
-    fn load_tools(&self, diagnostics: &mut Diagnostics) -> HashMap<Tool, HashMap<String, ToolSkill>> {
-        let mut tools = HashMap::new();
-        for tool in Tool::all() {
-            let tool_dir = self.tool_dirs.get(&tool).expect("tool dir");
-            let mut skills = HashMap::new();
-
-            let entries = match fs::read_dir(tool_dir) {
-                Ok(entries) => entries,
-                Err(_) => {
-                    tools.insert(tool, skills);
-                    continue;
-                }
-            };
-
-            for entry in entries.filter_map(|e| e.ok()) {
-                let skill_dir = entry.path();
-                if let Some(skill) = crate::skill::load_tool_skill(&skill_dir, diagnostics) {
-                    skills.insert(skill.name.clone(), skill);
-                }
-            }
-            tools.insert(tool, skills);
-        }
-        tools
+        Catalog::load_from(&FsStore, &self.roots(), diagnostics)
     }
 
-    /// Load local skills from the test local directories.
-    fn load_local(&self, diagnostics: &mut Diagnostics) -> HashMap<Tool, HashMap<String, LocalSkill>> {
-        let mut local = HashMap::new();
-        for tool in Tool::all() {
-            let local_dir = self.local_dirs.get(&tool).expect("local dir");
-            let mut skills = HashMap::new();
-
-            let entries = match fs::read_dir(local_dir) {
-                Ok(entries) => entries,
-                Err(_) => {
-                    local.insert(tool, skills);
-                    continue;
-                }
-            };
-
-            for entry in entries.filter_map(|e| e.ok()) {
-                let skill_dir = entry.path();
-                if let Some(skill) = crate::skill::load_local_skill(&skill_dir, tool, diagnostics) {
-                    skills.insert(skill.name.clone(), skill);
-                }
-            }
-            local.insert(tool, skills);
+    /// Catalog roots pointing at the test directories.
+    pub fn roots(&self) -> CatalogRoots {
+        CatalogRoots {
+            sources: vec![self.source_dir.clone()],
+            tools: Tool::all()
+                .into_iter()
+                .map(|tool| (tool, self.tool_dir(tool).to_path_buf()))
+                .collect(),
+            local: Tool::all()
+                .into_iter()
+                .map(|tool| (tool, self.local_dir(tool).to_path_buf()))
+                .collect(),
+            ..CatalogRoots::default()
         }
-        local
     }
 
     /// Read the contents of a source skill.
@@ -270,6 +233,69 @@ impl TestFixture {
     }
 }
 
+/// In-memory skill store for tests that do not need real files.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    /// File contents keyed by path.
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemoryStore {
+    /// Add a file to the store.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, contents: &str) -> Self {
+        self.files.insert(path.into(), contents.to_string());
+        self
+    }
+}
+
+impl SkillStore for MemoryStore {
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let entries = self
+            .files
+            .keys()
+            .filter_map(|path| path.strip_prefix(dir).ok())
+            .filter_map(|relative| relative.components().next())
+            .map(|component| dir.join(component))
+            .collect::<BTreeSet<_>>();
+        if entries.is_empty() {
+            return Err(io::Error::from(ErrorKind::NotFound));
+        }
+        Ok(entries.into_iter().collect())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Option<StoredFile>> {
+        Ok(self.files.get(path).map(|contents| StoredFile {
+            contents: contents.clone(),
+            modified: SystemTime::UNIX_EPOCH,
+        }))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Option<Vec<u8>>> {
+        Ok(self
+            .files
+            .get(path)
+            .map(|contents| contents.as_bytes().to_vec()))
+    }
+
+    fn hash(&self, path: &Path) -> io::Result<Option<ContentHash>> {
+        Ok(self
+            .files
+            .get(path)
+            .map(|contents| ContentHash::of(contents)))
+    }
+
+    fn dir_stats(&self, dir: &Path) -> io::Result<DirStats> {
+        Ok(self
+            .files
+            .iter()
+            .filter(|(path, _)| path.starts_with(dir))
+            .fold(DirStats::default(), |stats, (_, contents)| DirStats {
+                size: stats.size + contents.len() as u64,
+                files: stats.files + 1,
+            }))
+    }
+}
+
 impl Default for TestFixture {
     fn default() -> Self {
         Self::new()
@@ -294,8 +320,7 @@ mod tests {
 
     #[test]
     fn adds_source_skill() {
-        let fixture = TestFixture::new()
-            .with_source_skill("my-skill", &simple_skill("my-skill"));
+        let fixture = TestFixture::new().with_source_skill("my-skill", &simple_skill("my-skill"));
 
         assert!(fixture.source_skill_exists("my-skill"));
         let contents = fixture.read_source_skill("my-skill").unwrap();
@@ -304,8 +329,8 @@ mod tests {
 
     #[test]
     fn adds_tool_skill() {
-        let fixture = TestFixture::new()
-            .with_tool_skill(Tool::Claude, "my-skill", &simple_skill("my-skill"));
+        let fixture =
+            TestFixture::new().with_tool_skill(Tool::Claude, "my-skill", &simple_skill("my-skill"));
 
         assert!(fixture.tool_skill_exists(Tool::Claude, "my-skill"));
         assert!(!fixture.tool_skill_exists(Tool::Codex, "my-skill"));
@@ -313,8 +338,8 @@ mod tests {
 
     #[test]
     fn adds_local_skill() {
-        let fixture = TestFixture::new()
-            .with_local_skill(Tool::Codex, "my-skill", &simple_skill("my-skill"));
+        let fixture =
+            TestFixture::new().with_local_skill(Tool::Codex, "my-skill", &simple_skill("my-skill"));
 
         assert!(fixture.local_skill_exists(Tool::Codex, "my-skill"));
         assert!(!fixture.local_skill_exists(Tool::Claude, "my-skill"));
@@ -330,7 +355,19 @@ mod tests {
         let catalog = fixture.catalog();
 
         assert!(catalog.sources.contains_key("source-skill"));
-        assert!(catalog.tools.get(&Tool::Claude).unwrap().contains_key("tool-skill"));
-        assert!(catalog.local.get(&Tool::Codex).unwrap().contains_key("local-skill"));
+        assert!(
+            catalog
+                .tools
+                .get(&Tool::Claude)
+                .unwrap()
+                .contains_key("tool-skill")
+        );
+        assert!(
+            catalog
+                .local
+                .get(&Tool::Codex)
+                .unwrap()
+                .contains_key("local-skill")
+        );
     }
 }
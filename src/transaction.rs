@@ -0,0 +1,355 @@
+//! All-or-nothing writes of skill directories.
+//!
+//! Commands that write many skill copies first describe every write as a [`PlannedWrite`],
+//! [`check`] the whole set, then apply it through a [`Transaction`]. The transaction copies each
+//! directory aside before it is written, so a failure part way through can restore every copy
+//! already replaced instead of leaving tool directories half updated.
+
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use walkdir::WalkDir;
+
+use crate::{
+    commands::init::copy_dir,
+    error::{Error, Result},
+    permissions::find_access_problem,
+    units::format_size,
+};
+
+/// A skill directory a command is about to write.
+#[derive(Debug, Clone)]
+pub struct PlannedWrite {
+    /// Skill directory that will be written.
+    pub(crate) dir: PathBuf,
+    /// What is being written, e.g. `'pdf' for Claude Code`, for error messages.
+    pub(crate) label: String,
+    /// Hash of the content written; identical writes to one directory do not collide.
+    pub(crate) digest: String,
+    /// Bytes the write adds.
+    pub(crate) bytes: u64,
+}
+
+/// Return the directory holding transaction backups for a state file.
+pub fn backup_dir(state_path: &Path) -> PathBuf {
+    state_path.with_file_name("backups")
+}
+
+/// Check a set of writes before any is applied.
+///
+/// No two writes may replace one directory with different content, every directory must be
+/// writable, and each filesystem needs room for the new files plus backups of the copies they
+/// replace.
+pub fn check(writes: &[PlannedWrite], backups: &Path) -> Result<()> {
+    check_collisions(writes)?;
+    for write in writes {
+        check_writable(&write.dir)?;
+    }
+    check_space(writes, backups)
+}
+
+/// Refuse writes that would replace the same directory with different content.
+fn check_collisions(writes: &[PlannedWrite]) -> Result<()> {
+    let mut seen: BTreeMap<PathBuf, &PlannedWrite> = BTreeMap::new();
+    for write in writes {
+        match seen.entry(resolve(&write.dir)) {
+            Entry::Vacant(entry) => {
+                entry.insert(write);
+            }
+            Entry::Occupied(entry) if entry.get().digest != write.digest => {
+                return Err(Error::WriteCollision {
+                    path: write.dir.clone(),
+                    first: entry.get().label.clone(),
+                    second: write.label.clone(),
+                });
+            }
+            Entry::Occupied(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Resolve links in the existing part of a path, so one directory reached two ways compares
+/// equal.
+fn resolve(path: &Path) -> PathBuf {
+    let Some(existing) = existing_ancestor(path) else {
+        return path.to_path_buf();
+    };
+    let rest = path.strip_prefix(existing).unwrap_or(path);
+    dunce::canonicalize(existing)
+        .map(|real| real.join(rest))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Return the deepest part of a path that exists.
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+}
+
+/// Check that a skill directory can be written: it, or the nearest parent that exists, must
+/// accept new files, and every file in it must belong to the current user and be writable.
+fn check_writable(dir: &Path) -> Result<()> {
+    let not_writable = |path: &Path, message: &str| Error::NotWritable {
+        path: path.to_path_buf(),
+        message: message.to_string(),
+        fix: None,
+    };
+    let existing =
+        existing_ancestor(dir).ok_or_else(|| not_writable(dir, "no parent directory exists"))?;
+    if !existing.is_dir() {
+        return Err(not_writable(existing, "not a directory"));
+    }
+    if let Some(problem) = find_access_problem(existing, existing == dir) {
+        return Err(Error::NotWritable {
+            path: dir.to_path_buf(),
+            message: problem.describe(),
+            fix: Some(problem.fix(existing)),
+        });
+    }
+    let probe = existing.join(format!(".skills-probe-{}", process::id()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|error| not_writable(existing, &error.to_string()))?;
+    fs::remove_file(&probe).map_err(|source| Error::SkillWrite {
+        path: probe.clone(),
+        source,
+    })?;
+    Ok(())
+}
+
+/// Check that each filesystem written to has room for its share of the writes and backups.
+fn check_space(writes: &[PlannedWrite], backups: &Path) -> Result<()> {
+    let mut needed: Vec<(&Path, u64)> = Vec::new();
+    for write in writes {
+        if let Some(existing) = existing_ancestor(&write.dir) {
+            needed.push((existing, write.bytes));
+        }
+        if write.dir.exists()
+            && let Some(existing) = existing_ancestor(backups)
+        {
+            needed.push((existing, dir_size(&write.dir)));
+        }
+    }
+    let mut filesystems: BTreeMap<u64, (&Path, u64, u64)> = BTreeMap::new();
+    for (path, bytes) in needed {
+        let Some(filesystem) = filesystem(path) else {
+            continue;
+        };
+        filesystems
+            .entry(filesystem.id)
+            .or_insert((path, filesystem.free, 0))
+            .2 += bytes;
+    }
+    for (path, available, needed) in filesystems.into_values() {
+        if needed > available {
+            return Err(Error::InsufficientSpace {
+                path: path.to_path_buf(),
+                needed: format_size(needed),
+                available: format_size(available),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Return the total size of the files under a directory, or zero when it does not exist.
+pub fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Identity and free space of the filesystem holding a path.
+struct Filesystem {
+    /// Device the filesystem is mounted from.
+    id: u64,
+    /// Bytes available to unprivileged users.
+    free: u64,
+}
+
+/// Look up the filesystem holding a path.
+#[cfg(unix)]
+fn filesystem(path: &Path) -> Option<Filesystem> {
+    use std::os::unix::fs::MetadataExt;
+
+    use rustix::fs::statvfs;
+
+    let id = fs::metadata(path).ok()?.dev();
+    let stats = statvfs(path).ok()?;
+    Some(Filesystem {
+        id,
+        free: stats.f_bavail.saturating_mul(stats.f_frsize),
+    })
+}
+
+/// Look up the filesystem holding a path; free space is not checked on this platform.
+#[cfg(not(unix))]
+fn filesystem(_path: &Path) -> Option<Filesystem> {
+    None
+}
+
+/// A directory written by a transaction.
+#[derive(Debug)]
+struct Staged {
+    /// Directory written.
+    dir: PathBuf,
+    /// Copy of the directory from before the write, absent when it did not exist.
+    backup: Option<PathBuf>,
+}
+
+/// A set of directory writes that are kept or undone together.
+#[derive(Debug)]
+pub struct Transaction {
+    /// Directory holding this transaction's backups.
+    root: PathBuf,
+    /// Directories staged so far, oldest first.
+    staged: Vec<Staged>,
+}
+
+impl Transaction {
+    /// Start a transaction keeping its backups under `backups`.
+    pub fn begin(backups: &Path) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        Self {
+            root: backups.join(format!("{started}-{}", process::id())),
+            staged: Vec::new(),
+        }
+    }
+
+    /// Back up a directory before writing to it.
+    pub fn stage(&mut self, dir: &Path) -> Result<()> {
+        let backup = if dir.exists() {
+            let backup = self.root.join(self.staged.len().to_string());
+            copy_dir(dir, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+        self.staged.push(Staged {
+            dir: dir.to_path_buf(),
+            backup,
+        });
+        Ok(())
+    }
+
+    /// Keep every write and discard the backups.
+    pub fn commit(self) -> Result<()> {
+        remove_dir(&self.root).map_err(|source| Error::SkillWrite {
+            path: self.root.clone(),
+            source,
+        })
+    }
+
+    /// Undo every staged write, newest first, and return how many directories were restored.
+    pub fn rollback(self) -> Result<usize> {
+        for staged in self.staged.iter().rev() {
+            let failed = |message: String| Error::RollbackFailed {
+                path: staged.dir.clone(),
+                backup: staged.backup.clone().unwrap_or_else(|| self.root.clone()),
+                message,
+            };
+            remove_dir(&staged.dir).map_err(|error| failed(error.to_string()))?;
+            if let Some(backup) = &staged.backup
+                && fs::rename(backup, &staged.dir).is_err()
+            {
+                copy_dir(backup, &staged.dir).map_err(|error| failed(error.to_string()))?;
+            }
+        }
+        let restored = self.staged.len();
+        self.commit()?;
+        Ok(restored)
+    }
+}
+
+/// Remove a directory tree, succeeding when it does not exist.
+fn remove_dir(dir: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use tempfile::TempDir;
+
+    use super::{PlannedWrite, Transaction, check};
+    use crate::error::Error;
+
+    /// Describe a write to a directory.
+    fn planned(dir: &Path, label: &str, digest: &str) -> PlannedWrite {
+        PlannedWrite {
+            dir: dir.to_path_buf(),
+            label: label.to_string(),
+            digest: digest.to_string(),
+            bytes: 1,
+        }
+    }
+
+    #[test]
+    fn refuses_colliding_writes() {
+        let temp = TempDir::new().expect("temp");
+        let dir = temp.path().join("tool").join("pdf");
+        let backups = temp.path().join("backups");
+
+        let same = [planned(&dir, "a", "x"), planned(&dir, "b", "x")];
+        check(&same, &backups).expect("identical writes");
+
+        let different = [planned(&dir, "a", "x"), planned(&dir, "b", "y")];
+        assert!(matches!(
+            check(&different, &backups),
+            Err(Error::WriteCollision { .. })
+        ));
+
+        let missing = temp.path().join("file");
+        fs::write(&missing, "").expect("write");
+        assert!(matches!(
+            check(&[planned(&missing.join("pdf"), "a", "x")], &backups),
+            Err(Error::NotWritable { .. })
+        ));
+    }
+
+    #[test]
+    fn rolls_back_staged_writes() {
+        let temp = TempDir::new().expect("temp");
+        let existing = temp.path().join("existing");
+        let created = temp.path().join("created");
+        fs::create_dir_all(existing.join("scripts")).expect("mkdir");
+        fs::write(existing.join("SKILL.md"), "old").expect("write");
+        fs::write(existing.join("scripts").join("run.sh"), "echo").expect("write");
+        let backups = temp.path().join("backups");
+
+        let mut transaction = Transaction::begin(&backups);
+        transaction.stage(&existing).expect("stage");
+        fs::write(existing.join("SKILL.md"), "new").expect("write");
+        fs::remove_dir_all(existing.join("scripts")).expect("remove");
+        transaction.stage(&created).expect("stage");
+        fs::create_dir_all(&created).expect("mkdir");
+
+        assert_eq!(transaction.rollback().expect("rollback"), 2);
+        assert_eq!(
+            fs::read_to_string(existing.join("SKILL.md")).expect("read"),
+            "old"
+        );
+        assert!(existing.join("scripts").join("run.sh").is_file());
+        assert!(!created.exists());
+        assert!(fs::read_dir(&backups).expect("backups").next().is_none());
+    }
+}
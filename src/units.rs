@@ -0,0 +1,282 @@
+//! Human-readable formatting of sizes, counts, ages, and dates.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Units used when formatting byte sizes, in steps of 1024.
+const SIZE_UNITS: &[&str] = &["KB", "MB", "GB"];
+
+/// Seconds in a day.
+const DAY_SECS: u64 = 86_400;
+
+/// Days from 0000-03-01 to the Unix epoch in the proleptic Gregorian calendar.
+const EPOCH_DAYS: u64 = 719_468;
+
+/// Days in a 400-year Gregorian era.
+const ERA_DAYS: u64 = 146_097;
+
+/// How timestamps are shown in command output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+    /// Ages relative to now, e.g. `3 days ago`, and UTC times to the minute.
+    #[default]
+    Relative,
+    /// ISO 8601 UTC timestamps to the second, e.g. `2026-10-15T14:02:05Z`.
+    Iso,
+}
+
+impl DateFormat {
+    /// Format when something happened, as an age unless absolute dates were asked for.
+    pub fn age(self, time: SystemTime) -> String {
+        match self {
+            Self::Relative => format_age(time),
+            Self::Iso => format_iso(time),
+        }
+    }
+
+    /// Format a point in time, such as a snapshot in a history listing.
+    pub fn time(self, time: SystemTime) -> String {
+        match self {
+            Self::Relative => format_time(time),
+            Self::Iso => format_iso(time),
+        }
+    }
+}
+
+/// Format a byte count, e.g. `512 B` or `1.5 MB`.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = SIZE_UNITS[0];
+    for next in &SIZE_UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+/// Format a count with a singular or plural noun, e.g. `1 file` or `3 files`.
+pub fn format_count(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Format a relative age from a timestamp.
+pub fn format_age(modified: SystemTime) -> String {
+    let elapsed = modified.elapsed().unwrap_or(Duration::from_secs(0));
+    let seconds = elapsed.as_secs();
+    if seconds < 60 {
+        return "moments ago".to_string();
+    }
+    if seconds < 60 * 60 {
+        let minutes = seconds / 60;
+        return format!("{} minute{} ago", minutes, plural(minutes));
+    }
+    if seconds < 60 * 60 * 24 {
+        let hours = seconds / 60 / 60;
+        return format!("{} hour{} ago", hours, plural(hours));
+    }
+    let days = seconds / 60 / 60 / 24;
+    format!("{days} day{} ago", plural(days))
+}
+
+/// Format a timestamp as a UTC calendar date, e.g. `2026-10-15`.
+pub fn format_date(time: SystemTime) -> String {
+    let days = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / DAY_SECS;
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format a timestamp as a UTC date and time to the minute, e.g. `2026-10-15 14:02 UTC`.
+pub fn format_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let minutes = secs % DAY_SECS / 60;
+    format!(
+        "{} {:02}:{:02} UTC",
+        format_date(time),
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// Format a timestamp as an ISO 8601 UTC date and time, e.g. `2026-10-15T14:02:05Z`.
+pub fn format_iso(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let seconds = secs % DAY_SECS;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_date(time),
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Parse a UTC date, `YYYY-MM-DD`, optionally followed by a time, `HH:MM` or `HH:MM:SS`,
+/// separated by a space or `T`.
+pub fn parse_time(text: &str) -> Option<SystemTime> {
+    let text = text.trim().trim_end_matches('Z');
+    let (date, time) = match text.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+    let numbers = |text: &str, separator: char| -> Option<Vec<u64>> {
+        text.split(separator)
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    let [year, month, day] = numbers(date, '-')?[..] else {
+        return None;
+    };
+    if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut secs = days_from_civil(year, month, day) * DAY_SECS;
+    if let Some(time) = time {
+        let (hours, minutes, seconds) = match numbers(time, ':')?[..] {
+            [hours, minutes] => (hours, minutes, 0),
+            [hours, minutes, seconds] => (hours, minutes, seconds),
+            _ => return None,
+        };
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return None;
+        }
+        secs += hours * 3600 + minutes * 60 + seconds;
+    }
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Convert days since the Unix epoch to a year, month, and day.
+///
+/// Counts in 400-year eras with years starting in March, so leap days fall at the end.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + EPOCH_DAYS;
+    let era = days / ERA_DAYS;
+    let day_of_era = days % ERA_DAYS;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (year_of_era + era * 400 + u64::from(month <= 2), month, day)
+}
+
+/// Convert a year, month, and day on or after the Unix epoch to days since it.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * ERA_DAYS + day_of_era - EPOCH_DAYS
+}
+
+/// Return a plural suffix for counts.
+fn plural(count: u64) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{
+        DateFormat, format_age, format_count, format_date, format_size, format_time, parse_time,
+    };
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(format_count(1, "file"), "1 file");
+        assert_eq!(format_count(2, "file"), "2 files");
+    }
+
+    #[test]
+    fn formats_ages() {
+        let now = SystemTime::now();
+        assert_eq!(format_age(now), "moments ago");
+        assert_eq!(format_age(now - Duration::from_secs(60)), "1 minute ago");
+        assert_eq!(
+            format_age(now - Duration::from_secs(2 * 3600)),
+            "2 hours ago"
+        );
+        assert_eq!(
+            format_age(now - Duration::from_secs(3 * 86400)),
+            "3 days ago"
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        let date = |secs| format_date(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_791_936_000), "2026-10-14");
+    }
+
+    #[test]
+    fn parses_times() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(parse_time("2000-02-29"), Some(at(951_782_400)));
+        assert_eq!(
+            parse_time("2026-10-14T14:02"),
+            Some(at(1_791_936_000 + 14 * 3600 + 120))
+        );
+        assert_eq!(
+            parse_time("2026-10-14 14:02:05Z"),
+            Some(at(1_791_936_000 + 14 * 3600 + 125))
+        );
+        assert_eq!(
+            format_time(at(1_791_936_000 + 14 * 3600 + 125)),
+            "2026-10-14 14:02 UTC"
+        );
+        let taken = at(1_791_936_000 + 14 * 3600 + 125);
+        assert_eq!(DateFormat::Iso.time(taken), "2026-10-14T14:02:05Z");
+        assert_eq!(DateFormat::Iso.age(taken), "2026-10-14T14:02:05Z");
+        assert_eq!(DateFormat::Relative.time(taken), "2026-10-14 14:02 UTC");
+        for invalid in [
+            "yesterday",
+            "2026-13-01",
+            "2026-10",
+            "2026-10-14T25:00",
+            "12",
+        ] {
+            assert_eq!(parse_time(invalid), None, "{invalid}");
+        }
+    }
+}